@@ -4,7 +4,7 @@
 
 use std::collections::{BTreeMap, HashSet};
 
-use kani_metadata::{CbmcSolver, HarnessAttributes, HarnessKind, Stub};
+use kani_metadata::{CbmcSolver, ContractClause, HarnessAttributes, HarnessKind, Stub};
 use quote::ToTokens;
 use rustc_ast::{LitKind, MetaItem, MetaItemKind};
 use rustc_data_structures::fx::FxHashMap;
@@ -64,6 +64,9 @@ enum KaniAttributeKind {
     /// Attribute on a function that was auto-generated from expanding a
     /// function contract.
     IsContractGenerated,
+    /// Internal attribute of the contracts implementation that records the kind and
+    /// source text of one clause of the function's contract, e.g. `"ensures: |result| ..."`.
+    ContractClause,
     /// A function with contract expanded to include the write set as arguments.
     ///
     /// Contains the original body of the contracted function. The signature is
@@ -105,6 +108,7 @@ pub fn is_harness_only(self) -> bool {
             | KaniAttributeKind::ModifiesWrapper
             | KaniAttributeKind::AssertedWith
             | KaniAttributeKind::IsContractGenerated
+            | KaniAttributeKind::ContractClause
             | KaniAttributeKind::DisableChecks => false,
         }
     }
@@ -305,6 +309,22 @@ pub fn has_contract(&self) -> bool {
         self.map.contains_key(&KaniAttributeKind::CheckedWith)
     }
 
+    /// The clauses of this function's contract, in the order they were written.
+    /// Malformed clause attributes are skipped, since they only serve as documentation.
+    pub fn contract_clauses(&self) -> Vec<ContractClause> {
+        let Some(attrs) = self.map.get(&KaniAttributeKind::ContractClause) else {
+            return vec![];
+        };
+        attrs
+            .iter()
+            .filter_map(|attr| {
+                let value = expect_key_string_value(self.tcx.sess, attr).ok()?;
+                let (kind, text) = value.as_str().split_once(": ")?;
+                Some(ContractClause { kind: kind.to_string(), text: text.to_string() })
+            })
+            .collect()
+    }
+
     /// Check that all attributes assigned to an item is valid.
     /// Returns a tuple of (stub_verified_targets_with_spans, proof_for_contract_targets).
     /// Errors will be added to the session. Invoke self.tcx.sess.abort_if_errors() to terminate
@@ -397,7 +417,7 @@ pub(super) fn check_attributes(&self) -> (FxHashMap<FnDefStable, Span>, HashSet<
                 | KaniAttributeKind::ReplacedWith => {
                     self.attribute_value(kind);
                 }
-                KaniAttributeKind::IsContractGenerated => {
+                KaniAttributeKind::IsContractGenerated | KaniAttributeKind::ContractClause => {
                     // Ignored here because this is only used by the proc macros
                     // to communicate with one another. So by the time it gets
                     // here we don't care if it's valid or not.
@@ -570,6 +590,7 @@ pub fn harness_attributes(&self) -> HarnessAttributes {
                 }
                 KaniAttributeKind::CheckedWith
                 | KaniAttributeKind::IsContractGenerated
+                | KaniAttributeKind::ContractClause
                 | KaniAttributeKind::ModifiesWrapper
                 | KaniAttributeKind::RecursionCheck
                 | KaniAttributeKind::RecursionTracker
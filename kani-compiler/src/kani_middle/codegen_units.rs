@@ -18,8 +18,8 @@
 use crate::kani_middle::{can_derive_arbitrary, implements_arbitrary};
 use crate::kani_queries::QueryDb;
 use kani_metadata::{
-    ArtifactType, AssignsContract, AutoHarnessMetadata, AutoHarnessSkipReason, HarnessMetadata,
-    KaniMetadata, find_proof_harnesses,
+    ArtifactType, AssignsContract, AutoHarnessArgument, AutoHarnessMetadata, AutoHarnessSkipReason,
+    HarnessMetadata, KaniMetadata, find_proof_harnesses,
 };
 use regex::RegexSet;
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
@@ -98,7 +98,7 @@ pub fn new(queries: &QueryDb, tcx: TyCtxt) -> Self {
                 let kani_harness_intrinsic =
                     kani_fns.get(&KaniIntrinsic::AutomaticHarness.into()).unwrap();
 
-                let (chosen, skipped) = automatic_harness_partition(
+                let (chosen, skipped, arguments) = automatic_harness_partition(
                     tcx,
                     args,
                     &crate_info.name,
@@ -108,6 +108,7 @@ pub fn new(queries: &QueryDb, tcx: TyCtxt) -> Self {
                     .set(AutoHarnessMetadata {
                         chosen: chosen.iter().map(|func| func.name()).collect::<BTreeSet<_>>(),
                         skipped,
+                        arguments,
                     })
                     .expect("Initializing the autoharness metadata failed");
 
@@ -408,13 +409,18 @@ fn autoharness_filtered_out(
 }
 
 /// Partition every function in the crate into (chosen, skipped), where `chosen` is a vector of the Instances for which we'll generate automatic harnesses,
-/// and `skipped` is a map of function names to the reason why we skipped them.
+/// `skipped` is a map of function names to the reason why we skipped them,
+/// and the last map contains the arguments of every function whose argument types we checked.
 fn automatic_harness_partition(
     tcx: TyCtxt,
     args: &Arguments,
     crate_name: &str,
     kani_any_def: FnDef,
-) -> (Vec<Instance>, BTreeMap<String, AutoHarnessSkipReason>) {
+) -> (
+    Vec<Instance>,
+    BTreeMap<String, AutoHarnessSkipReason>,
+    BTreeMap<String, Vec<AutoHarnessArgument>>,
+) {
     let crate_fn_defs = rustc_public::local_crate().fn_defs().into_iter().collect::<FxHashSet<_>>();
     // Filter out CrateItems that are functions, but not functions defined in the crate itself, i.e., rustc-inserted functions
     // (c.f. https://github.com/model-checking/kani/issues/4189)
@@ -431,6 +437,7 @@ fn automatic_harness_partition(
 
     // Cache whether a type implements or can derive Arbitrary
     let mut ty_arbitrary_cache: FxHashMap<Ty, bool> = FxHashMap::default();
+    let mut arguments = BTreeMap::new();
 
     // If `func` is not eligible for an automatic harness, return the reason why; if it is eligible, return None.
     // Note that we only return one reason for ineligiblity, when there could be multiple;
@@ -469,6 +476,7 @@ fn automatic_harness_partition(
         // Each argument of `instance` must implement Arbitrary.
         // Note that we've already filtered out generic functions, so we know that each of these arguments has a concrete type.
         let mut problematic_args = vec![];
+        let mut fn_args = vec![];
         for (idx, arg) in body.arg_locals().iter().enumerate() {
             if !ty_arbitrary_cache.contains_key(&arg.ty) {
                 let impls_arbitrary =
@@ -476,22 +484,28 @@ fn automatic_harness_partition(
                         || can_derive_arbitrary(arg.ty, kani_any_def, &mut ty_arbitrary_cache);
                 ty_arbitrary_cache.insert(arg.ty, impls_arbitrary);
             }
-            let impls_arbitrary = ty_arbitrary_cache.get(&arg.ty).unwrap();
+            let impls_arbitrary = *ty_arbitrary_cache.get(&arg.ty).unwrap();
 
+            // Find the name of the argument by referencing var_debug_info.
+            // Note that enumerate() starts at 0, while rustc_public argument_index starts at 1, hence the idx+1.
+            let arg_name = body
+                .var_debug_info
+                .iter()
+                .find(|var| {
+                    var.argument_index.is_some_and(|arg_idx| idx + 1 == usize::from(arg_idx))
+                })
+                .map_or("_".to_string(), |debug_info| debug_info.name.to_string());
+            let arg_type = format!("{}", arg.ty);
             if !impls_arbitrary {
-                // Find the name of the argument by referencing var_debug_info.
-                // Note that enumerate() starts at 0, while rustc_public argument_index starts at 1, hence the idx+1.
-                let arg_name = body
-                    .var_debug_info
-                    .iter()
-                    .find(|var| {
-                        var.argument_index.is_some_and(|arg_idx| idx + 1 == usize::from(arg_idx))
-                    })
-                    .map_or("_".to_string(), |debug_info| debug_info.name.to_string());
-                let arg_type = format!("{}", arg.ty);
-                problematic_args.push((arg_name, arg_type))
+                problematic_args.push((arg_name.clone(), arg_type.clone()))
             }
+            fn_args.push(AutoHarnessArgument {
+                name: arg_name,
+                ty: arg_type,
+                arbitrary: impls_arbitrary,
+            });
         }
+        arguments.insert(fn_item.name(), fn_args);
         if !problematic_args.is_empty() {
             return Some(AutoHarnessSkipReason::MissingArbitraryImpl(problematic_args));
         }
@@ -509,7 +523,7 @@ fn automatic_harness_partition(
         }
     }
 
-    (chosen, skipped)
+    (chosen, skipped, arguments)
 }
 
 #[cfg(test)]
@@ -64,8 +64,11 @@ pub fn gen_contracts_metadata(
         let attributes = KaniAttributes::for_def_id(tcx, item.def_id());
 
         if attributes.has_contract() {
-            fn_to_data
-                .insert(item.def_id(), ContractedFunction { function, file, harnesses: vec![] });
+            let clauses = attributes.contract_clauses();
+            fn_to_data.insert(
+                item.def_id(),
+                ContractedFunction { function, file, harnesses: vec![], clauses },
+            );
         // This logic finds manual contract harnesses only (automatic harnesses are a Kani intrinsic, not crate items annotated with the proof_for_contract attribute).
         } else if let Some(def) = attributes.interpret_for_contract_attribute() {
             let target_def_id = def.def_id();
@@ -80,6 +83,7 @@ pub fn gen_contracts_metadata(
                         function: item.name(),
                         file,
                         harnesses: vec![function],
+                        clauses: vec![],
                     },
                 );
             }
@@ -5,7 +5,9 @@
 use std::path::PathBuf;
 
 use crate::args::list_args::Format;
-use crate::args::{ValidateArgs, VerificationArgs, validate_std_path};
+use crate::args::{
+    ExportSupport, ValidateArgs, VerificationArgs, check_export_flags, validate_std_path,
+};
 use crate::util::warning;
 use clap::{Error, Parser, error::ErrorKind};
 use kani_metadata::UnstableFeature;
@@ -30,6 +32,12 @@ pub struct CommonAutoharnessArgs {
     /// The format of the `list` output. Requires --list.
     #[arg(long, default_value = "pretty", requires = "list")]
     pub format: Format,
+
+    /// Verify the functions that previous runs found to reach constructs that Kani does not
+    /// support, instead of skipping them. These functions are recorded in
+    /// `target/kani/autoharness-skip.json` until their source or the Kani version changes.
+    #[arg(long)]
+    pub retry_unsupported: bool,
 }
 
 /// Automatically verify functions in a crate.
@@ -125,6 +133,9 @@ fn validate(&self) -> Result<(), Error> {
                 "The `--quiet` flag is not compatible with the `pretty` format, since `pretty` prints to the terminal. Either specify a different format or don't pass `--quiet`.",
             ));
         }
+        if self.common_autoharness_args.list {
+            check_export_flags(&self.verify_opts, "autoharness --list", ExportSupport::Document)?;
+        }
 
         if self
             .verify_opts
@@ -165,6 +176,9 @@ fn validate(&self) -> Result<(), Error> {
                 "The `--quiet` flag is not compatible with the `pretty` format, since `pretty` prints to the terminal. Either specify a different format or don't pass `--quiet`.",
             ));
         }
+        if self.common_autoharness_args.list {
+            check_export_flags(&self.verify_opts, "autoharness --list", ExportSupport::Document)?;
+        }
 
         if self.std {
             validate_std_path(&self.input)?;
@@ -0,0 +1,97 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the subcommand handling of the baseline subcommand
+
+use crate::args::{CommonArgs, ValidateArgs};
+use clap::{Error, Parser, Subcommand, error::ErrorKind};
+
+/// Prefix of the `--compare-baseline` values that refer to a saved baseline, e.g. `baseline:main`.
+pub const SAVED_BASELINE_PREFIX: &str = "baseline:";
+
+/// Manage the named baselines of the current package, which `--compare-baseline` can compare
+/// against as `baseline:<NAME>`
+#[derive(Debug, Parser)]
+pub struct CargoBaselineArgs {
+    #[command(flatten)]
+    pub common_args: CommonArgs,
+
+    #[command(subcommand)]
+    pub command: BaselineCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum BaselineCommand {
+    /// Save the run exported by the latest `cargo kani --export-json` as a named baseline.
+    Save {
+        #[arg(value_name = "NAME")]
+        name: String,
+
+        /// Save the run even if it had tool errors, e.g. CBMC crashing.
+        #[arg(long)]
+        force: bool,
+    },
+    /// List the saved baselines.
+    List,
+    /// Delete a saved baseline.
+    Delete {
+        #[arg(value_name = "NAME")]
+        name: String,
+    },
+}
+
+/// Baselines are stored as `<NAME>.json`, so names are restricted to characters that are valid
+/// in file names on every platform.
+pub fn is_valid_baseline_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('.')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+pub fn invalid_baseline_name(name: &str) -> Error {
+    Error::raw(
+        ErrorKind::InvalidValue,
+        format!(
+            "invalid baseline name `{name}`: expected ASCII letters, digits, `-`, `_` and `.`, \
+            not starting with `.`"
+        ),
+    )
+}
+
+impl ValidateArgs for CargoBaselineArgs {
+    fn validate(&self) -> Result<(), Error> {
+        self.common_args.validate()?;
+        match &self.command {
+            BaselineCommand::Save { name, .. } | BaselineCommand::Delete { name }
+                if !is_valid_baseline_name(name) =>
+            {
+                Err(invalid_baseline_name(name))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_baseline_args() {
+        let args = CargoBaselineArgs::try_parse_from(["baseline", "save", "main"]).unwrap();
+        assert!(
+            matches!(&args.command, BaselineCommand::Save { name, force: false } if name == "main")
+        );
+        assert!(args.validate().is_ok());
+
+        let args =
+            CargoBaselineArgs::try_parse_from(["baseline", "save", "v1.2", "--force"]).unwrap();
+        assert!(matches!(args.command, BaselineCommand::Save { force: true, .. }));
+        assert!(args.validate().is_ok());
+
+        for name in ["../main", ".hidden", "a b", "main/x"] {
+            let args = CargoBaselineArgs::try_parse_from(["baseline", "delete", name]).unwrap();
+            assert_eq!(args.validate().unwrap_err().kind(), ErrorKind::InvalidValue, "{name}");
+        }
+        assert!(CargoBaselineArgs::try_parse_from(["baseline"]).is_err());
+    }
+}
@@ -22,11 +22,6 @@ pub struct CommonArgs {
     #[arg(long, hide = true)]
     pub enable_unstable: bool,
 
-    /// We no longer support dry-run. Use `--verbose` to see the commands being printed during
-    /// Kani execution.
-    #[arg(long, hide = true)]
-    pub dry_run: bool,
-
     /// Enable an unstable feature.
     #[clap(flatten)]
     pub unstable_features: EnabledUnstableFeatures,
@@ -34,12 +29,6 @@ pub struct CommonArgs {
 
 impl ValidateArgs for CommonArgs {
     fn validate(&self) -> Result<(), Error> {
-        if self.dry_run {
-            return Err(Error::raw(
-                ErrorKind::ValueValidation,
-                "The `--dry-run` option is obsolete. Use --verbose instead.",
-            ));
-        }
         if self.enable_unstable {
             return Err(Error::raw(
                 ErrorKind::ValueValidation,
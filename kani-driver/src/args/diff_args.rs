@@ -0,0 +1,52 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the subcommand handling of the diff subcommand
+
+use std::path::PathBuf;
+
+use crate::args::{CommonArgs, ValidateArgs};
+use crate::frontend::DEFAULT_RUNTIME_THRESHOLD_PERCENT;
+use clap::{Error, Parser, error::ErrorKind};
+
+/// Compare two runs exported with `--export-json` and print their differences as JSON
+#[derive(Debug, Parser)]
+pub struct DiffArgs {
+    /// The run to compare against, e.g. the run before a code change.
+    #[arg(value_name = "OLD")]
+    pub old: PathBuf,
+
+    /// The run to compare.
+    #[arg(value_name = "NEW")]
+    pub new: PathBuf,
+
+    #[command(flatten)]
+    pub common_args: CommonArgs,
+
+    /// Report harnesses whose runtime changed by more than this percentage.
+    #[arg(long, value_name = "PERCENT", default_value_t = DEFAULT_RUNTIME_THRESHOLD_PERCENT)]
+    pub runtime_threshold: f64,
+}
+
+impl ValidateArgs for DiffArgs {
+    fn validate(&self) -> Result<(), Error> {
+        self.common_args.validate()?;
+        if !self.runtime_threshold.is_finite() || self.runtime_threshold < 0.0 {
+            return Err(Error::raw(
+                ErrorKind::InvalidValue,
+                "Invalid argument: `--runtime-threshold` must be a non-negative percentage.",
+            ));
+        }
+        for path in [&self.old, &self.new] {
+            if !path.is_file() {
+                return Err(Error::raw(
+                    ErrorKind::InvalidValue,
+                    format!(
+                        "Invalid argument: `{}` is not an exported run. Expected a file written by `--export-json`.",
+                        path.display()
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,277 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Classify the flags of `kani` and `cargo kani` by the invocation mode in which they apply.
+//!
+//! Both parsers share [VerificationArgs], so a flag that only makes sense in one mode is still
+//! accepted by the other one. [check_flag_modes] rejects these flags with an error that points
+//! to the right invocation, instead of silently ignoring them.
+
+use crate::args::VerificationArgs;
+use clap::error::{Error, ErrorKind};
+
+/// The invocation modes in which a flag has an effect.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlagMode {
+    /// Only `cargo kani`, e.g. package selection.
+    Cargo,
+    /// Only `kani` on a single file.
+    Standalone,
+}
+
+/// A flag that only has an effect in one invocation mode.
+struct ModeSpecificFlag {
+    /// How the flag is written in the error message, with its aliases separated by ` / `.
+    name: &'static str,
+    /// How the flag is used in the suggested invocation.
+    usage: &'static str,
+    mode: FlagMode,
+    /// Whether the flag was set. `None` if only one of the parsers defines the flag, in which
+    /// case clap already rejects it in the other mode.
+    is_set: Option<fn(&VerificationArgs) -> bool>,
+}
+
+/// Flags that only have an effect in one invocation mode.
+const MODE_SPECIFIC_FLAGS: &[ModeSpecificFlag] = &[
+    // Cargo common arguments.
+    cargo("--all-features", "--all-features", |a| a.cargo.all_features),
+    cargo("--exclude", "--workspace --exclude ...", |a| !a.cargo.exclude.is_empty()),
+    cargo("--features / -F", "--features ...", |a| !a.cargo.features().is_empty()),
+    cargo("--manifest-path", "--manifest-path ...", |a| a.cargo.manifest_path.is_some()),
+    cargo("--no-default-features", "--no-default-features", |a| a.cargo.no_default_features),
+    cargo("--package / -p", "-p ...", |a| !a.cargo.package.is_empty()),
+    cargo("--workspace", "--workspace", |a| a.cargo.workspace),
+    // Cargo target arguments.
+    cargo("--bin", "--bin ...", |a| !a.target.bin.is_empty()),
+    cargo("--bins", "--bins", |a| a.target.bins),
+    cargo("--lib", "--lib", |a| a.target.lib),
+    // Options that act on the cargo build or the cargo target directory.
+    cargo("--force-build", "--force-build", |a| a.force_build),
+    cargo("--no-history", "-Z unstable-options --no-history", |a| a.no_history),
+    cargo("--incremental", "-Z unstable-options --incremental", |a| a.incremental),
+    // Standalone options.
+    standalone("--smoke-test", Some(|a| a.smoke_test.is_some())),
+    standalone("--smoke-then-verify", Some(|a| a.smoke_then_verify)),
+    standalone("<INPUT>", None),
+    standalone("--crate-name", None),
+];
+
+const fn cargo(
+    name: &'static str,
+    usage: &'static str,
+    is_set: fn(&VerificationArgs) -> bool,
+) -> ModeSpecificFlag {
+    ModeSpecificFlag { name, usage, mode: FlagMode::Cargo, is_set: Some(is_set) }
+}
+
+const fn standalone(
+    name: &'static str,
+    is_set: Option<fn(&VerificationArgs) -> bool>,
+) -> ModeSpecificFlag {
+    ModeSpecificFlag { name, usage: name, mode: FlagMode::Standalone, is_set }
+}
+
+/// Reject the flags of `args` that have no effect in the invocation `mode`.
+/// TODO: Split the cargo specific arguments out of [VerificationArgs] as part of
+/// <https://github.com/model-checking/kani/issues/1831>
+pub fn check_flag_modes(args: &VerificationArgs, mode: FlagMode) -> Result<(), Error> {
+    let misplaced = MODE_SPECIFIC_FLAGS
+        .iter()
+        .filter(|flag| flag.mode != mode)
+        .find(|flag| flag.is_set.is_some_and(|is_set| is_set(args)));
+    let Some(flag) = misplaced else { return Ok(()) };
+    // Standalone Kani has always rejected cargo options as unknown arguments.
+    let (kind, message) = match flag.mode {
+        FlagMode::Cargo => (
+            ErrorKind::UnknownArgument,
+            format!(
+                "`{}` has no effect when invoking `kani` on a single file; did you mean \
+                `cargo kani {}`?",
+                flag.name, flag.usage
+            ),
+        ),
+        FlagMode::Standalone => (
+            ErrorKind::ArgumentConflict,
+            format!(
+                "`{}` has no effect when invoking `cargo kani`; it is only supported by \
+                `kani <FILE> {}`.",
+                flag.name, flag.usage
+            ),
+        ),
+    };
+    Err(Error::raw(kind, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::{CargoKaniArgs, StandaloneArgs};
+    use clap::{CommandFactory, Parser};
+
+    /// Flags that have an effect in both invocation modes, by clap argument id.
+    const FLAGS_FOR_BOTH_MODES: &[&str] = &[
+        // Verification arguments.
+        "auto_unwind",
+        "auto_unwind_max",
+        "baseline_runtime_threshold",
+        "c_lib",
+        "cbmc_args",
+        "cbmc_verbosity",
+        "compare_baseline",
+        "concrete_playback",
+        "coverage",
+        "coverage_out",
+        "coverage_overwrite",
+        "default_unwind",
+        "dry_run",
+        "event_log",
+        "export_json",
+        "export_json_merge",
+        "export_json_streaming",
+        "exact",
+        "exclude_harnesses",
+        "extra_pointer_checks",
+        "fail_fast",
+        "fail_on_regression",
+        "gen_c",
+        "harnesses",
+        "harness_patterns",
+        "harness_regexes",
+        "harness_timeout",
+        "ignore_global_asm",
+        "jobs",
+        "junit_xml",
+        "keep_temps",
+        "no_assert_contracts",
+        "no_assertion_reach_checks",
+        "no_codegen",
+        "no_restrict_vtable",
+        "no_slice_formula",
+        "only_codegen",
+        "output_format",
+        "output_into_files",
+        "print_config",
+        "print_llbc",
+        "prove_safety_only",
+        "quick",
+        "quick_setting",
+        "randomize_layout",
+        "restrict_vtable",
+        "run_sanity_checks",
+        "sarif",
+        "solver",
+        "strict_xfail",
+        "synthesize_loop_contracts",
+        "target_dir",
+        "tests",
+        "trace",
+        "unwind",
+        "with_contract_dependencies",
+        "write_json_symtab",
+        "xfail",
+        // Memory checks.
+        "no_default_checks",
+        "no_memory_safety_checks",
+        "no_overflow_checks",
+        "no_undefined_function_checks",
+        "no_unwinding_checks",
+        // Common arguments.
+        "debug",
+        "quiet",
+        "verbose",
+        "enable_unstable",
+        "enabled_unstable_features",
+        // Added by clap.
+        "help",
+        "version",
+    ];
+
+    /// Whether `arg` is the flag written `name` in the error messages, e.g. `--features / -F`.
+    fn is_named(arg: &clap::Arg, name: &str) -> bool {
+        name.split(" / ").any(|token| {
+            let positional = arg.is_positional().then(|| {
+                let value_name = arg.get_value_names().and_then(|names| names.first());
+                value_name.map_or(arg.get_id().as_str().to_uppercase(), |name| name.to_string())
+            });
+            arg.get_long().is_some_and(|long| token == format!("--{long}"))
+                || arg.get_short().is_some_and(|short| token == format!("-{short}"))
+                || positional.is_some_and(|name| token == format!("<{name}>"))
+        })
+    }
+
+    #[test]
+    fn check_every_flag_has_a_mode() {
+        // Building the commands adds the flags generated by clap, like `--help`.
+        let mut standalone = StandaloneArgs::command();
+        standalone.build();
+        let mut cargo = CargoKaniArgs::command();
+        cargo.build();
+        for (command, mode) in [(&standalone, FlagMode::Standalone), (&cargo, FlagMode::Cargo)] {
+            for arg in command.get_arguments() {
+                let id = arg.get_id().as_str();
+                if FLAGS_FOR_BOTH_MODES.contains(&id) {
+                    continue;
+                }
+                let Some(flag) = MODE_SPECIFIC_FLAGS.iter().find(|flag| is_named(arg, flag.name))
+                else {
+                    panic!(
+                        "`{id}` is not classified: add it to `MODE_SPECIFIC_FLAGS` or \
+                        `FLAGS_FOR_BOTH_MODES`"
+                    )
+                };
+                assert!(
+                    flag.mode == mode || flag.is_set.is_some(),
+                    "`{id}` is classified as {:?} but is accepted by `{}`",
+                    flag.mode,
+                    command.get_name()
+                );
+            }
+        }
+
+        // Every classified flag exists, in the modes it is classified for.
+        let has_id = |command: &clap::Command, id: &str| {
+            command.get_arguments().any(|arg| arg.get_id().as_str() == id)
+        };
+        for id in FLAGS_FOR_BOTH_MODES {
+            assert!(has_id(&standalone, id) && has_id(&cargo, id), "`{id}` is not a flag");
+        }
+        let has_flag = |command: &clap::Command, flag: &ModeSpecificFlag| {
+            command.get_arguments().any(|arg| is_named(arg, flag.name))
+        };
+        for flag in MODE_SPECIFIC_FLAGS {
+            let command = if flag.mode == FlagMode::Cargo { &cargo } else { &standalone };
+            assert!(has_flag(command, flag), "`{}` is not a flag", flag.name);
+            // Flags without a check must be rejected by the other parser.
+            let other = if flag.mode == FlagMode::Cargo { &standalone } else { &cargo };
+            assert_eq!(has_flag(other, flag), flag.is_set.is_some(), "{}", flag.name);
+        }
+    }
+
+    #[test]
+    fn check_cargo_flag_in_standalone_mode() {
+        let args = StandaloneArgs::try_parse_from("kani input.rs -p foo".split_whitespace());
+        let err = check_flag_modes(&args.unwrap().verify_opts, FlagMode::Standalone).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnknownArgument);
+        assert!(err.to_string().contains(
+            "`--package / -p` has no effect when invoking `kani` on a single file; \
+            did you mean `cargo kani -p ...`?"
+        ));
+
+        let args = StandaloneArgs::try_parse_from("kani input.rs --force-build".split_whitespace());
+        assert!(check_flag_modes(&args.unwrap().verify_opts, FlagMode::Standalone).is_err());
+    }
+
+    #[test]
+    fn check_standalone_flag_in_cargo_mode() {
+        let args = CargoKaniArgs::try_parse_from(
+            "cargo-kani -Z unstable-options --smoke-test=5".split_whitespace(),
+        );
+        let err = check_flag_modes(&args.unwrap().verify_opts, FlagMode::Cargo).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+        assert!(
+            err.to_string().contains("`--smoke-test` has no effect when invoking `cargo kani`")
+        );
+
+        let args = CargoKaniArgs::try_parse_from("cargo-kani -p foo --force-build".split(' '));
+        assert!(check_flag_modes(&args.unwrap().verify_opts, FlagMode::Cargo).is_ok());
+    }
+}
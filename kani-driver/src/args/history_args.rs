@@ -0,0 +1,28 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the subcommand handling of the history subcommand
+
+use crate::args::{CommonArgs, ValidateArgs};
+use clap::{Error, Parser};
+
+/// Show the local history of verification runs for the current package
+#[derive(Debug, Parser)]
+pub struct CargoHistoryArgs {
+    #[command(flatten)]
+    pub common_args: CommonArgs,
+
+    /// Show the status of the given harness over time instead of the run summary.
+    /// The harness name must match exactly.
+    #[arg(long = "harness", value_name = "HARNESS_NAME", conflicts_with = "clear")]
+    pub harness: Option<String>,
+
+    /// Delete the run history of this package.
+    #[arg(long)]
+    pub clear: bool,
+}
+
+impl ValidateArgs for CargoHistoryArgs {
+    fn validate(&self) -> Result<(), Error> {
+        self.common_args.validate()
+    }
+}
@@ -3,14 +3,24 @@
 //! Module that define Kani's command line interface. This includes all subcommands.
 
 pub mod autoharness_args;
+pub mod baseline_args;
 pub mod cargo;
 pub mod common;
+pub mod diff_args;
+pub mod flag_modes;
+pub mod history_args;
 pub mod list_args;
+pub mod new_proof_args;
 pub mod playback_args;
+pub mod resolved_config;
 pub mod std_args;
 
 use self::common::*;
 use crate::args::cargo::CargoTargetArgs;
+use crate::args::flag_modes::{FlagMode, check_flag_modes};
+use crate::frontend::DEFAULT_RUNTIME_THRESHOLD_PERCENT;
+use crate::metadata::HarnessPattern;
+use crate::session::{QUICK_TIMEOUT, QUICK_UNWIND_DEFAULT};
 use crate::util::warning;
 use cargo::CargoCommonArgs;
 use clap::builder::{PossibleValue, TypedValueParser};
@@ -91,6 +101,9 @@ pub fn print_stabilized_feature_warning(
 // By default we configure CBMC to use 16 bits to represent the object bits in pointers.
 const DEFAULT_OBJECT_BITS: u32 = 16;
 
+// By default `--auto-unwind` gives up once the unwind value would exceed 64.
+const AUTO_UNWIND_MAX_DEFAULT: u32 = 64;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, strum_macros::EnumString)]
 enum TimeUnit {
     #[strum(serialize = "s")]
@@ -138,6 +151,65 @@ fn from(timeout: Timeout) -> Self {
     }
 }
 
+/// The settings of the `--quick` bundle. Each one can be overridden with `--quick-setting`, e.g.
+/// from the `profile.quick` table of the Kani configuration in `Cargo.toml`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuickSettings {
+    pub default_unwind: u32,
+    pub harness_timeout: Timeout,
+    pub fail_fast: bool,
+    /// Whether unwinding assertions stay enabled.
+    pub unwinding_checks: bool,
+    /// Whether overflow checks stay enabled.
+    pub overflow_checks: bool,
+    /// Whether memory safety checks stay enabled.
+    pub memory_safety_checks: bool,
+}
+
+impl Default for QuickSettings {
+    fn default() -> Self {
+        QuickSettings {
+            default_unwind: QUICK_UNWIND_DEFAULT,
+            harness_timeout: Timeout::from_str(QUICK_TIMEOUT).unwrap(),
+            fail_fast: true,
+            unwinding_checks: false,
+            overflow_checks: false,
+            memory_safety_checks: false,
+        }
+    }
+}
+
+impl QuickSettings {
+    /// The default settings, with the `KEY=VALUE` entries of `overrides` applied in order.
+    pub fn with_overrides(overrides: &[String]) -> Result<Self, String> {
+        let mut settings = QuickSettings::default();
+        for entry in overrides {
+            let Some((key, value)) = entry.split_once('=') else {
+                return Err(format!("expected `KEY=VALUE` but found `{entry}`"));
+            };
+            let (key, value) = (key.trim(), value.trim());
+            let invalid =
+                |expected: &str| format!("expected {expected} for `{key}`, found `{value}`");
+            let flag = || value.parse::<bool>().map_err(|_| invalid("`true` or `false`"));
+            match key {
+                "default-unwind" => {
+                    settings.default_unwind = value.parse().map_err(|_| invalid("a number"))?
+                }
+                "harness-timeout" if !value.is_empty() => {
+                    settings.harness_timeout = Timeout::from_str(value)?
+                }
+                "harness-timeout" => return Err(invalid("a timeout")),
+                "fail-fast" => settings.fail_fast = flag()?,
+                "unwinding-checks" => settings.unwinding_checks = flag()?,
+                "overflow-checks" => settings.overflow_checks = flag()?,
+                "memory-safety-checks" => settings.memory_safety_checks = flag()?,
+                _ => return Err(format!("unknown setting `{key}`")),
+            }
+        }
+        Ok(settings)
+    }
+}
+
 #[derive(Debug, clap::Parser)]
 #[command(
     version,
@@ -169,6 +241,8 @@ pub struct StandaloneArgs {
 pub enum StandaloneSubcommand {
     /// Create and run harnesses automatically for eligible functions. Implies -Z function-contracts and -Z loop-contracts.
     Autoharness(Box<autoharness_args::StandaloneAutoharnessArgs>),
+    /// Compare two runs exported with `--export-json`.
+    Diff(Box<diff_args::DiffArgs>),
     /// List contracts and harnesses.
     List(Box<list_args::StandaloneListArgs>),
     /// Execute concrete playback testcases of a local crate.
@@ -199,9 +273,21 @@ pub enum CargoKaniSubcommand {
     /// See https://model-checking.github.io/kani/reference/experimental/autoharness.html for documentation.
     Autoharness(Box<autoharness_args::CargoAutoharnessArgs>),
 
+    /// Save, list and delete named baselines for `--compare-baseline`.
+    Baseline(Box<baseline_args::CargoBaselineArgs>),
+
+    /// Compare two runs exported with `--export-json`.
+    Diff(Box<diff_args::DiffArgs>),
+
+    /// Show the local history of verification runs.
+    History(Box<history_args::CargoHistoryArgs>),
+
     /// List contracts and harnesses.
     List(Box<list_args::CargoListArgs>),
 
+    /// Generate a proof harness skeleton for a function.
+    NewProof(Box<new_proof_args::CargoNewProofArgs>),
+
     /// Execute concrete playback testcases of a local package.
     Playback(Box<playback_args::CargoPlaybackArgs>),
 }
@@ -212,6 +298,33 @@ pub enum CargoKaniSubcommand {
 #[derive(Debug, clap::Args)]
 #[clap(next_help_heading = "Verification Options")]
 pub struct VerificationArgs {
+    /// When the only failures of a harness are unwinding assertions, verify it again with twice
+    /// the unwind value, up to `--auto-unwind-max`.
+    /// This feature is unstable and it requires `-Z unstable-options` to be used
+    #[arg(long, hide_short_help = true)]
+    pub auto_unwind: bool,
+
+    /// The largest unwind value tried by `--auto-unwind` (defaults to 64).
+    #[arg(
+        long,
+        hide_short_help = true,
+        requires("auto_unwind"),
+        value_name = "UNWIND",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub auto_unwind_max: Option<u32>,
+
+    /// With `--compare-baseline`, report harnesses whose runtime changed by more than this
+    /// percentage of their baseline runtime.
+    #[arg(
+        long,
+        hide_short_help = true,
+        requires("compare_baseline"),
+        value_name = "PERCENT",
+        default_value_t = DEFAULT_RUNTIME_THRESHOLD_PERCENT
+    )]
+    pub baseline_runtime_threshold: f64,
+
     /// Link external C files referenced by Rust code.
     /// This is an experimental feature and requires `-Z c-ffi` to be used
     #[arg(long, hide = true, num_args(1..))]
@@ -227,6 +340,20 @@ pub struct VerificationArgs {
     // consumes everything
     pub cbmc_args: Vec<OsString>,
 
+    /// Verbosity level passed to CBMC (0-10). Defaults to 9.
+    /// Kani raises the level when needed: verification results require at least 4, and the
+    /// CBMC statistics reported by `--export-json` require at least 8.
+    /// This feature is unstable and it requires `-Z unstable-options` to be used
+    #[arg(long, hide_short_help = true, value_parser = clap::value_parser!(u8).range(0..=10))]
+    pub cbmc_verbosity: Option<u8>,
+
+    /// Compare the results with a previous run exported with `--export-json`, and report the
+    /// harnesses that started failing or passing, were added or removed, or changed runtime.
+    /// Use `baseline:<NAME>` to compare with a baseline saved by `cargo kani baseline save`.
+    /// This feature is unstable and it requires `-Z unstable-options` to be used
+    #[arg(long, value_name = "FILE", hide_short_help = true)]
+    pub compare_baseline: Option<PathBuf>,
+
     /// Generate concrete playback unit test.
     /// If value supplied is 'print', Kani prints the unit test to stdout.
     /// If value supplied is 'inplace', Kani automatically adds the unit test to your source code.
@@ -238,19 +365,65 @@ pub struct VerificationArgs {
     #[arg(long, hide_short_help = true)]
     pub coverage: bool,
 
+    /// Save the coverage results to this directory instead of a new `kanicov_<date>` directory.
+    /// The directory must not exist unless `--coverage-overwrite` is also given.
+    #[arg(long, value_name = "DIR", requires("coverage"), hide_short_help = true)]
+    pub coverage_out: Option<PathBuf>,
+
+    /// Replace the `--coverage-out` directory if it already exists.
+    #[arg(long, requires("coverage_out"), hide_short_help = true)]
+    pub coverage_overwrite: bool,
+
     /// Specify the value used for loop unwinding in CBMC
     #[arg(long)]
     pub default_unwind: Option<u32>,
 
+    /// Build the harnesses and print the goto-cc, goto-instrument, goto-synthesizer and CBMC
+    /// commands that would verify them, without running these commands.
+    /// This feature is unstable and it requires `-Z unstable-options` to be used
+    #[arg(long, hide_short_help = true)]
+    pub dry_run: bool,
+
+    /// Write a log of the actions of the driver to the specified path as JSON Lines, e.g. the
+    /// processes it runs and the files it writes, to debug runs after the fact. Once the log
+    /// exceeds its size limit, it is moved to `<PATH>.1` and a new log is started.
+    /// This feature is unstable and it requires `-Z unstable-options` to be used
+    #[arg(long, hide_short_help = true, value_name = "PATH")]
+    pub event_log: Option<PathBuf>,
+
     /// Output the verification results to a JSON file at the specified path.
     /// This feature is unstable and it requires `-Z unstable-options` to be used
     #[arg(long)]
     pub export_json: Option<PathBuf>,
 
+    /// Write the `--export-json` file incrementally as JSON Lines, one record per item, so
+    /// results of finished harnesses are available while verification is still running.
+    #[arg(long, requires("export_json"), hide_short_help = true)]
+    pub export_json_streaming: bool,
+
+    /// Append this run to the JSON array of runs in the `--export-json` file, keyed by its
+    /// timestamp, instead of replacing the file. A missing or empty file is started as a new array.
+    /// Concurrent runs can merge into the same file; they take turns using `<FILE>.lock`.
+    #[arg(
+        long,
+        requires("export_json"),
+        conflicts_with("export_json_streaming"),
+        hide_short_help = true
+    )]
+    pub export_json_merge: bool,
+
     /// When specified, the harness filter will only match the exact fully qualified name of a harness
     #[arg(long, requires("harnesses"))]
     pub exact: bool,
 
+    /// Do not verify the harnesses whose fully qualified name matches this glob pattern, with the
+    /// same syntax as `--harness-pattern`. Exclusions apply after `--harness` and the harness
+    /// patterns select harnesses, and excluded harnesses are reported as such.
+    /// This option can be provided multiple times.
+    /// This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long = "exclude-harness", num_args(1), value_name = "GLOB", hide_short_help = true)]
+    pub exclude_harnesses: Vec<String>,
+
     /// Enable extra pointer checks such as invalid pointers in relation operations and pointer
     /// arithmetic overflow.
     /// This feature is unstable and it may yield false counter examples. It requires
@@ -259,10 +432,17 @@ pub struct VerificationArgs {
     pub extra_pointer_checks: bool,
 
     /// Stop the verification process as soon as one of the harnesses fails.
+    /// The harnesses that have not started yet are reported as skipped. With `--jobs`, the
+    /// harnesses that are already running are allowed to finish.
     #[arg(long)]
     pub fail_fast: bool,
 
-    /// Force Kani to rebuild all packages before the verification.
+    /// With `--compare-baseline`, fail if a harness that succeeded in the baseline fails now, or
+    /// if a harness became slower by more than `--baseline-runtime-threshold`.
+    #[arg(long, hide_short_help = true, requires("compare_baseline"))]
+    pub fail_on_regression: bool,
+
+    /// Force Kani to rebuild all packages before the verification. Only supported by `cargo kani`.
     #[arg(long)]
     pub force_build: bool,
 
@@ -277,6 +457,19 @@ pub struct VerificationArgs {
     #[arg(long = "harness", num_args(1), value_name = "HARNESS_FILTER")]
     pub harnesses: Vec<String>,
 
+    /// Only run harnesses whose fully qualified name matches this glob pattern, where `*` matches
+    /// any sequence of characters and `?` matches a single character, e.g. `parser::*`.
+    /// This option can be provided multiple times and it can be combined with `--harness`.
+    /// This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long = "harness-pattern", num_args(1), value_name = "GLOB", hide_short_help = true)]
+    pub harness_patterns: Vec<String>,
+
+    /// Only run harnesses whose fully qualified name matches this regular expression.
+    /// This option can be provided multiple times and it can be combined with `--harness`.
+    /// This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long = "harness-regex", num_args(1), value_name = "REGEX", hide_short_help = true)]
+    pub harness_regexes: Vec<String>,
+
     /// Timeout for each harness with optional suffix ('s': seconds, 'm': minutes, 'h': hours). Default is seconds. This option is experimental and requires `-Z unstable-options` to be used.
     #[arg(long)]
     pub harness_timeout: Option<Timeout>,
@@ -286,13 +479,28 @@ pub struct VerificationArgs {
     #[arg(long, hide_short_help = true)]
     pub ignore_global_asm: bool,
 
+    /// Reuse the result of harnesses that verified successfully in a previous `--incremental` run
+    /// if their goto binary and CBMC flags are unchanged. The results are cached in
+    /// `target/kani/cache`. `--force-build` verifies all harnesses again.
+    /// Only supported by `cargo kani`. This option is experimental and requires
+    /// `-Z unstable-options` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub incremental: bool,
+
     /// Number of threads to spawn to verify harnesses in parallel.
     /// Omit the flag entirely to run sequentially (i.e. one thread).
     /// Pass -j to run with the thread pool's default number of threads.
     /// Pass -j <N> to specify N threads.
+    /// Harnesses that were the slowest in previous runs are verified first.
     #[arg(short, long, hide_short_help = true)]
     jobs: Option<Option<usize>>,
 
+    /// Write a JUnit XML report of the verification results to the specified path.
+    /// Each harness is reported as a test case.
+    /// This feature is unstable and it requires `-Z unstable-options` to be used
+    #[arg(long, hide_short_help = true)]
+    pub junit_xml: Option<PathBuf>,
+
     /// Keep temporary files generated throughout Kani process. This is already the default
     /// behavior for `cargo-kani`.
     #[arg(long, hide_short_help = true)]
@@ -311,6 +519,12 @@ pub struct VerificationArgs {
     #[arg(long, hide_short_help = true)]
     pub no_codegen: bool,
 
+    /// Do not record this run in the local run history (`target/kani/history.jsonl`).
+    /// Only supported by `cargo kani`, since standalone runs have no history. This option is
+    /// experimental and requires `-Z unstable-options` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub no_history: bool,
+
     /// Disable restricting the targets of virtual table function pointer calls
     #[arg(long, hide_short_help = true)]
     pub no_restrict_vtable: bool,
@@ -331,6 +545,13 @@ pub struct VerificationArgs {
     #[arg(long, hide_short_help = true)]
     pub output_into_files: bool,
 
+    /// Print the fully resolved configuration as JSON and exit without verifying.
+    /// Each argument is listed with its value and where it came from (default, `Cargo.toml` or
+    /// command line).
+    /// This feature is unstable and it requires `-Z unstable-options` to be used
+    #[arg(long, hide_short_help = true)]
+    pub print_config: bool,
+
     /// Print final LLBC for Lean backend. This requires the `-Z lean` option.
     #[arg(long, hide = true)]
     pub print_llbc: bool,
@@ -340,6 +561,22 @@ pub struct VerificationArgs {
     #[arg(long, hide_short_help = true)]
     pub prove_safety_only: bool,
 
+    /// Run a fast, NOT SOUND check for quick feedback during development.
+    /// Unless set explicitly, this uses a default unwind of 2, a 60s harness timeout and
+    /// `--fail-fast`. It also turns off unwinding, overflow and memory safety checks.
+    /// Each of these settings can be changed with `--quick-setting`.
+    /// A passing quick check is not a proof.
+    #[arg(long, hide_short_help = true)]
+    pub quick: bool,
+
+    /// Override a setting of the `--quick` bundle, as `<KEY>=<VALUE>`. The settings are
+    /// `default-unwind`, `harness-timeout`, `fail-fast`, and `unwinding-checks`,
+    /// `overflow-checks` and `memory-safety-checks`, which keep those checks enabled when `true`.
+    /// Settings can also be given in the `profile.quick` table of the Kani configuration in
+    /// `Cargo.toml`. They have no effect without `--quick`.
+    #[arg(long, value_name = "KEY=VALUE", hide_short_help = true)]
+    pub quick_setting: Vec<String>,
+
     /// Randomize the layout of structures. This option can help catching code that relies on
     /// a specific layout chosen by the compiler that is not guaranteed to be stable in the future.
     /// If a value is given, it will be used as the seed for randomization
@@ -356,11 +593,41 @@ pub struct VerificationArgs {
     #[arg(long, hide_short_help = true)]
     pub run_sanity_checks: bool,
 
+    /// Write a SARIF 2.1.0 log of the failed checks to the specified path, e.g. for GitHub code
+    /// scanning. Each failed check is reported at its source location.
+    /// This feature is unstable and it requires `-Z unstable-options` to be used
+    #[arg(long, hide_short_help = true, value_name = "PATH")]
+    pub sarif: Option<PathBuf>,
+
+    /// Before verifying each harness, execute it N times (100 by default) with random concrete
+    /// values for `kani::any()`. A failing sample is reported right away with its values, and the
+    /// harness is not verified unless `--smoke-then-verify` is given. Passing samples are not a
+    /// proof. Only supported for standalone `kani`.
+    /// This feature is unstable and it requires `-Z unstable-options` to be used
+    #[arg(
+        long,
+        hide_short_help = true,
+        value_name = "N",
+        num_args(0..=1),
+        require_equals(true),
+        default_missing_value = "100",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub smoke_test: Option<u32>,
+
+    /// Verify harnesses even if their smoke test found a failure.
+    #[arg(long, hide_short_help = true, requires("smoke_test"))]
+    pub smoke_then_verify: bool,
+
     /// Specify the CBMC solver to use. Overrides the harness `solver` attribute.
     /// If no solver is specified (with --solver or harness attribute), Kani will use CaDiCaL.
     #[arg(long, value_parser = CbmcSolverValueParser::new(CbmcSolver::VARIANTS))]
     pub solver: Option<CbmcSolver>,
 
+    /// Fail the run if a harness marked with `--xfail` passes, so stale entries get removed.
+    #[arg(long, requires("xfail"), hide_short_help = true)]
+    pub strict_xfail: bool,
+
     /// Synthesize loop contracts for all loops.
     #[arg(
         long,
@@ -378,14 +645,41 @@ pub struct VerificationArgs {
     #[arg(long)]
     pub tests: bool,
 
+    /// When to produce counterexample traces for failed checks. With `on-failure`, harnesses are
+    /// verified without traces, and the failed checks of failing harnesses are verified again with
+    /// traces. `--concrete-playback` needs traces, so it implies at least `on-failure`.
+    #[arg(
+        long,
+        default_value = "on-failure",
+        ignore_case = true,
+        value_enum,
+        hide_short_help = true
+    )]
+    pub trace: TraceMode,
+
     /// Specify the value used for loop unwinding for the specified harness in CBMC
     #[arg(long, requires("harnesses"))]
     pub unwind: Option<u32>,
 
+    /// Also verify the `#[kani::proof_for_contract]` harnesses of every contract that the
+    /// selected harnesses assume with `#[kani::stub_verified]`, transitively. It is an error if
+    /// an assumed contract has no such harness.
+    /// This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub with_contract_dependencies: bool,
+
     /// Write the GotoC symbol table to a file in JSON format instead of goto binary format.
     #[arg(long, hide = true)]
     pub write_json_symtab: bool,
 
+    /// Mark a harness as expected to fail, as `<HARNESS>` or `<HARNESS>=<REASON>`. It is still
+    /// verified: a failure is reported as XFAIL and doesn't fail the run, and a success is
+    /// reported as XPASS. Entries can also be given in the `xfail` table of the Kani
+    /// configuration in `Cargo.toml`.
+    /// This feature is unstable and it requires `-Z unstable-options` to be used
+    #[arg(long, value_name = "HARNESS[=REASON]", hide_short_help = true)]
+    pub xfail: Vec<String>,
+
     #[command(flatten)]
     pub checks: CheckArgs,
 
@@ -433,6 +727,34 @@ pub fn assertion_reach_checks(&self) -> bool {
         !self.no_assertion_reach_checks
     }
 
+    /// The `--xfail` reason of the harness `pretty_name`, or `None` if it is not expected to fail.
+    pub fn xfail_reason(&self, pretty_name: &str) -> Option<&str> {
+        self.xfail.iter().find_map(|entry| {
+            let (harness, reason) = entry.split_once('=').unwrap_or((entry, ""));
+            (harness.trim() == pretty_name).then(|| reason.trim())
+        })
+    }
+
+    /// When CBMC produces traces, taking into account that `--concrete-playback` needs them.
+    pub fn trace_mode(&self) -> TraceMode {
+        if self.concrete_playback.is_some() && self.trace == TraceMode::Never {
+            TraceMode::OnFailure
+        } else {
+            self.trace
+        }
+    }
+
+    /// The name of the saved baseline `--compare-baseline` refers to, if it is given as
+    /// `baseline:<NAME>` rather than as the path of an exported run.
+    pub fn saved_baseline(&self) -> Option<&str> {
+        self.compare_baseline.as_ref()?.to_str()?.strip_prefix(baseline_args::SAVED_BASELINE_PREFIX)
+    }
+
+    /// The largest unwind value tried by `--auto-unwind`.
+    pub fn auto_unwind_cap(&self) -> u32 {
+        self.auto_unwind_max.unwrap_or(AUTO_UNWIND_MAX_DEFAULT)
+    }
+
     /// Suppress our default value, if the user has supplied it explicitly in --cbmc-args
     pub fn cbmc_object_bits(&self) -> Option<u32> {
         if self.cbmc_args.contains(&OsString::from("--object-bits")) {
@@ -483,6 +805,28 @@ pub fn jobs(&self) -> NumThreads {
         }
     }
 
+    /// The `--harness-pattern` and `--harness-regex` selectors, in this order.
+    /// Returns an error if one of them is not a valid pattern.
+    pub fn harness_selection_patterns(&self) -> Result<Vec<HarnessPattern>, regex::Error> {
+        let globs = self.harness_patterns.iter().map(|glob| HarnessPattern::glob(glob));
+        let regexes = self.harness_regexes.iter().map(|regex| HarnessPattern::regex(regex));
+        globs.chain(regexes).collect()
+    }
+
+    /// The `--exclude-harness` patterns.
+    pub fn harness_exclusion_patterns(&self) -> Result<Vec<HarnessPattern>, regex::Error> {
+        self.exclude_harnesses.iter().map(|glob| HarnessPattern::glob(glob)).collect()
+    }
+
+    /// Whether the compiler only generates the harnesses selected by `--harness`. Harness
+    /// patterns and `--with-contract-dependencies` are handled by the driver, which needs all
+    /// harnesses.
+    pub fn compiler_selects_harnesses(&self) -> bool {
+        self.harness_patterns.is_empty()
+            && self.harness_regexes.is_empty()
+            && !self.with_contract_dependencies
+    }
+
     /// Are experimental function contracts enabled?
     pub fn is_function_contracts_enabled(&self) -> bool {
         self.common_args.unstable_features.contains(UnstableFeature::FunctionContracts)
@@ -503,11 +847,22 @@ pub enum ConcretePlaybackMode {
     InPlace,
 }
 
+/// When CBMC produces counterexample traces, see `--trace`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum TraceMode {
+    Always,
+    OnFailure,
+    Never,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, ValueEnum)]
 pub enum OutputFormat {
     Regular,
     Terse,
     Old,
+    /// One JSON object per line on stdout for every check, harness result and the final summary.
+    /// Everything else is printed to stderr.
+    Json,
 }
 
 #[derive(Debug, clap::Args)]
@@ -549,22 +904,65 @@ pub fn unwinding_on(&self) -> bool {
     }
 }
 
-/// Utility function to error out on arguments that are invalid Cargo specific.
-///
-/// We currently define a bunch of cargo specific arguments as part of the overall arguments,
-/// however, they are invalid in the Kani standalone usage. Explicitly check them for now.
-/// TODO: Remove this as part of <https://github.com/model-checking/kani/issues/1831>
-fn check_no_cargo_opt(is_set: bool, name: &str) -> Result<(), Error> {
-    if is_set {
-        Err(Error::raw(
-            ErrorKind::UnknownArgument,
-            format!("argument `{name}` cannot be used with standalone Kani."),
-        ))
-    } else {
-        Ok(())
+/// What a `cargo kani` subcommand does with the `--export-json` flag given before it.
+/// `kani` already rejects verification flags before a subcommand.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ExportSupport {
+    /// The subcommand writes a single JSON document to the path, which cannot be streamed.
+    Document,
+    /// The subcommand has its own verification options, which take `--export-json`.
+    OwnOptions,
+    /// The subcommand never writes an export.
+    Unsupported,
+}
+
+impl CargoKaniSubcommand {
+    fn export_support(&self) -> (&'static str, ExportSupport) {
+        match self {
+            CargoKaniSubcommand::Autoharness(_) => ("autoharness", ExportSupport::OwnOptions),
+            CargoKaniSubcommand::Baseline(_) => ("baseline", ExportSupport::Unsupported),
+            CargoKaniSubcommand::Diff(_) => ("diff", ExportSupport::Unsupported),
+            CargoKaniSubcommand::History(_) => ("history", ExportSupport::Unsupported),
+            CargoKaniSubcommand::List(_) => ("list", ExportSupport::Document),
+            CargoKaniSubcommand::NewProof(_) => ("new-proof", ExportSupport::Unsupported),
+            CargoKaniSubcommand::Playback(_) => ("playback", ExportSupport::Unsupported),
+        }
     }
 }
 
+/// Reject `--export-json`, `--export-json-merge` and `--export-json-streaming` if `subcommand`
+/// cannot write the export, instead of silently leaving users without the file.
+fn check_export_flags(
+    args: &VerificationArgs,
+    subcommand: &str,
+    support: ExportSupport,
+) -> Result<(), Error> {
+    let flag = if args.export_json_streaming {
+        "--export-json-streaming"
+    } else if args.export_json_merge {
+        "--export-json-merge"
+    } else if args.export_json.is_some() {
+        "--export-json"
+    } else {
+        return Ok(());
+    };
+    let reason = match support {
+        ExportSupport::Document if args.export_json_streaming => {
+            "its JSON output is a single document, which cannot be streamed".to_string()
+        }
+        ExportSupport::Document if args.export_json_merge => {
+            "its JSON output is a single document, which cannot be merged".to_string()
+        }
+        ExportSupport::Document => return Ok(()),
+        ExportSupport::OwnOptions => format!("pass it after `{subcommand}` instead"),
+        ExportSupport::Unsupported => "it does not write a JSON export".to_string(),
+    };
+    Err(Error::raw(
+        ErrorKind::ArgumentConflict,
+        format!("`{flag}` cannot be used with the `{subcommand}` subcommand: {reason}."),
+    ))
+}
+
 impl ValidateArgs for StandaloneArgs {
     fn validate(&self) -> Result<(), Error> {
         self.verify_opts.validate()?;
@@ -573,22 +971,12 @@ fn validate(&self) -> Result<(), Error> {
             Some(StandaloneSubcommand::VerifyStd(args)) => args.validate()?,
             Some(StandaloneSubcommand::List(args)) => args.validate()?,
             Some(StandaloneSubcommand::Autoharness(args)) => args.validate()?,
+            Some(StandaloneSubcommand::Diff(args)) => args.validate()?,
             // TODO: Invoke PlaybackArgs::validate()
             None | Some(StandaloneSubcommand::Playback(..)) => {}
         };
 
-        // Cargo target arguments.
-        check_no_cargo_opt(self.verify_opts.target.bins, "--bins")?;
-        check_no_cargo_opt(self.verify_opts.target.lib, "--lib")?;
-        check_no_cargo_opt(!self.verify_opts.target.bin.is_empty(), "--bin")?;
-        // Cargo common arguments.
-        check_no_cargo_opt(self.verify_opts.cargo.all_features, "--all-features")?;
-        check_no_cargo_opt(self.verify_opts.cargo.no_default_features, "--no-default-features")?;
-        check_no_cargo_opt(!self.verify_opts.cargo.features().is_empty(), "--features / -F")?;
-        check_no_cargo_opt(!self.verify_opts.cargo.package.is_empty(), "--package / -p")?;
-        check_no_cargo_opt(!self.verify_opts.cargo.exclude.is_empty(), "--exclude")?;
-        check_no_cargo_opt(self.verify_opts.cargo.workspace, "--workspace")?;
-        check_no_cargo_opt(self.verify_opts.cargo.manifest_path.is_some(), "--manifest-path")?;
+        check_flag_modes(&self.verify_opts, FlagMode::Standalone)?;
         if let Some(input) = &self.input
             && !input.is_file()
         {
@@ -617,8 +1005,12 @@ impl ValidateArgs for CargoKaniSubcommand {
     fn validate(&self) -> Result<(), Error> {
         match self {
             CargoKaniSubcommand::Autoharness(autoharness) => autoharness.validate(),
+            CargoKaniSubcommand::Baseline(baseline) => baseline.validate(),
+            CargoKaniSubcommand::Diff(diff) => diff.validate(),
+            CargoKaniSubcommand::History(history) => history.validate(),
             CargoKaniSubcommand::Playback(playback) => playback.validate(),
             CargoKaniSubcommand::List(list) => list.validate(),
+            CargoKaniSubcommand::NewProof(new_proof) => new_proof.validate(),
         }
     }
 }
@@ -626,8 +1018,11 @@ fn validate(&self) -> Result<(), Error> {
 impl ValidateArgs for CargoKaniArgs {
     fn validate(&self) -> Result<(), Error> {
         self.verify_opts.validate()?;
+        if let Some((name, support)) = self.command.as_ref().map(|c| c.export_support()) {
+            check_export_flags(&self.verify_opts, name, support)?;
+        }
         self.command.validate()?;
-        Ok(())
+        check_flag_modes(&self.verify_opts, FlagMode::Cargo)
     }
 }
 
@@ -661,6 +1056,12 @@ fn validate(&self) -> Result<(), Error> {
                 UnstableFeature::UnstableOptions,
             )?;
 
+            self.common_args.check_unstable(
+                self.cbmc_verbosity.is_some(),
+                "cbmc-verbosity",
+                UnstableFeature::UnstableOptions,
+            )?;
+
             self.common_args.check_unstable(
                 self.no_codegen,
                 "no-codegen",
@@ -735,6 +1136,108 @@ fn validate(&self) -> Result<(), Error> {
                 UnstableFeature::UnstableOptions,
             )?;
 
+            self.common_args.check_unstable(
+                !self.harness_patterns.is_empty(),
+                "harness-pattern",
+                UnstableFeature::UnstableOptions,
+            )?;
+
+            self.common_args.check_unstable(
+                !self.harness_regexes.is_empty(),
+                "harness-regex",
+                UnstableFeature::UnstableOptions,
+            )?;
+
+            self.common_args.check_unstable(
+                !self.exclude_harnesses.is_empty(),
+                "exclude-harness",
+                UnstableFeature::UnstableOptions,
+            )?;
+
+            self.common_args.check_unstable(
+                self.with_contract_dependencies,
+                "with-contract-dependencies",
+                UnstableFeature::UnstableOptions,
+            )?;
+
+            self.common_args.check_unstable(
+                self.quick,
+                "quick",
+                UnstableFeature::UnstableOptions,
+            )?;
+
+            self.common_args.check_unstable(
+                self.junit_xml.is_some(),
+                "junit-xml",
+                UnstableFeature::UnstableOptions,
+            )?;
+
+            self.common_args.check_unstable(
+                self.sarif.is_some(),
+                "sarif",
+                UnstableFeature::UnstableOptions,
+            )?;
+
+            self.common_args.check_unstable(
+                self.incremental,
+                "incremental",
+                UnstableFeature::UnstableOptions,
+            )?;
+
+            self.common_args.check_unstable(
+                self.no_history,
+                "no-history",
+                UnstableFeature::UnstableOptions,
+            )?;
+
+            self.common_args.check_unstable(
+                self.print_config,
+                "print-config",
+                UnstableFeature::UnstableOptions,
+            )?;
+
+            self.common_args.check_unstable(
+                self.auto_unwind,
+                "auto-unwind",
+                UnstableFeature::UnstableOptions,
+            )?;
+
+            self.common_args.check_unstable(
+                self.smoke_test.is_some(),
+                "smoke-test",
+                UnstableFeature::UnstableOptions,
+            )?;
+
+            self.common_args.check_unstable(
+                self.compare_baseline.is_some(),
+                "compare-baseline",
+                UnstableFeature::UnstableOptions,
+            )?;
+
+            self.common_args.check_unstable(
+                !self.xfail.is_empty(),
+                "xfail",
+                UnstableFeature::UnstableOptions,
+            )?;
+
+            self.common_args.check_unstable(
+                self.dry_run,
+                "dry-run",
+                UnstableFeature::UnstableOptions,
+            )?;
+
+            self.common_args.check_unstable(
+                self.event_log.is_some(),
+                "event-log",
+                UnstableFeature::UnstableOptions,
+            )?;
+
+            self.common_args.check_unstable(
+                self.output_format == OutputFormat::Json,
+                "output-format=json",
+                UnstableFeature::UnstableOptions,
+            )?;
+
             Ok(())
         };
 
@@ -752,6 +1255,14 @@ fn validate(&self) -> Result<(), Error> {
                     "Conflicting flags: unwind flags provided to kani and in --cbmc-args.",
                 ));
             }
+            if self.cbmc_verbosity.is_some()
+                && self.cbmc_args.iter().any(|s| s.to_str().unwrap().starts_with("--verbosity"))
+            {
+                return Err(Error::raw(
+                    ErrorKind::ArgumentConflict,
+                    "Conflicting flags: --cbmc-verbosity and --verbosity in --cbmc-args.",
+                ));
+            }
             if self.cbmc_args.contains(&OsString::from("--function")) {
                 return Err(Error::raw(
                     ErrorKind::ArgumentConflict,
@@ -772,6 +1283,14 @@ fn validate(&self) -> Result<(), Error> {
                 --output-format=old.",
                 ));
             }
+            if self.output_format == OutputFormat::Json
+                && self.concrete_playback == Some(ConcretePlaybackMode::Print)
+            {
+                return Err(Error::raw(
+                    ErrorKind::ArgumentConflict,
+                    "Conflicting options: --concrete-playback=print and --output-format=json.",
+                ));
+            }
             if self.concrete_playback.is_some() && self.jobs().will_multithread() {
                 // Concrete playback currently embeds a lot of assumptions about the order in which harnesses get called.
                 return Err(Error::raw(
@@ -779,12 +1298,22 @@ fn validate(&self) -> Result<(), Error> {
                     "Conflicting options: --concrete-playback isn't compatible with --jobs specifying multiple threads.",
                 ));
             }
-            if self.jobs().will_multithread() && self.output_format != OutputFormat::Terse {
+            if self.jobs().will_multithread()
+                && !matches!(self.output_format, OutputFormat::Terse | OutputFormat::Json)
+            {
                 // More verbose output formats make it hard to interpret output right now when run in parallel.
                 // This can be removed when we change up how results are printed.
                 return Err(Error::raw(
                     ErrorKind::ArgumentConflict,
-                    "Conflicting options: --jobs requires `--output-format=terse`",
+                    "Conflicting options: --jobs requires `--output-format=terse` or \
+                    `--output-format=json`",
+                ));
+            }
+            if self.dry_run && self.only_codegen {
+                return Err(Error::raw(
+                    ErrorKind::ArgumentConflict,
+                    "Conflicting options: --dry-run prints the verification commands, but \
+                --only-codegen skips verification.",
                 ));
             }
             // TODO: error out for other CBMC-backend-specific arguments
@@ -829,7 +1358,66 @@ fn validate(&self) -> Result<(), Error> {
         conflicting_options()?;
         deprecated_stabilized_obsolete()?;
 
+        if let Err(err) = self.harness_selection_patterns() {
+            return Err(Error::raw(ErrorKind::InvalidValue, format!("{err}")));
+        }
+
         // Bespoke validations that don't fit into any of the categories above.
+        if !self.baseline_runtime_threshold.is_finite() || self.baseline_runtime_threshold < 0.0 {
+            return Err(Error::raw(
+                ErrorKind::InvalidValue,
+                "Invalid argument: `--baseline-runtime-threshold` must be a non-negative percentage.",
+            ));
+        }
+        if let Some(name) = self.saved_baseline()
+            && !baseline_args::is_valid_baseline_name(name)
+        {
+            return Err(baseline_args::invalid_baseline_name(name));
+        }
+        if let Some(baseline) = &self.compare_baseline
+            && self.saved_baseline().is_none()
+            && !baseline.is_file()
+        {
+            return Err(Error::raw(
+                ErrorKind::InvalidValue,
+                format!(
+                    "Invalid argument: `{}` is not an exported run. Expected a file written by `--export-json`.",
+                    baseline.display()
+                ),
+            ));
+        }
+
+        if self.quick
+            && let Err(err) = QuickSettings::with_overrides(&self.quick_setting)
+        {
+            return Err(Error::raw(
+                ErrorKind::InvalidValue,
+                format!("Invalid argument to `--quick-setting`: {err}"),
+            ));
+        }
+
+        if self.quick && self.coverage && !self.common_args.quiet {
+            warning(
+                "`--quick` disables checks and bounds loop unwinding, so the coverage results \
+                may not be representative of a full verification run.",
+            );
+        }
+
+        let strict_flags: Vec<_> = [
+            (self.fail_on_regression, "`--fail-on-regression`"),
+            (self.strict_xfail, "`--strict-xfail`"),
+        ]
+        .into_iter()
+        .filter_map(|(set, flag)| set.then_some(flag))
+        .collect();
+        if self.quick && !strict_flags.is_empty() && !self.common_args.quiet {
+            warning(&format!(
+                "`--quick` is not sound, so the exit status that {} enforce on its results is \
+                not a verification verdict. Use a full verification run to gate CI.",
+                strict_flags.join(" and ")
+            ));
+        }
+
         if self.randomize_layout.is_some() && self.concrete_playback.is_some() {
             let random_seed = if let Some(seed) = self.randomize_layout.unwrap() {
                 format!(" -Z layout-seed={seed}")
@@ -1040,15 +1628,6 @@ macro_rules! check_opt {
         };
     }
 
-    #[test]
-    fn check_dry_run_fails() {
-        // We don't support --dry-run anymore but we print a friendly reminder for now.
-        let args = vec!["kani", "file.rs", "--dry-run"];
-        let err =
-            StandaloneArgs::try_parse_from(&args).unwrap().verify_opts.validate().unwrap_err();
-        assert_eq!(err.kind(), ErrorKind::ValueValidation);
-    }
-
     /// Kani should fail if the argument given is not a file.
     #[test]
     fn check_invalid_input_fails() {
@@ -1103,6 +1682,250 @@ fn check_restrict_cbmc_args() {
         );
     }
 
+    #[test]
+    fn check_cbmc_verbosity() {
+        let unstable = Some(UnstableFeature::UnstableOptions);
+        check_opt!("--cbmc-verbosity 3", unstable, cbmc_verbosity, Some(3));
+        assert!(parse_unstable_disabled("--cbmc-verbosity 11").is_err());
+        expect_validation_error(
+            "kani -Z unstable-options file.rs --cbmc-verbosity 3 --cbmc-args --verbosity 5",
+            ErrorKind::ArgumentConflict,
+        );
+    }
+
+    #[test]
+    fn check_harness_pattern_unstable() {
+        let unstable = Some(UnstableFeature::UnstableOptions);
+        check_opt!("--harness-pattern parser::*", unstable, harness_patterns, vec!["parser::*"]);
+        check_opt!("--harness-regex check_.*", unstable, harness_regexes, vec!["check_.*"]);
+        check_opt!(
+            "--harness foo --exclude-harness foo --exclude-harness slow::*",
+            unstable,
+            exclude_harnesses,
+            vec!["foo", "slow::*"]
+        );
+        expect_validation_error(
+            "kani -Z unstable-options file.rs --harness-regex check_(",
+            ErrorKind::InvalidValue,
+        );
+    }
+
+    #[test]
+    fn check_with_contract_dependencies_unstable() {
+        check_unstable_flag!("--with-contract-dependencies", with_contract_dependencies);
+    }
+
+    #[test]
+    fn check_quick_unstable() {
+        check_unstable_flag!("--quick", quick);
+    }
+
+    #[test]
+    fn check_quick_settings() {
+        assert_eq!(QuickSettings::with_overrides(&[]), Ok(QuickSettings::default()));
+        let overrides = ["default-unwind=5", "harness-timeout=2m", "overflow-checks=true"];
+        let settings = QuickSettings::with_overrides(&overrides.map(String::from)).unwrap();
+        assert_eq!(
+            settings,
+            QuickSettings {
+                default_unwind: 5,
+                harness_timeout: Timeout::from_str("2m").unwrap(),
+                overflow_checks: true,
+                ..QuickSettings::default()
+            }
+        );
+        for invalid in ["unwind=2", "fail-fast", "fail-fast=yes", "harness-timeout="] {
+            assert!(QuickSettings::with_overrides(&[invalid.to_string()]).is_err(), "{invalid}");
+        }
+
+        let unstable = UnstableFeature::UnstableOptions;
+        assert!(
+            parse_unstable_enabled("--quick --quick-setting fail-fast=false", unstable).is_ok()
+        );
+        let err = parse_unstable_enabled("--quick --quick-setting unwind=2", unstable).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidValue);
+        // Settings are only checked when they are used.
+        assert!(parse_unstable_enabled("--quick-setting unwind=2", unstable).is_ok());
+    }
+
+    #[test]
+    fn check_auto_unwind() {
+        check_unstable_flag!("--auto-unwind", auto_unwind);
+        check_opt!(
+            "--auto-unwind --auto-unwind-max 16",
+            Some(UnstableFeature::UnstableOptions),
+            auto_unwind_max,
+            Some(16)
+        );
+
+        let err =
+            StandaloneArgs::try_parse_from("kani file.rs --auto-unwind-max 16".split_whitespace())
+                .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+        assert!(
+            StandaloneArgs::try_parse_from(
+                "kani file.rs --auto-unwind --auto-unwind-max 0".split_whitespace()
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn check_smoke_test() {
+        check_opt!("--smoke-test", Some(UnstableFeature::UnstableOptions), smoke_test, Some(100));
+        check_opt!("--smoke-test=5", Some(UnstableFeature::UnstableOptions), smoke_test, Some(5));
+        check_opt!(
+            "--smoke-test --smoke-then-verify",
+            Some(UnstableFeature::UnstableOptions),
+            smoke_then_verify,
+            true
+        );
+
+        let err =
+            StandaloneArgs::try_parse_from("kani file.rs --smoke-then-verify".split_whitespace())
+                .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+        assert!(
+            StandaloneArgs::try_parse_from("kani file.rs --smoke-test=0".split_whitespace())
+                .is_err()
+        );
+
+        let cargo_args = ["cargo-kani", "-Z", "unstable-options", "--smoke-test"];
+        let err = CargoKaniArgs::try_parse_from(cargo_args).unwrap().validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn check_coverage_out() {
+        let args =
+            "kani file.rs -Z source-coverage --coverage --coverage-out cov --coverage-overwrite";
+        let parsed = StandaloneArgs::try_parse_from(args.split_whitespace()).unwrap();
+        assert_eq!(parsed.verify_opts.coverage_out, Some(PathBuf::from("cov")));
+        assert!(parsed.verify_opts.coverage_overwrite);
+
+        let err =
+            StandaloneArgs::try_parse_from("kani file.rs --coverage-out cov".split_whitespace())
+                .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+        let err = StandaloneArgs::try_parse_from(
+            "kani file.rs -Z source-coverage --coverage --coverage-overwrite".split_whitespace(),
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn check_junit_xml_unstable() {
+        let unstable = Some(UnstableFeature::UnstableOptions);
+        check_opt!(
+            "--junit-xml report.xml",
+            unstable,
+            junit_xml,
+            Some(PathBuf::from("report.xml"))
+        );
+    }
+
+    #[test]
+    fn check_sarif_unstable() {
+        let unstable = Some(UnstableFeature::UnstableOptions);
+        check_opt!("--sarif report.sarif", unstable, sarif, Some(PathBuf::from("report.sarif")));
+    }
+
+    #[test]
+    fn check_event_log_unstable() {
+        let unstable = Some(UnstableFeature::UnstableOptions);
+        check_opt!(
+            "--event-log events.jsonl",
+            unstable,
+            event_log,
+            Some(PathBuf::from("events.jsonl"))
+        );
+    }
+
+    #[test]
+    fn check_output_format_json() {
+        let unstable = Some(UnstableFeature::UnstableOptions);
+        check_opt!("--output-format json", unstable, output_format, OutputFormat::Json);
+        check_opt!("--output-format json --jobs 4", unstable, output_format, OutputFormat::Json);
+
+        let args = "kani file.rs -Z unstable-options -Z concrete-playback --output-format json \
+            --concrete-playback print";
+        let res = StandaloneArgs::try_parse_from(args.split_whitespace()).unwrap();
+        assert_eq!(res.verify_opts.validate().unwrap_err().kind(), ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn check_export_flags_for_subcommands() {
+        // Each case is a full `cargo kani` invocation, and `None` if it is valid, or the message
+        // that explains why it's rejected.
+        let export = "--export-json out.json";
+        let streaming = "--export-json out.json --export-json-streaming";
+        let merge = "--export-json out.json --export-json-merge";
+        let mut cases = vec![];
+        // Subcommands that never write an export reject both flags.
+        for subcommand in
+            ["baseline list", "diff old.json new.json", "history", "new-proof foo", "playback"]
+        {
+            for flags in [export, streaming] {
+                cases.push((format!("{flags} {subcommand}"), Some("does not write a JSON export")));
+            }
+        }
+        // `list` writes its JSON document to the export path, which cannot be streamed.
+        cases.push((format!("{export} list"), None));
+        cases.push((format!("{streaming} list"), Some("cannot be streamed")));
+        cases.push((format!("{merge} list"), Some("cannot be merged")));
+        // `autoharness` takes the flags after the subcommand, like `-Z autoharness`.
+        cases.push((format!("{export} autoharness -Z autoharness"), Some("pass it after")));
+        cases.push((
+            format!("autoharness -Z autoharness --list {streaming}"),
+            Some("cannot be streamed"),
+        ));
+        cases.push((format!("autoharness -Z autoharness --list {export}"), None));
+        cases.push((format!("autoharness -Z autoharness {streaming}"), None));
+
+        for (args, expected) in cases {
+            let args = format!("cargo-kani {args}");
+            let result = CargoKaniArgs::try_parse_from(args.split_whitespace()).unwrap().validate();
+            match (result, expected) {
+                (Ok(()), None) => {}
+                (Err(err), Some(message)) => {
+                    assert_eq!(err.kind(), ErrorKind::ArgumentConflict, "{args}");
+                    assert!(err.to_string().contains(message), "{args}: {err}");
+                }
+                (result, _) => panic!("unexpected result for `{args}`: {result:?}"),
+            }
+        }
+
+        // `kani` rejects verification flags before any subcommand.
+        for subcommand in ["diff old.json new.json", "list file.rs", "playback file.rs"] {
+            let args = format!("kani {export} {subcommand}");
+            assert!(StandaloneArgs::try_parse_from(args.split_whitespace()).is_err());
+        }
+    }
+
+    #[test]
+    fn check_export_json_merge() {
+        check_opt!("--export-json runs.json --export-json-merge", None, export_json_merge, true);
+        assert!(parse_unstable_disabled("--export-json-merge").is_err());
+        let args = "--export-json runs.json --export-json-merge --export-json-streaming";
+        assert!(parse_unstable_disabled(args).is_err());
+    }
+
+    #[test]
+    fn check_incremental_unstable() {
+        check_unstable_flag!("--incremental", incremental);
+    }
+
+    #[test]
+    fn check_no_history_unstable() {
+        check_unstable_flag!("--no-history", no_history);
+    }
+
+    #[test]
+    fn check_print_config_unstable() {
+        check_unstable_flag!("--print-config", print_config);
+    }
+
     #[test]
     fn check_disable_slicing_unstable() {
         check_unstable_flag!("--no-slice-formula", no_slice_formula);
@@ -1123,6 +1946,114 @@ fn check_concrete_playback_unstable() {
         check("kani file.rs --concrete-playback=print");
     }
 
+    #[test]
+    fn check_compare_baseline() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let baseline = dir.path().join("baseline.json");
+        std::fs::write(&baseline, "{}").unwrap();
+        let baseline = baseline.to_str().unwrap();
+        let unstable = Some(UnstableFeature::UnstableOptions);
+        check_opt!(
+            &format!("--compare-baseline {baseline} --fail-on-regression"),
+            unstable,
+            fail_on_regression,
+            true
+        );
+        check_opt!(
+            &format!("--compare-baseline {baseline} --baseline-runtime-threshold 50"),
+            unstable,
+            baseline_runtime_threshold,
+            50.0
+        );
+
+        let err =
+            StandaloneArgs::try_parse_from("kani file.rs --fail-on-regression".split_whitespace())
+                .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+        expect_validation_error(
+            &format!(
+                "kani file.rs -Z unstable-options --compare-baseline {baseline} \
+                --baseline-runtime-threshold=-5"
+            ),
+            ErrorKind::InvalidValue,
+        );
+        expect_validation_error(
+            "kani file.rs -Z unstable-options --compare-baseline missing.json",
+            ErrorKind::InvalidValue,
+        );
+
+        // Saved baselines are resolved once the target directory is known.
+        let args = CargoKaniArgs::try_parse_from(
+            "cargo-kani -Z unstable-options --compare-baseline baseline:main".split_whitespace(),
+        )
+        .unwrap();
+        assert_eq!(args.verify_opts.saved_baseline(), Some("main"));
+        assert!(args.validate().is_ok());
+        expect_validation_error(
+            "kani file.rs -Z unstable-options --compare-baseline baseline:../main",
+            ErrorKind::InvalidValue,
+        );
+    }
+
+    #[test]
+    fn check_xfail() {
+        let unstable = Some(UnstableFeature::UnstableOptions);
+        check_opt!(
+            "--xfail proofs::check_a --xfail proofs::check_b=overflow --strict-xfail",
+            unstable,
+            strict_xfail,
+            true
+        );
+        let args = parse_unstable_enabled(
+            "--xfail proofs::check_a --xfail proofs::check_b=overflow",
+            UnstableFeature::UnstableOptions,
+        )
+        .unwrap();
+        assert_eq!(args.verify_opts.xfail_reason("proofs::check_a"), Some(""));
+        assert_eq!(args.verify_opts.xfail_reason("proofs::check_b"), Some("overflow"));
+        assert_eq!(args.verify_opts.xfail_reason("proofs::check"), None);
+
+        let err = StandaloneArgs::try_parse_from("kani file.rs --strict-xfail".split_whitespace())
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn check_dry_run() {
+        check_unstable_flag!("--dry-run", dry_run);
+        let err =
+            parse_unstable_enabled("--dry-run --only-codegen", UnstableFeature::UnstableOptions)
+                .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn check_trace_mode() {
+        let trace_mode = |args: &str| {
+            StandaloneArgs::try_parse_from(args.split_whitespace())
+                .unwrap()
+                .verify_opts
+                .trace_mode()
+        };
+        assert_eq!(trace_mode("kani file.rs"), TraceMode::OnFailure);
+        assert_eq!(trace_mode("kani file.rs --trace always"), TraceMode::Always);
+        assert_eq!(trace_mode("kani file.rs --trace never"), TraceMode::Never);
+        assert_eq!(
+            trace_mode("kani file.rs --trace never -Z concrete-playback --concrete-playback print"),
+            TraceMode::OnFailure
+        );
+        assert_eq!(
+            trace_mode(
+                "kani file.rs --trace always -Z concrete-playback --concrete-playback print"
+            ),
+            TraceMode::Always
+        );
+        assert!(
+            StandaloneArgs::try_parse_from("kani file.rs --trace sometimes".split_whitespace())
+                .is_err()
+        );
+    }
+
     /// Check if parsing the given argument string results in the given error.
     fn expect_validation_error(arg: &str, err: ErrorKind) {
         let args = StandaloneArgs::try_parse_from(arg.split_whitespace()).unwrap();
@@ -1179,6 +2110,27 @@ fn check_kani_playback() {
         assert!(matches!(args.command, Some(StandaloneSubcommand::Playback(..))));
     }
 
+    #[test]
+    fn check_diff_subcommand() {
+        let input = "kani diff old.json new.json --runtime-threshold 50".split_whitespace();
+        let args = StandaloneArgs::try_parse_from(input).unwrap();
+        let Some(StandaloneSubcommand::Diff(diff)) = args.command else {
+            panic!("Expected the diff subcommand");
+        };
+        assert_eq!(diff.old, PathBuf::from("old.json"));
+        assert_eq!(diff.new, PathBuf::from("new.json"));
+        assert_eq!(diff.runtime_threshold, 50.0);
+
+        let args =
+            CargoKaniArgs::try_parse_from(["cargo-kani", "diff", "old.json", "new.json"]).unwrap();
+        let Some(CargoKaniSubcommand::Diff(diff)) = args.command else {
+            panic!("Expected the diff subcommand");
+        };
+        assert_eq!(diff.runtime_threshold, crate::frontend::DEFAULT_RUNTIME_THRESHOLD_PERCENT);
+
+        assert!(StandaloneArgs::try_parse_from(["kani", "diff", "old.json"]).is_err());
+    }
+
     #[test]
     fn check_standalone_does_not_accept_cargo_opts() {
         fn check_invalid_args<'a, I>(args: I)
@@ -0,0 +1,65 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the subcommand handling of the new-proof subcommand
+
+use std::path::PathBuf;
+
+use crate::args::{CommonArgs, ValidateArgs};
+use clap::{Error, Parser, error::ErrorKind};
+
+/// Generate a proof harness skeleton for a function of the current package
+#[derive(Debug, Parser)]
+pub struct CargoNewProofArgs {
+    #[command(flatten)]
+    pub common_args: CommonArgs,
+
+    /// The path of the function to verify, e.g. `parser::parse_header` or
+    /// `my_crate::parser::parse_header`.
+    #[arg(value_name = "FUNCTION")]
+    pub function: String,
+
+    /// The file that the harness is added to, relative to the directory of the crate root.
+    /// The file is created and declared as a `#[cfg(kani)]` module if it doesn't exist.
+    #[arg(long, value_name = "FILE", default_value = "proofs.rs")]
+    pub proofs_file: PathBuf,
+}
+
+impl ValidateArgs for CargoNewProofArgs {
+    fn validate(&self) -> Result<(), Error> {
+        self.common_args.validate()?;
+        // The function path is passed to the compiler as an autoharness pattern, which can't
+        // contain whitespace.
+        if self.function.is_empty() || self.function.contains(char::is_whitespace) {
+            return Err(Error::raw(
+                ErrorKind::InvalidValue,
+                format!("invalid function path `{}`", self.function),
+            ));
+        }
+        if self.proofs_file.is_absolute() || self.proofs_file.extension() != Some("rs".as_ref()) {
+            return Err(Error::raw(
+                ErrorKind::InvalidValue,
+                "`--proofs-file` must be a relative path to a `.rs` file",
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_new_proof_args() {
+        let args = CargoNewProofArgs::try_parse_from(["new-proof", "parser::parse"]).unwrap();
+        assert_eq!(args.function, "parser::parse");
+        assert_eq!(args.proofs_file, PathBuf::from("proofs.rs"));
+        assert!(args.validate().is_ok());
+
+        let args = CargoNewProofArgs::try_parse_from(["new-proof", "f", "--proofs-file", "proofs"])
+            .unwrap();
+        assert_eq!(args.validate().unwrap_err().kind(), ErrorKind::InvalidValue);
+        let args = CargoNewProofArgs::try_parse_from(["new-proof", "a b"]).unwrap();
+        assert_eq!(args.validate().unwrap_err().kind(), ErrorKind::InvalidValue);
+    }
+}
@@ -0,0 +1,159 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Resolve the value and the source of every argument, as printed by `--print-config` and
+//! exported under `session.config`.
+
+use crate::args_toml::{ArgSource, SourcedArg};
+use anyhow::Result;
+use clap::parser::ValueSource;
+use clap::{Arg, ArgAction, Command};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The value an argument ended up with after merging all sources.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResolvedArg {
+    /// The argument identifier, which is the name of the corresponding field.
+    pub name: String,
+    /// `null` if the argument has no value, a string if it has one and an array otherwise.
+    pub value: Value,
+    pub source: ArgSource,
+}
+
+/// Resolve every argument of `command` when it is invoked with `args`.
+pub fn resolve_config(command: Command, args: &[SourcedArg]) -> Result<Vec<ResolvedArg>> {
+    let matches = command.clone().try_get_matches_from(args.iter().map(|arg| &arg.arg))?;
+    let resolved = command
+        .get_arguments()
+        .filter(|arg| {
+            !matches!(
+                arg.get_action(),
+                ArgAction::Help | ArgAction::HelpShort | ArgAction::HelpLong | ArgAction::Version
+            )
+        })
+        .map(|arg| {
+            let name = arg.get_id().as_str();
+            let values: Vec<Value> = matches
+                .try_get_raw(name)
+                .ok()
+                .flatten()
+                .into_iter()
+                .flatten()
+                .map(|value| Value::String(value.to_string_lossy().into_owned()))
+                .collect();
+            let value = match values.len() {
+                0 => Value::Null,
+                1 => values.into_iter().next().unwrap(),
+                _ => Value::Array(values),
+            };
+            let source = match matches.value_source(name) {
+                Some(ValueSource::CommandLine) => command_line_source(arg, args),
+                Some(ValueSource::EnvVariable) => ArgSource::Env,
+                _ => ArgSource::Default,
+            };
+            ResolvedArg { name: name.to_string(), value, source }
+        })
+        .collect();
+    Ok(resolved)
+}
+
+/// Whether `arg` was given on the command line or read from `Cargo.toml`.
+/// The command line takes precedence, so any occurrence there wins.
+fn command_line_source(arg: &Arg, args: &[SourcedArg]) -> ArgSource {
+    if arg.is_positional() {
+        return ArgSource::Cli;
+    }
+    let long = arg.get_long().map(|long| format!("--{long}"));
+    let short = arg.get_short().map(|short| format!("-{short}"));
+    let is_occurrence = |token: &str| {
+        long.as_ref().is_some_and(|long| {
+            token == long || token.strip_prefix(long.as_str()).is_some_and(|v| v.starts_with('='))
+        }) || short
+            .as_ref()
+            .is_some_and(|short| !token.starts_with("--") && token.starts_with(short))
+    };
+    // Everything after `--cbmc-args` is passed to CBMC and must not be confused with our flags.
+    let kani_args = args.iter().skip(1).scan(false, |after_cbmc_args, arg| {
+        let skip = *after_cbmc_args;
+        *after_cbmc_args |= arg.arg == "--cbmc-args";
+        (!skip).then_some(arg)
+    });
+    let sources: Vec<ArgSource> = kani_args
+        .filter(|arg| is_occurrence(&arg.arg.to_string_lossy()))
+        .map(|arg| arg.source)
+        .collect();
+    if sources.is_empty() || sources.contains(&ArgSource::Cli) {
+        ArgSource::Cli
+    } else {
+        ArgSource::Toml
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::StandaloneArgs;
+    use clap::CommandFactory;
+
+    fn resolve(args: &[(&str, ArgSource)]) -> Vec<ResolvedArg> {
+        let args: Vec<_> = args.iter().map(|(arg, source)| SourcedArg::new(arg, *source)).collect();
+        resolve_config(StandaloneArgs::command(), &args).unwrap()
+    }
+
+    fn find<'a>(config: &'a [ResolvedArg], name: &str) -> &'a ResolvedArg {
+        config.iter().find(|arg| arg.name == name).unwrap()
+    }
+
+    #[test]
+    fn check_resolved_sources() {
+        let config = resolve(&[
+            ("kani", ArgSource::Cli),
+            ("--default-unwind", ArgSource::Toml),
+            ("2", ArgSource::Toml),
+            ("file.rs", ArgSource::Cli),
+            ("--harness", ArgSource::Cli),
+            ("a", ArgSource::Cli),
+            ("--harness", ArgSource::Cli),
+            ("b", ArgSource::Cli),
+            ("--cbmc-args", ArgSource::Toml),
+            ("--unwind", ArgSource::Toml),
+            ("3", ArgSource::Toml),
+        ]);
+        let expected = |name: &str, value: Value, source| ResolvedArg {
+            name: name.to_string(),
+            value,
+            source,
+        };
+        assert_eq!(
+            find(&config, "default_unwind"),
+            &expected("default_unwind", "2".into(), ArgSource::Toml)
+        );
+        assert_eq!(find(&config, "input"), &expected("input", "file.rs".into(), ArgSource::Cli));
+        assert_eq!(
+            find(&config, "harnesses"),
+            &expected("harnesses", serde_json::json!(["a", "b"]), ArgSource::Cli)
+        );
+        // `--unwind` is a CBMC argument here, not the Kani one.
+        assert_eq!(find(&config, "unwind"), &expected("unwind", Value::Null, ArgSource::Default));
+        assert_eq!(
+            find(&config, "cbmc_args"),
+            &expected("cbmc_args", serde_json::json!(["--unwind", "3"]), ArgSource::Toml)
+        );
+        assert_eq!(find(&config, "tests"), &expected("tests", "false".into(), ArgSource::Default));
+        assert!(config.iter().all(|arg| arg.name != "help"));
+    }
+
+    #[test]
+    fn check_command_line_overrides_toml() {
+        let config = resolve(&[
+            ("kani", ArgSource::Cli),
+            ("--default-unwind", ArgSource::Toml),
+            ("2", ArgSource::Toml),
+            ("--default-unwind=4", ArgSource::Cli),
+            ("file.rs", ArgSource::Cli),
+        ]);
+        let default_unwind = find(&config, "default_unwind");
+        assert_eq!(default_unwind.value, Value::from("4"));
+        assert_eq!(default_unwind.source, ArgSource::Cli);
+    }
+}
@@ -3,25 +3,84 @@
 
 use anyhow::{Result, bail};
 use clap::Parser;
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
-use std::ffi::OsString;
-use std::path::PathBuf;
-use std::process::Command;
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
 use toml::Value;
 use toml::value::Table;
 
-/// Produce the list of arguments to pass to ourself (cargo-kani).
+/// Where the value of an argument came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArgSource {
+    /// The argument was not given, so its default value is used.
+    Default,
+    /// The argument was read from the Kani configuration in `Cargo.toml`.
+    Toml,
+    /// The argument was read from an environment variable.
+    Env,
+    /// The argument was given on the command line.
+    Cli,
+}
+
+/// One argument of the effective command line together with its source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourcedArg {
+    pub arg: OsString,
+    pub source: ArgSource,
+}
+
+impl SourcedArg {
+    pub fn new(arg: impl Into<OsString>, source: ArgSource) -> SourcedArg {
+        SourcedArg { arg: arg.into(), source }
+    }
+}
+
+impl AsRef<OsStr> for SourcedArg {
+    fn as_ref(&self) -> &OsStr {
+        &self.arg
+    }
+}
+
+/// Produce the list of arguments to pass to ourself (cargo-kani), recording for each of them
+/// whether it was given on the command line or read from the Cargo.toml.
 ///
 /// The arguments passed via command line have precedence over the ones from the Cargo.toml.
-pub fn join_args(input_args: Vec<OsString>) -> Result<Vec<OsString>> {
-    let toml_path = cargo_locate_project(&input_args);
+pub fn join_args(input_args: Vec<OsString>) -> Result<Vec<SourcedArg>> {
+    let toml_path = cargo_locate_project(&input_args, false);
+    let tag = |args: Vec<OsString>, source| {
+        args.into_iter().map(|arg| SourcedArg::new(arg, source)).collect::<Vec<_>>()
+    };
     if toml_path.is_err() {
         // We're not inside a Cargo project. Don't error... yet.
-        return Ok(input_args);
+        return Ok(tag(input_args, ArgSource::Cli));
+    }
+    let toml_path = toml_path?;
+    let file = std::fs::read_to_string(&toml_path)?;
+    // When invoked from a workspace member, the `workspace.metadata.kani` table is in the manifest
+    // of the workspace root.
+    let workspace_file = cargo_locate_project(&input_args, true)
+        .ok()
+        .filter(|workspace_path| !same_file(workspace_path, &toml_path))
+        .map(std::fs::read_to_string)
+        .transpose()?;
+    let (kani_args, cbmc_args) = match workspace_file {
+        Some(workspace_file) => member_toml_to_args(&workspace_file, &file)?,
+        None => toml_to_args(&file)?,
+    };
+    merge_args(
+        tag(input_args, ArgSource::Cli),
+        tag(kani_args, ArgSource::Toml),
+        tag(cbmc_args, ArgSource::Toml),
+    )
+}
+
+fn same_file(path1: &Path, path2: &Path) -> bool {
+    match (path1.canonicalize(), path2.canonicalize()) {
+        (Ok(path1), Ok(path2)) => path1 == path2,
+        _ => path1 == path2,
     }
-    let file = std::fs::read_to_string(toml_path?)?;
-    let (kani_args, cbmc_args) = toml_to_args(&file)?;
-    merge_args(input_args, kani_args, cbmc_args)
 }
 
 /// Join the arguments passed via command line with the ones found in the Cargo.toml.
@@ -35,11 +94,11 @@ pub fn join_args(input_args: Vec<OsString>) -> Result<Vec<OsString>> {
 /// ```text
 /// <bin_name> [<cfg_kani_args>]* [<cmd_kani_args>]* [--cbmc-args [<cfg_cbmc_args>]* [<cmd_cbmc_args>]*]
 /// ```
-fn merge_args(
-    cmd_args: Vec<OsString>,
-    cfg_kani_args: Vec<OsString>,
-    cfg_cbmc_args: Vec<OsString>,
-) -> Result<Vec<OsString>> {
+fn merge_args<T: Clone + AsRef<OsStr>>(
+    cmd_args: Vec<T>,
+    cfg_kani_args: Vec<T>,
+    cfg_cbmc_args: Vec<T>,
+) -> Result<Vec<T>> {
     let mut merged_args =
         vec![cmd_args.first().expect("Expected binary path as one argument").clone()];
     merged_args.extend(cfg_kani_args);
@@ -48,7 +107,7 @@ fn merge_args(
         // [<config_kani_args>]* [input_args]*
         merged_args.extend_from_slice(&cmd_args[1..]);
     } else {
-        let cbmc_flag = cmd_args.iter().enumerate().find(|&f| f.1 == "--cbmc-args");
+        let cbmc_flag = cmd_args.iter().enumerate().find(|&f| f.1.as_ref() == "--cbmc-args");
         if let Some((idx, _)) = cbmc_flag {
             // Both command line and config file have --cbmc-args. Merge them to be in order.
             merged_args.extend_from_slice(&cmd_args[1..idx]);
@@ -65,24 +124,15 @@ fn merge_args(
     Ok(merged_args)
 }
 
-/// `locate-project` produces a response like: `/full/path/to/src/cargo-kani/Cargo.toml`
-fn cargo_locate_project(input_args: &[OsString]) -> Result<PathBuf> {
+/// Locate the manifest of the package, or with `workspace` of the workspace root, that cargo-kani
+/// was invoked for.
+fn cargo_locate_project(input_args: &[OsString], workspace: bool) -> Result<PathBuf> {
     // Try parsing our command line arguments as they presently look, to see if a "manifest-path" has been given.
     let current_args = crate::args::CargoKaniArgs::parse_from(input_args);
-
-    if let Some(path) = current_args.verify_opts.cargo.manifest_path {
-        Ok(path)
-    } else {
-        let cmd =
-            Command::new("cargo").args(["locate-project", "--message-format", "plain"]).output()?;
-        if !cmd.status.success() {
-            let err = std::str::from_utf8(&cmd.stderr)?;
-            bail!("{}", err);
-        }
-        let path = std::str::from_utf8(&cmd.stdout)?;
-        // A trim is essential: remove the trailing newline
-        Ok(path.trim().into())
-    }
+    crate::project_root::cargo_locate_project(
+        current_args.verify_opts.cargo.manifest_path.as_deref(),
+        workspace,
+    )
 }
 
 /// Parse a config toml string and extract the cargo-kani arguments we should try injecting.
@@ -90,6 +140,7 @@ fn cargo_locate_project(input_args: &[OsString]) -> Result<PathBuf> {
 /// We currently support the following entries:
 /// - flags: Flags that get directly passed to Kani.
 /// - unstable: Unstable features (it will be passed using `-Z` flag).
+/// - xfail: Harnesses expected to fail, with the reason why (passed using `--xfail`).
 ///
 /// The tables supported are:
 /// - "workspace.metadata.kani"
@@ -97,29 +148,65 @@ fn cargo_locate_project(input_args: &[OsString]) -> Result<PathBuf> {
 /// - "kani"
 fn toml_to_args(tomldata: &str) -> Result<(Vec<OsString>, Vec<OsString>)> {
     let config = tomldata.parse::<Table>()?;
+    tables_to_args(KANI_TABLES.iter().filter_map(|table| get_table(&config, table)))
+}
+
+/// Like [toml_to_args], for a workspace member whose workspace root has the manifest
+/// `workspace_tomldata`. The `workspace.metadata.kani` table of the workspace root applies to the
+/// member, and the tables of the member take precedence over it.
+fn member_toml_to_args(
+    workspace_tomldata: &str,
+    member_tomldata: &str,
+) -> Result<(Vec<OsString>, Vec<OsString>)> {
+    let workspace = workspace_tomldata.parse::<Table>()?;
+    let member = member_tomldata.parse::<Table>()?;
+    tables_to_args(
+        get_table(&workspace, KANI_TABLES[0])
+            .into_iter()
+            .chain(KANI_TABLES.iter().filter_map(|table| get_table(&member, table))),
+    )
+}
+
+/// The tables of the Kani configuration, in increasing order of precedence.
+const KANI_TABLES: [&str; 3] = ["workspace.metadata.kani", "package.metadata.kani", "kani"];
+
+fn tables_to_args<'a>(
+    tables: impl Iterator<Item = &'a Table>,
+) -> Result<(Vec<OsString>, Vec<OsString>)> {
     // To make testing easier, our function contract is to produce a stable ordering of flags for a given input.
     // Consequently, we use BTreeMap instead of HashMap here.
     let mut map: BTreeMap<String, Value> = BTreeMap::new();
-    let tables = ["workspace.metadata.kani", "package.metadata.kani", "kani"];
     let mut args = Vec::new();
 
     for table in tables {
-        if let Some(table) = get_table(&config, table) {
-            if let Some(entry) = table.get("flags")
-                && let Some(val) = entry.as_table()
-            {
-                map.extend(val.iter().map(|(x, y)| (x.to_owned(), y.to_owned())));
+        if let Some(entry) = table.get("flags")
+            && let Some(val) = entry.as_table()
+        {
+            map.extend(val.iter().map(|(x, y)| (x.to_owned(), y.to_owned())));
+        }
+
+        if let Some(entry) = table.get("unstable")
+            && let Some(val) = entry.as_table()
+        {
+            args.append(
+                &mut val
+                    .iter()
+                    .filter_map(|(k, v)| unstable_entry(k, v).transpose())
+                    .collect::<Result<Vec<_>>>()?,
+            );
+        }
+
+        if let Some(entry) = table.get("xfail")
+            && let Some(val) = entry.as_table()
+        {
+            for (harness, reason) in val {
+                args.append(&mut xfail_entry(harness, reason)?);
             }
+        }
 
-            if let Some(entry) = table.get("unstable")
-                && let Some(val) = entry.as_table()
-            {
-                args.append(
-                    &mut val
-                        .iter()
-                        .filter_map(|(k, v)| unstable_entry(k, v).transpose())
-                        .collect::<Result<Vec<_>>>()?,
-                );
+        if let Some(val) = get_table(table, "profile.quick") {
+            for (setting, value) in val {
+                args.append(&mut quick_setting_entry(setting, value)?);
             }
         }
     }
@@ -148,6 +235,26 @@ fn unstable_entry(name: &String, value: &Value) -> Result<Option<OsString>> {
     }
 }
 
+/// Parse an entry from the xfail table and convert it into a `--xfail <harness>=<reason>` argument
+fn xfail_entry(harness: &str, reason: &Value) -> Result<Vec<OsString>> {
+    let Some(reason) = reason.as_str() else {
+        bail!("Expected the reason why `{harness}` is expected to fail but found `{reason}`")
+    };
+    Ok(vec!["--xfail".into(), format!("{harness}={reason}").into()])
+}
+
+/// Translates one entry of the `profile.quick` table into a `--quick-setting` argument.
+fn quick_setting_entry(setting: &str, value: &Value) -> Result<Vec<OsString>> {
+    let value = match value {
+        Value::String(s) => s.clone(),
+        Value::Integer(_) | Value::Boolean(_) => value.to_string(),
+        _ => bail!(
+            "Expected a string, integer or boolean for quick setting `{setting}` but found `{value}`"
+        ),
+    };
+    Ok(vec!["--quick-setting".into(), format!("{setting}={value}").into()])
+}
+
 /// Translates one toml entry (flag, value) into arguments and inserts it into `args`
 fn insert_arg_from_toml(flag: &str, value: &Value, args: &mut Vec<OsString>) -> Result<()> {
     match value {
@@ -235,6 +342,22 @@ fn check_toml_parsing() {
         assert_eq!(b.1, vec!["--cbmc-args", "--fake"]);
     }
 
+    #[test]
+    fn check_member_toml_parsing() {
+        let workspace = "[workspace]
+                         members = [\"member\"]
+                         [workspace.metadata.kani]
+                         flags = { default-unwind = \"2\", output-format = \"terse\" }
+                         [package.metadata.kani]
+                         flags = { coverage = true }";
+        let member = "[package.metadata.kani]
+                      flags = { default-unwind = \"4\" }";
+        let (args, cbmc_args) = member_toml_to_args(workspace, member).unwrap();
+        // Only the workspace table of the root applies, and the member overrides it.
+        assert_eq!(args, vec!["--default-unwind", "4", "--output-format", "terse"]);
+        assert!(cbmc_args.is_empty());
+    }
+
     #[test]
     fn check_merge_args_with_only_command_line_args() {
         let cmd_args: Vec<OsString> =
@@ -272,6 +395,30 @@ fn check_merge_args_order() {
         assert_eq!(merged[5], OsString::from("--fake"));
     }
 
+    #[test]
+    fn check_merge_args_records_sources() {
+        let cli = |arg: &str| SourcedArg::new(arg, ArgSource::Cli);
+        let toml = |arg: &str| SourcedArg::new(arg, ArgSource::Toml);
+        let merged = merge_args(
+            vec![cli("kani"), cli("--debug"), cli("--cbmc-args"), cli("--fake")],
+            vec![toml("--default-unwind"), toml("2")],
+            vec![toml("--cbmc-args"), toml("--trace")],
+        )
+        .unwrap();
+        assert_eq!(
+            merged,
+            vec![
+                cli("kani"),
+                toml("--default-unwind"),
+                toml("2"),
+                cli("--debug"),
+                toml("--cbmc-args"),
+                toml("--trace"),
+                cli("--fake"),
+            ]
+        );
+    }
+
     #[test]
     fn check_multiple_table_works() {
         let data = "[workspace.metadata.kani.unstable]
@@ -317,4 +464,52 @@ fn check_unstable_entry_invalid() {
         let name = String::from("feature");
         assert!(unstable_entry(&name, &Value::String("".to_string())).is_err());
     }
+
+    #[test]
+    fn check_xfail_table_works() {
+        let data = "[package.metadata.kani.xfail]
+                         \"proofs::check_overflow\"=\"known overflow in parse\"
+                         [package.metadata.kani.flags]
+                         harness-timeout=\"10s\"";
+        let (kani_args, cbmc_args) = toml_to_args(data).unwrap();
+        assert_eq!(
+            kani_args,
+            vec![
+                "--xfail",
+                "proofs::check_overflow=known overflow in parse",
+                "--harness-timeout",
+                "10s"
+            ]
+        );
+        assert!(cbmc_args.is_empty());
+
+        let invalid = "[package.metadata.kani.xfail]
+                       check=true";
+        assert!(toml_to_args(invalid).is_err());
+    }
+
+    #[test]
+    fn check_quick_profile_table_works() {
+        let data = "[package.metadata.kani.profile.quick]
+                         default-unwind=4
+                         overflow-checks=true
+                         harness-timeout=\"5m\"";
+        let (kani_args, cbmc_args) = toml_to_args(data).unwrap();
+        assert_eq!(
+            kani_args,
+            vec![
+                "--quick-setting",
+                "default-unwind=4",
+                "--quick-setting",
+                "harness-timeout=5m",
+                "--quick-setting",
+                "overflow-checks=true",
+            ]
+        );
+        assert!(cbmc_args.is_empty());
+
+        let invalid = "[package.metadata.kani.profile.quick]
+                       default-unwind=[1]";
+        assert!(toml_to_args(invalid).is_err());
+    }
 }
@@ -7,22 +7,28 @@
 use crate::args::autoharness_args::{
     CargoAutoharnessArgs, CommonAutoharnessArgs, StandaloneAutoharnessArgs,
 };
-use crate::args::common::UnstableFeature;
+use crate::args::common::{UnstableFeature, Verbosity};
 use crate::call_cbmc::VerificationStatus;
 use crate::harness_runner::HarnessResult;
 use crate::list::collect_metadata::process_metadata;
 use crate::list::output::output_list_results;
 use crate::project::{Project, standalone_project, std_project};
 use crate::session::KaniSession;
-use crate::{InvocationType, print_kani_version, project, verify_project};
+use crate::{
+    Invocation, InvocationType, dry_run_project, export_skipped_run, print_kani_version, project,
+    verify_project,
+};
 use anyhow::Result;
 use comfy_table::Table as PrettyTable;
-use kani_metadata::{AutoHarnessSkipReason, KaniMetadata};
+use kani_metadata::{AutoHarnessSkipReason, HarnessMetadata, KaniMetadata};
+use skip_cache::{CACHED_SKIP_REASON, SkipCache, skip_cache_file};
+
+pub mod skip_cache;
 
 const AUTOHARNESS_TIMEOUT: &str = "60s";
 const LOOP_UNWIND_DEFAULT: u32 = 20;
 
-pub fn autoharness_cargo(args: CargoAutoharnessArgs) -> Result<()> {
+pub fn autoharness_cargo(args: CargoAutoharnessArgs, invocation: Invocation) -> Result<()> {
     let mut session = KaniSession::new(args.verify_opts)?;
     setup_session(&mut session, &args.common_autoharness_args);
 
@@ -30,10 +36,13 @@ pub fn autoharness_cargo(args: CargoAutoharnessArgs) -> Result<()> {
         print_kani_version(InvocationType::CargoKani(vec![]));
     }
     let project = project::cargo_project(&mut session, false)?;
-    postprocess_project(project, session, args.common_autoharness_args)
+    postprocess_project(project, session, args.common_autoharness_args, invocation)
 }
 
-pub fn autoharness_standalone(args: StandaloneAutoharnessArgs) -> Result<()> {
+pub fn autoharness_standalone(
+    args: StandaloneAutoharnessArgs,
+    invocation: Invocation,
+) -> Result<()> {
     let mut session = KaniSession::new(args.verify_opts)?;
     setup_session(&mut session, &args.common_autoharness_args);
 
@@ -47,7 +56,7 @@ pub fn autoharness_standalone(args: StandaloneAutoharnessArgs) -> Result<()> {
         standalone_project(&args.input, args.crate_name, &session)?
     };
 
-    postprocess_project(project, session, args.common_autoharness_args)
+    postprocess_project(project, session, args.common_autoharness_args, invocation)
 }
 
 /// Execute autoharness-specific KaniSession configuration.
@@ -63,8 +72,9 @@ fn setup_session(session: &mut KaniSession, common_autoharness_args: &CommonAuto
 /// After generating the automatic harnesses, postprocess metadata and run verification.
 fn postprocess_project(
     project: Project,
-    session: KaniSession,
+    mut session: KaniSession,
     common_autoharness_args: CommonAutoharnessArgs,
+    invocation: Invocation,
 ) -> Result<()> {
     if !session.args.common_args.quiet {
         print_autoharness_metadata(project.metadata.clone());
@@ -75,9 +85,22 @@ fn postprocess_project(
             list_metadata,
             common_autoharness_args.format,
             session.args.common_args.quiet,
+            session.args.common_args.verbose(),
+            session.args.export_json.as_deref(),
         );
     }
-    if session.args.only_codegen { Ok(()) } else { verify_project(project, session) }
+    if let Some(path) = skip_cache_file(&session.args, &project) {
+        let retry = common_autoharness_args.retry_unsupported;
+        session.autoharness_skip_cache =
+            Some(SkipCache::load(path, &project.get_all_harnesses(), retry));
+    }
+    if session.args.only_codegen {
+        export_skipped_run(&project, &session, invocation, None)
+    } else if session.args.dry_run {
+        dry_run_project(&project, &session, invocation)
+    } else {
+        verify_project(project, session, invocation)
+    }
 }
 
 /// Print automatic harness metadata to the terminal.
@@ -184,18 +207,37 @@ pub fn add_default_bounds(&mut self) {
         }
     }
 
+    /// The unsupported construct that a previous run found reachable from the function of
+    /// `harness`, if the skip cache skips it in this run.
+    pub fn cached_unsupported(&self, harness: &HarnessMetadata) -> Option<&str> {
+        self.autoharness_skip_cache.as_ref()?.skip_reason(harness)
+    }
+
+    /// Update the skip cache with the functions that reached an unsupported construct in this
+    /// run. Failing to do so only means they are verified again later, so it is a warning.
+    pub fn record_unsupported_functions(&self, results: &[HarnessResult<'_>]) {
+        let Some(cache) = &self.autoharness_skip_cache else { return };
+        if let Err(err) = cache.update(results) {
+            crate::util::warning(&format!("Failed to update the autoharness skip cache: {err:#}"));
+        }
+    }
+
     /// Prints the results from running the `autoharness` subcommand.
     pub fn print_autoharness_summary(
         &self,
         mut automatic: Vec<&HarnessResult<'_>>,
     ) -> Result<usize> {
         automatic.sort_by(|a, b| a.harness.pretty_name.cmp(&b.harness.pretty_name));
+        let (cached_skips, automatic): (Vec<_>, Vec<_>) =
+            automatic.into_iter().partition(|r| self.cached_unsupported(r.harness).is_some());
+        // Harnesses skipped by `--fail-fast` are listed with the successes, as not failing.
         let (successes, failures): (Vec<_>, Vec<_>) =
-            automatic.into_iter().partition(|r| r.result.status == VerificationStatus::Success);
+            automatic.into_iter().partition(|r| r.result.status != VerificationStatus::Failure);
 
         let succeeding = successes.len();
         let failing = failures.len();
-        let total = succeeding + failing;
+        let cached = cached_skips.len();
+        let total = succeeding + failing + cached;
 
         println!("\nAutoharness Summary:");
 
@@ -221,7 +263,16 @@ pub fn print_autoharness_summary(
                 failure.harness.crate_name.clone(),
                 failure.harness.pretty_name.clone(),
                 failure.harness.attributes.kind.to_string(),
-                failure.result.status.to_string(),
+                failure.result.outcome().to_string(),
+            ]);
+        }
+
+        for skip in &cached_skips {
+            verified_fns.add_row(vec![
+                skip.harness.crate_name.clone(),
+                skip.harness.pretty_name.clone(),
+                skip.harness.attributes.kind.to_string(),
+                format!("Skipped: {CACHED_SKIP_REASON}"),
             ]);
         }
 
@@ -229,6 +280,17 @@ pub fn print_autoharness_summary(
             println!("{verified_fns}");
         }
 
+        if cached > 0 {
+            println!(
+                "{cached} function(s) were not verified because previous runs found them to reach constructs that Kani does not support:"
+            );
+            for skip in &cached_skips {
+                let reason = self.cached_unsupported(skip.harness).unwrap_or_default();
+                println!("  - {}: {reason}", skip.harness.pretty_name);
+            }
+            println!("Pass --retry-unsupported to verify them again.");
+        }
+
         if failing > 0 {
             println!(
                 "Note that `kani autoharness` sets default --harness-timeout of {AUTOHARNESS_TIMEOUT} and --default-unwind of {LOOP_UNWIND_DEFAULT}."
@@ -239,8 +301,13 @@ pub fn print_autoharness_summary(
         }
 
         if total > 0 {
+            let cached_count = if cached == 0 {
+                String::new()
+            } else {
+                format!(" {cached} skipped as previously unsupported,")
+            };
             println!(
-                "Complete - {succeeding} successfully verified functions, {failing} failures, {total} total."
+                "Complete - {succeeding} successfully verified functions, {failing} failures,{cached_count} {total} total."
             );
         } else {
             println!("No functions were eligible for automatic verification.");
@@ -0,0 +1,228 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! This module implements the skip cache of the `autoharness` subcommand.
+//!
+//! Functions whose automatic harness reaches a Rust construct that Kani does not support are
+//! recorded in `<target-dir>/kani/autoharness-skip.json`, together with a hash of their source.
+//! Later runs don't verify these functions again as long as their source and the Kani version are
+//! unchanged, and report them as previously unsupported instead. `--retry-unsupported` verifies
+//! them anyway. Functions that fail for any other reason are never cached.
+
+use std::collections::BTreeMap;
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use kani_metadata::HarnessMetadata;
+use serde::{Deserialize, Serialize};
+
+use crate::args::VerificationArgs;
+use crate::call_cbmc::VerificationStatus;
+use crate::harness_runner::HarnessResult;
+use crate::project::Project;
+use crate::version::KANI_VERSION;
+
+const SKIP_CACHE_FILENAME: &str = "autoharness-skip.json";
+
+/// How functions skipped because of the cache are reported.
+pub const CACHED_SKIP_REASON: &str = "previously unsupported (cached)";
+
+/// The content of the skip cache file. Entries written by another Kani version are ignored, since
+/// the construct may be supported now.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SkipFile {
+    kani_version: String,
+    /// The functions that reached an unsupported construct, by [function_id].
+    functions: BTreeMap<String, SkipEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct SkipEntry {
+    /// The [body_hash] of the function when it was verified.
+    body_hash: String,
+    /// The description of the unsupported construct that was reachable.
+    reason: String,
+}
+
+/// The skip cache of a cargo project, and the functions it skips in this run.
+#[derive(Debug)]
+pub struct SkipCache {
+    path: PathBuf,
+    functions: BTreeMap<String, SkipEntry>,
+    /// The functions that are not verified in this run, by [function_id], with the description
+    /// of their unsupported construct.
+    skipped: BTreeMap<String, String>,
+}
+
+/// Compute the location of the skip cache for a cargo project.
+/// Returns `None` for standalone projects, like the other caches.
+pub fn skip_cache_file(args: &VerificationArgs, project: &Project) -> Option<PathBuf> {
+    let metadata = project.cargo_metadata.as_ref()?;
+    let target_dir =
+        args.target_dir.clone().unwrap_or_else(|| metadata.target_directory.clone().into());
+    Some(target_dir.join("kani").join(SKIP_CACHE_FILENAME))
+}
+
+fn function_id(harness: &HarnessMetadata) -> String {
+    format!("{}::{}", harness.crate_name, harness.pretty_name)
+}
+
+/// Hash the source of the function that `harness` was generated for.
+/// Returns `None` if the source cannot be read, in which case the function is never skipped.
+fn body_hash(harness: &HarnessMetadata) -> Option<String> {
+    let source = fs::read_to_string(&harness.original_file).ok()?;
+    let start = harness.original_start_line.saturating_sub(1);
+    let len = harness.original_end_line.saturating_sub(start);
+    let mut hasher = DefaultHasher::new();
+    KANI_VERSION.hash(&mut hasher);
+    source.lines().skip(start).take(len).for_each(|line| line.hash(&mut hasher));
+    Some(format!("{:016x}", hasher.finish()))
+}
+
+impl SkipCache {
+    /// Read the skip cache at `path`, and select the automatic harnesses among `harnesses` whose
+    /// function is cached with an unchanged source. With `retry`, nothing is skipped.
+    /// A missing or unreadable cache is empty.
+    pub fn load(path: PathBuf, harnesses: &[&HarnessMetadata], retry: bool) -> SkipCache {
+        let functions = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<SkipFile>(&content).ok())
+            .filter(|file| file.kani_version == KANI_VERSION)
+            .map(|file| file.functions)
+            .unwrap_or_default();
+        let skipped = if retry {
+            BTreeMap::new()
+        } else {
+            harnesses
+                .iter()
+                .filter(|harness| harness.is_automatically_generated)
+                .filter_map(|harness| {
+                    let id = function_id(harness);
+                    let entry = functions.get(&id)?;
+                    (body_hash(harness)? == entry.body_hash).then(|| (id, entry.reason.clone()))
+                })
+                .collect()
+        };
+        SkipCache { path, functions, skipped }
+    }
+
+    /// The description of the unsupported construct of `harness`, if it is skipped in this run.
+    pub fn skip_reason(&self, harness: &HarnessMetadata) -> Option<&str> {
+        self.skipped.get(&function_id(harness)).map(String::as_str)
+    }
+
+    /// Record the functions of the automatic harnesses in `results` that reached an unsupported
+    /// construct, and forget those that were verified without reaching one.
+    pub fn update(&self, results: &[HarnessResult<'_>]) -> Result<()> {
+        let mut functions = self.functions.clone();
+        for result in results.iter().filter(|r| r.harness.is_automatically_generated) {
+            let id = function_id(result.harness);
+            let verified = matches!(
+                result.result.status,
+                VerificationStatus::Success | VerificationStatus::Failure
+            );
+            if !verified || self.skipped.contains_key(&id) {
+                continue;
+            }
+            match (result.result.unsupported_construct(), body_hash(result.harness)) {
+                (Some(reason), Some(body_hash)) => {
+                    functions.insert(id, SkipEntry { body_hash, reason: reason.to_string() });
+                }
+                _ => {
+                    functions.remove(&id);
+                }
+            }
+        }
+        write_skip_file(&self.path, SkipFile { kani_version: KANI_VERSION.to_string(), functions })
+    }
+}
+
+fn write_skip_file(path: &Path, file: SkipFile) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(&file)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call_cbmc::VerificationResult;
+    use crate::cbmc_output_parser::CheckStatus;
+    use crate::frontend::tests::{mock_harness, mock_property, mock_result};
+
+    const UNSUPPORTED: &str = "call to foreign C function `ffi` is not currently supported by Kani";
+
+    fn harness(file: &Path) -> HarnessMetadata {
+        let mut harness = mock_harness("lib::uses_ffi");
+        harness.original_file = file.to_string_lossy().into_owned();
+        harness.original_start_line = 2;
+        harness.original_end_line = 4;
+        harness.is_automatically_generated = true;
+        harness
+    }
+
+    fn result(description: &str) -> VerificationResult {
+        let mut property = mock_property("unsupported_construct", 1, CheckStatus::Failure);
+        property.description = description.to_string();
+        mock_result(VerificationStatus::Failure, Ok(vec![property]))
+    }
+
+    fn cache(dir: &Path, harness: &HarnessMetadata, retry: bool) -> SkipCache {
+        SkipCache::load(dir.join("kani").join(SKIP_CACHE_FILENAME), &[harness], retry)
+    }
+
+    #[test]
+    fn check_unsupported_functions_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("lib.rs");
+        fs::write(&source, "// header\nfn uses_ffi() {\n    unsafe { ffi() }\n}\n").unwrap();
+        let harness = harness(&source);
+        assert_eq!(cache(dir.path(), &harness, false).skip_reason(&harness), None);
+
+        let results = [HarnessResult { harness: &harness, result: result(UNSUPPORTED) }];
+        cache(dir.path(), &harness, false).update(&results).unwrap();
+        assert_eq!(cache(dir.path(), &harness, false).skip_reason(&harness), Some(UNSUPPORTED));
+        assert_eq!(cache(dir.path(), &harness, true).skip_reason(&harness), None);
+
+        // Changes outside of the function don't invalidate the entry, changes in it do.
+        fs::write(&source, "// new header\nfn uses_ffi() {\n    unsafe { ffi() }\n}\n").unwrap();
+        assert_eq!(cache(dir.path(), &harness, false).skip_reason(&harness), Some(UNSUPPORTED));
+        fs::write(&source, "// header\nfn uses_ffi() {\n    unsafe { ffi2() }\n}\n").unwrap();
+        assert_eq!(cache(dir.path(), &harness, false).skip_reason(&harness), None);
+    }
+
+    #[test]
+    fn check_other_failures_are_not_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("lib.rs");
+        fs::write(&source, "// header\nfn uses_ffi() {\n    unsafe { ffi() }\n}\n").unwrap();
+        let harness = harness(&source);
+        let unsupported = [HarnessResult { harness: &harness, result: result(UNSUPPORTED) }];
+        cache(dir.path(), &harness, false).update(&unsupported).unwrap();
+
+        // Verifying the function again without reaching the construct forgets it.
+        let failed = [HarnessResult { harness: &harness, result: result("assertion failed") }];
+        cache(dir.path(), &harness, true).update(&failed).unwrap();
+        assert_eq!(cache(dir.path(), &harness, false).skip_reason(&harness), None);
+    }
+
+    #[test]
+    fn check_other_versions_are_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("lib.rs");
+        fs::write(&source, "// header\nfn uses_ffi() {\n    unsafe { ffi() }\n}\n").unwrap();
+        let harness = harness(&source);
+        let entry =
+            SkipEntry { body_hash: body_hash(&harness).unwrap(), reason: UNSUPPORTED.into() };
+        let file = SkipFile {
+            kani_version: "0.1.0".to_string(),
+            functions: BTreeMap::from([(function_id(&harness), entry)]),
+        };
+        write_skip_file(&dir.path().join("kani").join(SKIP_CACHE_FILENAME), file).unwrap();
+        assert_eq!(cache(dir.path(), &harness, false).skip_reason(&harness), None);
+    }
+}
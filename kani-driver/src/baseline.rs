@@ -0,0 +1,423 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! This module implements the named baselines of the `baseline` subcommand.
+//!
+//! `cargo kani baseline save <NAME>` stores the run exported by the latest
+//! `cargo kani --export-json`, as recorded in the run history, in
+//! `<target-dir>/kani/baselines/<NAME>.json`. Along with the export, a baseline records the git
+//! commit it was saved at and a fingerprint of the flags of the run, so that
+//! `--compare-baseline baseline:<NAME>` can warn about comparisons across configurations.
+//! Baselines are meant to outlive builds, so `--force-build` keeps them.
+
+use std::collections::BTreeMap;
+use std::collections::hash_map::DefaultHasher;
+use std::ffi::OsStr;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+use comfy_table::Table as PrettyTable;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+use crate::InvocationType;
+use crate::args::VerificationArgs;
+use crate::args::baseline_args::{BaselineCommand, CargoBaselineArgs};
+use crate::args::resolved_config::ResolvedArg;
+use crate::frontend::{RUN_ERROR_KEY, SESSION_KEY, VERIFICATION_RESULTS_KEY, read_export};
+use crate::history::{HISTORY_FILENAME, read_records};
+use crate::project::Project;
+use crate::session::KaniSession;
+use crate::util::warning;
+use crate::version::print_kani_version;
+
+pub const BASELINES_DIRNAME: &str = "baselines";
+
+/// Arguments that only affect how a run is reported or built, and not its results.
+/// They are not part of the flags fingerprint.
+const REPORTING_ARGS: &[&str] = &[
+    "baseline_runtime_threshold",
+    "compare_baseline",
+    "concrete_playback",
+    "coverage_out",
+    "coverage_overwrite",
+    "debug",
+    "export_json",
+    "export_json_merge",
+    "export_json_streaming",
+    "fail_on_regression",
+    "force_build",
+    "incremental",
+    "junit_xml",
+    "keep_temps",
+    "no_history",
+    "output_format",
+    "output_into_files",
+    "print_config",
+    "quiet",
+    "sarif",
+    "target_dir",
+    "verbose",
+];
+
+/// A run saved by `cargo kani baseline save`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SavedBaseline {
+    pub name: String,
+    /// When the baseline was saved, in RFC 3339 format.
+    pub saved_at: String,
+    /// The commit checked out when the baseline was saved. `None` outside of git repositories.
+    pub git_commit: Option<String>,
+    /// The [flags_fingerprint] of the run. `None` if its export has no session configuration.
+    pub flags_fingerprint: Option<String>,
+    /// The `--export-json` file the baseline was saved from.
+    pub source: PathBuf,
+    /// The exported run, folded into a single document if it was streamed.
+    pub export: Value,
+}
+
+fn baseline_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{name}.json"))
+}
+
+/// Hash the arguments that affect the verification results, e.g. the unwind bound or the enabled
+/// checks, ignoring the [REPORTING_ARGS]. [DefaultHasher] is only stable for a given toolchain,
+/// so a new Kani release may report a different fingerprint for the same flags.
+fn flags_fingerprint<'a>(args: impl IntoIterator<Item = (&'a str, &'a Value)>) -> String {
+    let args: BTreeMap<&str, String> = args
+        .into_iter()
+        .filter(|(name, _)| !REPORTING_ARGS.contains(name))
+        .map(|(name, value)| (name, value.to_string()))
+        .collect();
+    let mut hasher = DefaultHasher::new();
+    args.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// The [flags_fingerprint] of the current run.
+fn config_fingerprint(config: &[ResolvedArg]) -> String {
+    flags_fingerprint(config.iter().map(|arg| (arg.name.as_str(), &arg.value)))
+}
+
+/// The [flags_fingerprint] of an exported run, computed from its [SESSION_KEY].
+fn export_fingerprint(export: &Value) -> Option<String> {
+    let config = export[SESSION_KEY]["config"].as_array()?;
+    Some(flags_fingerprint(
+        config.iter().filter_map(|arg| Some((arg["name"].as_str()?, &arg["value"]))),
+    ))
+}
+
+/// The tool errors of an exported run: the run stopped with an error, or a harness failed without
+/// verification results, e.g. because CBMC crashed. Comparisons with such a run are meaningless.
+fn tool_errors(export: &Value) -> Vec<String> {
+    let mut errors = vec![];
+    if let Some(error) = export[RUN_ERROR_KEY].as_str() {
+        errors.push(format!("the run stopped with an error: {error}"));
+    }
+    let results = export[VERIFICATION_RESULTS_KEY]["results"].as_array();
+    for result in results.into_iter().flatten() {
+        if matches!(result["status"].as_str(), Some("Failure" | "XFail"))
+            && result["checks"].as_array().is_some_and(Vec::is_empty)
+        {
+            let harness = result["harness_id"].as_str().unwrap_or("<unknown>");
+            errors.push(format!("harness `{harness}` failed without verification results"));
+        }
+    }
+    errors
+}
+
+/// The commit checked out in `dir`, if it's in a git repository.
+fn git_commit(dir: &Path) -> Option<String> {
+    let output = Command::new("git").args(["rev-parse", "HEAD"]).current_dir(dir).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Save the run exported by the latest run in `history` as the baseline `name` in `dir`.
+fn save_baseline(
+    dir: &Path,
+    name: &str,
+    history: &Path,
+    force: bool,
+    git_commit: Option<String>,
+) -> Result<PathBuf> {
+    let records = read_records(history)?;
+    let Some(latest) = records.last() else {
+        bail!(
+            "No run history found for this package. Run `cargo kani --export-json <FILE>` first."
+        );
+    };
+    let Some(source) = &latest.export else {
+        bail!(
+            "The latest run was not exported, so there is nothing to save. Run \
+            `cargo kani --export-json <FILE>` first."
+        );
+    };
+    let export = read_export(source)?;
+    let errors = tool_errors(&export);
+    if !errors.is_empty() && !force {
+        bail!(
+            "Refusing to save the latest run as baseline `{name}`, since {}. Every comparison \
+            with it would be meaningless. Use `--force` to save it anyway.",
+            errors.join(", and ")
+        );
+    }
+    let baseline = SavedBaseline {
+        name: name.to_string(),
+        saved_at: OffsetDateTime::now_utc().format(&Rfc3339).unwrap_or_default(),
+        git_commit,
+        flags_fingerprint: export_fingerprint(&export),
+        source: source.clone(),
+        export,
+    };
+    fs::create_dir_all(dir)?;
+    let path = baseline_path(dir, name);
+    fs::write(&path, serde_json::to_string_pretty(&baseline)?)
+        .with_context(|| format!("Failed to write baseline `{}`", path.display()))?;
+    Ok(path)
+}
+
+fn load_baseline(dir: &Path, name: &str) -> Result<SavedBaseline> {
+    let path = baseline_path(dir, name);
+    if !path.exists() {
+        bail!("No baseline named `{name}`. Save one with `cargo kani baseline save {name}`.");
+    }
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read baseline `{}`", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("Invalid baseline `{}`", path.display()))
+}
+
+/// Read all baselines in `dir`, sorted by name. Files that cannot be parsed are skipped.
+fn read_baselines(dir: &Path) -> Result<Vec<SavedBaseline>> {
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+    let mut baselines: Vec<SavedBaseline> = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension() != Some("json".as_ref()) {
+            continue;
+        }
+        if let Some(baseline) =
+            fs::read_to_string(&path).ok().and_then(|content| serde_json::from_str(&content).ok())
+        {
+            baselines.push(baseline);
+        }
+    }
+    baselines.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(baselines)
+}
+
+/// Remove the content of the Kani target directory `kani_dir`, except for the saved baselines.
+/// Used by `--force-build`.
+pub fn clean_kani_dir(kani_dir: &Path) -> Result<()> {
+    for entry in fs::read_dir(kani_dir)? {
+        let path = entry?.path();
+        if path.file_name() == Some(OsStr::new(BASELINES_DIRNAME)) {
+            continue;
+        }
+        if path.is_dir() { fs::remove_dir_all(&path)? } else { fs::remove_file(&path)? }
+    }
+    Ok(())
+}
+
+impl KaniSession {
+    /// Read the run `--compare-baseline` refers to: the exported run `baseline`, or a baseline
+    /// saved for `project`. Warns if a saved baseline was verified with other flags than `config`,
+    /// since some differences may then come from the configuration rather than from the code.
+    pub fn read_baseline(
+        &self,
+        project: &Project,
+        baseline: &Path,
+        config: &[ResolvedArg],
+    ) -> Result<Value> {
+        let Some(name) = self.args.saved_baseline() else { return read_export(baseline) };
+        let Some(metadata) = project.cargo_metadata.as_ref() else {
+            bail!("Saved baselines are only supported by `cargo kani`.");
+        };
+        let target_dir = self
+            .args
+            .target_dir
+            .clone()
+            .unwrap_or_else(|| metadata.target_directory.clone().into());
+        let saved = load_baseline(&target_dir.join("kani").join(BASELINES_DIRNAME), name)?;
+        if saved.flags_fingerprint.is_some_and(|f| f != config_fingerprint(config))
+            && !self.args.common_args.quiet
+        {
+            warning(&format!(
+                "Baseline `{name}` was verified with different flags than this run. Some \
+                differences may be caused by the configuration rather than by code changes."
+            ));
+        }
+        Ok(saved.export)
+    }
+}
+
+/// Implement `cargo kani baseline`.
+pub fn baseline_cargo(args: CargoBaselineArgs, mut verify_opts: VerificationArgs) -> Result<()> {
+    let quiet = args.common_args.quiet;
+    verify_opts.common_args = args.common_args;
+    let session = KaniSession::new(verify_opts)?;
+    if !quiet {
+        print_kani_version(InvocationType::CargoKani(vec![]));
+    }
+
+    let metadata = session.cargo_metadata(env!("TARGET"))?;
+    let target_dir =
+        session.args.target_dir.clone().unwrap_or_else(|| metadata.target_directory.clone().into());
+    let kani_dir = target_dir.join("kani");
+    let dir = kani_dir.join(BASELINES_DIRNAME);
+
+    match args.command {
+        BaselineCommand::Save { name, force } => {
+            let git_commit = git_commit(metadata.workspace_root.as_std_path());
+            let history = kani_dir.join(HISTORY_FILENAME);
+            let path = save_baseline(&dir, &name, &history, force, git_commit)?;
+            if !quiet {
+                println!("Saved baseline `{name}` to `{}`", path.display());
+            }
+        }
+        BaselineCommand::List => {
+            let baselines = read_baselines(&dir)?;
+            if baselines.is_empty() {
+                println!("No baselines saved for this package.");
+                return Ok(());
+            }
+            let mut table = PrettyTable::new();
+            table.set_header(vec!["Name", "Saved at", "Commit", "Harnesses", "Source"]);
+            for baseline in &baselines {
+                let harnesses = baseline.export[VERIFICATION_RESULTS_KEY]["results"]
+                    .as_array()
+                    .map_or(0, Vec::len);
+                table.add_row(vec![
+                    baseline.name.clone(),
+                    baseline.saved_at.clone(),
+                    baseline.git_commit.as_deref().map_or("-", |c| &c[..c.len().min(12)]).into(),
+                    harnesses.to_string(),
+                    baseline.source.display().to_string(),
+                ]);
+            }
+            println!("{table}");
+        }
+        BaselineCommand::Delete { name } => {
+            let path = baseline_path(&dir, &name);
+            if !path.exists() {
+                bail!("No baseline named `{name}`.");
+            }
+            fs::remove_file(&path)?;
+            if !quiet {
+                println!("Deleted baseline `{name}`");
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::{RunRecord, append_record};
+    use serde_json::json;
+
+    fn export(statuses: &[(&str, &str, usize)], unwind: u32) -> Value {
+        let results: Vec<Value> = statuses
+            .iter()
+            .map(|(harness, status, checks)| {
+                json!({ "harness_id": harness, "status": status, "checks": vec![json!({}); *checks] })
+            })
+            .collect();
+        json!({
+            SESSION_KEY: { "config": [
+                { "name": "default_unwind", "value": unwind.to_string(), "source": "CommandLine" },
+                { "name": "export_json", "value": "run.json", "source": "CommandLine" },
+            ]},
+            VERIFICATION_RESULTS_KEY: { "results": results },
+        })
+    }
+
+    /// Write `export` and record it as the latest run in the history of `dir`.
+    fn record_run(dir: &Path, export: &Value) -> PathBuf {
+        let source = dir.join("run.json");
+        fs::write(&source, export.to_string()).unwrap();
+        let history = dir.join(HISTORY_FILENAME);
+        let record = RunRecord::new(&[], std::time::Duration::ZERO, Some(source));
+        append_record(&history, &record).unwrap();
+        history
+    }
+
+    #[test]
+    fn check_flags_fingerprint() {
+        let fingerprint = export_fingerprint(&export(&[], 2)).unwrap();
+        assert_ne!(export_fingerprint(&export(&[], 3)).unwrap(), fingerprint);
+
+        // Reporting arguments don't change the fingerprint.
+        let mut other = export(&[], 2);
+        other[SESSION_KEY]["config"][1]["value"] = json!("other.json");
+        assert_eq!(export_fingerprint(&other).unwrap(), fingerprint);
+        assert_eq!(export_fingerprint(&json!({})), None);
+    }
+
+    #[test]
+    fn check_tool_errors() {
+        let clean = export(&[("check_a", "Success", 2), ("check_b", "Failure", 1)], 2);
+        assert!(tool_errors(&clean).is_empty());
+
+        let mut broken = export(&[("check_a", "Success", 2), ("check_b", "Failure", 0)], 2);
+        assert_eq!(tool_errors(&broken), ["harness `check_b` failed without verification results"]);
+        broken[RUN_ERROR_KEY] = json!("out of disk space");
+        assert_eq!(tool_errors(&broken).len(), 2);
+    }
+
+    #[test]
+    fn check_save_and_load_baseline() {
+        let dir = tempfile::tempdir().unwrap();
+        let baselines = dir.path().join(BASELINES_DIRNAME);
+        let history = record_run(dir.path(), &export(&[("check_a", "Success", 2)], 2));
+        let commit = Some("0123456789abcdef".to_string());
+        save_baseline(&baselines, "main", &history, false, commit.clone()).unwrap();
+
+        let saved = load_baseline(&baselines, "main").unwrap();
+        assert_eq!(saved.name, "main");
+        assert_eq!(saved.git_commit, commit);
+        assert_eq!(saved.flags_fingerprint, export_fingerprint(&saved.export));
+        assert_eq!(saved.export[VERIFICATION_RESULTS_KEY]["results"][0]["harness_id"], "check_a");
+        assert_eq!(read_baselines(&baselines).unwrap().len(), 1);
+        assert!(load_baseline(&baselines, "other").is_err());
+    }
+
+    #[test]
+    fn check_save_refuses_tool_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let baselines = dir.path().join(BASELINES_DIRNAME);
+        let history = record_run(dir.path(), &export(&[("check_a", "Failure", 0)], 2));
+        let err = save_baseline(&baselines, "broken", &history, false, None).unwrap_err();
+        assert!(err.to_string().contains("`check_a` failed without verification results"));
+        assert!(!baseline_path(&baselines, "broken").exists());
+        save_baseline(&baselines, "broken", &history, true, None).unwrap();
+        assert!(baseline_path(&baselines, "broken").exists());
+
+        // A run without `--export-json` cannot be saved.
+        let record = RunRecord::new(&[], std::time::Duration::ZERO, None);
+        append_record(&history, &record).unwrap();
+        assert!(save_baseline(&baselines, "unexported", &history, true, None).is_err());
+    }
+
+    #[test]
+    fn check_clean_kani_dir_keeps_baselines() {
+        let dir = tempfile::tempdir().unwrap();
+        let baselines = dir.path().join(BASELINES_DIRNAME);
+        fs::create_dir_all(&baselines).unwrap();
+        fs::write(baselines.join("main.json"), "{}").unwrap();
+        fs::create_dir_all(dir.path().join("x86_64-unknown-linux-gnu")).unwrap();
+        fs::write(dir.path().join(HISTORY_FILENAME), "").unwrap();
+
+        clean_kani_dir(dir.path()).unwrap();
+        let remaining: Vec<_> =
+            fs::read_dir(dir.path()).unwrap().map(|e| e.unwrap().file_name()).collect();
+        assert_eq!(remaining, [BASELINES_DIRNAME]);
+        assert!(baselines.join("main.json").exists());
+    }
+}
@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use crate::args::VerificationArgs;
+use crate::baseline::clean_kani_dir;
 use crate::call_single_file::LibConfig;
+use crate::error::KaniDriverError;
+use crate::event_log;
 use crate::project::Artifact;
 use crate::session::{
     KaniSession, get_cargo_path, lib_folder, lib_no_core_folder, setup_cargo_command,
@@ -145,7 +148,7 @@ pub fn cargo_build(&mut self, keep_going: bool) -> Result<CargoOutputs> {
         let outdir = target_dir.join(build_target).join("debug/deps");
 
         if self.args.force_build && target_dir.exists() {
-            fs::remove_dir_all(&target_dir)?;
+            clean_kani_dir(&target_dir)?;
         }
 
         let lib_path = lib_folder().unwrap();
@@ -285,6 +288,8 @@ pub fn cargo_metadata(&self, build_target: &str) -> Result<Metadata> {
     fn run_build(&self, cargo_cmd: Command) -> Result<Vec<RustcArtifact>> {
         let support_color = std::io::stdout().is_terminal();
         let mut artifacts = vec![];
+        let command = util::render_command(&cargo_cmd).to_string_lossy().into_owned();
+        let start = std::time::Instant::now();
         let mut cargo_process = self.run_piped(cargo_cmd)?;
         let reader = BufReader::new(cargo_process.stdout.take().unwrap());
         let mut error_count = 0;
@@ -302,10 +307,12 @@ fn run_build(&self, cargo_cmd: Command) -> Result<Vec<RustcArtifact>> {
                     DiagnosticLevel::Ice => {
                         print_msg(&msg.message, support_color)?;
                         let _ = cargo_process.wait();
-                        return Err(anyhow::Error::msg(msg.message).context(format!(
-                            "Failed to compile `{}` due to an internal compiler error.",
-                            msg.target.name
-                        )));
+                        return Err(anyhow::Error::msg(msg.message).context(
+                            KaniDriverError::CodegenFailed(format!(
+                                "Failed to compile `{}` due to an internal compiler error.",
+                                msg.target.name
+                            )),
+                        ));
                     }
                     _ => {
                         if !self.args.common_args.quiet {
@@ -336,8 +343,9 @@ fn run_build(&self, cargo_cmd: Command) -> Result<Vec<RustcArtifact>> {
             }
         }
         let status = cargo_process.wait()?;
+        event_log::process_exited("cargo", Some(cargo_process.id()), Some(status), start.elapsed());
         if !status.success() {
-            bail!("Failed to execute cargo ({status}). Found {error_count} compilation errors.");
+            return Err(KaniDriverError::CompilationFailed { status, error_count, command }.into());
         }
         Ok(artifacts)
     }
@@ -487,10 +495,15 @@ pub fn cargo_config_args() -> Vec<CargoArg> {
 
 /// Print the compiler message following the coloring schema.
 fn print_msg(diagnostic: &Diagnostic, use_rendered: bool) -> Result<()> {
-    if use_rendered {
-        print!("{diagnostic}");
+    let msg = if use_rendered {
+        diagnostic.to_string()
     } else {
-        print!("{}", console::strip_ansi_codes(diagnostic.rendered.as_ref().unwrap()));
+        console::strip_ansi_codes(diagnostic.rendered.as_ref().unwrap()).into_owned()
+    };
+    if crate::util::stdout_is_json() {
+        eprint!("{msg}")
+    } else {
+        print!("{msg}")
     }
     Ok(())
 }
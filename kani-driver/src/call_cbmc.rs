@@ -5,6 +5,7 @@
 use kani_metadata::{CbmcSolver, HarnessMetadata};
 use regex::Regex;
 use rustc_demangle::demangle;
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::collections::btree_map::Entry;
 use std::ffi::OsString;
@@ -16,15 +17,22 @@
 use tokio::process::Command as TokioCommand;
 
 use crate::args::common::Verbosity;
-use crate::args::{OutputFormat, VerificationArgs};
+use crate::args::{OutputFormat, TraceMode, VerificationArgs};
+use crate::call_goto_synthesizer::LoopContractSynthesis;
 use crate::cbmc_output_parser::{
-    CheckStatus, Property, VerificationOutput, extract_results, process_cbmc_output,
+    CheckStatus, Property, TraceItem, VerificationOutput, extract_results, process_cbmc_output,
+    process_status,
+};
+use crate::cbmc_property_renderer::{
+    UNSUPPORTED_CONSTRUCT_DESC, format_coverage, format_result, kani_cbmc_output_filter,
 };
-use crate::cbmc_property_renderer::{format_coverage, format_result, kani_cbmc_output_filter};
 use crate::coverage::cov_results::{CoverageCheck, CoverageResults};
 use crate::coverage::cov_results::{CoverageRegion, CoverageTerm};
-use crate::session::KaniSession;
-use crate::util::render_command;
+use crate::error::KaniDriverError;
+use crate::event_log;
+use crate::session::{KaniSession, kill_process_tree};
+use crate::smoke_test::SmokeTest;
+use crate::util::{self, render_command};
 
 /// CBMC version and system information
 #[derive(Debug, Clone)]
@@ -34,7 +42,7 @@ pub struct CbmcInfo {
 }
 
 /// CBMC runtime and execution statistics
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct CbmcStats {
     pub runtime_symex_s: Option<f64>,
     pub size_program_expression: Option<u32>,
@@ -46,6 +54,112 @@ pub struct CbmcStats {
     pub runtime_post_process_s: Option<f64>,
     pub runtime_solver_s: Option<f64>,
     pub runtime_decision_procedure_s: Option<f64>,
+    /// The kind of solver CBMC used. The solver statistics below depend on it.
+    pub solver_backend: Option<SolverBackend>,
+    /// The solver as reported by CBMC, e.g. `CaDiCaL sc2021` or `SMT2 QF_AUFBV using Z3`.
+    pub solver_name: Option<String>,
+    /// Size of the propositional formula. Only reported for SAT backends.
+    pub sat_variables: Option<u64>,
+    pub sat_clauses: Option<u64>,
+    /// Search statistics. Only reported by solvers that print them, e.g. Kissat.
+    pub solver_decisions: Option<u64>,
+    pub solver_conflicts: Option<u64>,
+    /// Peak resident memory of the CBMC process, as reported by CBMC at the end of the run.
+    /// If CBMC didn't report it, e.g. because it was killed, this is the peak Kani measured
+    /// while CBMC was running, where available.
+    pub peak_memory_bytes: Option<u64>,
+}
+
+/// The kind of decision procedure used by CBMC, exported in [CbmcStats].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SolverBackend {
+    /// A SAT solver built into CBMC, e.g. CaDiCaL or MiniSat.
+    Sat,
+    /// A SAT solver binary invoked by CBMC, e.g. Kissat.
+    ExternalSat,
+    /// An SMT solver invoked through CBMC's SMT2 interface.
+    Smt,
+}
+
+impl CbmcStats {
+    /// Merge the statistics found in a later message into `self`.
+    /// Values from `other` take precedence, since later messages may have more complete info.
+    fn merge(&mut self, other: CbmcStats) {
+        self.runtime_symex_s = other.runtime_symex_s.or(self.runtime_symex_s);
+        self.size_program_expression =
+            other.size_program_expression.or(self.size_program_expression);
+        self.slicing_removed_assignments =
+            other.slicing_removed_assignments.or(self.slicing_removed_assignments);
+        self.vccs_generated = other.vccs_generated.or(self.vccs_generated);
+        self.vccs_remaining = other.vccs_remaining.or(self.vccs_remaining);
+        self.runtime_postprocess_equation_s =
+            other.runtime_postprocess_equation_s.or(self.runtime_postprocess_equation_s);
+        self.runtime_convert_ssa_s = other.runtime_convert_ssa_s.or(self.runtime_convert_ssa_s);
+        self.runtime_post_process_s = other.runtime_post_process_s.or(self.runtime_post_process_s);
+        self.runtime_solver_s = other.runtime_solver_s.or(self.runtime_solver_s);
+        self.runtime_decision_procedure_s =
+            other.runtime_decision_procedure_s.or(self.runtime_decision_procedure_s);
+        self.solver_backend = other.solver_backend.or(self.solver_backend);
+        self.solver_name = other.solver_name.or(self.solver_name.take());
+        self.sat_variables = other.sat_variables.or(self.sat_variables);
+        self.sat_clauses = other.sat_clauses.or(self.sat_clauses);
+        self.solver_decisions = other.solver_decisions.or(self.solver_decisions);
+        self.solver_conflicts = other.solver_conflicts.or(self.solver_conflicts);
+        self.peak_memory_bytes = other.peak_memory_bytes.or(self.peak_memory_bytes);
+    }
+}
+
+/// How often [PeakMemorySampler] reads the memory usage of CBMC.
+const MEMORY_SAMPLING_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Measures the peak resident memory of a running CBMC process, so that it is known even if CBMC
+/// is killed before it reports its own memory usage, e.g. by the OOM killer.
+///
+/// The child is reaped by tokio, so we can't get its resource usage from `wait4`. Instead, a
+/// thread reads the high-water mark the kernel keeps in `/proc/<pid>/status`. This is only
+/// available on Linux; elsewhere, no sampler is started.
+struct PeakMemorySampler {
+    stop: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    thread: std::thread::JoinHandle<Option<u64>>,
+}
+
+impl PeakMemorySampler {
+    fn start(pid: u32) -> Option<Self> {
+        if !cfg!(target_os = "linux") {
+            return None;
+        }
+        let stop = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let stopped = stop.clone();
+        let thread = std::thread::spawn(move || {
+            let status = format!("/proc/{pid}/status");
+            let mut peak = None;
+            loop {
+                let stopping = stopped.load(std::sync::atomic::Ordering::Relaxed);
+                // The process may have exited already, in which case we keep the last value.
+                let sample = std::fs::read_to_string(&status).ok().and_then(|s| vm_hwm_bytes(&s));
+                peak = sample.max(peak);
+                if stopping {
+                    return peak;
+                }
+                std::thread::sleep(MEMORY_SAMPLING_INTERVAL);
+            }
+        });
+        Some(PeakMemorySampler { stop, thread })
+    }
+
+    /// Take a last sample and return the peak memory measured so far, if any.
+    fn stop(self) -> Option<u64> {
+        self.stop.store(true, std::sync::atomic::Ordering::Relaxed);
+        self.thread.join().ok().flatten()
+    }
+}
+
+/// Extract the peak resident set size (`VmHWM`) from the content of `/proc/<pid>/status`.
+fn vm_hwm_bytes(status: &str) -> Option<u64> {
+    let line = status.lines().find_map(|line| line.strip_prefix("VmHWM:"))?;
+    let kib = line.trim().strip_suffix("kB")?.trim().parse::<u64>().ok()?;
+    Some(kib * 1024)
 }
 
 impl KaniSession {
@@ -170,10 +284,79 @@ fn extract_cbmc_stats_from_message(message: &str) -> Option<CbmcStats> {
             found_any = true;
         }
 
+        // Example: "Solving with CaDiCaL sc2021" or "Solving with External SAT solver"
+        if let Some(captures) = regex::Regex::new(r"Solving with (.+)").ok()?.captures(message) {
+            let name = captures[1].trim();
+            stats.solver_backend = Some(if name == "External SAT solver" {
+                SolverBackend::ExternalSat
+            } else {
+                SolverBackend::Sat
+            });
+            stats.solver_name = Some(name.to_string());
+            found_any = true;
+        }
+
+        // Example: "Passing problem to SMT2 QF_AUFBV using Z3"
+        if let Some(captures) =
+            regex::Regex::new(r"Passing problem to (SMT2 .+)").ok()?.captures(message)
+        {
+            stats.solver_backend = Some(SolverBackend::Smt);
+            stats.solver_name = Some(captures[1].trim().to_string());
+            found_any = true;
+        }
+
+        // Example: "1324 variables, 3527 clauses"
+        if let Some(captures) =
+            regex::Regex::new(r"(\d+) variables, (\d+) clauses").ok()?.captures(message)
+        {
+            if let Ok(variables) = captures[1].parse::<u64>() {
+                stats.sat_variables = Some(variables);
+                found_any = true;
+            }
+            if let Ok(clauses) = captures[2].parse::<u64>() {
+                stats.sat_clauses = Some(clauses);
+                found_any = true;
+            }
+        }
+
+        // Example (Kissat): "c decisions:                             42               14.00 per conflict"
+        if let Some(captures) =
+            regex::Regex::new(r"(?m)^(?:c\s+)?decisions:\s+(\d+)").ok()?.captures(message)
+            && let Ok(val) = captures[1].parse::<u64>()
+        {
+            stats.solver_decisions = Some(val);
+            found_any = true;
+        }
+
+        // Example (Kissat): "c conflicts:                              3                3.00 per second"
+        if let Some(captures) =
+            regex::Regex::new(r"(?m)^(?:c\s+)?conflicts:\s+(\d+)").ok()?.captures(message)
+            && let Ok(val) = captures[1].parse::<u64>()
+        {
+            stats.solver_conflicts = Some(val);
+            found_any = true;
+        }
+
+        // Example (Linux, macOS): "  maximum resident set size [bytes]: 58392576"
+        // Example (Windows): "  peak working set size [bytes]: 58392576"
+        if let Some(captures) =
+            regex::Regex::new(r"(?:maximum resident set|peak working set) size \[bytes\]: (\d+)")
+                .ok()?
+                .captures(message)
+            && let Ok(val) = captures[1].parse::<u64>()
+        {
+            stats.peak_memory_bytes = Some(val);
+            found_any = true;
+        }
+
         if found_any { Some(stats) } else { None }
     }
 }
 
+/// Printed for harnesses that reached `--harness-timeout`.
+const TIMEOUT_EXPLANATION: &str = "CBMC timed out. You may want to rerun your proof with a larger \
+    timeout or use stubbing to reduce the size of the code the verifier reasons about.\n";
+
 /// We will use Cadical by default since it performed better than MiniSAT in our analysis.
 /// Note: Kissat was marginally better, but it is an external solver which could be more unstable.
 static DEFAULT_SOLVER: CbmcSolver = CbmcSolver::Cadical;
@@ -182,6 +365,10 @@ fn extract_cbmc_stats_from_message(message: &str) -> Option<CbmcStats> {
 pub enum VerificationStatus {
     Success,
     Failure,
+    /// The harness was not verified because `--fail-fast` stopped the run after a failure.
+    Skipped,
+    /// The harness was not verified because it matches an `--exclude-harness` pattern.
+    Excluded,
 }
 
 /// Represents failed properties in three different categories.
@@ -203,6 +390,8 @@ pub enum ExitStatus {
     OutOfMemory,
     /// the integer is the process exit status
     Other(i32),
+    /// CBMC could not be checked because the driver panicked while handling the harness
+    DriverPanic,
 }
 
 /// Our (kani-driver) notions of CBMC results.
@@ -225,16 +414,159 @@ pub struct VerificationResult {
     pub coverage_results: Option<CoverageResults>,
     /// CBMC execution statistics extracted from messages
     pub cbmc_stats: Option<CbmcStats>,
+    /// How `--auto-unwind` verified this harness. `None` if it was not used.
+    pub auto_unwind: Option<AutoUnwind>,
+    /// The `--smoke-test` of this harness. `None` if it was not used.
+    pub smoke_test: Option<SmokeTest>,
+    /// Whether CBMC was killed after reaching `--harness-timeout`. `results` then holds the
+    /// checks CBMC reported before the timeout, or `ExitStatus::Timeout` if there were none.
+    pub timed_out: bool,
+    /// The second pass of `--trace=on-failure`. `None` if there was none.
+    pub trace_rerun: Option<TraceRerun>,
+    /// The `--xfail` reason of this harness, if it is expected to fail.
+    pub expected_failure: Option<String>,
+    /// Whether this result was reused from the `--incremental` cache instead of running CBMC.
+    pub cached: bool,
+    /// The outcome of `--synthesize-loop-contracts`. `None` if it was not used.
+    pub loop_contracts: Option<LoopContractSynthesis>,
+}
+
+/// The outcome of verifying a harness with `--auto-unwind`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AutoUnwind {
+    /// The unwind value of the last attempt.
+    pub unwind: u32,
+    /// Number of times the harness was verified, including the first attempt.
+    pub attempts: u32,
+    /// Whether unwinding assertions still failed with the largest allowed unwind value.
+    pub reached_cap: bool,
+}
+
+/// The second pass of `--trace=on-failure`, which verified the failed checks of a harness again
+/// to produce their traces.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceRerun {
+    /// Number of failed checks that were verified again.
+    pub checks: usize,
+    /// Time spent in the second pass, which is included in the runtime of the harness.
+    pub runtime_ms: u64,
+    /// Failed checks that did not fail again, e.g. because the solver result is flaky.
+    /// These keep the result of the first pass, without a trace.
+    pub no_longer_failing: Vec<String>,
+}
+
+/// The checks CBMC produces traces for.
+#[derive(Clone, Copy, Debug)]
+pub enum TracedChecks<'a> {
+    None,
+    All,
+    /// Only verify these checks, named as in [Property::property_name], and trace them.
+    Only(&'a [String]),
 }
 
 impl KaniSession {
-    /// Verify a goto binary that's been prepared with goto-instrument
-    pub fn run_cbmc(&self, file: &Path, harness: &HarnessMetadata) -> Result<VerificationResult> {
-        let args: Vec<OsString> = self.cbmc_flags(file, harness)?;
+    /// Verify a goto binary that's been prepared with goto-instrument, with the given unwind value
+    /// (see [resolve_unwind_value]).
+    /// Traces are only produced with `--trace=always`, see [KaniSession::add_failure_traces].
+    pub fn run_cbmc(
+        &self,
+        file: &Path,
+        harness: &HarnessMetadata,
+        unwind: Option<u32>,
+    ) -> Result<VerificationResult> {
+        self.run_cbmc_checks(file, harness, unwind, self.traced_checks())
+    }
 
-        // TODO get cbmc path from self
-        let mut cmd = TokioCommand::new("cbmc");
-        cmd.args(args);
+    /// The checks traced by [KaniSession::run_cbmc].
+    pub fn traced_checks(&self) -> TracedChecks<'static> {
+        match self.args.trace_mode() {
+            TraceMode::Always => TracedChecks::All,
+            TraceMode::OnFailure | TraceMode::Never => TracedChecks::None,
+        }
+    }
+
+    /// Implement `--trace=on-failure`: if `result` failed, verify its failed checks again with
+    /// traces and attach the traces to them. The statuses of the checks are kept.
+    /// The runtime of `result` then includes both passes.
+    pub fn add_failure_traces(
+        &self,
+        file: &Path,
+        harness: &HarnessMetadata,
+        unwind: Option<u32>,
+        result: &mut VerificationResult,
+    ) -> Result<()> {
+        if self.args.trace_mode() != TraceMode::OnFailure
+            || result.status != VerificationStatus::Failure
+            || result.timed_out
+        {
+            return Ok(());
+        }
+        let Ok(properties) = &mut result.results else { return Ok(()) };
+        let failed: Vec<String> = properties
+            .iter()
+            .filter(|prop| {
+                prop.status == CheckStatus::Failure
+                    && !prop.is_cover_property()
+                    && !prop.is_code_coverage_property()
+            })
+            .map(Property::property_name)
+            .collect();
+        if failed.is_empty() {
+            return Ok(());
+        }
+        if self.args.common_args.verbose() {
+            println!(
+                "[Kani] Verifying the {} failed check(s) of harness {} again to produce traces",
+                failed.len(),
+                harness.pretty_name
+            );
+        }
+
+        let rerun = self.run_cbmc_checks(file, harness, unwind, TracedChecks::Only(&failed))?;
+        let mut traces: BTreeMap<String, Option<Vec<TraceItem>>> = rerun
+            .results
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|prop| prop.status == CheckStatus::Failure)
+            .map(|prop| (prop.property_name(), prop.trace))
+            .collect();
+        let mut no_longer_failing = vec![];
+        for prop in properties.iter_mut() {
+            let name = prop.property_name();
+            if !failed.contains(&name) {
+                continue;
+            }
+            match traces.remove(&name) {
+                Some(trace) => prop.trace = trace.or(prop.trace.take()),
+                None => no_longer_failing.push(name),
+            }
+        }
+        if !no_longer_failing.is_empty() {
+            util::warning(&format!(
+                "Harness {} failed, but these checks did not fail again when verified with traces, \
+                 so the solver result may be flaky: {}",
+                harness.pretty_name,
+                no_longer_failing.join(", ")
+            ));
+        }
+
+        result.runtime += rerun.runtime;
+        result.trace_rerun = Some(TraceRerun {
+            checks: failed.len(),
+            runtime_ms: rerun.runtime.as_millis() as u64,
+            no_longer_failing,
+        });
+        Ok(())
+    }
+
+    fn run_cbmc_checks(
+        &self,
+        file: &Path,
+        harness: &HarnessMetadata,
+        unwind: Option<u32>,
+        traced: TracedChecks,
+    ) -> Result<VerificationResult> {
+        let cmd = self.cbmc_command(file, harness, unwind, traced)?;
 
         let verification_results = if self.args.output_format == crate::args::OutputFormat::Old {
             if self.run_terminal_timeout(cmd).is_err() {
@@ -243,17 +575,35 @@ pub fn run_cbmc(&self, file: &Path, harness: &HarnessMetadata) -> Result<Verific
                 VerificationResult::mock_success()
             }
         } else {
-            // Add extra argument to receive the output in JSON format.
-            // Done here because now removed `--visualize` used the XML format instead.
-            // TODO: move this now that we don't use --visualize
-            cmd.arg("--json-ui");
-
             self.runtime.block_on(self.run_cbmc_piped(cmd, harness))?
         };
 
         Ok(verification_results)
     }
 
+    /// The CBMC command that verifies `file`. It is also printed by `--dry-run`.
+    pub fn cbmc_command(
+        &self,
+        file: &Path,
+        harness: &HarnessMetadata,
+        unwind: Option<u32>,
+        traced: TracedChecks,
+    ) -> Result<TokioCommand> {
+        let args: Vec<OsString> = self.cbmc_flags(file, harness, unwind, traced)?;
+
+        // TODO get cbmc path from self
+        let mut cmd = TokioCommand::new("cbmc");
+        cmd.args(args);
+
+        if self.args.output_format != crate::args::OutputFormat::Old {
+            // Add extra argument to receive the output in JSON format.
+            // Done here because now removed `--visualize` used the XML format instead.
+            // TODO: move this now that we don't use --visualize
+            cmd.arg("--json-ui");
+        }
+        Ok(cmd)
+    }
+
     async fn run_cbmc_piped(
         &self,
         mut cmd: TokioCommand,
@@ -262,58 +612,57 @@ async fn run_cbmc_piped(
         if self.args.common_args.verbose() {
             println!("[Kani] Running: `{}`", render_command(cmd.as_std()).to_string_lossy());
         }
-        // Spawn the CBMC process and process its output below
+        // Spawn the CBMC process and process its output below.
+        // CBMC stays in our process group, so Ctrl-C reaches all in-flight CBMC processes. If the
+        // thread handling this harness panics instead, dropping the child makes sure it is killed.
         let mut cbmc_process = cmd
             .stdout(std::process::Stdio::piped())
+            .kill_on_drop(true)
             .spawn()
-            .map_err(|_| anyhow::Error::msg("Failed to run cbmc"))?;
+            .map_err(|_| KaniDriverError::CbmcNotStarted)?;
+        event_log::process_spawned(cmd.as_std(), cbmc_process.id());
 
         let start_time = Instant::now();
+        let memory_sampler = cbmc_process.id().and_then(PeakMemorySampler::start);
 
-        let res = if let Some(timeout) = self.args.harness_timeout {
-            tokio::time::timeout(
-                timeout.into(),
-                process_cbmc_output(&mut cbmc_process, |i| {
-                    kani_cbmc_output_filter(
-                        i,
-                        self.args.extra_pointer_checks,
-                        self.args.common_args.quiet,
-                        &self.args.output_format,
-                    )
-                }),
-            )
-            .await
-        } else {
-            Ok(process_cbmc_output(&mut cbmc_process, |i| {
+        let mut stdout = cbmc_process.stdout.take().unwrap();
+        let mut processed_items = vec![];
+        let processing = process_cbmc_output(
+            &mut stdout,
+            |i| {
                 kani_cbmc_output_filter(
                     i,
                     self.args.extra_pointer_checks,
                     self.args.common_args.quiet,
                     &self.args.output_format,
                 )
-            })
-            .await)
-        };
-
-        if let Ok(output) = res {
-            // The timeout wasn't reached
-            Ok(VerificationResult::from(output?, harness.attributes.should_panic, start_time))
+            },
+            &mut processed_items,
+        );
+        let timed_out = if let Some(timeout) = self.args.harness_timeout {
+            tokio::time::timeout(timeout.into(), processing).await.is_err()
         } else {
-            // An error occurs if the timeout was reached
-
-            // Kill the process
-            cbmc_process.kill().await?;
-
-            Ok(VerificationResult {
-                status: VerificationStatus::Failure,
-                failed_properties: FailedProperties::None,
-                results: Err(ExitStatus::Timeout),
-                runtime: start_time.elapsed(),
-                generated_concrete_test: false,
-                coverage_results: None,
-                cbmc_stats: None,
-            })
+            processing.await;
+            false
+        };
+        // Stop sampling before the process is reaped, after which its pid may be reused.
+        let measured_peak_memory = memory_sampler.and_then(PeakMemorySampler::stop);
+        if timed_out {
+            kill_process_tree(&mut cbmc_process).await?;
         }
+
+        let pid = cbmc_process.id();
+        let status = cbmc_process.wait().await?;
+        event_log::process_exited("cbmc", pid, Some(status), start_time.elapsed());
+        let output = VerificationOutput {
+            process_status: process_status(status),
+            processed_items,
+            timed_out,
+        };
+        let mut result =
+            VerificationResult::from(output, harness.attributes.should_panic, start_time);
+        result.record_measured_peak_memory(measured_peak_memory);
+        Ok(result)
     }
 
     /// "Internal," but also used by call_cbmc_viewer
@@ -321,6 +670,8 @@ pub fn cbmc_flags(
         &self,
         file: &Path,
         harness_metadata: &HarnessMetadata,
+        unwind: Option<u32>,
+        traced: TracedChecks,
     ) -> Result<Vec<OsString>> {
         let mut args = self.cbmc_check_flags();
 
@@ -329,7 +680,7 @@ pub fn cbmc_flags(
             args.push(object_bits.to_string().into());
         }
 
-        if let Some(unwind_value) = resolve_unwind_value(&self.args, harness_metadata) {
+        if let Some(unwind_value) = unwind {
             args.push("--unwind".into());
             args.push(unwind_value.to_string().into());
         }
@@ -345,18 +696,24 @@ pub fn cbmc_flags(
             args.push("--slice-formula".into());
         }
 
-        if self.args.concrete_playback.is_some() {
-            args.push("--trace".into());
+        match traced {
+            TracedChecks::None => {}
+            TracedChecks::All => args.push("--trace".into()),
+            TracedChecks::Only(checks) => {
+                args.push("--trace".into());
+                for check in checks {
+                    args.push("--property".into());
+                    args.push(check.into());
+                }
+            }
         }
 
         args.extend(self.args.cbmc_args.iter().cloned());
 
         args.push(file.to_owned().into_os_string());
 
-        // Make CBMC verbose by default to tell users about unwinding progress. This should be
-        // reviewed as CBMC's verbosity defaults evolve.
         args.push("--verbosity".into());
-        args.push("9".into());
+        args.push(resolve_cbmc_verbosity(&self.args).to_string().into());
 
         Ok(args)
     }
@@ -484,50 +841,35 @@ fn from(
             if let crate::cbmc_output_parser::ParserItem::Message { message_text, .. } = item
                 && let Some(stats) = KaniSession::extract_cbmc_stats_from_message(message_text)
             {
-                // Merge stats (later messages may have more complete info)
-                if stats.runtime_symex_s.is_some() {
-                    cbmc_stats.runtime_symex_s = stats.runtime_symex_s;
-                }
-                if stats.size_program_expression.is_some() {
-                    cbmc_stats.size_program_expression = stats.size_program_expression;
-                }
-                if stats.slicing_removed_assignments.is_some() {
-                    cbmc_stats.slicing_removed_assignments = stats.slicing_removed_assignments;
-                }
-                if stats.vccs_generated.is_some() {
-                    cbmc_stats.vccs_generated = stats.vccs_generated;
-                }
-                if stats.vccs_remaining.is_some() {
-                    cbmc_stats.vccs_remaining = stats.vccs_remaining;
-                }
-                if stats.runtime_postprocess_equation_s.is_some() {
-                    cbmc_stats.runtime_postprocess_equation_s =
-                        stats.runtime_postprocess_equation_s;
-                }
-                if stats.runtime_convert_ssa_s.is_some() {
-                    cbmc_stats.runtime_convert_ssa_s = stats.runtime_convert_ssa_s;
-                }
-                if stats.runtime_post_process_s.is_some() {
-                    cbmc_stats.runtime_post_process_s = stats.runtime_post_process_s;
-                }
-                if stats.runtime_solver_s.is_some() {
-                    cbmc_stats.runtime_solver_s = stats.runtime_solver_s;
-                }
-                if stats.runtime_decision_procedure_s.is_some() {
-                    cbmc_stats.runtime_decision_procedure_s = stats.runtime_decision_procedure_s;
-                }
+                cbmc_stats.merge(stats);
             }
         }
 
-        let cbmc_stats = if cbmc_stats.runtime_symex_s.is_some()
-            || cbmc_stats.size_program_expression.is_some()
-        {
-            Some(cbmc_stats)
-        } else {
-            None
-        };
+        // Keep the statistics if CBMC reported any of them.
+        let cbmc_stats = (cbmc_stats != CbmcStats::default()).then_some(cbmc_stats);
 
-        if let Some(results) = results {
+        if output.timed_out {
+            // Keep whatever CBMC reported before it was killed, but never report success.
+            let failed_properties =
+                results.as_deref().map_or(FailedProperties::None, determine_failed_properties);
+            let coverage_results = results.as_deref().and_then(coverage_results_from_properties);
+            VerificationResult {
+                status: VerificationStatus::Failure,
+                failed_properties,
+                results: results.ok_or(ExitStatus::Timeout),
+                runtime,
+                generated_concrete_test: false,
+                coverage_results,
+                cbmc_stats,
+                auto_unwind: None,
+                smoke_test: None,
+                timed_out: true,
+                trace_rerun: None,
+                expected_failure: None,
+                cached: false,
+                loop_contracts: None,
+            }
+        } else if let Some(results) = results {
             let (status, failed_properties) =
                 verification_outcome_from_properties(&results, should_panic);
             let coverage_results = coverage_results_from_properties(&results);
@@ -539,6 +881,13 @@ fn from(
                 generated_concrete_test: false,
                 coverage_results,
                 cbmc_stats,
+                auto_unwind: None,
+                smoke_test: None,
+                timed_out: false,
+                trace_rerun: None,
+                expected_failure: None,
+                cached: false,
+                loop_contracts: None,
             }
         } else {
             // We never got results from CBMC - something went wrong (e.g. crash) so it's failure
@@ -555,10 +904,36 @@ fn from(
                 generated_concrete_test: false,
                 coverage_results: None,
                 cbmc_stats,
+                auto_unwind: None,
+                smoke_test: None,
+                timed_out: false,
+                trace_rerun: None,
+                expected_failure: None,
+                cached: false,
+                loop_contracts: None,
             }
         }
     }
 
+    /// Use the peak memory Kani measured if CBMC didn't report its own.
+    fn record_measured_peak_memory(&mut self, measured: Option<u64>) {
+        let Some(measured) = measured else { return };
+        let stats = self.cbmc_stats.get_or_insert_with(CbmcStats::default);
+        stats.peak_memory_bytes.get_or_insert(measured);
+    }
+
+    /// The peak resident memory of CBMC, if known.
+    pub fn peak_memory_bytes(&self) -> Option<u64> {
+        self.cbmc_stats.as_ref().and_then(|stats| stats.peak_memory_bytes)
+    }
+
+    /// The line that follows the verification time, e.g. `Peak Memory: 55.7 MiB`.
+    fn render_peak_memory(&self) -> String {
+        self.peak_memory_bytes()
+            .map(|bytes| format!("Peak Memory: {:.1} MiB\n", bytes as f64 / (1024.0 * 1024.0)))
+            .unwrap_or_default()
+    }
+
     pub fn mock_success() -> VerificationResult {
         VerificationResult {
             status: VerificationStatus::Success,
@@ -568,9 +943,113 @@ pub fn mock_success() -> VerificationResult {
             generated_concrete_test: false,
             coverage_results: None,
             cbmc_stats: None,
+            auto_unwind: None,
+            smoke_test: None,
+            timed_out: false,
+            trace_rerun: None,
+            expected_failure: None,
+            cached: false,
+            loop_contracts: None,
+        }
+    }
+
+    /// The result of a harness whose verification was interrupted by a panic in the driver.
+    pub fn driver_panic(runtime: Duration) -> VerificationResult {
+        VerificationResult {
+            status: VerificationStatus::Failure,
+            failed_properties: FailedProperties::None,
+            results: Err(ExitStatus::DriverPanic),
+            runtime,
+            generated_concrete_test: false,
+            coverage_results: None,
+            cbmc_stats: None,
+            auto_unwind: None,
+            smoke_test: None,
+            timed_out: false,
+            trace_rerun: None,
+            expected_failure: None,
+            cached: false,
+            loop_contracts: None,
         }
     }
 
+    /// The result of a harness that was not verified because `--fail-fast` stopped the run.
+    pub fn skipped() -> VerificationResult {
+        VerificationResult {
+            status: VerificationStatus::Skipped,
+            failed_properties: FailedProperties::None,
+            results: Ok(vec![]),
+            runtime: Duration::ZERO,
+            generated_concrete_test: false,
+            coverage_results: None,
+            cbmc_stats: None,
+            auto_unwind: None,
+            smoke_test: None,
+            timed_out: false,
+            trace_rerun: None,
+            expected_failure: None,
+            cached: false,
+            loop_contracts: None,
+        }
+    }
+
+    /// The result of a harness that was not verified because of `--exclude-harness`.
+    pub fn excluded() -> VerificationResult {
+        VerificationResult { status: VerificationStatus::Excluded, ..VerificationResult::skipped() }
+    }
+
+    /// The result of a harness whose smoke test failed, without verifying it.
+    pub fn smoke_test_failure(mut smoke_test: SmokeTest, runtime: Duration) -> VerificationResult {
+        smoke_test.verification_skipped = true;
+        VerificationResult {
+            status: VerificationStatus::Failure,
+            failed_properties: FailedProperties::Other,
+            results: Ok(vec![]),
+            runtime,
+            generated_concrete_test: false,
+            coverage_results: None,
+            cbmc_stats: None,
+            auto_unwind: None,
+            smoke_test: Some(smoke_test),
+            timed_out: false,
+            trace_rerun: None,
+            expected_failure: None,
+            cached: false,
+            loop_contracts: None,
+        }
+    }
+
+    /// A successful result reused from the `--incremental` cache, without running CBMC.
+    /// Returns `None` if `properties` do not make up a successful verification.
+    pub fn cached_success(
+        properties: Vec<Property>,
+        should_panic: bool,
+        cbmc_stats: Option<CbmcStats>,
+        auto_unwind: Option<AutoUnwind>,
+    ) -> Option<VerificationResult> {
+        let (status, failed_properties) =
+            verification_outcome_from_properties(&properties, should_panic);
+        if status != VerificationStatus::Success {
+            return None;
+        }
+        Some(VerificationResult {
+            status,
+            failed_properties,
+            results: Ok(properties),
+            runtime: Duration::ZERO,
+            generated_concrete_test: false,
+            coverage_results: None,
+            cbmc_stats,
+            auto_unwind,
+            smoke_test: None,
+            timed_out: false,
+            trace_rerun: None,
+            expected_failure: None,
+            cached: true,
+            loop_contracts: None,
+        })
+    }
+
     fn mock_failure() -> VerificationResult {
         VerificationResult {
             status: VerificationStatus::Failure,
@@ -583,10 +1062,112 @@ fn mock_failure() -> VerificationResult {
             generated_concrete_test: false,
             coverage_results: None,
             cbmc_stats: None,
+            auto_unwind: None,
+            smoke_test: None,
+            timed_out: false,
+            trace_rerun: None,
+            expected_failure: None,
+            cached: false,
+            loop_contracts: None,
+        }
+    }
+
+    /// The outcome of verification as shown in summaries: `Success`, `Failure`, `Timeout`,
+    /// `Skipped` or `Excluded`.
+    /// Harnesses marked with `--xfail` are `XFail` if they failed in any way, and `XPass` otherwise.
+    pub fn outcome(&self) -> &'static str {
+        match self.status {
+            VerificationStatus::Skipped => return "Skipped",
+            VerificationStatus::Excluded => return "Excluded",
+            VerificationStatus::Success | VerificationStatus::Failure => {}
+        }
+        if self.expected_failure.is_some() {
+            return if self.status == VerificationStatus::Success { "XPass" } else { "XFail" };
+        }
+        match (self.timed_out, self.status) {
+            (true, _) => "Timeout",
+            (false, VerificationStatus::Success) => "Success",
+            (false, VerificationStatus::Failure) => "Failure",
+            (false, VerificationStatus::Skipped) => "Skipped",
+            (false, VerificationStatus::Excluded) => "Excluded",
+        }
+    }
+
+    /// Whether verification failed only because of unwinding assertions, so it may succeed with
+    /// a larger unwind value.
+    pub fn fails_only_unwinding_assertions(&self) -> bool {
+        let Ok(properties) = &self.results else { return false };
+        let mut failed =
+            properties.iter().filter(|prop| prop.status == CheckStatus::Failure).peekable();
+        self.status == VerificationStatus::Failure
+            && !self.timed_out
+            && failed.peek().is_some()
+            && failed.all(|prop| prop.is_unwinding_assertion())
+    }
+
+    /// The description of a check for a Rust construct that Kani does not support, if one was
+    /// found reachable. The harness then failed because of a limitation of Kani, which may hide
+    /// other failures.
+    pub fn unsupported_construct(&self) -> Option<&str> {
+        let Ok(properties) = &self.results else { return None };
+        properties
+            .iter()
+            .find(|prop| {
+                prop.status == CheckStatus::Failure
+                    && prop.description.contains(UNSUPPORTED_CONSTRUCT_DESC)
+            })
+            .map(|prop| prop.description.as_str())
+    }
+
+    /// Whether this harness fails the run: it failed without being marked with `--xfail`, or it
+    /// passed while marked with `--xfail` and `strict_xfail` is set.
+    pub fn fails_run(&self, strict_xfail: bool) -> bool {
+        match (self.status, self.expected_failure.is_some()) {
+            (VerificationStatus::Failure, expected) => !expected,
+            (VerificationStatus::Success, expected) => expected && strict_xfail,
+            (VerificationStatus::Skipped | VerificationStatus::Excluded, _) => false,
         }
     }
 
     pub fn render(&self, output_format: &OutputFormat, should_panic: bool) -> String {
+        let mut rendered = self.render_verification(output_format, should_panic);
+        if let Some(synthesis) = &self.loop_contracts {
+            rendered.push_str(&synthesis.render());
+        }
+        if let Some(reason) = &self.expected_failure {
+            let reason = if reason.is_empty() { String::new() } else { format!(": {reason}") };
+            if self.status == VerificationStatus::Success {
+                writeln!(
+                    rendered,
+                    "{} The harness passed, but it is marked as expected to fail{reason}. \
+                    Remove it from `--xfail`.",
+                    console::style("UNEXPECTED PASS (XPASS)").yellow()
+                )
+                .unwrap();
+            } else {
+                writeln!(
+                    rendered,
+                    "{} The harness is marked as expected to fail{reason}",
+                    console::style("EXPECTED FAILURE (XFAIL)").yellow()
+                )
+                .unwrap();
+            }
+        }
+        rendered
+    }
+
+    fn render_verification(&self, output_format: &OutputFormat, should_panic: bool) -> String {
+        if let Some(smoke_test) = self.smoke_test.as_ref().filter(|t| t.verification_skipped) {
+            return format!(
+                "{}Symbolic verification was skipped. Pass `--smoke-then-verify` to also verify \
+                this harness.\n\
+                VERIFICATION:- {}\n\
+                Verification Time: {}s\n",
+                smoke_test.render(),
+                console::style("FAILED").red(),
+                self.runtime.as_secs_f32()
+            );
+        }
         match &self.results {
             Ok(results) => {
                 let status = self.status;
@@ -601,15 +1182,40 @@ pub fn render(&self, output_format: &OutputFormat, should_panic: bool) -> String
                         should_panic,
                         failed_properties,
                         show_checks,
+                        self.timed_out,
                     )
                 } else {
-                    format_result(results, status, should_panic, failed_properties, show_checks)
+                    format_result(
+                        results,
+                        status,
+                        should_panic,
+                        failed_properties,
+                        show_checks,
+                        self.timed_out,
+                    )
                 };
+                if self.timed_out {
+                    result.push_str(TIMEOUT_EXPLANATION);
+                }
                 writeln!(result, "Verification Time: {}s", self.runtime.as_secs_f32()).unwrap();
+                result.push_str(&self.render_peak_memory());
+                if self.cached {
+                    result.push_str("Result reused from the `--incremental` cache (cached)\n");
+                }
+                if let Some(auto_unwind) = &self.auto_unwind {
+                    result.push_str(&auto_unwind.render());
+                }
+                if let Some(smoke_test) = &self.smoke_test {
+                    result.push_str(&smoke_test.render());
+                }
                 result
             }
             Err(exit_status) => {
-                let verification_result = console::style("FAILED").red();
+                let verification_result = if matches!(exit_status, ExitStatus::Timeout) {
+                    console::style("TIMEOUT").yellow()
+                } else {
+                    console::style("FAILED").red()
+                };
                 let (header, explanation) = match exit_status {
                     ExitStatus::OutOfMemory => (
                         String::from("CBMC failed"),
@@ -617,19 +1223,21 @@ pub fn render(&self, output_format: &OutputFormat, should_panic: bool) -> String
                     an environment with additional memory or use stubbing to reduce the size of the \
                     code the verifier reasons about.\n",
                     ),
-                    ExitStatus::Timeout => (
-                        String::from("CBMC failed"),
-                        "CBMC timed out. You may want to rerun your proof with a larger timeout \
-                    or use stubbing to reduce the size of the code the verifier reasons about.\n",
-                    ),
+                    ExitStatus::Timeout => (String::from("CBMC failed"), TIMEOUT_EXPLANATION),
                     ExitStatus::Other(exit_status) => {
                         (format!("CBMC failed with status {exit_status}"), "")
                     }
+                    ExitStatus::DriverPanic => (
+                        String::from("Kani failed"),
+                        "Kani panicked while checking this harness. The results of the other \
+                    harnesses are not affected. Please file a bug report.\n",
+                    ),
                 };
                 format!(
                     "\n{header}\n\
                     VERIFICATION:- {verification_result}\n\
-                    {explanation}",
+                    {explanation}{}",
+                    self.render_peak_memory()
                 )
             }
         }
@@ -736,8 +1344,30 @@ fn coverage_results_from_properties(properties: &[Property]) -> Option<CoverageR
         assert!(prop_processed, "error: coverage property not processed\n{prop:?}");
     }
 
-    Some(CoverageResults::new(coverage_results))
+    let mut coverage_results = CoverageResults::new(coverage_results);
+    coverage_results.exclude_contract_instrumentation();
+    Some(coverage_results)
 }
+impl AutoUnwind {
+    /// Explain the unwind value that `--auto-unwind` ended up with.
+    fn render(&self) -> String {
+        if self.reached_cap {
+            format!(
+                "[Kani] info: Unwinding assertions still fail with unwind {}, the largest value \
+                allowed by `--auto-unwind-max`.\n",
+                self.unwind
+            )
+        } else if self.attempts > 1 {
+            format!(
+                "[Kani] info: Verified with unwind {} after {} attempts.\n",
+                self.unwind, self.attempts
+            )
+        } else {
+            String::new()
+        }
+    }
+}
+
 /// Solve Unwind Value from conflicting inputs of unwind values. (--default-unwind, annotation-unwind, --unwind)
 pub fn resolve_unwind_value(
     args: &VerificationArgs,
@@ -748,6 +1378,29 @@ pub fn resolve_unwind_value(
     args.unwind.or(harness_metadata.attributes.unwind_value).or(args.default_unwind)
 }
 
+/// Make CBMC verbose by default to tell users about unwinding progress. This should be
+/// reviewed as CBMC's verbosity defaults evolve.
+pub const DEFAULT_CBMC_VERBOSITY: u8 = 9;
+/// CBMC reports property results, and therefore traces, at the "result" level.
+/// Kani cannot produce any verification outcome below it.
+const MIN_VERBOSITY_FOR_RESULTS: u8 = 4;
+/// CBMC reports the runtime and VCC statistics in [CbmcStats] at the "statistics" level.
+/// Below it, the statistics are simply missing from the output.
+const MIN_VERBOSITY_FOR_STATS: u8 = 8;
+
+/// Compute the verbosity CBMC is invoked with.
+/// The value requested with `--cbmc-verbosity` is raised to the minimum level required by the
+/// features in use: results are always required, and statistics are required by `--export-json`.
+pub fn resolve_cbmc_verbosity(args: &VerificationArgs) -> u8 {
+    let requested = args.cbmc_verbosity.unwrap_or(DEFAULT_CBMC_VERBOSITY);
+    let required = if args.export_json.is_some() {
+        MIN_VERBOSITY_FOR_STATS
+    } else {
+        MIN_VERBOSITY_FOR_RESULTS
+    };
+    requested.max(required)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::args;
@@ -787,4 +1440,275 @@ fn resolve(args: &[&str], harness: &HarnessMetadata) -> Option<u32> {
         assert_eq!(resolve(&args_only_harness, &harness_some), Some(1));
         assert_eq!(resolve(&args_both, &harness_some), Some(1));
     }
+
+    #[test]
+    fn check_resolve_cbmc_verbosity() {
+        fn resolve(args: &[&str]) -> u8 {
+            resolve_cbmc_verbosity(&args::StandaloneArgs::try_parse_from(args).unwrap().verify_opts)
+        }
+
+        assert_eq!(resolve(&["kani", "x.rs"]), DEFAULT_CBMC_VERBOSITY);
+        assert_eq!(resolve(&["kani", "x.rs", "--cbmc-verbosity", "10"]), 10);
+        assert_eq!(resolve(&["kani", "x.rs", "--cbmc-verbosity", "5"]), 5);
+        // Results are always needed.
+        assert_eq!(resolve(&["kani", "x.rs", "--cbmc-verbosity", "0"]), MIN_VERBOSITY_FOR_RESULTS);
+        // Statistics are needed for the JSON export.
+        assert_eq!(
+            resolve(&["kani", "x.rs", "--cbmc-verbosity", "5", "--export-json", "out.json"]),
+            MIN_VERBOSITY_FOR_STATS
+        );
+    }
+
+    /// Merge the statistics of `messages` like [VerificationResult::from] does.
+    fn stats_from_messages(messages: &[&str]) -> CbmcStats {
+        let mut stats = CbmcStats::default();
+        for message in messages {
+            if let Some(found) = KaniSession::extract_cbmc_stats_from_message(message) {
+                stats.merge(found);
+            }
+        }
+        stats
+    }
+
+    #[test]
+    fn check_sat_solver_stats() {
+        let stats = stats_from_messages(&[
+            "Runtime Symex: 0.00408627s",
+            "Running propositional reduction",
+            "Solving with CaDiCaL sc2021",
+            "1324 variables, 3527 clauses",
+            "SAT checker: instance is UNSATISFIABLE",
+            "Runtime Solver: 0.00167592s",
+        ]);
+        assert_eq!(stats.solver_backend, Some(SolverBackend::Sat));
+        assert_eq!(stats.solver_name.as_deref(), Some("CaDiCaL sc2021"));
+        assert_eq!(stats.sat_variables, Some(1324));
+        assert_eq!(stats.sat_clauses, Some(3527));
+        assert_eq!(stats.solver_decisions, None);
+        assert_eq!(stats.solver_conflicts, None);
+        assert_eq!(stats.runtime_solver_s, Some(0.00167592));
+
+        let stats = stats_from_messages(&[
+            "Solving with MiniSAT 2.2.1 with simplifier",
+            "87 variables, 102 clauses",
+        ]);
+        assert_eq!(stats.solver_backend, Some(SolverBackend::Sat));
+        assert_eq!(stats.solver_name.as_deref(), Some("MiniSAT 2.2.1 with simplifier"));
+        assert_eq!(stats.sat_clauses, Some(102));
+    }
+
+    #[test]
+    fn check_external_sat_solver_stats() {
+        let stats = stats_from_messages(&[
+            "Solving with External SAT solver",
+            "1324 variables, 3527 clauses",
+            "c decisions:                             42               14.00 per conflict\n\
+             c conflicts:                              3                3.00 per second",
+            "Runtime Solver: 0.0123s",
+        ]);
+        assert_eq!(stats.solver_backend, Some(SolverBackend::ExternalSat));
+        assert_eq!(stats.solver_name.as_deref(), Some("External SAT solver"));
+        assert_eq!(stats.sat_variables, Some(1324));
+        assert_eq!(stats.sat_clauses, Some(3527));
+        assert_eq!(stats.solver_decisions, Some(42));
+        assert_eq!(stats.solver_conflicts, Some(3));
+    }
+
+    #[test]
+    fn check_smt_solver_stats() {
+        let stats = stats_from_messages(&[
+            "Runtime Symex: 0.00408627s",
+            "Passing problem to SMT2 QF_AUFBV using Z3",
+            "converting SSA",
+            "Runtime decision procedure: 0.0452s",
+        ]);
+        assert_eq!(stats.solver_backend, Some(SolverBackend::Smt));
+        assert_eq!(stats.solver_name.as_deref(), Some("SMT2 QF_AUFBV using Z3"));
+        assert_eq!(stats.sat_variables, None);
+        assert_eq!(stats.sat_clauses, None);
+        assert_eq!(stats.solver_decisions, None);
+        assert_eq!(stats.solver_conflicts, None);
+        assert_eq!(stats.runtime_decision_procedure_s, Some(0.0452));
+    }
+
+    #[test]
+    fn check_memory_stats() {
+        let stats = stats_from_messages(&[
+            "Runtime Symex: 0.00408627s",
+            "1324 variables, 3527 clauses",
+            "Memory consumption:\n  maximum resident set size [bytes]: 58392576",
+        ]);
+        assert_eq!(stats.peak_memory_bytes, Some(58392576));
+        assert_eq!(stats.sat_variables, Some(1324));
+        assert_eq!(stats.sat_clauses, Some(3527));
+
+        let stats = stats_from_messages(&["  peak working set size [bytes]: 1048576"]);
+        assert_eq!(stats.peak_memory_bytes, Some(1048576));
+
+        // Older CBMC versions don't report their memory usage.
+        let stats = stats_from_messages(&[
+            "Runtime Symex: 0.00408627s",
+            "Generated 1 VCC(s), 1 remaining after simplification",
+        ]);
+        assert_eq!(stats.peak_memory_bytes, None);
+        assert_eq!(stats.sat_variables, None);
+        assert_eq!(stats.sat_clauses, None);
+        assert_eq!(stats.vccs_generated, Some(1));
+    }
+
+    #[test]
+    fn check_vm_hwm_bytes() {
+        let status = "Name:\tcbmc\nVmPeak:\t  204800 kB\nVmHWM:\t   57024 kB\nVmRSS:\t 1024 kB\n";
+        assert_eq!(vm_hwm_bytes(status), Some(57024 * 1024));
+        // The memory lines are missing once the process has exited.
+        assert_eq!(vm_hwm_bytes("Name:\tcbmc\nState:\tZ (zombie)\n"), None);
+        assert_eq!(vm_hwm_bytes("VmHWM:\t  unknown\n"), None);
+    }
+
+    #[test]
+    fn check_measured_peak_memory() {
+        // CBMC was killed before it reported anything: fall back to the measured peak.
+        let output =
+            VerificationOutput { process_status: 137, processed_items: vec![], timed_out: false };
+        let mut result = VerificationResult::from(output, false, Instant::now());
+        result.record_measured_peak_memory(Some(3 * 1024 * 1024 * 1024));
+        assert_eq!(result.peak_memory_bytes(), Some(3 * 1024 * 1024 * 1024));
+        let rendered = result.render(&OutputFormat::Regular, false);
+        assert!(rendered.contains("run out of memory"));
+        assert!(rendered.contains("Peak Memory: 3072.0 MiB"));
+
+        // The peak reported by CBMC takes precedence.
+        let output = VerificationOutput {
+            process_status: 0,
+            processed_items: vec![crate::cbmc_output_parser::ParserItem::Message {
+                message_text: "maximum resident set size [bytes]: 58392576".to_string(),
+                message_type: "STATUS-MESSAGE".to_string(),
+            }],
+            timed_out: false,
+        };
+        let mut result = VerificationResult::from(output, false, Instant::now());
+        result.record_measured_peak_memory(Some(1024));
+        assert_eq!(result.peak_memory_bytes(), Some(58392576));
+
+        let mut result = VerificationResult::mock_success();
+        result.record_measured_peak_memory(None);
+        assert_eq!(result.peak_memory_bytes(), None);
+        assert!(!result.render(&OutputFormat::Terse, false).contains("Peak Memory"));
+    }
+
+    #[test]
+    fn check_fails_only_unwinding_assertions() {
+        use crate::cbmc_output_parser::{PropertyId, SourceLocation};
+        let property = |description: &str, status| Property {
+            description: description.to_string(),
+            property_id: PropertyId { fn_name: None, class: "unwind".to_string(), id: 0 },
+            source_location: SourceLocation {
+                column: None,
+                file: None,
+                function: None,
+                line: None,
+            },
+            status,
+            reach: None,
+            trace: None,
+        };
+        let result = |properties| VerificationResult {
+            status: VerificationStatus::Failure,
+            failed_properties: FailedProperties::Other,
+            results: Ok(properties),
+            runtime: Duration::ZERO,
+            generated_concrete_test: false,
+            coverage_results: None,
+            cbmc_stats: None,
+            auto_unwind: None,
+            smoke_test: None,
+            timed_out: false,
+            trace_rerun: None,
+            expected_failure: None,
+            cached: false,
+            loop_contracts: None,
+        };
+        let unwinding = || property("unwinding assertion loop 0", CheckStatus::Failure);
+        let recursion = || property("recursion unwinding assertion", CheckStatus::Failure);
+        let passing = || property("assertion failed: x < 10", CheckStatus::Success);
+        let failing = || property("assertion failed: x < 10", CheckStatus::Failure);
+
+        assert!(
+            result(vec![unwinding(), recursion(), passing()]).fails_only_unwinding_assertions()
+        );
+        assert!(!result(vec![unwinding(), failing()]).fails_only_unwinding_assertions());
+        assert!(!result(vec![passing()]).fails_only_unwinding_assertions());
+        assert!(!VerificationResult::mock_success().fails_only_unwinding_assertions());
+    }
+
+    #[test]
+    fn check_timed_out_result() {
+        use crate::cbmc_output_parser::{ParserItem, PropertyId, SourceLocation};
+        let message = |text: &str| ParserItem::Message {
+            message_text: text.to_string(),
+            message_type: "STATUS-MESSAGE".to_string(),
+        };
+        let timed_out = |processed_items| VerificationOutput {
+            process_status: 137,
+            processed_items,
+            timed_out: true,
+        };
+
+        // CBMC was killed before reporting any check.
+        let result = VerificationResult::from(
+            timed_out(vec![message("Runtime Symex: 0.5s")]),
+            false,
+            Instant::now(),
+        );
+        assert!(result.timed_out);
+        assert_eq!(result.status, VerificationStatus::Failure);
+        assert!(matches!(result.results, Err(ExitStatus::Timeout)));
+        assert_eq!(result.cbmc_stats.as_ref().unwrap().runtime_symex_s, Some(0.5));
+        assert_eq!(result.outcome(), "Timeout");
+        let rendered = result.render(&OutputFormat::Regular, false);
+        assert!(rendered.contains("VERIFICATION:- ") && rendered.contains("TIMEOUT"));
+        assert!(!rendered.contains("FAILED"));
+
+        // The checks reported before the timeout are kept, but never count as a success.
+        let passing = Property {
+            description: "assertion failed: x < 10".to_string(),
+            property_id: PropertyId { fn_name: None, class: "assertion".to_string(), id: 1 },
+            source_location: SourceLocation {
+                column: None,
+                file: None,
+                function: None,
+                line: None,
+            },
+            status: CheckStatus::Success,
+            reach: None,
+            trace: None,
+        };
+        let result = VerificationResult::from(
+            timed_out(vec![ParserItem::Result { result: vec![passing] }]),
+            false,
+            Instant::now(),
+        );
+        assert_eq!(result.status, VerificationStatus::Failure);
+        assert_eq!(result.results.as_ref().unwrap().len(), 1);
+        let rendered = result.render(&OutputFormat::Regular, false);
+        assert!(rendered.contains("TIMEOUT") && rendered.contains("CBMC timed out"));
+        assert!(!rendered.contains("SUCCESSFUL"));
+
+        // A harness that wasn't killed is not a timeout, even if CBMC was killed by a signal.
+        let output =
+            VerificationOutput { process_status: 137, processed_items: vec![], timed_out: false };
+        let result = VerificationResult::from(output, false, Instant::now());
+        assert!(!result.timed_out);
+        assert!(matches!(result.results, Err(ExitStatus::OutOfMemory)));
+        assert_eq!(result.outcome(), "Failure");
+    }
+
+    #[test]
+    fn check_auto_unwind_render() {
+        let auto_unwind =
+            |unwind, attempts, reached_cap| AutoUnwind { unwind, attempts, reached_cap };
+        assert_eq!(auto_unwind(4, 1, false).render(), "");
+        assert!(auto_unwind(16, 3, false).render().contains("unwind 16 after 3 attempts"));
+        assert!(auto_unwind(64, 5, true).render().contains("still fail with unwind 64"));
+    }
 }
@@ -1,17 +1,33 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use anyhow::Result;
+use anyhow::{Context, Result, bail};
+use regex::Regex;
 use std::ffi::OsString;
+use std::fmt::Write as _;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use crate::args::common::Verbosity;
+use crate::event_log;
 use crate::session::KaniSession;
+use crate::util::render_command;
 
 impl KaniSession {
-    /// Given a set of goto binaries (`inputs`), produce `output` by linking everything
-    /// together (including essential libraries). The result is generic over all proof harnesses.
-    pub fn link_goto_binary(&self, inputs: &[PathBuf], output: &Path) -> Result<()> {
+    /// Given a set of goto binaries (`inputs`) built from `crate_name`, produce `output` by
+    /// linking everything together (including essential libraries). The result is generic over
+    /// all proof harnesses.
+    ///
+    /// If goto-cc reports conflicting or undefined symbols, the error names the crate or C
+    /// library of every definition. The raw goto-cc output is only printed with `--verbose` or
+    /// `--debug`.
+    pub fn link_goto_binary(
+        &self,
+        inputs: &[PathBuf],
+        output: &Path,
+        crate_name: &str,
+    ) -> Result<()> {
         let mut args: Vec<OsString> = Vec::new();
         args.extend(inputs.iter().map(|x| x.clone().into_os_string()));
         args.extend(self.args.c_lib.iter().map(|x| x.clone().into_os_string()));
@@ -27,23 +43,320 @@ pub fn link_goto_binary(&self, inputs: &[PathBuf], output: &Path) -> Result<()>
         let mut cmd = Command::new("goto-cc");
         cmd.args(args);
 
-        self.run_suppress(cmd)?;
+        let verbosity = &self.args.common_args;
+        if verbosity.verbose() {
+            println!("[Kani] Running: `{}`", render_command(&cmd).to_string_lossy());
+        }
+        let result = self
+            .with_timer(|| event_log::output(&mut cmd), "goto-cc")
+            .context("Failed to invoke goto-cc")?;
+        let show_raw_output = |always: bool| -> Result<()> {
+            if !verbosity.quiet() && (always || verbosity.verbose()) {
+                let stdout = std::io::stdout();
+                let mut handle = stdout.lock();
+                handle.write_all(&result.stdout)?;
+                handle.write_all(&result.stderr)?;
+            }
+            Ok(())
+        };
+        if result.status.success() {
+            return show_raw_output(false);
+        }
 
-        Ok(())
+        let raw_output = [result.stdout.as_slice(), result.stderr.as_slice()].concat();
+        let conflicts = parse_link_conflicts(&String::from_utf8_lossy(&raw_output));
+        if conflicts.is_empty() {
+            // Not a diagnostic we know about, so let the user read goto-cc's own explanation.
+            show_raw_output(true)?;
+            bail!("goto-cc exited with status {}", result.status);
+        }
+        show_raw_output(false)?;
+        let origins = LinkOrigins::new(inputs, &self.args.c_lib, &self.kani_lib_c, crate_name);
+        bail!("{}", render_link_conflicts(output, &conflicts, &origins))
     }
 
-    /// Produce a goto binary with its entry point set to a particular proof harness.
+    /// The goto-cc command that produces a goto binary with its entry point set to a particular
+    /// proof harness.
     pub fn specialize_to_proof_harness(
         &self,
         input: &Path,
         output: &Path,
         function: &str,
-    ) -> Result<()> {
+    ) -> Command {
         let mut cmd = Command::new("goto-cc");
         cmd.arg(input).args(["--function", function, "-o"]).arg(output);
+        cmd
+    }
+}
+
+/// A symbol goto-cc failed to link, parsed from its diagnostics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum LinkConflict {
+    /// The symbol is defined in two modules, e.g. two `#[no_mangle]` functions with the same name.
+    Duplicate {
+        symbol: String,
+        /// goto-cc's description of the conflict, e.g. `duplicate definition of function`.
+        reason: String,
+        first: Option<Definition>,
+        second: Option<Definition>,
+    },
+    /// The symbol is used, but none of the linked modules defines it.
+    Undefined { symbol: String, location: Option<String> },
+}
+
+/// Where goto-cc found one of the definitions of a conflicting symbol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Definition {
+    /// The module name, which goto-cc derives from the name of the file the symbol comes from.
+    module: String,
+    /// The source location, e.g. `file src/lib.rs line 3 function foo`.
+    location: String,
+}
 
-        self.run_suppress(cmd)?;
+/// Parse the linking errors in the output of goto-cc. Other errors are ignored.
+///
+/// goto-cc reports a conflict as:
+/// ```text
+/// error: duplicate definition of function 'foo'
+/// old definition in module 'a' file a.c line 1 function foo
+/// new definition in module 'b' file b.c line 2 function foo
+/// ```
+fn parse_link_conflicts(output: &str) -> Vec<LinkConflict> {
+    let conflict = Regex::new(r"error: (?P<reason>[^'`]+?) '(?P<symbol>[^']+)'\s*$").unwrap();
+    let definition = Regex::new(
+        r"^(?P<age>old|new) definition in module '(?P<module>[^']*)'\s*(?P<location>.*)$",
+    )
+    .unwrap();
+    let undefined = Regex::new(
+        r"^(?:(?P<location>.*?):\s*)?(?:error: )?undefined reference to [`'](?P<symbol>[^'`]+)'",
+    )
+    .unwrap();
+
+    let mut conflicts: Vec<LinkConflict> = vec![];
+    for line in output.lines().map(str::trim) {
+        if let Some(captures) = definition.captures(line)
+            && let Some(LinkConflict::Duplicate { first, second, .. }) = conflicts.last_mut()
+        {
+            let parsed = Some(Definition {
+                module: captures["module"].to_string(),
+                location: captures["location"].trim().to_string(),
+            });
+            if &captures["age"] == "old" {
+                *first = parsed;
+            } else {
+                *second = parsed;
+            }
+        } else if let Some(captures) = undefined.captures(line) {
+            let symbol = captures["symbol"].to_string();
+            let location = captures.name("location").map(|l| l.as_str().trim().to_string());
+            // A missing symbol is reported once per use, but listed once.
+            if !conflicts
+                .iter()
+                .any(|c| matches!(c, LinkConflict::Undefined { symbol: s, .. } if *s == symbol))
+            {
+                conflicts.push(LinkConflict::Undefined { symbol, location });
+            }
+        } else if let Some(captures) = conflict.captures(line) {
+            conflicts.push(LinkConflict::Duplicate {
+                symbol: captures["symbol"].to_string(),
+                reason: captures["reason"].trim().to_string(),
+                first: None,
+                second: None,
+            });
+        }
+    }
+    // goto-cc reports where both definitions of a symbol are. Without them, the error is about
+    // something else, e.g. a missing file.
+    conflicts.retain(|conflict| {
+        !matches!(conflict, LinkConflict::Duplicate { first: None, second: None, .. })
+    });
+    conflicts
+}
+
+/// Maps the modules named by goto-cc back to the crate or C library that was linked.
+struct LinkOrigins {
+    /// The module name of every linked file, with a description of where it comes from.
+    modules: Vec<(String, String)>,
+    crate_name: String,
+}
+
+impl LinkOrigins {
+    fn new(inputs: &[PathBuf], c_libs: &[PathBuf], kani_lib_c: &Path, crate_name: &str) -> Self {
+        let crate_files =
+            inputs.iter().map(|input| (input.as_path(), format!("crate `{crate_name}`")));
+        let c_lib_files =
+            c_libs.iter().map(|lib| (lib.as_path(), format!("C library `{}`", lib.display())));
+        let kani_files = std::iter::once((kani_lib_c, "Kani's C library".to_string()));
+        let modules = crate_files
+            .chain(c_lib_files)
+            .chain(kani_files)
+            .filter_map(|(path, origin)| Some((module_name(path)?, origin)))
+            .collect();
+        LinkOrigins { modules, crate_name: crate_name.to_string() }
+    }
+
+    /// Describe where `definition` comes from.
+    /// Definitions in Rust source files that don't match a linked file belong to the crate.
+    fn describe(&self, definition: &Definition) -> String {
+        let origin = self
+            .modules
+            .iter()
+            .find(|(module, _)| *module == definition.module)
+            .map(|(_, origin)| origin.clone())
+            .unwrap_or_else(|| {
+                if definition.location.contains(".rs ") || definition.location.ends_with(".rs") {
+                    format!("crate `{}`", self.crate_name)
+                } else {
+                    format!("module `{}`", definition.module)
+                }
+            });
+        if definition.location.is_empty() {
+            origin
+        } else {
+            format!("{origin} ({})", definition.location)
+        }
+    }
+}
+
+/// The module name goto-cc gives to the symbols of `path`: its file name up to the first `.`.
+fn module_name(path: &Path) -> Option<String> {
+    let file_name = path.file_name()?.to_string_lossy();
+    Some(file_name.split('.').next().unwrap_or_default().to_string())
+}
+
+/// The error reported for the `conflicts` goto-cc found while linking `output`.
+fn render_link_conflicts(
+    output: &Path,
+    conflicts: &[LinkConflict],
+    origins: &LinkOrigins,
+) -> String {
+    let mut message = format!("goto-cc failed to link `{}`:\n", output.display());
+    let mut has_duplicates = false;
+    let mut has_undefined = false;
+    for conflict in conflicts {
+        match conflict {
+            LinkConflict::Duplicate { symbol, reason, first, second } => {
+                has_duplicates = true;
+                let describe = |definition: &Option<Definition>| {
+                    definition
+                        .as_ref()
+                        .map_or_else(|| "an unknown module".to_string(), |d| origins.describe(d))
+                };
+                let _ = writeln!(
+                    message,
+                    " - `{symbol}` ({reason}) is defined in {} and in {}",
+                    describe(first),
+                    describe(second)
+                );
+            }
+            LinkConflict::Undefined { symbol, location } => {
+                has_undefined = true;
+                let location = location.as_ref().map(|l| format!(" ({l})")).unwrap_or_default();
+                let _ = writeln!(message, " - `{symbol}` is used but never defined{location}");
+            }
+        }
+    }
+    if has_duplicates {
+        message.push_str(
+            "Rename one of the definitions, or leave one of them out of the build by disabling the \
+            feature that enables it or excluding its package with `--exclude`.\n",
+        );
+    }
+    if has_undefined {
+        message.push_str(
+            "Enable the feature that provides the missing definitions, or link the C library that \
+            defines them with `--c-lib`.\n",
+        );
+    }
+    message.push_str("Rerun with `--debug` to see the full goto-cc output.");
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// goto-cc output for a `#[no_mangle]` function that is also defined in a `--c-lib` file.
+    const DUPLICATE_OUTPUT: &str = "\
+Reading GOTO program from file my_harness.symtab.out
+Reading GOTO program from file helpers.c
+file helpers.c line 1 function checksum: error: duplicate definition of function 'checksum'
+old definition in module 'my_harness' file src/lib.rs line 3 column 1 function checksum
+new definition in module 'helpers' file helpers.c line 1 function checksum
+CONVERSION ERROR
+";
+
+    fn origins() -> LinkOrigins {
+        LinkOrigins::new(
+            &[PathBuf::from("target/my_crate/my_harness.symtab.out")],
+            &[PathBuf::from("c/helpers.c")],
+            Path::new("/kani/lib/kani_lib.c"),
+            "my_crate",
+        )
+    }
+
+    #[test]
+    fn check_parse_duplicate_symbol() {
+        let conflicts = parse_link_conflicts(DUPLICATE_OUTPUT);
+        assert_eq!(
+            conflicts,
+            vec![LinkConflict::Duplicate {
+                symbol: "checksum".to_string(),
+                reason: "duplicate definition of function".to_string(),
+                first: Some(Definition {
+                    module: "my_harness".to_string(),
+                    location: "file src/lib.rs line 3 column 1 function checksum".to_string(),
+                }),
+                second: Some(Definition {
+                    module: "helpers".to_string(),
+                    location: "file helpers.c line 1 function checksum".to_string(),
+                }),
+            }]
+        );
+    }
+
+    #[test]
+    fn check_parse_undefined_symbol() {
+        let output = "\
+main.c: error: undefined reference to `compute'
+main.c: error: undefined reference to `compute'
+";
+        assert_eq!(
+            parse_link_conflicts(output),
+            vec![LinkConflict::Undefined {
+                symbol: "compute".to_string(),
+                location: Some("main.c".to_string()),
+            }]
+        );
+        assert!(parse_link_conflicts("error: invalid option\n").is_empty());
+        assert!(parse_link_conflicts("error: failed to open file 'lib.c'\n").is_empty());
+    }
+
+    #[test]
+    fn check_render_duplicate_symbol() {
+        let conflicts = parse_link_conflicts(DUPLICATE_OUTPUT);
+        let message = render_link_conflicts(Path::new("my_harness.out"), &conflicts, &origins());
+        assert_eq!(
+            message,
+            "goto-cc failed to link `my_harness.out`:\n \
+             - `checksum` (duplicate definition of function) is defined in crate `my_crate` \
+             (file src/lib.rs line 3 column 1 function checksum) and in C library `c/helpers.c` \
+             (file helpers.c line 1 function checksum)\n\
+             Rename one of the definitions, or leave one of them out of the build by disabling \
+             the feature that enables it or excluding its package with `--exclude`.\n\
+             Rerun with `--debug` to see the full goto-cc output."
+        );
+    }
 
-        Ok(())
+    #[test]
+    fn check_describe_unknown_module() {
+        let origins = origins();
+        let rust = Definition {
+            module: "other".to_string(),
+            location: "file src/a.rs line 1".to_string(),
+        };
+        assert_eq!(origins.describe(&rust), "crate `my_crate` (file src/a.rs line 1)");
+        let c = Definition { module: "other".to_string(), location: String::new() };
+        assert_eq!(origins.describe(&c), "module `other`");
     }
 }
@@ -23,18 +23,51 @@ pub fn instrument_model(
         project: &Project,
         harness: &HarnessMetadata,
     ) -> Result<()> {
+        for cmd in self.instrument_model_commands(input, output, project, harness)? {
+            self.run_suppress(cmd)?;
+        }
+
+        if self.args.gen_c {
+            let c_outfile = alter_extension(output, "c");
+            // We don't put the C file into temporaries to be deleted.
+            if !self.args.common_args.quiet {
+                println!("Generated C code written to {}", c_outfile.to_string_lossy());
+            }
+
+            let c_demangled = alter_extension(output, "demangled.c");
+            let prett_name_map =
+                project.get_harness_artifact(harness, ArtifactType::PrettyNameMap).unwrap();
+            self.demangle_c(prett_name_map, &c_outfile, &c_demangled)?;
+            if !self.args.common_args.quiet {
+                println!("Demangled GotoC code written to {}", c_demangled.to_string_lossy())
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The goto-cc and goto-instrument commands run by [KaniSession::instrument_model], in order.
+    /// They are also printed by `--dry-run`.
+    pub fn instrument_model_commands(
+        &self,
+        input: &Path,
+        output: &Path,
+        project: &Project,
+        harness: &HarnessMetadata,
+    ) -> Result<Vec<Command>> {
         // We actually start by calling goto-cc to start the specialization:
-        self.specialize_to_proof_harness(input, output, &harness.mangled_name)?;
+        let mut commands =
+            vec![self.specialize_to_proof_harness(input, output, &harness.mangled_name)];
 
         let restrictions = project.get_harness_artifact(harness, ArtifactType::VTableRestriction);
         if let Some(restrictions_path) = restrictions {
-            self.apply_vtable_restrictions(output, restrictions_path)?;
+            commands.push(self.apply_vtable_restrictions(output, restrictions_path)?);
         }
 
         // Run sanity checks in the model generated by kani-compiler before any goto-instrument
         // transformation.
         if self.args.run_sanity_checks {
-            self.goto_sanity_check(output)?;
+            commands.push(self.goto_sanity_check(output));
         }
 
         let is_loop_contracts_enabled = self
@@ -43,41 +76,30 @@ pub fn instrument_model(
             .unstable_features
             .contains(kani_metadata::UnstableFeature::LoopContracts)
             && harness.has_loop_contracts;
-        self.instrument_contracts(harness, is_loop_contracts_enabled, output)?;
+        commands.extend(self.instrument_contracts(harness, is_loop_contracts_enabled, output));
 
         if self.args.checks.undefined_function_on() {
-            self.add_library(output)?;
-            self.undefined_functions(output)?;
+            commands.push(self.add_library(output));
+            commands.push(self.undefined_functions(output));
         } else {
-            self.just_drop_unused_functions(output)?;
+            commands.push(self.just_drop_unused_functions(output));
         }
 
-        self.rewrite_back_edges(output)?;
+        commands.push(self.rewrite_back_edges(output));
 
         if self.args.gen_c {
-            let c_outfile = alter_extension(output, "c");
-            // We don't put the C file into temporaries to be deleted.
-
-            self.gen_c(output, &c_outfile)?;
-
-            if !self.args.common_args.quiet {
-                println!("Generated C code written to {}", c_outfile.to_string_lossy());
-            }
-
-            let c_demangled = alter_extension(output, "demangled.c");
-            let prett_name_map =
-                project.get_harness_artifact(harness, ArtifactType::PrettyNameMap).unwrap();
-            self.demangle_c(prett_name_map, &c_outfile, &c_demangled)?;
-            if !self.args.common_args.quiet {
-                println!("Demangled GotoC code written to {}", c_demangled.to_string_lossy())
-            }
+            commands.push(self.gen_c(output, &alter_extension(output, "c")));
         }
 
-        Ok(())
+        Ok(commands)
     }
 
     /// Apply -Z restrict-vtable to a goto binary.
-    pub fn apply_vtable_restrictions(&self, goto_file: &Path, restrictions: &Path) -> Result<()> {
+    pub fn apply_vtable_restrictions(
+        &self,
+        goto_file: &Path,
+        restrictions: &Path,
+    ) -> Result<Command> {
         let linked_restrictions = alter_extension(goto_file, "linked-restrictions.json");
         self.record_temporary_file(&linked_restrictions);
         collect_and_link_function_pointer_restrictions(restrictions, &linked_restrictions)?;
@@ -89,14 +111,14 @@ pub fn apply_vtable_restrictions(&self, goto_file: &Path, restrictions: &Path) -
             goto_file.to_owned().into_os_string(), // output
         ];
 
-        self.call_goto_instrument(args)
+        Ok(self.goto_instrument_command(args))
     }
 
     /// Link the binary against the CBMC model for C library functions.
     /// Normally this happens implicitly, but we use this explicitly
     /// before we invoke `undefined_functions` below, otherwise these
     /// functions appear undefined.
-    fn add_library(&self, file: &Path) -> Result<()> {
+    fn add_library(&self, file: &Path) -> Command {
         let args: Vec<OsString> = vec![
             "--add-library".into(),
             "--no-malloc-may-fail".into(),
@@ -104,7 +126,7 @@ fn add_library(&self, file: &Path) -> Result<()> {
             file.to_owned().into_os_string(), // output
         ];
 
-        self.call_goto_instrument(args)
+        self.goto_instrument_command(args)
     }
 
     /// Instruct CBMC to "assert false" when invoking an undefined function.
@@ -113,7 +135,7 @@ fn add_library(&self, file: &Path) -> Result<()> {
     /// Then remove unused functions. (Oddly, it seems CBMC will both see some
     /// functions as unused and remove them, and also as used and so would
     /// generate "assert false". So it's essential to do this afterwards.)
-    fn undefined_functions(&self, file: &Path) -> Result<()> {
+    fn undefined_functions(&self, file: &Path) -> Command {
         let args: Vec<OsString> = vec![
             "--generate-function-body-options".into(),
             "assert-false-assume-false".into(),
@@ -124,49 +146,49 @@ fn undefined_functions(&self, file: &Path) -> Result<()> {
             file.to_owned().into_os_string(), // output
         ];
 
-        self.call_goto_instrument(args)
+        self.goto_instrument_command(args)
     }
 
     /// Remove all functions unreachable from the current proof harness.
-    fn just_drop_unused_functions(&self, file: &Path) -> Result<()> {
+    fn just_drop_unused_functions(&self, file: &Path) -> Command {
         let args: Vec<OsString> = vec![
             "--drop-unused-functions".into(),
             file.to_owned().into_os_string(), // input
             file.to_owned().into_os_string(), // output
         ];
 
-        self.call_goto_instrument(args)
+        self.goto_instrument_command(args)
     }
 
-    fn rewrite_back_edges(&self, file: &Path) -> Result<()> {
+    fn rewrite_back_edges(&self, file: &Path) -> Command {
         let args: Vec<OsString> = vec![
             "--ensure-one-backedge-per-target".into(),
             file.to_owned().into_os_string(), // input
             file.to_owned().into_os_string(), // output
         ];
 
-        self.call_goto_instrument(args)
+        self.goto_instrument_command(args)
     }
 
-    fn goto_sanity_check(&self, file: &Path) -> Result<()> {
+    fn goto_sanity_check(&self, file: &Path) -> Command {
         let args: Vec<OsString> = vec![
             "--validate-goto-model".into(),
             file.to_owned().into_os_string(), // input
             file.to_owned().into_os_string(), // output
         ];
 
-        self.call_goto_instrument(args)
+        self.goto_instrument_command(args)
     }
 
     /// Generate a .c file from a goto binary (i.e. --gen-c)
-    pub fn gen_c(&self, file: &Path, output_file: &Path) -> Result<()> {
+    pub fn gen_c(&self, file: &Path, output_file: &Path) -> Command {
         let args: Vec<OsString> = vec![
             "--dump-c".into(),
             file.to_owned().into_os_string(),
             output_file.to_owned().into_os_string(),
         ];
 
-        self.call_goto_instrument(args)
+        self.goto_instrument_command(args)
     }
 
     /// Apply annotated function contracts and loop contracts with goto-instrument.
@@ -175,10 +197,10 @@ pub fn instrument_contracts(
         harness: &HarnessMetadata,
         is_loop_contracts_enabled: bool,
         file: &Path,
-    ) -> Result<()> {
-        // Do nothing if neither loop contracts nor function contracts is enabled.
+    ) -> Option<Command> {
+        // No command if neither loop contracts nor function contracts is enabled.
         if !is_loop_contracts_enabled && harness.contract.is_none() {
-            return Ok(());
+            return None;
         }
 
         let mut args: Vec<OsString> =
@@ -209,7 +231,7 @@ pub fn instrument_contracts(
         args.push(file.into());
         args.push(file.into());
 
-        self.call_goto_instrument(&args)
+        Some(self.goto_instrument_command(&args))
     }
 
     /// Generate a .demangled.c file from the .c file using the `prettyName`s from the symbol table
@@ -240,15 +262,14 @@ pub fn demangle_c(
         Ok(())
     }
 
-    /// Non-public helper function to actually do the run of goto-instrument
-    fn call_goto_instrument<S: AsRef<OsStr>>(
+    /// Non-public helper function to build a goto-instrument command
+    fn goto_instrument_command<S: AsRef<OsStr>>(
         &self,
         args: impl IntoIterator<Item = S>,
-    ) -> Result<()> {
+    ) -> Command {
         // TODO get goto-instrument path from self
         let mut cmd = Command::new("goto-instrument");
         cmd.args(args);
-
-        self.run_suppress(cmd)
+        cmd
     }
 }
@@ -1,26 +1,101 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use crate::util::warning;
+use crate::args::common::Verbosity;
+use crate::error::KaniDriverError;
+use crate::event_log;
+use crate::util::{render_command, warning};
 use anyhow::Result;
 use kani_metadata::HarnessMetadata;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::ffi::OsString;
+use std::io::Write;
 use std::path::Path;
 use std::process::Command;
+use std::time::Instant;
 
 use crate::session::KaniSession;
 
+/// The outcome of `--synthesize-loop-contracts` for a harness.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoopContractSynthesis {
+    /// Whether goto-synthesizer finished successfully. If it did not, the harness was verified
+    /// without synthesized loop contracts.
+    pub succeeded: bool,
+    /// Time spent in goto-synthesizer.
+    pub runtime_ms: u64,
+    /// The loops of the harness, named as by `goto-instrument --show-loops`, e.g. `main.0`.
+    pub loops: Vec<String>,
+    /// The invariant clauses synthesized for each loop. `None` if the synthesizer did not report
+    /// them.
+    pub invariants: Option<BTreeMap<String, Vec<String>>>,
+    /// The loops the synthesizer found no invariant for. `None` if the invariants are unknown.
+    pub gave_up: Option<Vec<String>>,
+}
+
+impl LoopContractSynthesis {
+    fn new(
+        succeeded: bool,
+        runtime_ms: u64,
+        loops: Vec<String>,
+        invariants: Option<BTreeMap<String, Vec<String>>>,
+    ) -> Self {
+        let gave_up = invariants.as_ref().map(|invariants| {
+            loops.iter().filter(|l| !invariants.contains_key(*l)).cloned().collect()
+        });
+        LoopContractSynthesis { succeeded, runtime_ms, loops, invariants, gave_up }
+    }
+
+    /// Number of loops with a synthesized invariant, if known.
+    pub fn synthesized(&self) -> Option<usize> {
+        self.invariants.as_ref().map(BTreeMap::len)
+    }
+
+    /// Summarize the synthesis in the output of the harness.
+    pub fn render(&self) -> String {
+        let seconds = self.runtime_ms as f64 / 1000.0;
+        if !self.succeeded {
+            return format!(
+                "{} goto-synthesizer failed after {seconds:.1}s, so the harness was verified \
+                without synthesized loop invariants.\n",
+                console::style("LOOP CONTRACT SYNTHESIS FAILED").red()
+            );
+        }
+        let Some(synthesized) = self.synthesized() else {
+            return format!(
+                "[Kani] info: Loop contract synthesis finished for {} loops in {seconds:.1}s.\n",
+                self.loops.len()
+            );
+        };
+        let total = self.loops.len().max(synthesized);
+        let mut rendered = format!(
+            "[Kani] info: Loop contract synthesis: synthesized {synthesized}/{total} loop \
+            invariants in {seconds:.1}s.\n"
+        );
+        if let Some(gave_up) = self.gave_up.as_ref().filter(|gave_up| !gave_up.is_empty()) {
+            rendered.push_str(&format!(
+                "[Kani] info: No loop invariant was synthesized for: {}.\n",
+                gave_up.join(", ")
+            ));
+        }
+        rendered
+    }
+}
+
 impl KaniSession {
     /// Synthesize loop contracts for a goto binary `input` and produce a new goto binary `output`
     /// The synthesizer we use is `goto-synthesizer` built in CBMC codebase, which is an enumerative
     /// loop-contracts synthesizer. `goto-synthesizer` enumerates and checks if a candidate can be
     /// used to prove some assertions, and applies found invariants when all checks pass.
+    /// A failure of the synthesizer is part of the returned outcome rather than an error, so the
+    /// harness is still verified and its report explains why no invariant was used.
     pub fn synthesize_loop_contracts(
         &self,
         input: &Path,
         output: &Path,
         harness_metadata: &HarnessMetadata,
-    ) -> Result<()> {
+    ) -> Result<LoopContractSynthesis> {
         if !self.args.common_args.quiet {
             println!("Running loop contract synthesizer.");
             warning("This process may not terminate.");
@@ -29,6 +104,51 @@ pub fn synthesize_loop_contracts(
             );
         }
 
+        let loops = self.show_loops(input);
+        let mut cmd = self.goto_synthesizer_command(input, output, harness_metadata)?;
+        let verbosity = &self.args.common_args;
+        if verbosity.verbose() {
+            println!("[Kani] Running: `{}`", render_command(&cmd).to_string_lossy());
+        }
+        let start = Instant::now();
+        let result = event_log::output(&mut cmd).map_err(|source| {
+            KaniDriverError::tool_not_started(&cmd.get_program().to_string_lossy(), source)
+        })?;
+        let runtime_ms = start.elapsed().as_millis() as u64;
+        let succeeded = result.status.success();
+        if verbosity.verbose() || (!succeeded && !verbosity.quiet()) {
+            let stdout = std::io::stdout();
+            let mut handle = stdout.lock();
+            handle.write_all(&result.stdout)?;
+            handle.write_all(&result.stderr)?;
+        }
+
+        let invariants = succeeded
+            .then(|| parse_synthesized_invariants(&String::from_utf8_lossy(&result.stdout)))
+            .flatten();
+        Ok(LoopContractSynthesis::new(succeeded, runtime_ms, loops, invariants))
+    }
+
+    /// The loops of the goto binary `file`. They are only used to report on the synthesis, so
+    /// failing to list them is not an error.
+    fn show_loops(&self, file: &Path) -> Vec<String> {
+        let mut cmd = Command::new("goto-instrument");
+        cmd.arg("--show-loops").arg(file);
+        match cmd.output() {
+            Ok(output) if output.status.success() => {
+                parse_show_loops(&String::from_utf8_lossy(&output.stdout))
+            }
+            _ => vec![],
+        }
+    }
+
+    /// The goto-synthesizer command run by [KaniSession::synthesize_loop_contracts].
+    pub fn goto_synthesizer_command(
+        &self,
+        input: &Path,
+        output: &Path,
+        harness_metadata: &HarnessMetadata,
+    ) -> Result<Command> {
         let mut args: Vec<OsString> = vec![
             "--loop-contracts-no-unwind".into(),
             input.to_owned().into_os_string(),  // input
@@ -50,9 +170,115 @@ pub fn synthesize_loop_contracts(
 
         let mut cmd = Command::new("goto-synthesizer");
         cmd.args(args);
+        Ok(cmd)
+    }
+}
+
+/// Parse the loop names out of the output of `goto-instrument --show-loops`, which lists every
+/// loop as `Loop <function>.<number>:` followed by its location.
+fn parse_show_loops(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("Loop ")?.strip_suffix(':'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// The loop contracts dumped by goto-synthesizer, see RFC 0004:
+/// ```json
+/// { "sources": [...], "functions": [{ "main": ["loop 1 invariant y >= 0", "loop 1 assigns y"] }] }
+/// ```
+#[derive(Deserialize)]
+struct DumpedLoopContracts {
+    functions: Vec<BTreeMap<String, Vec<String>>>,
+}
+
+/// Parse the invariant clauses out of the loop contracts that goto-synthesizer reported in
+/// `output`, by loop name. Loops are numbered from 1 in the dump, so `loop 1` of `main` is the
+/// loop `goto-instrument` calls `main.0`. Returns `None` if `output` has no loop contracts.
+fn parse_synthesized_invariants(output: &str) -> Option<BTreeMap<String, Vec<String>>> {
+    let json = &output[output.find('{')?..=output.rfind('}')?];
+    let dumped: DumpedLoopContracts = serde_json::from_str(json).ok()?;
+    let mut invariants: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (function, clauses) in dumped.functions.iter().flatten() {
+        for clause in clauses {
+            let Some((number, invariant)) = clause
+                .strip_prefix("loop ")
+                .and_then(|rest| rest.split_once(" invariant "))
+                .and_then(|(number, invariant)| Some((number.parse::<usize>().ok()?, invariant)))
+            else {
+                continue;
+            };
+            let name = format!("{function}.{}", number.saturating_sub(1));
+            invariants.entry(name).or_default().push(invariant.trim().to_string());
+        }
+    }
+    Some(invariants)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_parse_show_loops() {
+        let output = "Loop main.0:\n  file main.rs line 13 column 5 function main\n\n\
+            Loop _RNvCs_foo.1:\n  file lib.rs line 4 function foo\n";
+        assert_eq!(parse_show_loops(output), ["main.0", "_RNvCs_foo.1"]);
+        assert!(parse_show_loops("").is_empty());
+    }
+
+    #[test]
+    fn check_parse_synthesized_invariants() {
+        let output = r#"Enumerative loop contracts synthesizer
+{
+    "sources": [ "/work/synthesis/test.rs" ],
+    "functions": [
+      {
+        "main": [ "loop 1 invariant y >= 0",
+                  "loop 1 assigns var_9,var_10,y",
+                  "loop 2 invariant x == 0 || x == 1" ]
+      },
+      { "foo": [ "loop 1 assigns i" ] }
+    ],
+    "output": "stdout"
+}"#;
+        let invariants = parse_synthesized_invariants(output).unwrap();
+        assert_eq!(
+            invariants,
+            BTreeMap::from([
+                ("main.0".to_string(), vec!["y >= 0".to_string()]),
+                ("main.1".to_string(), vec!["x == 0 || x == 1".to_string()]),
+            ])
+        );
+        assert_eq!(parse_synthesized_invariants("no contracts were dumped"), None);
+        assert_eq!(parse_synthesized_invariants("{ not json }"), None);
+    }
+
+    #[test]
+    fn check_synthesis_outcome() {
+        let loops = vec!["main.0".to_string(), "main.1".to_string(), "foo.0".to_string()];
+        let invariants = BTreeMap::from([("main.0".to_string(), vec!["y >= 0".to_string()])]);
+        let outcome = LoopContractSynthesis::new(true, 12_400, loops.clone(), Some(invariants));
+        assert_eq!(outcome.synthesized(), Some(1));
+        assert_eq!(
+            outcome.gave_up.as_deref(),
+            Some(&["main.1".to_string(), "foo.0".to_string()][..])
+        );
+        assert_eq!(
+            outcome.render(),
+            "[Kani] info: Loop contract synthesis: synthesized 1/3 loop invariants in 12.4s.\n\
+            [Kani] info: No loop invariant was synthesized for: main.1, foo.0.\n"
+        );
 
-        self.run_suppress(cmd)?;
+        let unknown = LoopContractSynthesis::new(true, 500, loops.clone(), None);
+        assert_eq!(unknown.gave_up, None);
+        assert_eq!(
+            unknown.render(),
+            "[Kani] info: Loop contract synthesis finished for 3 loops in 0.5s.\n"
+        );
 
-        Ok(())
+        let failed = LoopContractSynthesis::new(false, 1_000, loops, None);
+        assert!(failed.render().contains("the harness was verified without synthesized loop"));
     }
 }
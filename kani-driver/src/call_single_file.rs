@@ -163,12 +163,14 @@ pub fn kani_compiler_local_flags(&self) -> Vec<KaniArg> {
             flags.push("--no-assert-contracts".into());
         }
 
-        for harness in &self.args.harnesses {
-            flags.push(format!("--harness {harness}").into());
-        }
+        if self.args.compiler_selects_harnesses() {
+            for harness in &self.args.harnesses {
+                flags.push(format!("--harness {harness}").into());
+            }
 
-        if self.args.exact {
-            flags.push("--exact".into());
+            if self.args.exact {
+                flags.push("--exact".into());
+            }
         }
 
         if let Some(args) = self.autoharness_compiler_flags.clone() {
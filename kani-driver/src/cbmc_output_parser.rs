@@ -33,7 +33,7 @@
 use std::os::unix::process::ExitStatusExt;
 use std::path::PathBuf;
 use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::{Child, ChildStdout};
+use tokio::process::ChildStdout;
 
 const RESULT_ITEM_PREFIX: &str = "  {\n    \"result\":";
 
@@ -112,6 +112,12 @@ pub fn is_cover_property(&self) -> bool {
         self.property_id.class == Self::COVER_PROPERTY_CLASS
     }
 
+    /// Returns true if this is an unwinding assertion of a loop or of a recursive call
+    pub fn is_unwinding_assertion(&self) -> bool {
+        self.description.contains("unwinding assertion loop")
+            || self.description.contains("recursion unwinding assertion")
+    }
+
     pub fn property_name(&self) -> String {
         let class = &self.property_id.class;
         let id = self.property_id.id;
@@ -124,6 +130,15 @@ pub fn property_name(&self) -> String {
     pub fn has_property_class_format(string: &str) -> bool {
         string == "NaN" || string.chars().all(|c| c.is_ascii_lowercase() || c == '_' || c == '-')
     }
+
+    /// Returns the trace step that made uninitialized memory nondeterministic, if this check
+    /// failed with a trace that contains one.
+    pub fn nondet_uninit_step(&self) -> Option<&TraceItem> {
+        if self.status != CheckStatus::Failure {
+            return None;
+        }
+        self.trace.as_ref()?.iter().find(|step| step.is_nondet_uninit())
+    }
 }
 
 impl<'de> serde::Deserialize<'de> for PropertyId {
@@ -224,11 +239,34 @@ pub struct SourceLocation {
     pub line: Option<String>,
 }
 
+/// Path fragments of the files that contain Kani's models of functions that are not suitable for
+/// verification (e.g. intrinsics, allocation or `memcmp`), including CBMC's builtin library.
+const KANI_MODEL_FILES: &[&str] = &[
+    "library/kani/src/models/",
+    "library/kani_core/src/models.rs",
+    "library/kani/kani_lib.c",
+    "<builtin-library-",
+];
+
+/// Marker appended to locations inside Kani's models, so users can tell them from their own code.
+pub const KANI_MODEL_MARKER: &str = "<kani-model>";
+
 impl SourceLocation {
     /// Determines if fundamental parts of a source location are missing.
     pub fn is_missing(&self) -> bool {
         self.file.is_none() && self.function.is_none()
     }
+
+    /// Whether this location is in one of Kani's models rather than in user code.
+    pub fn is_kani_model(&self) -> bool {
+        self.file.as_ref().is_some_and(|file| is_kani_model_file(file))
+    }
+}
+
+/// Whether `file` is one of the files that contain Kani's models.
+pub fn is_kani_model_file(file: &str) -> bool {
+    let file = file.replace('\\', "/");
+    KANI_MODEL_FILES.iter().any(|marker| file.contains(marker))
 }
 
 /// `Display` implement for `SourceLocation`.
@@ -239,7 +277,8 @@ pub fn is_missing(&self) -> bool {
 ///
 /// Source locations formatted this way will look like:
 /// `<file>:<line>:<column> in function <function>`
-/// if all attributes were specified. Otherwise, we:
+/// if all attributes were specified, followed by `<kani-model>` for locations in Kani's models.
+/// Otherwise, we:
 ///  * Omit `in function <function>` if the function isn't specified.
 ///  * Use `Unknown file` instead of `<file>:<line>:<column>` if the file isn't
 ///    specified.
@@ -263,6 +302,9 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             let demangled_function = demangle(&function);
             write!(f, " in function {demangled_function:#}")?;
         }
+        if self.is_kani_model() {
+            write!(f, " {KANI_MODEL_MARKER}")?;
+        }
         Ok(())
     }
 }
@@ -288,11 +330,35 @@ fn filepath(file: String) -> String {
 #[serde(rename_all = "camelCase")]
 pub struct TraceItem {
     pub step_type: String,
+    /// Whether an assignment is to a `variable` or an `actual-parameter`.
+    pub assignment_type: Option<String>,
+    /// CBMC hides steps that do not correspond to user code, e.g. the ones of its models.
+    #[serde(default)]
+    pub hidden: bool,
     pub lhs: Option<String>,
     pub source_location: Option<SourceLocation>,
     pub value: Option<TraceValue>,
 }
 
+impl TraceItem {
+    /// Whether this step gives uninitialized memory its nondeterministic contents.
+    ///
+    /// CBMC reports variables declared without an initializer as `declaration` steps, and the
+    /// objects it creates for heap allocations as hidden assignments to a `dynamic_object`.
+    /// The source location is the one of the declaration or allocation.
+    pub fn is_nondet_uninit(&self) -> bool {
+        match self.step_type.as_str() {
+            "declaration" => true,
+            "assignment" => {
+                self.hidden
+                    && self.assignment_type.as_deref() == Some("variable")
+                    && self.lhs.as_deref().is_some_and(|lhs| lhs.starts_with("dynamic_object"))
+            }
+            _ => false,
+        }
+    }
+}
+
 /// Struct that represents a trace value.
 ///
 /// Note: this struct can have a lot of different fields depending on the value type.
@@ -518,6 +584,9 @@ async fn read_output(
 pub struct VerificationOutput {
     pub process_status: i32,
     pub processed_items: Vec<ParserItem>,
+    /// Whether CBMC was killed because it reached the harness timeout.
+    /// `processed_items` then only contains the items CBMC reported before the timeout.
+    pub timed_out: bool,
 }
 
 /// The main function to process CBMC's output.
@@ -527,27 +596,26 @@ pub struct VerificationOutput {
 /// In general, a filter will pre-process an item (this may or may not transform the item),
 /// then formatted (according to the output format) and print.
 ///
-/// The cbmc process status is returned, along with the (post-filter) items.
+/// The (post-filter) items are pushed to `processed_items` as soon as they are read, so the
+/// items reported so far are kept if the caller stops waiting for CBMC, e.g. on a timeout.
 pub async fn process_cbmc_output(
-    process: &mut Child,
+    stdout: &mut ChildStdout,
     mut eager_filter: impl FnMut(ParserItem) -> Option<ParserItem>,
-) -> Result<VerificationOutput> {
-    let stdout = process.stdout.as_mut().unwrap();
+    processed_items: &mut Vec<ParserItem>,
+) {
     let mut stdout_reader = BufReader::new(stdout);
     let mut parser = Parser::new();
-    // This should run until stdout is closed (which should mean the process
-    // exited) or the specified timeout is reached
-    let mut processed_items = Vec::new();
+    // This should run until stdout is closed (which should mean the process exited)
     while let Some(item) = parser.read_output(&mut stdout_reader).await {
         if let Some(item) = eager_filter(item) {
             processed_items.push(item);
         }
     }
+}
 
-    // This will get us the process's exit code
-    let status = process.wait().await?;
-
-    let process_status = match (status.code(), status.signal()) {
+/// Convert the status of the CBMC process to an exit code.
+pub fn process_status(status: std::process::ExitStatus) -> i32 {
+    match (status.code(), status.signal()) {
         // normal unix exit codes (cbmc uses currently 0-10)
         // https://github.com/diffblue/cbmc/blob/develop/src/util/exit_codes.h
         (Some(x), _) => x,
@@ -557,9 +625,7 @@ pub async fn process_cbmc_output(
         (_, Some(x)) => 128 + x,
         // I think this shouldn't happen? either exit or signal, right?
         (None, None) => unreachable!("Process exited with neither status code nor signal?"),
-    };
-
-    Ok(VerificationOutput { process_status, processed_items })
+    }
 }
 
 /// Takes (by ownership) a vector of messages, and returns that vector with the `Result`
@@ -612,6 +678,23 @@ fn check_property_id_deserialization_general() {
         assert_eq!(dummy_prop.property_name(), prop_id_string[1..prop_id_string.len() - 1]);
     }
 
+    #[test]
+    fn check_kani_model_location() {
+        let location = |file: &str| SourceLocation {
+            function: Some("memcmp".to_string()),
+            file: Some(file.to_string()),
+            column: None,
+            line: Some("10".to_string()),
+        };
+        let model = location("/home/user/.kani/kani-0.66.0/library/kani_core/src/models.rs");
+        assert!(model.is_kani_model());
+        assert!(model.to_string().ends_with(KANI_MODEL_MARKER));
+        assert!(location("<builtin-library-memcmp>").is_kani_model());
+        let user = location("/home/user/project/src/models.rs");
+        assert!(!user.is_kani_model());
+        assert!(!user.to_string().contains(KANI_MODEL_MARKER));
+    }
+
     #[test]
     fn check_property_id_deserialization_only_name() {
         let prop_id_string = "\"alloc::raw_vec::RawVec::<u8>::allocate_in.1\"";
@@ -3,7 +3,7 @@
 
 use crate::args::OutputFormat;
 use crate::call_cbmc::{FailedProperties, VerificationStatus};
-use crate::cbmc_output_parser::{CheckStatus, ParserItem, Property, TraceItem};
+use crate::cbmc_output_parser::{CheckStatus, KANI_MODEL_MARKER, ParserItem, Property, TraceItem};
 use crate::coverage::cov_results::CoverageResults;
 use console::style;
 use once_cell::sync::Lazy;
@@ -149,7 +149,7 @@
     map
 });
 
-const UNSUPPORTED_CONSTRUCT_DESC: &str = "is not currently supported by Kani";
+pub const UNSUPPORTED_CONSTRUCT_DESC: &str = "is not currently supported by Kani";
 const UNWINDING_ASSERT_DESC: &str = "unwinding assertion loop";
 const UNWINDING_ASSERT_REC_DESC: &str = "recursion unwinding assertion";
 const UNDEFINED_FUNCTION_DESC: &str = "undefined function should be unreachable";
@@ -214,6 +214,8 @@ fn format_item(item: &ParserItem, output_format: &OutputFormat) -> Option<String
         OutputFormat::Old => todo!(),
         OutputFormat::Regular => format_item_regular(item),
         OutputFormat::Terse => format_item_terse(item),
+        // The results are printed as JSON events by the harness runner instead.
+        OutputFormat::Json => None,
     }
 }
 
@@ -240,6 +242,9 @@ fn format_item_terse(_item: &ParserItem) -> Option<String> {
 /// `kani::cover` macro) separately. Cover properties currently do not impact
 /// the overall verification success or failure.
 ///
+/// If `timed_out`, `properties` are the checks CBMC reported before it was killed, and the
+/// overall result is reported as a timeout.
+///
 /// TODO: We could `write!` to `result_str` instead
 /// <https://github.com/model-checking/kani/issues/1480>
 pub fn format_result(
@@ -248,6 +253,7 @@ pub fn format_result(
     should_panic: bool,
     failed_properties: FailedProperties,
     show_checks: bool,
+    timed_out: bool,
 ) -> String {
     let mut result_str = String::new();
     let mut number_checks_failed = 0;
@@ -381,14 +387,19 @@ pub fn format_result(
     for prop in failed_tests {
         let failure_message = build_failure_message(prop.description.clone(), &prop.trace.clone());
         result_str.push_str(&failure_message);
+        if let Some(step) = prop.nondet_uninit_step() {
+            result_str.push_str(&nondet_uninit_hint(step));
+        }
     }
 
-    let verification_result = if status == VerificationStatus::Success {
+    let verification_result = if timed_out {
+        style("TIMEOUT").yellow()
+    } else if status == VerificationStatus::Success {
         style("SUCCESSFUL").green()
     } else {
         style("FAILED").red()
     };
-    let should_panic_info = if should_panic {
+    let should_panic_info = if should_panic && !timed_out {
         match failed_properties {
             FailedProperties::None => " (encountered no panics, but at least one was expected)",
             FailedProperties::PanicsOnly => " (encountered one or more panics as expected)",
@@ -434,12 +445,19 @@ pub fn format_coverage(
     should_panic: bool,
     failed_properties: FailedProperties,
     show_checks: bool,
+    timed_out: bool,
 ) -> String {
     let (_coverage_checks, non_coverage_checks): (Vec<Property>, Vec<Property>) =
         properties.iter().cloned().partition(|x| x.property_class() == "code_coverage");
 
-    let verification_output =
-        format_result(&non_coverage_checks, status, should_panic, failed_properties, show_checks);
+    let verification_output = format_result(
+        &non_coverage_checks,
+        status,
+        should_panic,
+        failed_properties,
+        show_checks,
+        timed_out,
+    );
     let cov_results_intro = "Source-based code coverage results:";
     let result = format!("{verification_output}\n{cov_results_intro}\n\n{cov_results}");
 
@@ -449,6 +467,7 @@ pub fn format_coverage(
 /// Attempts to build a message for a failed property with as much detailed
 /// information on the source location as possible.
 fn build_failure_message(description: String, trace: &Option<Vec<TraceItem>>) -> String {
+    let description = contract_clause_description(&description).unwrap_or(description);
     let backup_failure_message = format!("Failed Checks: {description}\n");
     if trace.is_none() {
         return backup_failure_message;
@@ -465,16 +484,45 @@ fn build_failure_message(description: String, trace: &Option<Vec<TraceItem>>) ->
         && failure_source.function.is_some()
         && failure_source.line.is_some()
     {
+        let origin = if failure_source.is_kani_model() {
+            format!(" {KANI_MODEL_MARKER}")
+        } else {
+            String::new()
+        };
         let failure_file = failure_source.file.unwrap();
         let failure_function = failure_source.function.unwrap();
         let failure_line = failure_source.line.unwrap();
         return format!(
-            "Failed Checks: {description}\n File: \"{failure_file}\", line {failure_line}, in {failure_function}\n"
+            "Failed Checks: {description}\n File: \"{failure_file}\", line {failure_line}, in {failure_function}{origin}\n"
         );
     }
     backup_failure_message
 }
 
+/// Explain that a failure depends on the arbitrary contents CBMC gives to uninitialized memory,
+/// and may not happen in a concrete execution.
+fn nondet_uninit_hint(step: &TraceItem) -> String {
+    let origin = match &step.source_location {
+        Some(location) if !location.is_missing() => {
+            format!(" It was declared or allocated at {location}.")
+        }
+        _ => String::new(),
+    };
+    format!(
+        " Hint: this failure involves a read of uninitialized memory, whose contents are \
+        nondeterministic.{origin}\n"
+    )
+}
+
+/// Contract `ensures` clauses are checked with the closure source as their description,
+/// e.g. `|result : &u32| *result <= x`. Rewrite such descriptions to name the clause that failed,
+/// collapsed to a single line: ``ensures clause `*result <= x` failed``.
+fn contract_clause_description(description: &str) -> Option<String> {
+    let (_, body) = description.strip_prefix('|')?.split_once('|')?;
+    let body = body.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!body.is_empty()).then(|| format!("ensures clause `{body}` failed"))
+}
+
 /// Edits an error message.
 ///
 /// At present, we only know one case where CBMC emits an error message, related
@@ -803,3 +851,49 @@ fn annotate_properties_with_reach_results(
     }
     properties
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbmc_output_parser::SourceLocation;
+
+    #[test]
+    fn check_contract_clause_description() {
+        assert_eq!(
+            contract_clause_description("|result : &u32| *result\n    <= x").as_deref(),
+            Some("ensures clause `*result <= x` failed")
+        );
+        assert_eq!(
+            contract_clause_description("|_| old(*ptr) == *ptr").as_deref(),
+            Some("ensures clause `old(*ptr) == *ptr` failed")
+        );
+        assert_eq!(contract_clause_description("assertion failed: x < 10"), None);
+        assert_eq!(contract_clause_description("|result|"), None);
+    }
+
+    #[test]
+    fn check_nondet_uninit_hint() {
+        let step = |step_type: &str, hidden, lhs: &str| TraceItem {
+            step_type: step_type.to_string(),
+            assignment_type: Some("variable".to_string()),
+            hidden,
+            lhs: Some(lhs.to_string()),
+            source_location: Some(SourceLocation {
+                column: Some("17".to_string()),
+                file: Some("/tmp/src/lib.rs".to_string()),
+                function: None,
+                line: Some("4".to_string()),
+            }),
+            value: None,
+        };
+        assert!(step("declaration", false, "x").is_nondet_uninit());
+        assert!(step("assignment", true, "dynamic_object1").is_nondet_uninit());
+        assert!(!step("assignment", false, "dynamic_object1").is_nondet_uninit());
+        assert!(!step("assignment", true, "x").is_nondet_uninit());
+
+        let hint = nondet_uninit_hint(&step("declaration", false, "x"));
+        assert!(hint.contains("uninitialized memory"));
+        assert!(hint.contains("declared or allocated at"));
+        assert!(hint.contains("lib.rs:4:17"));
+    }
+}
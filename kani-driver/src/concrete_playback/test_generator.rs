@@ -748,6 +748,8 @@ fn check_concrete_vals_extractor_primitive() {
             },
             trace: Some(vec![TraceItem {
                 step_type: "assignment".to_string(),
+                assignment_type: Some("variable".to_string()),
+                hidden: false,
                 lhs: Some("goto_symex$$return_value".to_string()),
                 source_location: Some(SourceLocation {
                     column: None,
@@ -794,6 +796,8 @@ fn check_concrete_vals_extractor_array() {
             trace: Some(vec![
                 TraceItem {
                     step_type: "assignment".to_string(),
+                    assignment_type: Some("variable".to_string()),
+                    hidden: false,
                     lhs: Some("goto_symex$$return_value".to_string()),
                     source_location: Some(SourceLocation {
                         column: None,
@@ -844,6 +848,8 @@ fn check_concrete_vals_extractor_array() {
                 // Since the array is of size 4, there are also TraceItems for each element of the array, which extract_harness_value should ignore.
                 TraceItem {
                     step_type: "assignment".to_string(),
+                    assignment_type: Some("variable".to_string()),
+                    hidden: false,
                     lhs: Some("goto_symex$$return_value".to_string()),
                     source_location: Some(SourceLocation {
                         column: None,
@@ -860,6 +866,8 @@ fn check_concrete_vals_extractor_array() {
                 },
                 TraceItem {
                     step_type: "assignment".to_string(),
+                    assignment_type: Some("variable".to_string()),
+                    hidden: false,
                     lhs: Some("goto_symex$$return_value".to_string()),
                     source_location: Some(SourceLocation {
                         column: None,
@@ -876,6 +884,8 @@ fn check_concrete_vals_extractor_array() {
                 },
                 TraceItem {
                     step_type: "assignment".to_string(),
+                    assignment_type: Some("variable".to_string()),
+                    hidden: false,
                     lhs: Some("goto_symex$$return_value".to_string()),
                     source_location: Some(SourceLocation {
                         column: None,
@@ -892,6 +902,8 @@ fn check_concrete_vals_extractor_array() {
                 },
                 TraceItem {
                     step_type: "assignment".to_string(),
+                    assignment_type: Some("variable".to_string()),
+                    hidden: false,
                     lhs: Some("goto_symex$$return_value".to_string()),
                     source_location: Some(SourceLocation {
                         column: None,
@@ -5,6 +5,17 @@
 use serde::{Deserialize, Serialize};
 use std::{collections::BTreeMap, fmt, fmt::Display};
 
+/// Fragments of the names of functions generated by the contract instrumentation.
+/// These functions are attributed to the source lines of the contracted function, so their
+/// coverage checks must not be reported as if they were user code.
+const CONTRACT_INSTRUMENTATION_MARKERS: [&str; 3] =
+    ["__kani_", "kani_register_contract", "kani_contract_mode"];
+
+/// Returns true if `function` was generated by Kani to instrument a function contract.
+pub fn is_contract_instrumentation(function: &str) -> bool {
+    CONTRACT_INSTRUMENTATION_MARKERS.iter().any(|marker| function.contains(marker))
+}
+
 /// The coverage data maps a function name to a set of coverage checks.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CoverageResults {
@@ -15,6 +26,78 @@ impl CoverageResults {
     pub fn new(data: BTreeMap<String, Vec<CoverageCheck>>) -> Self {
         Self { data }
     }
+
+    /// Remove the checks of functions generated by the contract instrumentation.
+    pub fn exclude_contract_instrumentation(&mut self) {
+        for checks in self.data.values_mut() {
+            checks.retain(|check| !is_contract_instrumentation(&check.function));
+        }
+        self.data.retain(|_, checks| !checks.is_empty());
+    }
+
+    /// Remove the results of files with regions that cannot exist in them: regions that start at
+    /// line 0, end before they start, or end past the last line of the file. `line_count` returns
+    /// the number of lines of a file, or `None` if it cannot be determined, in which case the file
+    /// is kept.
+    /// Returns the names of the files that were removed.
+    pub fn remove_impossible_files(
+        &mut self,
+        line_count: impl Fn(&str) -> Option<usize>,
+    ) -> Vec<String> {
+        let mut removed = vec![];
+        self.data.retain(|file, checks| {
+            let Some(total_lines) = line_count(file) else { return true };
+            let valid = checks.iter().all(|check| {
+                let region = &check.region;
+                region.start.0 > 0
+                    && region.start <= region.end
+                    && region.end.0 as usize <= total_lines
+            });
+            if !valid {
+                removed.push(file.clone());
+            }
+            valid
+        });
+        removed
+    }
+
+    /// Count the covered and total regions of each file.
+    /// A region is covered if any of its checks is.
+    pub fn file_summaries(&self) -> Vec<FileCoverage> {
+        self.data
+            .iter()
+            .map(|(file, checks)| {
+                let mut regions: BTreeMap<&CoverageRegion, bool> = BTreeMap::new();
+                for check in checks {
+                    *regions.entry(&check.region).or_default() |=
+                        check.status == CheckStatus::Covered;
+                }
+                FileCoverage {
+                    file: file.clone(),
+                    covered_regions: regions.values().filter(|covered| **covered).count(),
+                    total_regions: regions.len(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Coverage summary of a source file for one harness.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileCoverage {
+    pub file: String,
+    pub covered_regions: usize,
+    pub total_regions: usize,
+}
+
+/// Coverage summary of one harness, computed from the results saved to its `_kaniraw` file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HarnessCoverage {
+    pub harness: String,
+    /// Empty if no coverage results were saved for this harness.
+    pub files: Vec<FileCoverage>,
+    /// Why no coverage results were saved for this harness.
+    pub error: Option<String>,
 }
 
 impl fmt::Display for CoverageResults {
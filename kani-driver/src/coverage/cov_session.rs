@@ -4,84 +4,85 @@
 use std::fs;
 use std::fs::File;
 use std::io::Write;
+use std::path::{Path, PathBuf};
 
 use crate::KaniSession;
+use crate::call_cbmc::VerificationStatus;
+use crate::coverage::cov_results::{CoverageResults, HarnessCoverage};
 use crate::harness_runner::HarnessResult;
 use crate::project::Project;
+use crate::util::warning;
 use anyhow::{Result, bail};
+use time::{OffsetDateTime, format_description};
 
 impl KaniSession {
-    /// Saves metadata required for coverage-related features.
-    /// At present, this metadata consists of the following:
-    ///  - The file names of the project's source code.
+    /// Determine the directory where the coverage results of this run are saved.
     ///
-    /// Note: Currently, coverage mappings are not included due to technical
-    /// limitations. But this is where we should save them.
-    pub fn save_coverage_metadata(&self, project: &Project, stamp: &String) -> Result<()> {
-        if project.input.is_none() {
-            self.save_coverage_metadata_cargo(project, stamp)
-        } else {
-            self.save_coverage_metadata_standalone(project, stamp)
-        }
-    }
-
-    fn save_coverage_metadata_cargo(&self, project: &Project, stamp: &String) -> Result<()> {
-        let build_target = env!("TARGET");
-        let metadata = self.cargo_metadata(build_target)?;
-        let target_dir = self
-            .args
-            .target_dir
-            .as_ref()
-            .unwrap_or(&metadata.target_directory.clone().into())
-            .clone()
-            .join("kani");
-
-        let outdir = target_dir.join(build_target).join(format!("kanicov_{stamp}"));
-
-        // Generally we don't expect this directory to exist, but there's no
-        // reason to delete it if it does.
-        if !outdir.exists() {
-            fs::create_dir(&outdir)?;
-        }
-
-        // Collect paths to source files in the project
-        let mut source_targets = Vec::new();
-        if let Some(metadata) = &project.cargo_metadata {
-            for package in &metadata.packages {
-                for target in &package.targets {
-                    source_targets.push(target.src_path.clone());
-                }
+    /// This is the `--coverage-out` directory if one was given, which must not exist unless
+    /// `--coverage-overwrite` was also given. Otherwise, it is a new `kanicov_<date>`
+    /// directory in the target directory (cargo projects) or next to the input file (standalone).
+    /// The timestamp makes coverage results easy to relate to verification runs. If a run in the
+    /// same second already created that directory, a numeric suffix is appended to keep them apart.
+    pub fn coverage_dir(&self, project: &Project) -> Result<PathBuf> {
+        if let Some(outdir) = &self.args.coverage_out {
+            if outdir.exists() && !self.args.coverage_overwrite {
+                bail!(
+                    "coverage output directory `{}` already exists. Pass `--coverage-overwrite` \
+                    to replace it.",
+                    outdir.display()
+                );
             }
-        } else {
-            bail!("could not find project metadata required for coverage metadata");
+            return Ok(outdir.clone());
         }
 
-        let kanimap_name = format!("kanicov_{stamp}_kanimap");
-        let file_name = outdir.join(kanimap_name).with_extension("json");
-        let mut kanimap_file = File::create(file_name)?;
-
-        let serialized_data = serde_json::to_string(&source_targets)?;
-        kanimap_file.write_all(serialized_data.as_bytes())?;
-
-        Ok(())
+        let parent = if project.input.is_none() {
+            let build_target = env!("TARGET");
+            let metadata = self.cargo_metadata(build_target)?;
+            self.args
+                .target_dir
+                .clone()
+                .unwrap_or_else(|| metadata.target_directory.clone().into())
+                .join("kani")
+                .join(build_target)
+        } else {
+            let input = project.input.clone().unwrap().canonicalize()?;
+            input.parent().unwrap().to_path_buf()
+        };
+        Ok(unused_dir(&parent, &format!("kanicov_{}", coverage_stamp())))
     }
 
-    fn save_coverage_metadata_standalone(&self, project: &Project, stamp: &String) -> Result<()> {
-        let input = project.input.clone().unwrap().canonicalize().unwrap();
-        let input_dir = input.parent().unwrap().to_path_buf();
-        let outdir = input_dir.join(format!("kanicov_{stamp}"));
+    /// Saves metadata required for coverage-related features to `outdir`,
+    /// which is created (or replaced, with `--coverage-overwrite`) here.
+    /// At present, this metadata consists of the following:
+    ///  - The file names of the project's source code.
+    ///
+    /// Note: Currently, coverage mappings are not included due to technical
+    /// limitations. But this is where we should save them.
+    pub fn save_coverage_metadata(&self, project: &Project, outdir: &Path) -> Result<()> {
+        let source_targets: Vec<PathBuf> = if project.input.is_none() {
+            // Collect paths to source files in the project
+            let Some(metadata) = &project.cargo_metadata else {
+                bail!("could not find project metadata required for coverage metadata");
+            };
+            metadata
+                .packages
+                .iter()
+                .flat_map(|package| &package.targets)
+                .map(|target| target.src_path.clone().into_std_path_buf())
+                .collect()
+        } else {
+            // In this case, the source files correspond to the input file
+            vec![project.input.clone().unwrap().canonicalize()?]
+        };
 
-        // Generally we don't expect this directory to exist, but there's no
-        // reason to delete it if it does.
-        if !outdir.exists() {
-            fs::create_dir(&outdir)?;
+        if outdir.exists() && self.args.coverage_overwrite {
+            fs::remove_dir_all(outdir)?;
         }
+        fs::create_dir_all(outdir)?;
 
-        // In this case, the source files correspond to the input file
-        let source_targets = vec![input];
-
-        let kanimap_name = format!("kanicov_{stamp}_kanimap");
-        let file_name = outdir.join(kanimap_name).with_extension("json");
+        // `kani-cov` expects the map to be named after the directory.
+        let dir_name = outdir.file_name().unwrap_or_default().to_string_lossy();
+        let file_name = outdir.join(format!("{dir_name}_kanimap")).with_extension("json");
         let mut kanimap_file = File::create(file_name)?;
 
         let serialized_data = serde_json::to_string(&source_targets)?;
@@ -91,86 +92,154 @@ fn save_coverage_metadata_standalone(&self, project: &Project, stamp: &String) -
     }
 
     /// Saves raw coverage check results required for coverage-related features.
+    /// Returns a summary of the results saved for each harness, in the order of `results`.
+    ///
+    /// Harnesses without coverage results (e.g., because CBMC timed out or the harness was skipped
+    /// by `--fail-fast`) are reported in the summary with an error, and the results of the other
+    /// harnesses are saved as usual.
     pub fn save_coverage_results(
         &self,
-        project: &Project,
-        results: &Vec<HarnessResult>,
-        stamp: &String,
-    ) -> Result<()> {
-        if project.input.is_none() {
-            self.save_coverage_results_cargo(results, stamp)
-        } else {
-            self.save_coverage_results_standalone(project, results, stamp)
-        }
-    }
-
-    pub fn save_coverage_results_cargo(
-        &self,
-        results: &Vec<HarnessResult>,
-        stamp: &String,
-    ) -> Result<()> {
-        let build_target = env!("TARGET");
-        let metadata = self.cargo_metadata(build_target)?;
-        let target_dir = self
-            .args
-            .target_dir
-            .as_ref()
-            .unwrap_or(&metadata.target_directory.clone().into())
-            .clone()
-            .join("kani");
-
-        let outdir = target_dir.join(build_target).join(format!("kanicov_{stamp}"));
-
+        results: &[HarnessResult],
+        outdir: &Path,
+    ) -> Result<Vec<HarnessCoverage>> {
         // This directory should have been created by `save_coverage_metadata`,
         // so now we expect it to exist.
         if !outdir.exists() {
             bail!("directory associated to coverage run does not exist")
         }
 
+        let mut summaries = Vec::with_capacity(results.len());
         for harness_res in results {
+            let harness = harness_res.harness.pretty_name.clone();
+            // Nothing is written for a harness that was not verified.
+            let not_verified = match harness_res.result.status {
+                VerificationStatus::Skipped => Some("the harness was skipped by `--fail-fast`"),
+                VerificationStatus::Excluded => {
+                    Some("the harness was excluded by `--exclude-harness`")
+                }
+                VerificationStatus::Success | VerificationStatus::Failure => None,
+            };
+            if let Some(error) = not_verified {
+                summaries.push(HarnessCoverage {
+                    harness,
+                    files: vec![],
+                    error: Some(error.to_string()),
+                });
+                continue;
+            }
+            let Some(cov_results) = validated_coverage_results(harness_res) else {
+                let error = if harness_res.result.timed_out {
+                    "CBMC timed out before producing coverage results"
+                } else {
+                    "no coverage results were produced"
+                };
+                warning(&format!("No coverage results were produced for harness `{harness}`."));
+                summaries.push(HarnessCoverage {
+                    harness,
+                    files: vec![],
+                    error: Some(error.to_string()),
+                });
+                continue;
+            };
+
             let harness_name = harness_res.harness.mangled_name.clone();
             let kaniraw_name = format!("{harness_name}_kaniraw");
             let file_name = outdir.join(kaniraw_name).with_extension("json");
             let mut cov_file = File::create(file_name)?;
 
-            let cov_results = &harness_res.result.coverage_results.clone().unwrap();
             let serialized_data = serde_json::to_string(&cov_results)?;
             cov_file.write_all(serialized_data.as_bytes())?;
+            summaries.push(HarnessCoverage {
+                harness,
+                files: cov_results.file_summaries(),
+                error: None,
+            });
         }
 
         println!("[info] Coverage results saved to {}", &outdir.display());
-        Ok(())
-    }
 
-    pub fn save_coverage_results_standalone(
-        &self,
-        project: &Project,
-        results: &Vec<HarnessResult>,
-        stamp: &String,
-    ) -> Result<()> {
-        let input = project.input.clone().unwrap().canonicalize().unwrap();
-        let input_dir = input.parent().unwrap().to_path_buf();
-        let outdir = input_dir.join(format!("kanicov_{stamp}"));
+        Ok(summaries)
+    }
+}
 
-        // This directory should have been created by `save_coverage_metadata`,
-        // so now we expect it to exist.
-        if !outdir.exists() {
-            bail!("directory associated to coverage run does not exist")
-        }
+/// The `<date>` part of default coverage directory names, e.g. `2025-01-31_14-05-09`.
+fn coverage_stamp() -> String {
+    let format =
+        format_description::parse_borrowed::<2>("[year]-[month]-[day]_[hour]-[minute]-[second]")
+            .unwrap();
+    OffsetDateTime::now_utc().format(&format).unwrap()
+}
 
-        for harness_res in results {
-            let harness_name = harness_res.harness.mangled_name.clone();
-            let kaniraw_name = format!("{harness_name}_kaniraw");
-            let file_name = outdir.join(kaniraw_name).with_extension("json");
-            let mut cov_file = File::create(file_name)?;
+/// The first of `<parent>/<name>`, `<parent>/<name>_1`, `<parent>/<name>_2`, ... that does not
+/// exist yet.
+fn unused_dir(parent: &Path, name: &str) -> PathBuf {
+    let mut dir = parent.join(name);
+    let mut suffix = 0;
+    while dir.exists() {
+        suffix += 1;
+        dir = parent.join(format!("{name}_{suffix}"));
+    }
+    dir
+}
 
-            let cov_results = &harness_res.result.coverage_results.clone().unwrap();
-            let serialized_data = serde_json::to_string(&cov_results)?;
-            cov_file.write_all(serialized_data.as_bytes())?;
-        }
+/// Drop the coverage results of files with impossible regions (e.g., due to instrumentation code
+/// attributed to user source lines) and warn about them.
+/// Returns `None` if the harness has no coverage results.
+fn validated_coverage_results(harness_res: &HarnessResult) -> Option<CoverageResults> {
+    let mut cov_results = harness_res.result.coverage_results.clone()?;
+    let removed = cov_results.remove_impossible_files(|file| {
+        fs::read_to_string(file).ok().map(|content| content.lines().count())
+    });
+    for file in removed {
+        warning(&format!(
+            "Coverage results for `{file}` in harness `{}` report regions outside of the file \
+            and were discarded.",
+            harness_res.harness.pretty_name
+        ));
+    }
+    Some(cov_results)
+}
 
-        println!("[info] Coverage results saved to {}", &outdir.display());
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbmc_output_parser::CheckStatus;
+    use crate::coverage::cov_results::{CoverageCheck, CoverageRegion, CoverageTerm};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn check_impossible_files_are_removed() {
+        let check = |file: &str, start: (u32, u32), end: (u32, u32)| {
+            CoverageCheck::new(
+                "main".to_string(),
+                CoverageTerm::Counter(0),
+                CoverageRegion { file: file.to_string(), start, end },
+                CheckStatus::Covered,
+            )
+        };
+        let mut results = CoverageResults::new(BTreeMap::from([
+            ("valid.rs".to_string(), vec![check("valid.rs", (1, 1), (10, 5))]),
+            ("past_end.rs".to_string(), vec![check("past_end.rs", (9, 1), (11, 1))]),
+            ("line_zero.rs".to_string(), vec![check("line_zero.rs", (0, 1), (2, 1))]),
+            ("reversed.rs".to_string(), vec![check("reversed.rs", (4, 8), (4, 2))]),
+            ("unknown.rs".to_string(), vec![check("unknown.rs", (1, 1), (500, 1))]),
+        ]));
+        let removed = results.remove_impossible_files(|file| (file != "unknown.rs").then_some(10));
+        assert_eq!(removed, ["line_zero.rs", "past_end.rs", "reversed.rs"]);
+        assert_eq!(results.data.keys().collect::<Vec<_>>(), ["unknown.rs", "valid.rs"]);
+    }
 
-        Ok(())
+    #[test]
+    fn check_unused_dir_within_same_second() {
+        let parent = tempfile::tempdir().unwrap();
+        let name = "kanicov_2025-01-31_14-05-09";
+        let first = unused_dir(parent.path(), name);
+        assert_eq!(first, parent.path().join(name));
+        fs::create_dir(&first).unwrap();
+
+        let second = unused_dir(parent.path(), name);
+        assert_eq!(second, parent.path().join(format!("{name}_1")));
+        fs::create_dir(&second).unwrap();
+        assert_eq!(unused_dir(parent.path(), name), parent.path().join(format!("{name}_2")));
     }
 }
@@ -0,0 +1,285 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! The categories of errors that `main` reports with a distinct exit code, so the tools that run
+//! Kani can tell them apart. Errors are still propagated with `anyhow`; `main` looks for a
+//! [KaniDriverError] in the error chain, and other errors are internal errors that exit with `1`.
+//! With `--export-json`, the category is also exported, see [ErrorCategory].
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Invalid arguments. This is also the exit code clap uses for arguments it can't parse.
+pub const INVALID_ARGUMENTS_EXIT_CODE: u8 = 2;
+/// Kani is not installed correctly, or one of the tools it runs could not be started.
+pub const SETUP_EXIT_CODE: u8 = 3;
+/// The project failed to compile.
+pub const COMPILATION_EXIT_CODE: u8 = 4;
+/// Verification completed, but some harnesses failed or regressed.
+pub const VERIFICATION_FAILED_EXIT_CODE: u8 = 10;
+/// Any other error, which is likely a bug in Kani.
+pub const INTERNAL_EXIT_CODE: u8 = 1;
+
+#[derive(Debug, thiserror::Error)]
+pub enum KaniDriverError {
+    /// The arguments are valid on their own, but don't apply to the project.
+    #[error("{0}")]
+    InvalidArguments(String),
+    /// The `--harness` filters or harness patterns match no harness.
+    #[error("{0}")]
+    NoHarnessesMatched(String),
+    /// Kani's installation is incomplete.
+    #[error("{0}")]
+    Setup(String),
+    /// One of the tools Kani runs, like CBMC, could not be started, e.g. because it is missing.
+    #[error("Failed to invoke {program}")]
+    ToolNotStarted {
+        program: String,
+        #[source]
+        source: std::io::Error,
+    },
+    /// CBMC could not be started to verify a harness.
+    #[error("Failed to run cbmc")]
+    CbmcNotStarted,
+    /// Cargo or the Kani compiler reported compilation errors.
+    #[error("Failed to execute cargo ({status}). Found {error_count} compilation errors.")]
+    CompilationFailed { status: std::process::ExitStatus, error_count: usize, command: String },
+    /// The Kani compiler crashed while compiling a crate.
+    #[error("{0}")]
+    CodegenFailed(String),
+    /// A tool Kani ran exited with an error.
+    #[error("{program} exited with status {status}")]
+    CommandFailed { program: String, status: std::process::ExitStatus, command: String },
+    /// At least one harness failed. The failures are reported in the final summary, so `main`
+    /// doesn't print this error.
+    #[error("verification failed")]
+    VerificationFailed,
+    /// `--fail-on-regression` found harnesses that regressed compared to the baseline run.
+    #[error("regressions compared to the baseline run: `{}`", .0.join("`, `"))]
+    Regressions(Vec<String>),
+}
+
+/// The category of the error that stopped a run, exported under the `error` key of
+/// `--export-json`. The names are stable, so tools can match on them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    InvalidArguments,
+    NoHarnessesMatched,
+    /// CBMC is not installed, or could not be started.
+    CbmcNotFound,
+    /// Another problem with Kani's installation.
+    Setup,
+    /// The project has compilation errors.
+    CargoBuildFailed,
+    /// The Kani compiler failed to generate the goto programs of the project.
+    CodegenFailed,
+    VerificationFailed,
+    /// An error without a category, which is likely a bug in Kani.
+    Internal,
+}
+
+impl ErrorCategory {
+    pub fn exit_code(self) -> u8 {
+        match self {
+            ErrorCategory::InvalidArguments | ErrorCategory::NoHarnessesMatched => {
+                INVALID_ARGUMENTS_EXIT_CODE
+            }
+            ErrorCategory::CbmcNotFound | ErrorCategory::Setup => SETUP_EXIT_CODE,
+            ErrorCategory::CargoBuildFailed | ErrorCategory::CodegenFailed => COMPILATION_EXIT_CODE,
+            ErrorCategory::VerificationFailed => VERIFICATION_FAILED_EXIT_CODE,
+            ErrorCategory::Internal => INTERNAL_EXIT_CODE,
+        }
+    }
+}
+
+impl KaniDriverError {
+    /// The error for a tool that could not be started, e.g. because it is not installed.
+    pub fn tool_not_started(program: &str, source: std::io::Error) -> Self {
+        KaniDriverError::ToolNotStarted { program: program.to_string(), source }
+    }
+
+    /// The error for `--harness` filters that match no harness.
+    pub fn no_harnesses_matched(filters: &[String]) -> Self {
+        KaniDriverError::NoHarnessesMatched(match filters {
+            [filter] => format!("no harnesses matched the harness filter: `{filter}`"),
+            filters => {
+                format!("no harnesses matched the harness filters: `{}`", filters.join("`, `"))
+            }
+        })
+    }
+
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            KaniDriverError::InvalidArguments(_) => ErrorCategory::InvalidArguments,
+            KaniDriverError::NoHarnessesMatched(_) => ErrorCategory::NoHarnessesMatched,
+            KaniDriverError::Setup(_) => ErrorCategory::Setup,
+            KaniDriverError::ToolNotStarted { program, .. } if is_program(program, "cbmc") => {
+                ErrorCategory::CbmcNotFound
+            }
+            KaniDriverError::ToolNotStarted { .. } => ErrorCategory::Setup,
+            KaniDriverError::CbmcNotStarted => ErrorCategory::CbmcNotFound,
+            KaniDriverError::CompilationFailed { .. } => ErrorCategory::CargoBuildFailed,
+            KaniDriverError::CodegenFailed(_) => ErrorCategory::CodegenFailed,
+            // In standalone mode, the Kani compiler reports the compilation errors itself.
+            KaniDriverError::CommandFailed { program, .. }
+                if is_program(program, "kani-compiler") =>
+            {
+                ErrorCategory::CodegenFailed
+            }
+            KaniDriverError::CommandFailed { .. } => ErrorCategory::Internal,
+            KaniDriverError::VerificationFailed | KaniDriverError::Regressions(_) => {
+                ErrorCategory::VerificationFailed
+            }
+        }
+    }
+
+    /// The exit code of the driver when it fails with this error.
+    pub fn exit_code(&self) -> u8 {
+        self.category().exit_code()
+    }
+
+    /// The command that failed, if the error comes from one.
+    pub fn command(&self) -> Option<&str> {
+        match self {
+            KaniDriverError::CompilationFailed { command, .. }
+            | KaniDriverError::CommandFailed { command, .. } => Some(command),
+            _ => None,
+        }
+    }
+}
+
+/// Whether `program`, which may be a path, is the tool `name`.
+fn is_program(program: &str, name: &str) -> bool {
+    Path::new(program).file_stem().is_some_and(|stem| stem == name)
+}
+
+/// The category of `error`, from the first [KaniDriverError] in its chain.
+pub fn category(error: &anyhow::Error) -> ErrorCategory {
+    error
+        .downcast_ref::<KaniDriverError>()
+        .map_or(ErrorCategory::Internal, KaniDriverError::category)
+}
+
+/// The exit code of the driver when it fails with `error`.
+pub fn exit_code(error: &anyhow::Error) -> u8 {
+    error.downcast_ref::<KaniDriverError>().map_or(INTERNAL_EXIT_CODE, KaniDriverError::exit_code)
+}
+
+/// The command that caused `error`, if any.
+pub fn failed_command(error: &anyhow::Error) -> Option<&str> {
+    error.downcast_ref::<KaniDriverError>().and_then(KaniDriverError::command)
+}
+
+/// Whether `error` should be printed by `main`.
+pub fn should_report(error: &anyhow::Error) -> bool {
+    !matches!(error.downcast_ref::<KaniDriverError>(), Some(KaniDriverError::VerificationFailed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn check_exit_codes() {
+        let not_found = std::io::Error::from(std::io::ErrorKind::NotFound);
+        let errors = [
+            (KaniDriverError::InvalidArguments("no harnesses matched".to_string()), 2),
+            (KaniDriverError::tool_not_started("cbmc", not_found), 3),
+            (KaniDriverError::CbmcNotStarted, 3),
+            (KaniDriverError::Setup("Unable to find kani_lib.c".into()), 3),
+            (
+                KaniDriverError::CompilationFailed {
+                    status: std::process::ExitStatus::default(),
+                    error_count: 2,
+                    command: "cargo build".into(),
+                },
+                4,
+            ),
+            (KaniDriverError::no_harnesses_matched(&["check_a".into()]), 2),
+            (KaniDriverError::CodegenFailed("internal compiler error".into()), 4),
+            (
+                KaniDriverError::CommandFailed {
+                    program: "/kani/bin/kani-compiler".into(),
+                    status: std::process::ExitStatus::default(),
+                    command: "kani-compiler lib.rs".into(),
+                },
+                4,
+            ),
+            (
+                KaniDriverError::CommandFailed {
+                    program: "goto-instrument".into(),
+                    status: std::process::ExitStatus::default(),
+                    command: "goto-instrument a.out".into(),
+                },
+                1,
+            ),
+            (KaniDriverError::VerificationFailed, 10),
+            (KaniDriverError::Regressions(vec!["check_a".to_string()]), 10),
+        ];
+        for (error, code) in errors {
+            assert_eq!(error.exit_code(), code, "{error:?}");
+            assert_eq!(exit_code(&error.into()), code);
+        }
+        assert_eq!(exit_code(&anyhow::anyhow!("unexpected")), 1);
+    }
+
+    #[test]
+    fn check_categories() {
+        let not_found = || std::io::Error::from(std::io::ErrorKind::NotFound);
+        let error: anyhow::Error = KaniDriverError::tool_not_started("cbmc", not_found()).into();
+        assert_eq!(category(&error), ErrorCategory::CbmcNotFound);
+        assert_eq!(KaniDriverError::CbmcNotStarted.category(), ErrorCategory::CbmcNotFound);
+        assert_eq!(
+            KaniDriverError::tool_not_started("goto-cc", not_found()).category(),
+            ErrorCategory::Setup
+        );
+        assert_eq!(category(&anyhow::anyhow!("unexpected")), ErrorCategory::Internal);
+        assert_eq!(
+            serde_json::to_value(ErrorCategory::CargoBuildFailed).unwrap(),
+            "cargo_build_failed"
+        );
+
+        let error: anyhow::Error = KaniDriverError::CompilationFailed {
+            status: std::process::ExitStatus::default(),
+            error_count: 1,
+            command: "cargo build --target-dir target/kani".into(),
+        }
+        .into();
+        assert_eq!(failed_command(&error), Some("cargo build --target-dir target/kani"));
+        assert_eq!(failed_command(&anyhow::anyhow!("unexpected")), None);
+
+        assert_eq!(
+            KaniDriverError::no_harnesses_matched(&["a".into(), "b".into()]).to_string(),
+            "no harnesses matched the harness filters: `a`, `b`"
+        );
+    }
+
+    #[test]
+    fn check_messages() {
+        let error: anyhow::Error = KaniDriverError::tool_not_started(
+            "goto-cc",
+            std::io::Error::new(std::io::ErrorKind::NotFound, "No such file or directory"),
+        )
+        .into();
+        assert_eq!(format!("{error:#}"), "Failed to invoke goto-cc: No such file or directory");
+        assert_eq!(KaniDriverError::CbmcNotStarted.to_string(), "Failed to run cbmc");
+
+        let error = KaniDriverError::Regressions(vec!["check_a".into(), "check_b".into()]);
+        assert_eq!(
+            error.to_string(),
+            "regressions compared to the baseline run: `check_a`, `check_b`"
+        );
+    }
+
+    #[test]
+    fn check_wrapped_error() {
+        // The category is kept when context is added on top of the error.
+        let error = Err::<(), _>(KaniDriverError::VerificationFailed)
+            .context("while verifying the project")
+            .unwrap_err();
+        assert_eq!(exit_code(&error), VERIFICATION_FAILED_EXIT_CODE);
+        assert!(!should_report(&error));
+        assert!(should_report(&anyhow::anyhow!("unexpected")));
+    }
+}
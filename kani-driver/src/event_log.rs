@@ -0,0 +1,244 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! This module implements the event log of `--event-log`: a JSON Lines file that records what the
+//! driver did, e.g. the processes it ran and the files it wrote, so a run that hung or misbehaved
+//! can be debugged after the fact.
+//!
+//! Events are regular [tracing] events with the [EVENT_TARGET] target. They are written by a
+//! dedicated JSON layer of the session logger, see [layer], which only accepts this target, so
+//! the log is independent of `KANI_LOG` and `--debug`.
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Output, Stdio};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use tracing::level_filters::LevelFilter;
+use tracing::{Subscriber, info};
+use tracing_subscriber::Layer;
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::util::render_command;
+
+/// The target of the events written to the event log.
+pub const EVENT_TARGET: &str = "kani_event";
+
+/// Once the event log reaches this size, it is moved to `<path>.1`, replacing the previous one,
+/// so the event log of a run never takes more than twice this size.
+const MAX_EVENT_LOG_BYTES: u64 = 16 * 1024 * 1024;
+
+/// The path of the event log of this run, if any.
+static EVENT_LOG_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// The path of the event log of this run, if `--event-log` was given.
+pub fn path() -> Option<&'static Path> {
+    EVENT_LOG_PATH.get().map(PathBuf::as_path)
+}
+
+/// Create the event log at `path`, and the layer that writes the events to it.
+pub fn layer<S>(path: &Path) -> Result<impl Layer<S>>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    let file = RotatingFile::create(path, MAX_EVENT_LOG_BYTES)
+        .with_context(|| format!("Failed to create the event log `{}`", path.display()))?;
+    let _ = EVENT_LOG_PATH.set(path.to_path_buf());
+    Ok(json_layer(file))
+}
+
+/// The layer writing every event of [EVENT_TARGET] to `file` as one JSON object per line, with
+/// the timestamp and the fields of the event at the top level.
+fn json_layer<S>(file: RotatingFile) -> impl Layer<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    tracing_subscriber::fmt::layer()
+        .json()
+        .flatten_event(true)
+        .with_current_span(false)
+        .with_span_list(false)
+        .with_target(false)
+        .with_writer(Mutex::new(file))
+        .with_filter(Targets::new().with_target(EVENT_TARGET, LevelFilter::INFO))
+}
+
+/// A file that is moved to `<path>.1` once writing to it would make it larger than `max_bytes`.
+/// Writes are not split, so every event stays on a single line of one of the files.
+struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    written: u64,
+    max_bytes: u64,
+}
+
+impl RotatingFile {
+    fn create(path: &Path, max_bytes: u64) -> io::Result<Self> {
+        Ok(RotatingFile {
+            path: path.to_path_buf(),
+            file: File::create(path)?,
+            written: 0,
+            max_bytes,
+        })
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        let mut rotated = self.path.clone().into_os_string();
+        rotated.push(".1");
+        fs::rename(&self.path, rotated)?;
+        self.file = File::create(&self.path)?;
+        self.written = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.written > 0 && self.written + buf.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        let written = self.file.write(buf)?;
+        self.written += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Record that `cmd` was started as the process `pid`.
+pub fn process_spawned(cmd: &Command, pid: Option<u32>) {
+    info!(
+        target: EVENT_TARGET,
+        event = "spawn",
+        argv = %render_command(cmd).to_string_lossy(),
+        pid
+    );
+}
+
+/// Record that the process `pid` running `program` exited with `status` after `duration`.
+/// `status` is `None` if waiting for the process failed.
+pub fn process_exited(
+    program: &str,
+    pid: Option<u32>,
+    status: Option<ExitStatus>,
+    duration: Duration,
+) {
+    info!(
+        target: EVENT_TARGET,
+        event = "exit",
+        program,
+        pid,
+        code = status.and_then(|status| status.code()),
+        success = status.is_some_and(|status| status.success()),
+        duration_ms = duration.as_millis() as u64
+    );
+}
+
+/// Record that the driver entered `phase`, e.g. `build` or `verify`.
+pub fn phase(phase: &str) {
+    info!(target: EVENT_TARGET, event = "phase", phase);
+}
+
+/// Record that the driver wrote the report `path`.
+pub fn file_written(path: &Path) {
+    info!(target: EVENT_TARGET, event = "file_written", path = %path.display());
+}
+
+/// Record a warning printed to the user.
+pub fn warning(message: &str) {
+    info!(target: EVENT_TARGET, event = "warning", message);
+}
+
+/// Run `cmd` to completion like [Command::status], recording the process in the event log.
+pub fn status(cmd: &mut Command) -> io::Result<ExitStatus> {
+    let start = Instant::now();
+    let mut child = cmd.spawn()?;
+    process_spawned(cmd, Some(child.id()));
+    let status = child.wait();
+    process_exited(&program(cmd), Some(child.id()), status.as_ref().ok().copied(), start.elapsed());
+    status
+}
+
+/// Run `cmd` to completion like [Command::output], recording the process in the event log.
+pub fn output(cmd: &mut Command) -> io::Result<Output> {
+    let start = Instant::now();
+    let child = cmd.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+    let pid = child.id();
+    process_spawned(cmd, Some(pid));
+    let output = child.wait_with_output();
+    process_exited(
+        &program(cmd),
+        Some(pid),
+        output.as_ref().ok().map(|o| o.status),
+        start.elapsed(),
+    );
+    output
+}
+
+fn program(cmd: &Command) -> String {
+    cmd.get_program().to_string_lossy().into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::Registry;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    fn read_events(path: &Path) -> Vec<serde_json::Value> {
+        fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn check_events_are_json_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.jsonl");
+        let file = RotatingFile::create(&path, MAX_EVENT_LOG_BYTES).unwrap();
+        let subscriber = Registry::default().with(json_layer(file));
+        tracing::subscriber::with_default(subscriber, || {
+            phase("verify");
+            file_written(Path::new("report.xml"));
+            process_exited("cbmc", Some(42), None, Duration::from_millis(1500));
+            // Other targets are not part of the event log.
+            info!(target: "kani_driver::session", "not an event");
+        });
+
+        let events = read_events(&path);
+        assert_eq!(events.len(), 3);
+        assert!(events.iter().all(|event| event["timestamp"].is_string()));
+        assert_eq!(events[0]["event"], "phase");
+        assert_eq!(events[0]["phase"], "verify");
+        assert_eq!(events[1]["event"], "file_written");
+        assert_eq!(events[1]["path"], "report.xml");
+        assert_eq!(events[2]["program"], "cbmc");
+        assert_eq!(events[2]["pid"], 42);
+        assert_eq!(events[2]["success"], false);
+        assert_eq!(events[2]["duration_ms"], 1500);
+        assert!(events[2].get("code").is_none());
+    }
+
+    #[test]
+    fn check_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.jsonl");
+        let mut file = RotatingFile::create(&path, 10).unwrap();
+        file.write_all(b"first\n").unwrap();
+        file.write_all(b"second\n").unwrap();
+        file.write_all(b"third\n").unwrap();
+        // A single write larger than the limit is not split.
+        file.write_all(b"a long line\n").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a long line\n");
+        assert_eq!(fs::read_to_string(dir.path().join("events.jsonl.1")).unwrap(), "third\n");
+    }
+}
@@ -1,8 +1,371 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
-use std::path::PathBuf;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tempfile::NamedTempFile;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+use crate::args::resolved_config::ResolvedArg;
+use crate::call_cbmc::CbmcStats;
+use crate::coverage::cov_results::HarnessCoverage;
+use crate::error::{self, ErrorCategory};
+use crate::version::KANI_VERSION;
+
+/// Version of the layout of the exported JSON, so consumers can tell which shape they are parsing.
+/// Bump this whenever a key is added, removed, renamed or changes its structure.
+pub const SCHEMA_VERSION: u64 = 31;
+
+// Top-level keys of the exported JSON.
+pub const SCHEMA_VERSION_KEY: &str = "schema_version";
+pub const DRIVER_VERSION_KEY: &str = "kani_driver_version";
+/// Whether this was a full verification run or a `--quick` check, see [VerificationMode].
+pub const MODE_KEY: &str = "mode";
+pub const METADATA_KEY: &str = "metadata";
+pub const PROJECT_KEY: &str = "project";
+pub const HARNESS_METADATA_KEY: &str = "harness_metadata";
+/// Functions `autoharness` did not generate a harness for. Only present for `autoharness` runs.
+pub const SKIPPED_FUNCTIONS_KEY: &str = "skipped_functions";
+/// The constructs Kani does not support that the compiler found, see [ExportedWarning].
+pub const WARNINGS_KEY: &str = "warnings";
+/// The commands that would verify each harness. Only present for `--dry-run` runs.
+pub const PLANNED_COMMANDS_KEY: &str = "planned_commands";
+/// Key of the per-harness records emitted while verification is still running.
+pub const HARNESS_RESULT_KEY: &str = "harness_result";
+pub const VERIFICATION_RESULTS_KEY: &str = "verification_results";
+pub const ERROR_DETAILS_KEY: &str = "error_details";
+pub const PROPERTY_DETAILS_KEY: &str = "property_details";
+pub const CBMC_KEY: &str = "cbmc";
+pub const COVERAGE_KEY: &str = "coverage";
+pub const RUN_SUMMARY_KEY: &str = "run_summary";
+/// The contracts assumed by the verified harnesses, with the harnesses checking them, see
+/// [ExportedContractDependency].
+pub const CONTRACT_DEPENDENCIES_KEY: &str = "contract_dependencies";
+/// Differences with the `--compare-baseline` run, see [crate::frontend::RunDiff].
+/// Only present with `--compare-baseline`.
+pub const BASELINE_DIFF_KEY: &str = "baseline_diff";
+/// Information about the whole run, see [ExportedRunInfo].
+pub const RUN_KEY: &str = "run";
+/// How Kani was configured, see [ExportedSession].
+pub const SESSION_KEY: &str = "session";
+/// The arguments the driver was started with, as received from the shell or from cargo, before
+/// the ones in `Cargo.toml` are added. See [ExportedRunInfo::command_line] for the effective ones.
+pub const INVOCATION_KEY: &str = "invocation";
+/// Error that stopped the run early. Only present if the run did not complete.
+pub const RUN_ERROR_KEY: &str = "run_error";
+/// The category and details of the error under [RUN_ERROR_KEY], see [ExportedError].
+pub const ERROR_KEY: &str = "error";
+
+/// How thoroughly the harnesses were verified, exported under [MODE_KEY].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationMode {
+    Full,
+    /// Results of `--quick` runs are NOT SOUND.
+    Quick,
+}
+
+/// Harness metadata entry, exported under [HARNESS_METADATA_KEY].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedHarness {
+    /// Fully qualified name of the harness. Other entries refer to the harness by this name.
+    pub pretty_name: String,
+    pub mangled_name: String,
+    pub crate_name: String,
+    pub source: ExportedSource,
+    pub goto_file: Option<String>,
+    pub attributes: ExportedAttributes,
+    pub contract: ExportedContract,
+    pub has_loop_contracts: bool,
+    pub is_automatically_generated: bool,
+    /// The function an automatic harness verifies. `None` for manual harnesses.
+    pub target_function: Option<String>,
+    /// The `--harness` filter or harness pattern that selected this harness.
+    /// `None` if no harness selection was requested.
+    pub selected_by: Option<String>,
+}
+
+/// A function `autoharness` did not generate a harness for, exported under [SKIPPED_FUNCTIONS_KEY].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedSkippedFunction {
+    pub crate_name: String,
+    pub function: String,
+    /// Why no harness was generated, as printed in the table of skipped functions.
+    pub reason: String,
+    /// The `name: type` of the arguments that don't implement `kani::Arbitrary`, if that is the
+    /// reason the function was skipped.
+    pub missing_arbitrary: Vec<String>,
+}
+
+/// A construct Kani does not support, found by the compiler in a crate and exported under
+/// [WARNINGS_KEY]. The compiler replaces it with an `unsupported_construct` check that fails
+/// verification if it is reachable, so it is only a warning for the harnesses that don't reach it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedWarning {
+    pub crate_name: String,
+    /// The construct, as named in the compiler's warning.
+    pub construct: String,
+    /// The `file:line` of each occurrence, in order.
+    pub locations: Vec<String>,
+}
+
+/// The commands that would verify a harness, exported under [PLANNED_COMMANDS_KEY].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedPlannedCommands {
+    pub harness: String,
+    /// The goto-cc, goto-instrument, goto-synthesizer and CBMC command lines, in the order they
+    /// would run.
+    pub commands: Vec<String>,
+}
+
+/// Location of a harness in the source code.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedSource {
+    pub file: String,
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+/// The `#[kani::<>]` attributes of a harness.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedAttributes {
+    /// `Proof`, `ProofForContract` or `Test`.
+    pub kind: String,
+    pub should_panic: bool,
+    /// The functions replaced with `#[kani::stub]`.
+    pub stubs: Vec<ExportedStub>,
+    /// The functions replaced by their contract with `#[kani::stub_verified]`. Whether these
+    /// contracts were verified in the run is exported under [CONTRACT_DEPENDENCIES_KEY].
+    pub verified_stubs: Vec<String>,
+}
+
+/// A function replaced with `#[kani::stub(original, replacement)]`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedStub {
+    pub original: String,
+    pub replacement: String,
+}
+
+/// The contract checked by a harness, if any.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedContract {
+    /// The function whose contract the harness checks, for `#[kani::proof_for_contract]`.
+    pub checked_function: Option<String>,
+    pub contracted_function_name: Option<String>,
+    pub recursion_tracker: Option<String>,
+}
+
+/// A contract assumed by a harness with `#[kani::stub_verified]`, exported under
+/// [CONTRACT_DEPENDENCIES_KEY].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedContractDependency {
+    /// The harness that assumes the contract.
+    pub harness_id: String,
+    /// The function whose contract is assumed.
+    pub function: String,
+    /// The `#[kani::proof_for_contract]` harnesses of `function` that were selected in this run.
+    pub checked_by: Vec<String>,
+    pub status: ContractDependencyStatus,
+}
+
+/// Whether an assumed contract was verified in this run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContractDependencyStatus {
+    /// Every harness checking the contract that ran succeeded.
+    Verified,
+    /// A harness checking the contract failed.
+    Failed,
+    /// No harness checking the contract was verified, e.g. because none was selected.
+    NotVerifiedInThisRun,
+}
+
+/// CBMC invocation details for one harness, exported under [CBMC_KEY].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedCbmcRun {
+    pub harness_id: String,
+    pub cbmc_metadata: ExportedCbmcMetadata,
+    pub configuration: ExportedCbmcConfiguration,
+    /// The number of checks CBMC reported as passed, without the coverage checks.
+    /// `None` if the harness did not run or CBMC did not report its checks.
+    pub checks_passed: Option<usize>,
+    /// The number of checks CBMC reported as failed, without the coverage checks.
+    pub checks_failed: Option<usize>,
+    /// `None` if the harness did not run or CBMC did not report any statistics.
+    pub cbmc_stats: Option<CbmcStats>,
+}
+
+/// CBMC version and system information. They are the same for all harnesses in a run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedCbmcMetadata {
+    pub version: Option<String>,
+    pub os_info: Option<String>,
+}
+
+/// Configuration passed to CBMC for a harness.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedCbmcConfiguration {
+    pub object_bits: Option<u32>,
+    pub verbosity: u8,
+    pub solver: String,
+}
+
+/// Coverage information, exported under [COVERAGE_KEY].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedCoverage {
+    pub enabled: bool,
+    /// Directory where the coverage results were saved, `None` if coverage is disabled.
+    pub output_dir: Option<String>,
+    /// When the coverage results were saved, in RFC 3339 format.
+    pub timestamp: Option<String>,
+    /// Summary of the coverage results of each harness.
+    pub harnesses: Vec<HarnessCoverage>,
+}
+
+/// Totals of the whole run, exported under [RUN_SUMMARY_KEY].
+/// The counts match the ones printed in the final summary of the run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedRunSummary {
+    pub total_harnesses: usize,
+    pub successes: usize,
+    pub failures: usize,
+    /// Harnesses that were not verified because `--fail-fast` stopped the run.
+    pub skipped: usize,
+    /// Harnesses that were not verified because they match an `--exclude-harness` pattern.
+    pub excluded: usize,
+    /// Sum of the verification time of all harnesses.
+    pub total_runtime_ms: u64,
+    pub coverage_enabled: bool,
+}
+
+/// Information about the whole run, exported under [RUN_KEY].
+/// It is also exported when no harness was selected or verification was skipped.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedRunInfo {
+    pub kani_version: String,
+    /// When the run started, in RFC 3339 format.
+    pub timestamp: String,
+    /// The arguments Kani was invoked with, including the ones read from `Cargo.toml`.
+    pub command_line: Vec<String>,
+    /// The cargo packages that were built. Empty for standalone runs.
+    pub packages: Vec<String>,
+    /// The crates that were built.
+    pub targets: Vec<String>,
+    /// Whether verification was skipped, e.g. because of `--only-codegen`.
+    pub verification_skipped: bool,
+    pub no_harnesses_selected: bool,
+    pub total_harnesses: usize,
+    pub status_counts: ExportedStatusCounts,
+    /// Wall-clock duration of the verification of all harnesses. Zero if it was skipped.
+    pub wall_clock_ms: u64,
+    /// Sum of the CBMC solver time of all harnesses.
+    /// `None` if no harness reported its solver time.
+    pub cumulative_solver_time_s: Option<f64>,
+}
+
+/// Number of harnesses per outcome.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExportedStatusCounts {
+    pub success: usize,
+    /// Harnesses with failed checks.
+    pub failure: usize,
+    pub timeout: usize,
+    pub out_of_memory: usize,
+    /// Harnesses CBMC or Kani failed to check for another reason.
+    pub error: usize,
+    /// Harnesses marked with `--xfail` that failed, not counted as failures.
+    pub xfail: usize,
+    /// Harnesses marked with `--xfail` that succeeded, not counted as successes.
+    pub xpass: usize,
+    /// Harnesses that were not verified because `--fail-fast` stopped the run.
+    pub skipped: usize,
+    /// Harnesses that were not verified because they match an `--exclude-harness` pattern.
+    pub excluded: usize,
+}
+
+/// The configuration of the session, exported under [SESSION_KEY].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedSession {
+    /// Every argument with its resolved value and source, as printed by `--print-config`.
+    pub config: Vec<ResolvedArg>,
+}
+
+/// The error that stopped a run, exported under [ERROR_KEY].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedError {
+    pub category: ErrorCategory,
+    /// The messages of the error chain, outermost first.
+    pub messages: Vec<String>,
+    /// The command line that failed, e.g. the cargo build, if the error comes from one.
+    pub command: Option<String>,
+}
+
+impl ExportedError {
+    pub fn new(error: &anyhow::Error) -> Self {
+        ExportedError {
+            category: error::category(error),
+            messages: error.chain().map(ToString::to_string).collect(),
+            command: error::failed_command(error).map(str::to_string),
+        }
+    }
+}
+
+/// The parts of an exported file with a fixed structure.
+/// Used to check that the export can be read back.
+#[cfg(test)]
+#[derive(Debug, Deserialize)]
+pub struct ExportedRun {
+    pub schema_version: u64,
+    pub kani_driver_version: String,
+    pub mode: VerificationMode,
+    #[serde(default)]
+    pub harness_metadata: Vec<ExportedHarness>,
+    #[serde(default)]
+    pub skipped_functions: Vec<ExportedSkippedFunction>,
+    #[serde(default)]
+    pub warnings: Vec<ExportedWarning>,
+    #[serde(default)]
+    pub cbmc: Vec<ExportedCbmcRun>,
+    pub coverage: Option<ExportedCoverage>,
+    pub run_summary: Option<ExportedRunSummary>,
+    pub run: Option<ExportedRunInfo>,
+    pub session: Option<ExportedSession>,
+    #[serde(default)]
+    pub invocation: Vec<String>,
+    pub run_error: Option<String>,
+    pub error: Option<ExportedError>,
+}
+
+/// How a `JsonHandler` writes its data to the export path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportMode {
+    /// Buffer everything in memory and write a single pretty-printed JSON document on `export()`.
+    Buffered,
+    /// Write every item and harness detail to the export file as a separate NDJSON line as soon
+    /// as it is added. Each line is flushed once written, so the file holds every record added so
+    /// far, even if Kani dies mid-run. Only the last line can be incomplete.
+    Streaming,
+    /// Like `Buffered`, but the file holds a JSON array of runs, and `export()` appends this run
+    /// to it as `{"timestamp": <RFC 3339>, "run": <document>}` instead of replacing the file.
+    Merge,
+}
+
+impl ExportMode {
+    /// The export mode selected by `--export-json-streaming` and `--export-json-merge`.
+    pub fn from_flags(streaming: bool, merge: bool) -> Self {
+        if streaming {
+            ExportMode::Streaming
+        } else if merge {
+            ExportMode::Merge
+        } else {
+            ExportMode::Buffered
+        }
+    }
+}
 
 /// A handler for building and exporting JSON data structures.
 ///
@@ -14,18 +377,70 @@ pub struct JsonHandler {
     pub(crate) data: Value,
     /// Optional path where the JSON data will be exported.
     export_path: Option<PathBuf>,
+    /// Whether data is written at the end of the run or as it is produced.
+    export_mode: ExportMode,
+    /// The export file that records are appended to in streaming mode.
+    stream: Option<File>,
 }
 
 impl JsonHandler {
     /// Creates a new `JsonHandler` with an optional export path.
     /// If `export_path` is `None`, calls to `export()` will be no-ops.
+    /// The data is seeded with the schema and driver versions.
     pub fn new(export_path: Option<PathBuf>) -> Self {
-        Self { data: json!({}), export_path }
+        let mut handler =
+            Self { data: json!({}), export_path, export_mode: ExportMode::Buffered, stream: None };
+        handler.add_version_info();
+        handler
+    }
+
+    /// Creates a new `JsonHandler` that streams NDJSON records to `export_path`.
+    /// Any existing file at `export_path` is truncated.
+    /// The first records written are the schema and driver versions.
+    pub fn new_streaming(export_path: PathBuf) -> Result<Self, std::io::Error> {
+        let stream = File::create(&export_path)?;
+        let mut handler = Self {
+            data: json!({}),
+            export_path: Some(export_path),
+            export_mode: ExportMode::Streaming,
+            stream: Some(stream),
+        };
+        handler.add_version_info();
+        Ok(handler)
+    }
+
+    /// Creates the handler for `--export-json`, or `None` if nothing is exported.
+    /// Callers should only build the exported records when there is a handler to add them to.
+    pub fn for_export(
+        export_path: Option<PathBuf>,
+        mode: ExportMode,
+    ) -> Result<Option<Self>, std::io::Error> {
+        match (export_path, mode) {
+            (None, _) => Ok(None),
+            (Some(path), ExportMode::Streaming) => Self::new_streaming(path).map(Some),
+            (Some(path), ExportMode::Buffered) => Ok(Some(Self::new(Some(path)))),
+            (Some(path), ExportMode::Merge) => {
+                let mut handler = Self::new(Some(path));
+                handler.export_mode = ExportMode::Merge;
+                Ok(Some(handler))
+            }
+        }
+    }
+
+    fn add_version_info(&mut self) {
+        self.add_item(SCHEMA_VERSION_KEY, json!(SCHEMA_VERSION));
+        self.add_item(DRIVER_VERSION_KEY, json!(KANI_VERSION));
+    }
+
+    /// Whether records are written to the export file as soon as they are added.
+    pub fn is_streaming(&self) -> bool {
+        self.export_mode == ExportMode::Streaming
     }
 
     /// Adds or updates a key-value pair in the JSON object.
     /// If the key already exists, its value will be overwritten.
     pub fn add_item(&mut self, key: &str, value: Value) {
+        self.stream_record(key, &value);
         self.data[key] = value;
     }
 
@@ -33,19 +448,138 @@ pub fn add_item(&mut self, key: &str, value: Value) {
     /// Creates a new array if the key doesn't exist or is null.
     /// Panics if the key exists but is not an array or null.
     pub fn add_harness_detail(&mut self, key: &str, value: Value) {
+        self.stream_record(key, &value);
         if self.data[key].is_null() {
             self.data[key] = json!([]);
         }
         self.data[key].as_array_mut().unwrap().push(value);
     }
 
+    /// Sets the information about the whole run under [RUN_KEY].
+    pub fn set_run_summary(&mut self, run: &ExportedRunInfo) {
+        self.add_item(RUN_KEY, json!(run));
+    }
+
     /// Exports the JSON data to the configured file path with pretty-printing.
     /// Returns an error if the file cannot be written.
+    /// In streaming mode, the data has already been written, so this only records the export in
+    /// the event log. In merge mode, the run is appended to the runs already in the file, while
+    /// holding a lock so that concurrent runs merging into the same file don't drop each other.
     pub fn export(&self) -> Result<(), std::io::Error> {
-        if let Some(path) = &self.export_path {
-            std::fs::write(path, serde_json::to_string_pretty(&self.data)?)
-        } else {
-            Ok(())
+        let Some(path) = &self.export_path else { return Ok(()) };
+        match self.export_mode {
+            ExportMode::Buffered => {
+                std::fs::write(path, serde_json::to_string_pretty(&self.data)?)?;
+            }
+            ExportMode::Merge => {
+                let _lock = lock_merged_export(path)?;
+                let runs = merge_run(path, &self.data)?;
+                write_atomically(path, &serde_json::to_string_pretty(&runs)?)?;
+            }
+            ExportMode::Streaming => {}
+        }
+        crate::event_log::file_written(path);
+        Ok(())
+    }
+
+    /// Records `error` under [RUN_ERROR_KEY] and [ERROR_KEY] and exports whatever was gathered
+    /// before it.
+    pub fn export_after_error(&mut self, error: &anyhow::Error) -> Result<(), std::io::Error> {
+        self.add_item(RUN_ERROR_KEY, json!(format!("{error:#}")));
+        self.add_item(ERROR_KEY, json!(ExportedError::new(error)));
+        self.export()
+    }
+
+    /// Read the exported file back into the typed structures to check that it is well formed.
+    #[cfg(test)]
+    pub fn validate(&self) -> anyhow::Result<ExportedRun> {
+        use anyhow::Context;
+        let path = self.export_path.as_ref().context("No export path configured")?;
+        anyhow::ensure!(self.export_mode == ExportMode::Buffered, "Cannot validate NDJSON exports");
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// In streaming mode, append `{"key": <key>, "value": <value>}` as one line to the export file.
+    /// Failing to write a record should not abort verification, so errors are only reported.
+    fn stream_record(&mut self, key: &str, value: &Value) {
+        let (Some(stream), Some(path)) = (&mut self.stream, &self.export_path) else { return };
+        let record = json!({ "key": key, "value": value });
+        if let Err(err) = writeln!(stream, "{record}").and_then(|()| stream.flush()) {
+            crate::util::warning(&format!(
+                "Failed to write JSON record `{key}` to `{}`: {err}",
+                path.display()
+            ));
         }
     }
 }
+
+/// Pass `result` through, exporting `handler` with the error first if it is one.
+/// Failing to export is only reported, so the original error is the one returned.
+pub fn export_on_error<T>(
+    handler: Option<&mut JsonHandler>,
+    result: anyhow::Result<T>,
+) -> anyhow::Result<T> {
+    if let (Err(err), Some(handler)) = (&result, handler)
+        && let Err(export_err) = handler.export_after_error(err)
+    {
+        crate::util::warning(&format!("Failed to export JSON after an error: {export_err}"));
+    }
+    result
+}
+
+/// Take an exclusive advisory lock for merging into the export at `path`, which is released when
+/// the returned file is dropped. The lock is held on `<path>.lock`, since the export itself is
+/// replaced by every merge. The lock file is left in place, as removing it would race with the
+/// runs waiting for it.
+fn lock_merged_export(path: &Path) -> Result<File, std::io::Error> {
+    let mut lock_path = path.as_os_str().to_owned();
+    lock_path.push(".lock");
+    let lock = File::options().create(true).truncate(false).write(true).open(lock_path)?;
+    lock.lock()?;
+    Ok(lock)
+}
+
+/// The runs of the merged export at `path` followed by `run`. A missing or empty file has no runs.
+fn merge_run(path: &Path, run: &Value) -> Result<Value, std::io::Error> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err),
+    };
+    let mut runs = if content.trim().is_empty() {
+        vec![]
+    } else {
+        match serde_json::from_str(&content)? {
+            Value::Array(runs) => runs,
+            _ => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!(
+                        "`{}` is not a merged export: expected a JSON array of runs",
+                        path.display()
+                    ),
+                ));
+            }
+        }
+    };
+    let timestamp = OffsetDateTime::now_utc()
+        .format(&Rfc3339)
+        .unwrap_or_else(|_| "1970-01-01T00:00:00Z".to_string());
+    runs.push(json!({ "timestamp": timestamp, "run": run }));
+    Ok(Value::Array(runs))
+}
+
+/// Write `content` to a temporary file next to `path` and rename it over `path`, so readers never
+/// observe a partially written file.
+fn write_atomically(path: &Path, content: &str) -> Result<(), std::io::Error> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let mut file = NamedTempFile::new_in(dir)?;
+    file.write_all(content.as_bytes())?;
+    file.flush()?;
+    file.persist(path).map_err(|err| err.error)?;
+    Ok(())
+}
@@ -0,0 +1,54 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Events printed to stdout by `--output-format json`, one JSON object per line, instead of the
+//! human readable results. Every event has an `event` field with its kind:
+//! - `check`: one check of a harness, as in the `checks` of the exported harness results.
+//! - `harness`: the result of a harness, as exported in `verification_results`, without the
+//!   checks that were already printed.
+//! - `summary`: the totals of the run, as exported in `run_summary`.
+//!
+//! The fields of the events are the exported ones, so tools can share their parsers.
+
+use serde_json::{Map, Value, json};
+
+use crate::frontend::schema_utils::{
+    create_check_json, create_run_summary, create_verification_result_json,
+};
+use crate::harness_runner::HarnessResult;
+
+/// The `check` events of `result` followed by its `harness` event, one per line.
+pub fn harness_events(result: &HarnessResult) -> String {
+    let harness = &result.harness.pretty_name;
+    let properties = result.result.results.as_deref().unwrap_or_default();
+    let mut events: Vec<String> = properties
+        .iter()
+        .enumerate()
+        .map(|(idx, prop)| {
+            event("check", json!({ "harness_id": harness }), create_check_json(idx + 1, prop))
+        })
+        .collect();
+    let mut harness_result = create_verification_result_json(result);
+    if let Some(fields) = harness_result.as_object_mut() {
+        fields.remove("checks");
+    }
+    events.push(event("harness", json!({}), harness_result));
+    events.join("\n")
+}
+
+/// The `summary` event of the run.
+pub fn summary_event(results: &[HarnessResult], coverage_enabled: bool) -> String {
+    event("summary", json!({}), json!(create_run_summary(results, coverage_enabled)))
+}
+
+/// An event of `kind` with the fields of `prefix` followed by the fields of `fields`.
+fn event(kind: &str, prefix: Value, fields: Value) -> String {
+    let mut event = Map::new();
+    event.insert("event".to_string(), json!(kind));
+    for value in [prefix, fields] {
+        if let Value::Object(fields) = value {
+            event.extend(fields);
+        }
+    }
+    Value::Object(event).to_string()
+}
@@ -0,0 +1,230 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! JUnit XML report of a verification run, written by `--junit-xml` for CI systems that only
+//! understand JUnit test summaries.
+//!
+//! Every harness is reported as a `<testcase>`:
+//! * harnesses with failed checks contain a `<failure>` listing those checks;
+//! * harnesses that CBMC could not check (e.g. a timeout) or with undetermined checks contain an
+//!   `<error>`;
+//! * harnesses whose checks are all unreachable contain a `<skipped>` element.
+
+use std::fmt::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+
+use crate::call_cbmc::{ExitStatus, VerificationResult, VerificationStatus};
+use crate::cbmc_output_parser::{CheckStatus, Property};
+use crate::frontend::{ExportedHarness, create_exported_harness};
+use crate::harness_runner::HarnessResult;
+
+/// Name of the `<testsuites>` and `<testsuite>` elements.
+const SUITE_NAME: &str = "kani";
+
+/// How a harness is reported in the JUnit file.
+#[derive(Debug)]
+enum Outcome {
+    Passed,
+    /// At least one check failed, or the harness failed without failing a check
+    /// (e.g. a `should_panic` harness that did not panic).
+    Failure {
+        message: String,
+        details: String,
+    },
+    /// Verification could not determine the result of the harness.
+    Error {
+        message: String,
+        details: String,
+    },
+    /// None of the checks of the harness are reachable.
+    Skipped {
+        message: String,
+    },
+}
+
+/// Write the JUnit report for `results` to `path`.
+pub fn write_junit_report(path: &Path, results: &[HarnessResult]) -> Result<()> {
+    std::fs::write(path, create_junit_report(results))
+        .with_context(|| format!("Failed to write JUnit report to `{}`", path.display()))
+}
+
+/// Create the JUnit XML document for `results`, in the order the harnesses were verified.
+pub fn create_junit_report(results: &[HarnessResult]) -> String {
+    let outcomes: Vec<_> = results.iter().map(|r| outcome(&r.result)).collect();
+    let count = |pred: fn(&Outcome) -> bool| outcomes.iter().filter(|o| pred(o)).count();
+    let failures = count(|o| matches!(o, Outcome::Failure { .. }));
+    let errors = count(|o| matches!(o, Outcome::Error { .. }));
+    let skipped = count(|o| matches!(o, Outcome::Skipped { .. }));
+    let time =
+        format_time(results.iter().map(|r| r.result.runtime).sum::<Duration>().as_secs_f64());
+    let suite_attrs = format!(
+        "name=\"{SUITE_NAME}\" tests=\"{}\" failures=\"{failures}\" errors=\"{errors}\" skipped=\"{skipped}\" time=\"{time}\"",
+        results.len()
+    );
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    writeln!(xml, "<testsuites {suite_attrs}>").unwrap();
+    writeln!(xml, "  <testsuite {suite_attrs}>").unwrap();
+    for (result, outcome) in results.iter().zip(&outcomes) {
+        let harness = create_exported_harness(result.harness);
+        write!(
+            xml,
+            "    <testcase classname=\"{}\" name=\"{}\" time=\"{}\"",
+            escape_xml(&classname(&harness)),
+            escape_xml(&harness.pretty_name),
+            format_time(result.result.runtime.as_secs_f64())
+        )
+        .unwrap();
+        match outcome {
+            Outcome::Passed => xml.push_str("/>\n"),
+            Outcome::Failure { message, details } => {
+                write_element(&mut xml, "failure", message, details)
+            }
+            Outcome::Error { message, details } => {
+                write_element(&mut xml, "error", message, details)
+            }
+            Outcome::Skipped { message } => {
+                writeln!(xml, ">\n      <skipped message=\"{}\"/>", escape_xml(message)).unwrap();
+                xml.push_str("    </testcase>\n");
+            }
+        }
+    }
+    xml.push_str("  </testsuite>\n");
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Close the opening `<testcase` tag and add a `<failure>` or `<error>` child with `details` as
+/// its text.
+fn write_element(xml: &mut String, tag: &str, message: &str, details: &str) {
+    writeln!(
+        xml,
+        ">\n      <{tag} message=\"{}\">{}</{tag}>",
+        escape_xml(message),
+        escape_xml(details)
+    )
+    .unwrap();
+    xml.push_str("    </testcase>\n");
+}
+
+/// Group harnesses by crate and module, e.g. `my_crate::proofs` for `proofs::check_foo`.
+fn classname(harness: &ExportedHarness) -> String {
+    match harness.pretty_name.rsplit_once("::") {
+        Some((module, _)) => format!("{}::{module}", harness.crate_name),
+        None => harness.crate_name.clone(),
+    }
+}
+
+fn outcome(result: &VerificationResult) -> Outcome {
+    if result.status == VerificationStatus::Skipped {
+        return Outcome::Skipped {
+            message: "Not verified because `--fail-fast` stopped the run".to_string(),
+        };
+    }
+    if result.status == VerificationStatus::Excluded {
+        return Outcome::Skipped {
+            message: "Not verified because of `--exclude-harness`".to_string(),
+        };
+    }
+    if result.status == VerificationStatus::Failure
+        && let Some(reason) = &result.expected_failure
+    {
+        let message = if reason.is_empty() {
+            "Expected failure".to_string()
+        } else {
+            format!("Expected failure: {reason}")
+        };
+        return Outcome::Skipped { message };
+    }
+    if result.timed_out {
+        return Outcome::Error {
+            message: exit_status_message(&ExitStatus::Timeout),
+            details: String::new(),
+        };
+    }
+    let properties = match &result.results {
+        Ok(properties) => properties,
+        Err(exit_status) => {
+            return Outcome::Error {
+                message: exit_status_message(exit_status),
+                details: String::new(),
+            };
+        }
+    };
+    let with_status =
+        |status| properties.iter().filter(move |prop| prop.status == status).collect::<Vec<_>>();
+
+    if result.status == VerificationStatus::Success {
+        let unreachable = with_status(CheckStatus::Unreachable);
+        return if !properties.is_empty() && unreachable.len() == properties.len() {
+            Outcome::Skipped { message: "All checks are unreachable".to_string() }
+        } else {
+            Outcome::Passed
+        };
+    }
+
+    let failed = with_status(CheckStatus::Failure);
+    if !failed.is_empty() {
+        return Outcome::Failure {
+            message: format!("{} of {} checks failed", failed.len(), properties.len()),
+            details: describe_checks(&failed),
+        };
+    }
+    let undetermined = with_status(CheckStatus::Undetermined);
+    if !undetermined.is_empty() {
+        return Outcome::Error {
+            message: format!(
+                "{} of {} checks are undetermined",
+                undetermined.len(),
+                properties.len()
+            ),
+            details: describe_checks(&undetermined),
+        };
+    }
+    Outcome::Failure { message: "Verification failed".to_string(), details: String::new() }
+}
+
+/// List each check with its description and source location.
+fn describe_checks(properties: &[&Property]) -> String {
+    properties
+        .iter()
+        .map(|prop| {
+            format!("{}: {}\n at {}", prop.property_name(), prop.description, prop.source_location)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn exit_status_message(exit_status: &ExitStatus) -> String {
+    match exit_status {
+        ExitStatus::Timeout => "CBMC timed out".to_string(),
+        ExitStatus::OutOfMemory => "CBMC ran out of memory".to_string(),
+        ExitStatus::Other(code) => format!("CBMC failed with status {code}"),
+        ExitStatus::DriverPanic => "Kani panicked while checking this harness".to_string(),
+    }
+}
+
+fn format_time(seconds: f64) -> String {
+    format!("{seconds:.3}")
+}
+
+/// Escape `text` for use in XML attributes and text, dropping characters XML 1.0 can't represent.
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
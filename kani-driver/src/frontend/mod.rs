@@ -5,10 +5,18 @@
 //! This module separates the JSON handling logic from the main verification logic
 
 pub mod json_handler;
+pub mod json_output;
+pub mod junit;
+pub mod run_diff;
+pub mod sarif;
 pub mod schema_utils;
 
-pub use json_handler::JsonHandler;
+pub use json_handler::*;
+pub use json_output::*;
+pub use junit::*;
+pub use run_diff::*;
+pub use sarif::*;
 pub use schema_utils::*;
 
 #[cfg(test)]
-mod tests;
+pub mod tests;
@@ -0,0 +1,262 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Comparison of two runs exported with `--export-json`, used by the `diff` subcommand and by
+//! `--compare-baseline`.
+//!
+//! Harnesses are matched by their pretty name, which is the `harness_id` of the entries under
+//! [VERIFICATION_RESULTS_KEY]. Both buffered JSON and streaming NDJSON exports can be compared.
+//! Harnesses that `--fail-fast` skipped or `--exclude-harness` excluded in either run are not
+//! compared.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::args::diff_args::DiffArgs;
+use crate::frontend::schema_utils::create_verification_result_json;
+use crate::frontend::{SCHEMA_VERSION, SCHEMA_VERSION_KEY, VERIFICATION_RESULTS_KEY};
+use crate::harness_runner::HarnessResult;
+
+/// Default relative runtime change, in percent, above which a harness is reported.
+pub const DEFAULT_RUNTIME_THRESHOLD_PERCENT: f64 = 20.0;
+/// Runtime changes smaller than this are noise and never reported, whatever their percentage.
+const MIN_RUNTIME_CHANGE_MS: u64 = 100;
+
+/// Differences between an old and a new run.
+/// Every list is sorted by harness name.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunDiff {
+    pub old_schema_version: Option<u64>,
+    pub new_schema_version: Option<u64>,
+    /// Set if the runs were exported with different schema versions, in which case some
+    /// differences may come from the export format rather than from the harnesses.
+    pub warning: Option<String>,
+    /// Harnesses that succeeded in the old run and fail in the new one.
+    pub newly_failing: Vec<String>,
+    /// Harnesses that failed in the old run and succeed in the new one.
+    pub newly_passing: Vec<String>,
+    /// Harnesses whose runtime changed by more than the threshold.
+    pub runtime_changes: Vec<RuntimeChange>,
+    pub only_in_old: Vec<String>,
+    pub only_in_new: Vec<String>,
+}
+
+/// Runtime of a harness present in both runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuntimeChange {
+    pub harness: String,
+    pub old_duration_ms: u64,
+    pub new_duration_ms: u64,
+    /// Relative change from the old runtime, e.g. `50.0` if the harness became 1.5 times slower.
+    /// `None` if the old runtime was zero.
+    pub change_percent: Option<f64>,
+}
+
+/// Outcome of one harness in an exported run.
+#[derive(Debug, Clone, PartialEq)]
+struct HarnessOutcome {
+    success: bool,
+    /// The harness was not verified because `--fail-fast` stopped the run or it was excluded, so
+    /// there is nothing to compare.
+    skipped: bool,
+    duration_ms: u64,
+}
+
+/// Implement `kani diff` and `cargo kani diff`.
+pub fn diff_exported_runs(args: DiffArgs) -> Result<()> {
+    let diff = diff_runs(&args.old, &args.new, args.runtime_threshold)?;
+    println!("{}", serde_json::to_string_pretty(&diff)?);
+    Ok(())
+}
+
+/// Implement `--compare-baseline`: compare `results` with the exported run `baseline`.
+pub fn compare_with_baseline(
+    baseline: &Value,
+    results: &[HarnessResult],
+    runtime_threshold_percent: f64,
+) -> RunDiff {
+    let results: Vec<Value> = results.iter().map(create_verification_result_json).collect();
+    let current = json!({
+        SCHEMA_VERSION_KEY: SCHEMA_VERSION,
+        VERIFICATION_RESULTS_KEY: { "results": results },
+    });
+    diff_exports(baseline, &current, runtime_threshold_percent)
+}
+
+/// Compare the exported runs `old` and `new`.
+/// Runtime changes are reported if they exceed `runtime_threshold_percent` of the old runtime.
+pub fn diff_runs(old: &Path, new: &Path, runtime_threshold_percent: f64) -> Result<RunDiff> {
+    Ok(diff_exports(&read_export(old)?, &read_export(new)?, runtime_threshold_percent))
+}
+
+/// Compare two exported documents. See [diff_runs].
+pub fn diff_exports(old: &Value, new: &Value, runtime_threshold_percent: f64) -> RunDiff {
+    let old_schema_version = old[SCHEMA_VERSION_KEY].as_u64();
+    let new_schema_version = new[SCHEMA_VERSION_KEY].as_u64();
+    let warning = (old_schema_version != new_schema_version).then(|| {
+        format!(
+            "The runs were exported with different schema versions ({} and {}). \
+             Some differences may be caused by the export format.",
+            format_version(old_schema_version),
+            format_version(new_schema_version)
+        )
+    });
+
+    let old_outcomes = harness_outcomes(old);
+    let new_outcomes = harness_outcomes(new);
+    let mut diff = RunDiff {
+        old_schema_version,
+        new_schema_version,
+        warning,
+        newly_failing: vec![],
+        newly_passing: vec![],
+        runtime_changes: vec![],
+        only_in_old: old_outcomes
+            .keys()
+            .filter(|h| !new_outcomes.contains_key(*h))
+            .cloned()
+            .collect(),
+        only_in_new: new_outcomes
+            .keys()
+            .filter(|h| !old_outcomes.contains_key(*h))
+            .cloned()
+            .collect(),
+    };
+    for (harness, old_outcome) in &old_outcomes {
+        let Some(new_outcome) = new_outcomes.get(harness) else { continue };
+        if old_outcome.skipped || new_outcome.skipped {
+            continue;
+        }
+        match (old_outcome.success, new_outcome.success) {
+            (true, false) => diff.newly_failing.push(harness.clone()),
+            (false, true) => diff.newly_passing.push(harness.clone()),
+            _ => {}
+        }
+        if let Some(change) =
+            runtime_change(harness, old_outcome, new_outcome, runtime_threshold_percent)
+        {
+            diff.runtime_changes.push(change);
+        }
+    }
+    diff
+}
+
+impl RunDiff {
+    /// The harnesses that fail `--fail-on-regression`: the newly failing ones and the ones that
+    /// became slower by more than the threshold.
+    pub fn regressions(&self) -> Vec<&str> {
+        let slower = self
+            .runtime_changes
+            .iter()
+            .filter(|change| change.new_duration_ms > change.old_duration_ms)
+            .map(|change| change.harness.as_str());
+        let mut regressions: Vec<&str> =
+            self.newly_failing.iter().map(String::as_str).chain(slower).collect();
+        regressions.sort_unstable();
+        regressions.dedup();
+        regressions
+    }
+
+    /// Summary of the differences, printed after the verification results.
+    pub fn render(&self) -> String {
+        let mut lines = vec![];
+        let mut list = |title: &str, harnesses: &[String]| {
+            if !harnesses.is_empty() {
+                lines.push(format!("{title}: {}", harnesses.join(", ")));
+            }
+        };
+        list("Newly failing", &self.newly_failing);
+        list("Newly passing", &self.newly_passing);
+        list("Added", &self.only_in_new);
+        list("Removed", &self.only_in_old);
+        for change in &self.runtime_changes {
+            let percent = change.change_percent.map(|p| format!(" ({p:+.1}%)")).unwrap_or_default();
+            lines.push(format!(
+                "Runtime of {}: {}ms -> {}ms{percent}",
+                change.harness, change.old_duration_ms, change.new_duration_ms
+            ));
+        }
+        if lines.is_empty() {
+            lines.push("No differences".to_string());
+        }
+        lines.join("\n")
+    }
+}
+
+fn format_version(version: Option<u64>) -> String {
+    version.map_or_else(|| "unknown".to_string(), |version| version.to_string())
+}
+
+fn runtime_change(
+    harness: &str,
+    old: &HarnessOutcome,
+    new: &HarnessOutcome,
+    threshold_percent: f64,
+) -> Option<RuntimeChange> {
+    if old.duration_ms.abs_diff(new.duration_ms) < MIN_RUNTIME_CHANGE_MS {
+        return None;
+    }
+    // A harness that used to take no time at all always changed by more than the threshold.
+    let change_percent = (old.duration_ms != 0).then(|| {
+        (new.duration_ms as f64 - old.duration_ms as f64) / old.duration_ms as f64 * 100.0
+    });
+    change_percent.is_none_or(|change| change.abs() > threshold_percent).then(|| RuntimeChange {
+        harness: harness.to_string(),
+        old_duration_ms: old.duration_ms,
+        new_duration_ms: new.duration_ms,
+        change_percent,
+    })
+}
+
+/// The outcome of every harness under [VERIFICATION_RESULTS_KEY], by harness name.
+/// Entries that don't have the expected shape are ignored.
+fn harness_outcomes(export: &Value) -> BTreeMap<String, HarnessOutcome> {
+    let Some(results) = export[VERIFICATION_RESULTS_KEY]["results"].as_array() else {
+        return BTreeMap::new();
+    };
+    results
+        .iter()
+        .filter_map(|result| {
+            let harness = result["harness_id"].as_str()?;
+            let status = result["status"].as_str()?;
+            let outcome = HarnessOutcome {
+                success: matches!(status, "Success" | "XPass"),
+                skipped: matches!(status, "Skipped" | "Excluded"),
+                duration_ms: result["duration_ms"].as_u64().unwrap_or_default(),
+            };
+            Some((harness.to_string(), outcome))
+        })
+        .collect()
+}
+
+/// Read an export written by [crate::frontend::JsonHandler].
+/// Streaming exports are folded into a single document, where a later record replaces an earlier
+/// one with the same key.
+pub fn read_export(path: &Path) -> Result<Value> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read exported run `{}`", path.display()))?;
+    parse_export(&content).with_context(|| format!("Invalid exported run `{}`", path.display()))
+}
+
+fn parse_export(content: &str) -> Result<Value> {
+    // A streaming export with a single record is also a valid JSON document.
+    if let Ok(export) = serde_json::from_str::<Value>(content)
+        && export.is_object()
+        && export.get("key").is_none()
+    {
+        return Ok(export);
+    }
+    let mut export = serde_json::Map::new();
+    for line in content.lines().filter(|line| !line.trim().is_empty()) {
+        let record: Value = serde_json::from_str(line)?;
+        let (Some(key), Some(value)) = (record["key"].as_str(), record.get("value")) else {
+            bail!("Expected a JSON document or NDJSON records with a `key` and a `value`");
+        };
+        export.insert(key.to_string(), value.clone());
+    }
+    Ok(Value::Object(export))
+}
@@ -0,0 +1,245 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! SARIF 2.1.0 log of the failed checks of a verification run, written by `--sarif` so failures
+//! can be shown as code scanning annotations, e.g. on GitHub pull requests.
+//!
+//! Every property class with a failed check is a rule, and every failed check is a result located
+//! at the source location of the check. Checks without a source location, e.g. assertions in the
+//! standard library models, are located at the harness instead. Harnesses that are expected to
+//! fail (`--xfail`) are not reported.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::cbmc_output_parser::{CheckStatus, Property};
+use crate::harness_runner::HarnessResult;
+use crate::version::KANI_VERSION;
+
+/// The schema of the SARIF 2.1.0 format.
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+pub const SARIF_VERSION: &str = "2.1.0";
+
+#[derive(Debug, Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    pub schema: &'static str,
+    pub version: &'static str,
+    pub runs: Vec<SarifRun>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SarifRun {
+    pub tool: SarifTool,
+    pub results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SarifTool {
+    pub driver: SarifDriver,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifDriver {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub information_uri: &'static str,
+    pub rules: Vec<SarifRule>,
+}
+
+/// A property class, e.g. `assertion` or `arithmetic_overflow`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRule {
+    pub id: String,
+    pub short_description: SarifMessage,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SarifMessage {
+    pub text: String,
+}
+
+/// A failed check.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: String,
+    pub rule_index: usize,
+    pub level: &'static str,
+    pub message: SarifMessage,
+    pub locations: Vec<SarifLocation>,
+    pub properties: SarifResultProperties,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLocation {
+    pub physical_location: SarifPhysicalLocation,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub logical_locations: Vec<SarifLogicalLocation>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifPhysicalLocation {
+    pub artifact_location: SarifArtifactLocation,
+    pub region: SarifRegion,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SarifArtifactLocation {
+    pub uri: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRegion {
+    pub start_line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_column: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLogicalLocation {
+    pub fully_qualified_name: String,
+    pub kind: &'static str,
+}
+
+/// Kani specific details of a result.
+#[derive(Debug, Serialize)]
+pub struct SarifResultProperties {
+    pub harness: String,
+    pub property: String,
+}
+
+/// Write the SARIF log of the failed checks in `results` to `path`. Paths are made relative to the
+/// project root, or the current directory if it is unknown.
+pub fn write_sarif_report(path: &Path, results: &[HarnessResult]) -> Result<()> {
+    let base_dir = match crate::project_root::get() {
+        Some(root) => root.to_path_buf(),
+        None => std::env::current_dir()?,
+    };
+    let log = create_sarif_log(results, &base_dir);
+    std::fs::write(path, serde_json::to_string_pretty(&log)?)
+        .with_context(|| format!("Failed to write SARIF log to `{}`", path.display()))
+}
+
+/// Create the SARIF log of the failed checks in `results`. The paths of the source files under
+/// `base_dir` are made relative to it, as code scanning expects paths relative to the repository.
+pub fn create_sarif_log(results: &[HarnessResult], base_dir: &Path) -> SarifLog {
+    let failed_checks: Vec<(&HarnessResult, &Property)> = results
+        .iter()
+        .filter(|r| r.result.expected_failure.is_none())
+        .filter_map(|r| r.result.results.as_ref().ok().map(|properties| (r, properties)))
+        .flat_map(|(r, properties)| {
+            properties.iter().filter(|p| p.status == CheckStatus::Failure).map(move |p| (r, p))
+        })
+        .collect();
+
+    // The rules are sorted by property class, so results refer to them by a stable index.
+    let classes: BTreeSet<String> = failed_checks.iter().map(|(_, p)| p.property_class()).collect();
+    let rule_indices: BTreeMap<&str, usize> =
+        classes.iter().enumerate().map(|(idx, class)| (class.as_str(), idx)).collect();
+    let rules = classes
+        .iter()
+        .map(|class| SarifRule {
+            id: class.clone(),
+            short_description: SarifMessage { text: format!("Kani `{class}` check") },
+        })
+        .collect();
+
+    let sarif_results = failed_checks
+        .into_iter()
+        .map(|(r, p)| {
+            let class = p.property_class();
+            SarifResult {
+                rule_index: rule_indices[class.as_str()],
+                rule_id: class,
+                level: "error",
+                message: SarifMessage {
+                    text: format!(
+                        "Check `{}` failed in harness `{}`: {}",
+                        p.property_name(),
+                        r.harness.pretty_name,
+                        p.description
+                    ),
+                },
+                locations: vec![location(r, p, base_dir)],
+                properties: SarifResultProperties {
+                    harness: r.harness.pretty_name.clone(),
+                    property: p.property_name(),
+                },
+            }
+        })
+        .collect();
+
+    SarifLog {
+        schema: SARIF_SCHEMA,
+        version: SARIF_VERSION,
+        runs: vec![SarifRun {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: "Kani",
+                    version: KANI_VERSION,
+                    information_uri: "https://github.com/model-checking/kani",
+                    rules,
+                },
+            },
+            results: sarif_results,
+        }],
+    }
+}
+
+/// The location of a failed check, or of its harness if the check has no source location.
+fn location(result: &HarnessResult, property: &Property, base_dir: &Path) -> SarifLocation {
+    let source = &property.source_location;
+    let line = source.line.as_deref().and_then(|line| line.parse::<usize>().ok());
+    let (file, start_line, start_column) = match (&source.file, line) {
+        (Some(file), Some(line)) if line > 0 => (
+            file.as_str(),
+            line,
+            source
+                .column
+                .as_deref()
+                .and_then(|column| column.parse().ok())
+                .filter(|column| *column > 0),
+        ),
+        _ => {
+            (result.harness.original_file.as_str(), result.harness.original_start_line.max(1), None)
+        }
+    };
+    SarifLocation {
+        physical_location: SarifPhysicalLocation {
+            artifact_location: SarifArtifactLocation { uri: artifact_uri(file, base_dir) },
+            region: SarifRegion { start_line, start_column },
+        },
+        logical_locations: source
+            .function
+            .iter()
+            .map(|function| SarifLogicalLocation {
+                fully_qualified_name: function.clone(),
+                kind: "function",
+            })
+            .collect(),
+    }
+}
+
+/// The URI of `file`: relative to `base_dir` if it's under it, and a `file` URI otherwise.
+fn artifact_uri(file: &str, base_dir: &Path) -> String {
+    let path = Path::new(file);
+    let path = if path.is_absolute() {
+        match path.strip_prefix(base_dir) {
+            Ok(relative) => relative,
+            Err(_) => return format!("file://{}", file.replace('\\', "/")),
+        }
+    } else {
+        path
+    };
+    path.to_string_lossy().replace('\\', "/")
+}
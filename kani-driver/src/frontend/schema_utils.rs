@@ -4,15 +4,27 @@
 // Utility functions for creating structured JSON schemas
 // This module contains helper functions to convert Kani internal structures to JSON
 
-use crate::call_cbmc::VerificationStatus;
-use crate::frontend::JsonHandler;
+use crate::args::VerificationArgs;
+use crate::call_cbmc::{CbmcInfo, ExitStatus, VerificationStatus, resolve_cbmc_verbosity};
+use crate::cbmc_output_parser::{CheckStatus, Property, SourceLocation};
+use crate::coverage::cov_results::HarnessCoverage;
+use crate::frontend::{
+    CBMC_KEY, ContractDependencyStatus, ERROR_DETAILS_KEY, ExportedAttributes,
+    ExportedCbmcConfiguration, ExportedCbmcMetadata, ExportedCbmcRun, ExportedContract,
+    ExportedContractDependency, ExportedCoverage, ExportedHarness, ExportedRunInfo,
+    ExportedRunSummary, ExportedSkippedFunction, ExportedSource, ExportedStatusCounts,
+    ExportedStub, ExportedWarning, JsonHandler, PROPERTY_DETAILS_KEY, VERIFICATION_RESULTS_KEY,
+};
 use crate::harness_runner::HarnessResult;
 use crate::project::Project;
-use crate::session::KaniSession;
+use crate::version::KANI_VERSION;
 use anyhow::Result;
-use kani_metadata::HarnessMetadata;
+use kani_metadata::{AutoHarnessSkipReason, HarnessKind, HarnessMetadata};
 use serde::Serialize;
 use serde_json::{Value, json};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::time::Duration;
 use time::OffsetDateTime;
 use time::format_description::well_known::Rfc3339;
 
@@ -46,79 +58,284 @@ pub fn create_project_metadata_json(project: &Project) -> Value {
     "workspace_root": project.outdir.clone(),
     })
 }
+
+/// Lists the functions `autoharness` skipped in every crate of the project.
+/// Returns `None` if the project was not built for `autoharness`.
+/// Kani implementations are left out, as in the table of skipped functions.
+pub fn create_skipped_functions(project: &Project) -> Option<Vec<ExportedSkippedFunction>> {
+    let mut autoharness_crates = project
+        .metadata
+        .iter()
+        .filter_map(|md| md.autoharness_md.as_ref().map(|autoharness_md| (md, autoharness_md)))
+        .peekable();
+    autoharness_crates.peek()?;
+    let skipped = autoharness_crates
+        .flat_map(|(md, autoharness_md)| {
+            autoharness_md.skipped.iter().filter_map(|(function, reason)| {
+                let missing_arbitrary = match reason {
+                    AutoHarnessSkipReason::KaniImpl => return None,
+                    AutoHarnessSkipReason::MissingArbitraryImpl(args) => {
+                        args.iter().map(|(name, typ)| format!("{name}: {typ}")).collect()
+                    }
+                    AutoHarnessSkipReason::GenericFn
+                    | AutoHarnessSkipReason::NoBody
+                    | AutoHarnessSkipReason::UserFilter => vec![],
+                };
+                Some(ExportedSkippedFunction {
+                    crate_name: md.crate_name.clone(),
+                    function: function.clone(),
+                    reason: reason.to_string(),
+                    missing_arbitrary,
+                })
+            })
+        })
+        .collect();
+    Some(skipped)
+}
+
 /// Creates structured JSON metadata for a harness
 /// This utility function separates harness metadata creation from the main verification logic
-pub fn create_harness_metadata_json(h: &HarnessMetadata) -> Value {
-    json!({
-        "pretty_name": h.pretty_name, // use this as identifier
-        "mangled_name": h.mangled_name,
-        "crate_name": h.crate_name,
-        "source": {
-            "file": h.original_file,
-            "start_line": h.original_start_line,
-            "end_line": h.original_end_line
+/// `selected_by` is the `--harness` filter or harness pattern that selected the harness, if any.
+pub fn create_harness_metadata_json(h: &HarnessMetadata, selected_by: Option<String>) -> Value {
+    json!(ExportedHarness { selected_by, ..create_exported_harness(h) })
+}
+
+/// Converts the harness metadata into its exported representation
+pub fn create_exported_harness(h: &HarnessMetadata) -> ExportedHarness {
+    ExportedHarness {
+        pretty_name: h.pretty_name.clone(), // use this as identifier
+        mangled_name: h.mangled_name.clone(),
+        crate_name: h.crate_name.clone(),
+        source: ExportedSource {
+            file: h.original_file.clone(),
+            start_line: h.original_start_line,
+            end_line: h.original_end_line,
         },
-        "goto_file": h.goto_file.as_ref().map(|p| p.to_string_lossy().to_string()),
-        "attributes": {
-            "kind": format!("{:?}", h.attributes.kind),
-            "should_panic": h.attributes.should_panic,
+        goto_file: h.goto_file.as_ref().map(|p| p.to_string_lossy().to_string()),
+        attributes: ExportedAttributes {
+            kind: harness_kind_name(&h.attributes.kind).to_string(),
+            should_panic: h.attributes.should_panic,
+            stubs: h
+                .attributes
+                .stubs
+                .iter()
+                .map(|stub| ExportedStub {
+                    original: stub.original.clone(),
+                    replacement: stub.replacement.clone(),
+                })
+                .collect(),
+            verified_stubs: h.attributes.verified_stubs.clone(),
         },
-        "contract":{
-            "contracted_function_name": h.contract.as_ref()
-            .map(|c| c.contracted_function_name.as_str()),
-            "recursion_tracker": h.contract.as_ref()
-            .and_then(|c| c.recursion_tracker.as_ref()),
+        contract: ExportedContract {
+            checked_function: checked_function(h).map(str::to_string),
+            contracted_function_name: h
+                .contract
+                .as_ref()
+                .map(|c| c.contracted_function_name.clone()),
+            recursion_tracker: h.contract.as_ref().and_then(|c| c.recursion_tracker.clone()),
         },
-        "has_loop_contracts": h.has_loop_contracts,
-        "is_automatically_generated": h.is_automatically_generated,
+        has_loop_contracts: h.has_loop_contracts,
+        is_automatically_generated: h.is_automatically_generated,
+        // Automatic harnesses are named after the function they verify.
+        target_function: h.is_automatically_generated.then(|| h.pretty_name.clone()),
+        selected_by: None,
+    }
+}
 
-    })
+fn harness_kind_name(kind: &HarnessKind) -> &'static str {
+    match kind {
+        HarnessKind::Proof => "Proof",
+        HarnessKind::ProofForContract { .. } => "ProofForContract",
+        HarnessKind::Test => "Test",
+    }
+}
+
+/// The function whose contract `harness` checks, if it is a `#[kani::proof_for_contract]`.
+fn checked_function(harness: &HarnessMetadata) -> Option<&str> {
+    match &harness.attributes.kind {
+        HarnessKind::ProofForContract { target_fn } => Some(target_fn),
+        HarnessKind::Proof | HarnessKind::Test => None,
+    }
+}
+
+/// Resolve the contracts that the harnesses of `results` assume with `#[kani::stub_verified]` to
+/// the harnesses of this run that check them. Contracts are matched by the name of their
+/// function, as for `--with-contract-dependencies`.
+pub fn create_contract_dependencies(results: &[HarnessResult]) -> Vec<ExportedContractDependency> {
+    results
+        .iter()
+        .flat_map(|result| {
+            result.harness.attributes.verified_stubs.iter().map(move |function| {
+                let checks: Vec<&HarnessResult> = results
+                    .iter()
+                    .filter(|r| checked_function(r.harness) == Some(function.as_str()))
+                    .collect();
+                let statuses: Vec<VerificationStatus> =
+                    checks.iter().map(|r| r.result.status).collect();
+                let status = if statuses.contains(&VerificationStatus::Failure) {
+                    ContractDependencyStatus::Failed
+                } else if statuses.contains(&VerificationStatus::Success) {
+                    ContractDependencyStatus::Verified
+                } else {
+                    ContractDependencyStatus::NotVerifiedInThisRun
+                };
+                ExportedContractDependency {
+                    harness_id: result.harness.pretty_name.clone(),
+                    function: function.clone(),
+                    checked_by: checks.iter().map(|r| r.harness.pretty_name.clone()).collect(),
+                    status,
+                }
+            })
+        })
+        .collect()
+}
+
+/// Lists the unsupported constructs the compiler found in every crate of the project, by crate and
+/// construct.
+pub fn create_warnings(project: &Project) -> Vec<ExportedWarning> {
+    let mut warnings: Vec<ExportedWarning> = project
+        .metadata
+        .iter()
+        .flat_map(|md| {
+            md.unsupported_features.iter().map(|feature| {
+                let mut locations: Vec<_> = feature.locations.iter().collect();
+                locations.sort_by(|l1, l2| {
+                    (&l1.filename, l1.start_line).cmp(&(&l2.filename, l2.start_line))
+                });
+                ExportedWarning {
+                    crate_name: md.crate_name.clone(),
+                    construct: feature.feature.clone(),
+                    locations: locations
+                        .iter()
+                        .map(|location| format!("{}:{}", location.filename, location.start_line))
+                        .collect(),
+                }
+            })
+        })
+        .collect();
+    warnings
+        .sort_by(|w1, w2| (&w1.crate_name, &w1.construct).cmp(&(&w2.crate_name, &w2.construct)));
+    warnings
 }
 
 /// Creates verification result JSON with harness reference
 /// This reduces duplication between harness metadata and verification results
 pub fn create_verification_result_json(result: &HarnessResult) -> Value {
     // Extract detailed verification results as "checks"
-    let checks = match &result.result.results {
+    let (checks, check_counts, codegen_stats) = match &result.result.results {
         Ok(properties) => {
-            properties.iter().enumerate().map(|(i, prop)| {
-                json!({
-                    "id": i + 1,
-                    "function": prop.property_id.fn_name.as_ref().unwrap_or(&"unknown".to_string()),
-                    "status": format!("{:?}", prop.status),
-                    "description": prop.description,
-                    "location": {
-                        "file": prop.source_location.file.as_ref().unwrap_or(&"unknown".to_string()),
-                        "line": prop.source_location.line.as_ref().unwrap_or(&"unknown".to_string()),
-                        "column": prop.source_location.column.as_ref().unwrap_or(&"unknown".to_string()),
-                    },
-                    "category": prop.property_id.class,
-                })
-            }).collect::<Vec<_>>()
-        },
-        Err(_) => vec![],
+            let checks = properties
+                .iter()
+                .enumerate()
+                .map(|(i, prop)| create_check_json(i + 1, prop))
+                .collect::<Vec<_>>();
+            (checks, create_check_counts_json(properties), create_codegen_stats_json(properties))
+        }
+        Err(_) => (vec![], create_check_counts_json(&[]), create_codegen_stats_json(&[])),
     };
+    // Failed checks whose trace reads uninitialized memory, see `Property::nondet_uninit_step`.
+    let nondet_uninit_reads =
+        checks.iter().filter(|check| check["nondet_uninit_read"] == true).count();
 
     json!({
         "harness_id": result.harness.pretty_name,  // Reference to harness instead of duplicating name
-        "status": match result.result.status {
-            VerificationStatus::Success => "Success",
-            VerificationStatus::Failure => "Failure",
-        },
+        "status": result.result.outcome(),
         "duration_ms": (result.result.runtime.as_millis() as u64),
+        "check_counts": check_counts,
+        "codegen_stats": codegen_stats,
+        "nondet_uninit_reads": nondet_uninit_reads,
+        "auto_unwind": result.result.auto_unwind,
+        // Whether the result was reused from the `--incremental` cache instead of running CBMC.
+        "cached": result.result.cached,
+        "trace_rerun": result.result.trace_rerun,
+        // The outcome of `--synthesize-loop-contracts`, with the synthesized invariants by loop.
+        "loop_contracts": result.result.loop_contracts,
+        // Findings of concrete executions, which are not proofs even when no sample fails.
+        "smoke_test": result.result.smoke_test,
         "checks": checks,
     })
 }
 
+/// Creates the exported check `prop`, the `id`-th check of its harness.
+pub fn create_check_json(id: usize, prop: &Property) -> Value {
+    json!({
+        "id": id,
+        "name": prop.property_name(),
+        "function": prop.property_id.fn_name.as_ref().unwrap_or(&"unknown".to_string()),
+        "status": prop.status,
+        "kind": check_kind(prop),
+        "description": prop.description,
+        "location": {
+            "file": prop.source_location.file.as_ref().unwrap_or(&"unknown".to_string()),
+            "line": prop.source_location.line.as_ref().unwrap_or(&"unknown".to_string()),
+            "column": prop.source_location.column.as_ref().unwrap_or(&"unknown".to_string()),
+            "origin": location_origin(&prop.source_location),
+        },
+        "category": prop.property_id.class,
+        "nondet_uninit_read": prop.nondet_uninit_step().is_some(),
+    })
+}
+
+/// Whether a check comes from the user code or from one of Kani's models.
+fn location_origin(location: &SourceLocation) -> &'static str {
+    if location.is_kani_model() { "kani-model" } else { "user" }
+}
+
+/// Statistics about the code included in a harness.
+/// `model_functions` is the number of distinct Kani model functions that contain a check.
+pub fn create_codegen_stats_json(properties: &[Property]) -> Value {
+    let model_functions: HashSet<_> = properties
+        .iter()
+        .filter(|p| p.source_location.is_kani_model())
+        .filter_map(|p| p.source_location.function.as_ref().or(p.property_id.fn_name.as_ref()))
+        .collect();
+    json!({ "model_functions": model_functions.len() })
+}
+
+/// Distinguish `kani::cover!` checks and coverage checks from regular assertions.
+fn check_kind(prop: &Property) -> &'static str {
+    if prop.is_cover_property() {
+        "cover"
+    } else if prop.is_code_coverage_property() {
+        "code_coverage"
+    } else {
+        "assertion"
+    }
+}
+
+/// Aggregate the number of checks per status.
+/// Cover checks are counted in `satisfied` and `unsatisfiable`, and coverage checks are skipped.
+pub fn create_check_counts_json(properties: &[Property]) -> Value {
+    let count = |status| count_checks(properties, status);
+    json!({
+        "passed": count(CheckStatus::Success),
+        "failed": count(CheckStatus::Failure),
+        "unreachable": count(CheckStatus::Unreachable),
+        "undetermined": count(CheckStatus::Undetermined) + count(CheckStatus::Unknown),
+        "satisfied": count(CheckStatus::Satisfied),
+        "unsatisfiable": count(CheckStatus::Unsatisfiable),
+    })
+}
+
+/// The number of `properties` with `status`, without the coverage checks.
+fn count_checks(properties: &[Property], status: CheckStatus) -> usize {
+    properties.iter().filter(|p| !p.is_code_coverage_property() && p.status == status).count()
+}
+
 /// Creates a verification summary with clean structure
 pub fn create_verification_summary_json(
     results: &[HarnessResult],
     selected: usize,
     status_label: &str,
 ) -> Value {
-    let successful =
-        results.iter().filter(|r| r.result.status == VerificationStatus::Success).count();
-    let failed = results.len() - successful;
+    let count = |status| results.iter().filter(|r| r.result.status == status).count();
+    let successful = count(VerificationStatus::Success);
+    let skipped = count(VerificationStatus::Skipped);
+    let excluded = count(VerificationStatus::Excluded);
+    let failed = count(VerificationStatus::Failure);
+    let timed_out = results.iter().filter(|r| r.result.timed_out).count();
+    let xfail = results.iter().filter(|r| r.result.outcome() == "XFail").count();
+    let xpass = results.iter().filter(|r| r.result.outcome() == "XPass").count();
     let total_duration_ms: u64 = results.iter().map(|r| r.result.runtime.as_millis() as u64).sum();
 
     let verification_results: Vec<_> =
@@ -127,16 +344,110 @@ pub fn create_verification_summary_json(
     json!({
         "summary": {
             "total_harnesses": selected,
-            "executed": results.len(),
+            "executed": results.len() - skipped - excluded,
             "status": status_label,
             "successful": successful,
             "failed": failed,
+            "skipped": skipped,
+            "excluded": excluded,
+            "timed_out": timed_out,
+            "xfail": xfail,
+            "xpass": xpass,
             "duration_ms": total_duration_ms
         },
         "results": verification_results
     })
 }
 
+/// Roll up the results of all harnesses of the run.
+pub fn create_run_summary(results: &[HarnessResult], coverage_enabled: bool) -> ExportedRunSummary {
+    let count = |status| results.iter().filter(|r| r.result.status == status).count();
+    ExportedRunSummary {
+        total_harnesses: results.len(),
+        successes: count(VerificationStatus::Success),
+        failures: count(VerificationStatus::Failure),
+        skipped: count(VerificationStatus::Skipped),
+        excluded: count(VerificationStatus::Excluded),
+        total_runtime_ms: results.iter().map(|r| r.result.runtime.as_millis() as u64).sum(),
+        coverage_enabled,
+    }
+}
+
+/// Creates the coverage information of the run. `output_dir` is `None` if coverage is disabled,
+/// and `harnesses` summarizes the results saved there.
+pub fn create_exported_coverage(
+    enabled: bool,
+    output_dir: Option<&Path>,
+    harnesses: Vec<HarnessCoverage>,
+) -> ExportedCoverage {
+    let timestamp = output_dir.map(|_| {
+        OffsetDateTime::now_utc()
+            .format(&Rfc3339)
+            .unwrap_or_else(|_| "1970-01-01T00:00:00Z".to_string())
+    });
+    ExportedCoverage {
+        enabled,
+        output_dir: output_dir.map(|dir| dir.display().to_string()),
+        timestamp,
+        harnesses,
+    }
+}
+
+/// Creates the information about the whole run, exported with [JsonHandler::set_run_summary].
+/// `results` is `None` if verification was skipped, and `wall_clock` is the time elapsed since
+/// the run started.
+pub fn create_run_info(
+    project: &Project,
+    command_line: Vec<String>,
+    results: Option<&[HarnessResult]>,
+    wall_clock: Duration,
+) -> ExportedRunInfo {
+    let targets: Vec<String> = project.metadata.iter().map(|m| m.crate_name.clone()).collect();
+    let packages = project
+        .cargo_metadata
+        .iter()
+        .flat_map(|metadata| metadata.workspace_packages())
+        .filter(|pkg| pkg.targets.iter().any(|t| targets.contains(&t.name.replace('-', "_"))))
+        .map(|pkg| pkg.name.to_string())
+        .collect();
+
+    let verified = results.unwrap_or_default();
+    let mut status_counts = ExportedStatusCounts::default();
+    for result in verified {
+        match (&result.result.results, result.result.status) {
+            (_, VerificationStatus::Skipped) => status_counts.skipped += 1,
+            (_, VerificationStatus::Excluded) => status_counts.excluded += 1,
+            (_, VerificationStatus::Success) if result.result.expected_failure.is_some() => {
+                status_counts.xpass += 1
+            }
+            _ if result.result.expected_failure.is_some() => status_counts.xfail += 1,
+            _ if result.result.timed_out => status_counts.timeout += 1,
+            (_, VerificationStatus::Success) => status_counts.success += 1,
+            (Ok(_), VerificationStatus::Failure) => status_counts.failure += 1,
+            (Err(ExitStatus::Timeout), _) => status_counts.timeout += 1,
+            (Err(ExitStatus::OutOfMemory), _) => status_counts.out_of_memory += 1,
+            (Err(ExitStatus::Other(_) | ExitStatus::DriverPanic), _) => status_counts.error += 1,
+        }
+    }
+    let solver_times: Vec<f64> =
+        verified.iter().filter_map(|r| r.result.cbmc_stats.as_ref()?.runtime_solver_s).collect();
+
+    let started = OffsetDateTime::now_utc() - wall_clock;
+    ExportedRunInfo {
+        kani_version: KANI_VERSION.to_string(),
+        timestamp: started.format(&Rfc3339).unwrap_or_else(|_| "1970-01-01T00:00:00Z".to_string()),
+        command_line,
+        packages,
+        targets,
+        verification_skipped: results.is_none(),
+        no_harnesses_selected: results.is_some_and(|results| results.is_empty()),
+        total_harnesses: verified.len(),
+        status_counts,
+        wall_clock_ms: wall_clock.as_millis() as u64,
+        cumulative_solver_time_s: (!solver_times.is_empty()).then(|| solver_times.iter().sum()),
+    }
+}
+
 /// Helper function to add verification results to JsonHandler
 /// This utility function encapsulates the logic for adding verification summary to JSON output
 pub fn add_runner_results_to_json(
@@ -147,114 +458,124 @@ pub fn add_runner_results_to_json(
 ) {
     // Use frontend utility to create structured verification summary
     let summary = create_verification_summary_json(results, selected, status_label);
-    handler.add_item("verification_results", summary);
+    handler.add_item(VERIFICATION_RESULTS_KEY, summary);
 }
 
 /// Process harness results and enrich JSON handler with additional metadata.
 /// This function handles the complex harness processing logic, combining verification results
 /// with harness metadata to create enriched JSON output.
+///
+/// The details are computed in a single pass over `harnesses`. The CBMC details are only added
+/// after the error and property details of all harnesses, which keeps the order of the records.
 pub fn process_harness_results(
     handler: &mut JsonHandler,
     harnesses: &[&HarnessMetadata],
     results: &[HarnessResult],
+    cbmc_info: Option<&CbmcInfo>,
+    args: &VerificationArgs,
 ) -> Result<()> {
-    // The main verification results are handled by the harness runner
+    let results_by_name: HashMap<&str, &HarnessResult> =
+        results.iter().map(|r| (r.harness.pretty_name.as_str(), r)).collect();
+    let mut cbmc_runs = Vec::with_capacity(harnesses.len());
     for h in harnesses {
-        let harness_result = results.iter().find(|r| r.harness.pretty_name == h.pretty_name);
-
-        // Add error details for this harness
+        let harness_result = results_by_name.get(h.pretty_name.as_str()).copied();
         if let Some(result) = harness_result {
-            handler.add_item("error_details", match result.result.status {
-                VerificationStatus::Failure => {
-                    json!({
-                        "has_errors": true,
-                        "error_type": match result.result.failed_properties {
-                            crate::call_cbmc::FailedProperties::None => "unknown_failure",
-                            crate::call_cbmc::FailedProperties::PanicsOnly => "assertion_failure",
-                            crate::call_cbmc::FailedProperties::Other => "verification_failure",
-                        },
-                        "failed_properties_type": format!("{:?}", result.result.failed_properties),
-                        "exit_status": match &result.result.results {
-                            Err(crate::call_cbmc::ExitStatus::Timeout) => "timeout".to_string(),
-                            Err(crate::call_cbmc::ExitStatus::OutOfMemory) => "out_of_memory".to_string(),
-                            Err(crate::call_cbmc::ExitStatus::Other(code)) => format!("exit_code_{}", code),
-                            Ok(_) => "properties_failed".to_string()
-                        }
-                    })
+            handler.add_item(ERROR_DETAILS_KEY, create_error_details_json(result));
+            handler.add_harness_detail(PROPERTY_DETAILS_KEY, create_property_details_json(result));
+        }
+        cbmc_runs.push(create_exported_cbmc_run(h, harness_result, cbmc_info, args));
+    }
+    for cbmc_run in cbmc_runs {
+        handler.add_harness_detail(CBMC_KEY, json!(cbmc_run));
+    }
+    Ok(())
+}
+
+/// The error details of one harness, exported under [ERROR_DETAILS_KEY].
+pub fn create_error_details_json(result: &HarnessResult) -> Value {
+    match result.result.status {
+        VerificationStatus::Failure => {
+            json!({
+                "has_errors": true,
+                "error_type": match result.result.failed_properties {
+                    crate::call_cbmc::FailedProperties::None => "unknown_failure",
+                    crate::call_cbmc::FailedProperties::PanicsOnly => "assertion_failure",
+                    crate::call_cbmc::FailedProperties::Other => "verification_failure",
                 },
-                VerificationStatus::Success => json!({
-                    "has_errors": false
-                })
-            });
-
-            // Add property details for this harness
-            handler.add_harness_detail("property_details", json!({
-                "property_details": match &result.result.results {
-                    Ok(properties) => {
-                        let total_properties = properties.len();
-                        let passed_properties = properties.iter().filter(|p| matches!(p.status, crate::cbmc_output_parser::CheckStatus::Success)).count();
-                        let failed_properties = properties.iter().filter(|p| matches!(p.status, crate::cbmc_output_parser::CheckStatus::Failure)).count();
-
-                        json!({
-                            "total_properties": total_properties,
-                            "passed": passed_properties,
-                            "failed": failed_properties,
-                            "unreachable": total_properties - passed_properties - failed_properties
-                        })
-                    },
-                    Err(_) => json!({
-                        "total_properties": 0,
-                        "error": "Could not extract property details due to verification failure"
-                    })
+                "failed_properties_type": format!("{:?}", result.result.failed_properties),
+                "exit_status": match &result.result.results {
+                    _ if result.result.timed_out => "timeout".to_string(),
+                    Err(crate::call_cbmc::ExitStatus::Timeout) => "timeout".to_string(),
+                    Err(crate::call_cbmc::ExitStatus::OutOfMemory) => "out_of_memory".to_string(),
+                    Err(crate::call_cbmc::ExitStatus::Other(code)) => format!("exit_code_{}", code),
+                    Err(crate::call_cbmc::ExitStatus::DriverPanic) => "driver_panic".to_string(),
+                    Ok(_) => "properties_failed".to_string()
                 }
-            }));
+            })
         }
+        VerificationStatus::Success
+        | VerificationStatus::Skipped
+        | VerificationStatus::Excluded => json!({
+            "has_errors": false
+        }),
     }
-
-    Ok(())
 }
 
-pub fn process_cbmc_results(
-    handler: &mut JsonHandler,
-    harnesses: &[&HarnessMetadata],
-    results: &[HarnessResult],
-    session: &KaniSession,
-) -> Result<()> {
-    let cbmc_info_opt = session.get_cbmc_info().ok();
-    for h in harnesses {
-        let harness_result = results.iter().find(|r| r.harness.pretty_name == h.pretty_name);
-        handler.add_harness_detail("cbmc", json!({
-            // basic name for harnesses
-            "harness_id": h.pretty_name,
-
-            // Per-harness CBMC info (key-value pairs) without parsing CBMC stdout
-            "cbmc_metadata": {
-                // Version / OS info (same for all harnesses in a run)
-                "version": cbmc_info_opt.as_ref().map(|i| i.version.clone()),
-                "os_info": cbmc_info_opt.as_ref().map(|i| i.os_info.clone()),
-            },
-            // Configuration passed to CBMC for this harness
-            "configuration": {
-                "object_bits": session.args.cbmc_object_bits(),
-                "solver": h.attributes.solver.as_ref().map(|s| format!("{:?}", s)).unwrap_or_else(|| "Cadical".to_string()),
+/// The property details of one harness, exported under [PROPERTY_DETAILS_KEY].
+pub fn create_property_details_json(result: &HarnessResult) -> Value {
+    json!({
+        "property_details": match &result.result.results {
+            Ok(properties) => {
+                let total_properties = properties.len();
+                let passed_properties = properties.iter().filter(|p| matches!(p.status, crate::cbmc_output_parser::CheckStatus::Success)).count();
+                let failed_properties = properties.iter().filter(|p| matches!(p.status, crate::cbmc_output_parser::CheckStatus::Failure)).count();
+
+                json!({
+                    "total_properties": total_properties,
+                    "passed": passed_properties,
+                    "failed": failed_properties,
+                    "unreachable": total_properties - passed_properties - failed_properties
+                })
             },
+            Err(_) => json!({
+                "total_properties": 0,
+                "error": "Could not extract property details due to verification failure"
+            })
+        }
+    })
+}
 
-            // CBMC execution statistics extracted from messages
-            "cbmc_stats": harness_result.and_then(|r| r.result.cbmc_stats.as_ref()).map(|s| json!({
-            "runtime_symex_s": s.runtime_symex_s,
-            "size_program_expression": s.size_program_expression,
-            "slicing_removed_assignments": s.slicing_removed_assignments,
-            "vccs_generated": s.vccs_generated,
-            "vccs_remaining": s.vccs_remaining,
-            "runtime_postprocess_equation_s": s.runtime_postprocess_equation_s,
-            "runtime_convert_ssa_s": s.runtime_convert_ssa_s,
-            "runtime_post_process_s": s.runtime_post_process_s,
-            "runtime_solver_s": s.runtime_solver_s,
-            "runtime_decision_procedure_s": s.runtime_decision_procedure_s
-            }))
-        }));
+/// Collects the CBMC invocation details for one harness.
+/// Per-harness CBMC info is gathered without parsing CBMC stdout, except for the statistics
+/// that are extracted from CBMC messages and the check counts of the parsed results.
+pub fn create_exported_cbmc_run(
+    h: &HarnessMetadata,
+    harness_result: Option<&HarnessResult>,
+    cbmc_info: Option<&CbmcInfo>,
+    args: &VerificationArgs,
+) -> ExportedCbmcRun {
+    let properties = harness_result.and_then(|r| r.result.results.as_deref().ok());
+    ExportedCbmcRun {
+        // basic name for harnesses
+        harness_id: h.pretty_name.clone(),
+        cbmc_metadata: ExportedCbmcMetadata {
+            version: cbmc_info.map(|i| i.version.clone()),
+            os_info: cbmc_info.map(|i| i.os_info.clone()),
+        },
+        configuration: ExportedCbmcConfiguration {
+            object_bits: args.cbmc_object_bits(),
+            verbosity: resolve_cbmc_verbosity(args),
+            solver: h
+                .attributes
+                .solver
+                .as_ref()
+                .map(|s| format!("{:?}", s))
+                .unwrap_or_else(|| "Cadical".to_string()),
+        },
+        checks_passed: properties.map(|properties| count_checks(properties, CheckStatus::Success)),
+        checks_failed: properties.map(|properties| count_checks(properties, CheckStatus::Failure)),
+        cbmc_stats: harness_result.and_then(|r| r.result.cbmc_stats.clone()),
     }
-    Ok(())
 }
 
 /// Simple container to standardize tool outputs captured during verification
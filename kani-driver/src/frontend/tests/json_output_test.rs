@@ -0,0 +1,74 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+/// Tests for the json_output module
+use super::{mock_harness, mock_property, mock_result};
+use crate::call_cbmc::{ExitStatus, VerificationStatus};
+use crate::cbmc_output_parser::CheckStatus;
+use crate::frontend::json_output::{harness_events, summary_event};
+use crate::harness_runner::HarnessResult;
+use serde_json::Value;
+
+fn parse_lines(output: &str) -> Vec<Value> {
+    output.lines().map(|line| serde_json::from_str(line).unwrap()).collect()
+}
+
+#[test]
+fn test_harness_events() {
+    let failing = mock_harness("check_fail");
+    let checks = vec![
+        mock_property("assertion", 1, CheckStatus::Success),
+        mock_property("assertion", 2, CheckStatus::Failure),
+    ];
+    let failed = HarnessResult {
+        harness: &failing,
+        result: mock_result(VerificationStatus::Failure, Ok(checks)),
+    };
+    let events = parse_lines(&harness_events(&failed));
+    assert_eq!(events.len(), 3);
+
+    let kinds: Vec<_> = events.iter().map(|event| event["event"].as_str().unwrap()).collect();
+    assert_eq!(kinds, ["check", "check", "harness"]);
+    assert_eq!(events[1]["harness_id"], "check_fail");
+    assert_eq!(events[1]["id"], 2);
+    assert_eq!(events[1]["name"], "foo.assertion.2");
+    assert_eq!(events[1]["status"], "FAILURE");
+    assert_eq!(events[1]["location"]["line"], "42");
+
+    assert_eq!(events[2]["harness_id"], "check_fail");
+    assert_eq!(events[2]["status"], "Failure");
+    assert_eq!(events[2]["check_counts"]["failed"], 1);
+    assert!(events[2].get("checks").is_none());
+
+    // Harnesses without checks, e.g. after a timeout, only have a harness event.
+    let timeout = mock_harness("check_timeout");
+    let timed_out = HarnessResult {
+        harness: &timeout,
+        result: mock_result(VerificationStatus::Failure, Err(ExitStatus::Timeout)),
+    };
+    let events = parse_lines(&harness_events(&timed_out));
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["event"], "harness");
+}
+
+#[test]
+fn test_summary_event() {
+    let passing = mock_harness("check_pass");
+    let failing = mock_harness("check_fail");
+    let results = vec![
+        HarnessResult {
+            harness: &passing,
+            result: mock_result(VerificationStatus::Success, Ok(vec![])),
+        },
+        HarnessResult {
+            harness: &failing,
+            result: mock_result(VerificationStatus::Failure, Ok(vec![])),
+        },
+    ];
+    let events = parse_lines(&summary_event(&results, false));
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["event"], "summary");
+    assert_eq!(events[0]["total_harnesses"], 2);
+    assert_eq!(events[0]["successes"], 1);
+    assert_eq!(events[0]["failures"], 1);
+}
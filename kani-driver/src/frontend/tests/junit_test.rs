@@ -0,0 +1,142 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+/// Tests for the junit module
+use super::{mock_harness, mock_property, mock_result};
+use crate::call_cbmc::{ExitStatus, VerificationResult, VerificationStatus};
+use crate::cbmc_output_parser::CheckStatus;
+use crate::frontend::junit::create_junit_report;
+use crate::harness_runner::HarnessResult;
+use std::time::Duration;
+
+/// Check that start and end tags are balanced and return the names of the opened elements in
+/// document order.
+fn check_well_formed(xml: &str) -> Vec<String> {
+    let body = xml.strip_prefix("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n").unwrap();
+    let mut open = vec![];
+    let mut elements = vec![];
+    for tag in body.split('<').skip(1) {
+        let tag = &tag[..tag.find('>').expect("unterminated tag")];
+        if let Some(name) = tag.strip_prefix('/') {
+            assert_eq!(open.pop().as_deref(), Some(name), "mismatched closing tag in {xml}");
+        } else {
+            let name = tag.split_whitespace().next().unwrap().trim_end_matches('/').to_string();
+            elements.push(name.clone());
+            if !tag.ends_with('/') {
+                open.push(name);
+            }
+        }
+    }
+    assert!(open.is_empty(), "unclosed elements {open:?} in {xml}");
+    elements
+}
+
+#[test]
+fn test_junit_report_for_mixed_results() {
+    let passing = mock_harness("proofs::check_pass");
+    let failing = mock_harness("proofs::nested::check_fail");
+    let timeout = mock_harness("check_timeout");
+    let unreachable = mock_harness("proofs::check_unreachable");
+    let mut passed = mock_result(
+        VerificationStatus::Success,
+        Ok(vec![mock_property("assertion", 1, CheckStatus::Success)]),
+    );
+    passed.runtime = Duration::from_millis(1500);
+    let mut failed_check = mock_property("assertion", 2, CheckStatus::Failure);
+    failed_check.description = "assertion failed: a < \"b\" && c".to_string();
+    let results = vec![
+        HarnessResult { harness: &passing, result: passed },
+        HarnessResult {
+            harness: &failing,
+            result: mock_result(
+                VerificationStatus::Failure,
+                Ok(vec![mock_property("assertion", 1, CheckStatus::Success), failed_check]),
+            ),
+        },
+        HarnessResult {
+            harness: &timeout,
+            result: mock_result(VerificationStatus::Failure, Err(ExitStatus::Timeout)),
+        },
+        HarnessResult {
+            harness: &unreachable,
+            result: mock_result(
+                VerificationStatus::Success,
+                Ok(vec![mock_property("assertion", 1, CheckStatus::Unreachable)]),
+            ),
+        },
+    ];
+
+    let xml = create_junit_report(&results);
+    assert_eq!(
+        check_well_formed(&xml),
+        [
+            "testsuites",
+            "testsuite",
+            "testcase",
+            "testcase",
+            "failure",
+            "testcase",
+            "error",
+            "testcase",
+            "skipped"
+        ]
+    );
+    assert!(xml.contains(
+        "<testsuite name=\"kani\" tests=\"4\" failures=\"1\" errors=\"1\" skipped=\"1\" time=\"1.500\">"
+    ));
+    assert!(xml.contains(
+        "<testcase classname=\"sample_crate::proofs\" name=\"proofs::check_pass\" time=\"1.500\"/>"
+    ));
+    assert!(xml.contains(
+        "<testcase classname=\"sample_crate::proofs::nested\" name=\"proofs::nested::check_fail\""
+    ));
+    assert!(xml.contains("<failure message=\"1 of 2 checks failed\">foo.assertion.2: assertion failed: a &lt; &quot;b&quot; &amp;&amp; c\n at src/lib.rs:42:5 in function foo</failure>"));
+    assert!(xml.contains("<testcase classname=\"sample_crate\" name=\"check_timeout\""));
+    assert!(xml.contains("<error message=\"CBMC timed out\"></error>"));
+    assert!(xml.contains("<skipped message=\"All checks are unreachable\"/>"));
+}
+
+#[test]
+fn test_junit_report_without_harnesses() {
+    let xml = create_junit_report(&[]);
+    assert_eq!(check_well_formed(&xml), vec!["testsuites", "testsuite"]);
+    assert!(xml.contains("tests=\"0\" failures=\"0\" errors=\"0\" skipped=\"0\" time=\"0.000\""));
+}
+
+#[test]
+fn test_junit_report_for_expected_failure() {
+    let xfail = mock_harness("proofs::check_xfail");
+    let mut failed = mock_result(
+        VerificationStatus::Failure,
+        Ok(vec![mock_property("assertion", 1, CheckStatus::Failure)]),
+    );
+    failed.expected_failure = Some("known overflow".to_string());
+    let results = vec![HarnessResult { harness: &xfail, result: failed }];
+
+    let xml = create_junit_report(&results);
+    assert_eq!(check_well_formed(&xml), ["testsuites", "testsuite", "testcase", "skipped"]);
+    assert!(xml.contains("failures=\"0\" errors=\"0\" skipped=\"1\""));
+    assert!(xml.contains("<skipped message=\"Expected failure: known overflow\"/>"));
+}
+
+#[test]
+fn test_junit_report_for_fail_fast() {
+    let failing = mock_harness("proofs::check_failing");
+    let skipped = mock_harness("proofs::check_skipped");
+    let results = vec![
+        HarnessResult {
+            harness: &failing,
+            result: mock_result(
+                VerificationStatus::Failure,
+                Ok(vec![mock_property("assertion", 1, CheckStatus::Failure)]),
+            ),
+        },
+        HarnessResult { harness: &skipped, result: VerificationResult::skipped() },
+    ];
+
+    let xml = create_junit_report(&results);
+    assert!(xml.contains("tests=\"2\" failures=\"1\" errors=\"0\" skipped=\"1\""));
+    assert!(
+        xml.contains("<skipped message=\"Not verified because `--fail-fast` stopped the run\"/>")
+    );
+}
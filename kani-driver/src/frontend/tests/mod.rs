@@ -2,8 +2,60 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 //! Tests for the frontend module
-//! This module contains tests for the schema_utils module
-//! and the json_handler module
+//! This module contains tests for the schema_utils module,
+//! the json_handler module, the json_output module, the junit module, the run_diff module and the
+//! sarif module. It also provides the harness and result fixtures that are shared with the tests
+//! of other modules.
 
+use crate::call_cbmc::{ExitStatus, VerificationResult, VerificationStatus};
+use crate::cbmc_output_parser::{CheckStatus, Property, PropertyId, SourceLocation};
+use crate::metadata::tests::mock_proof_harness;
+use kani_metadata::HarnessMetadata;
+
+#[cfg(test)]
+mod json_output_test;
+#[cfg(test)]
+mod junit_test;
+#[cfg(test)]
+mod run_diff_test;
+#[cfg(test)]
+mod sarif_test;
 #[cfg(test)]
 mod schema_utils_test;
+
+/// A proof harness of `sample_crate` declared in `/work/sample/src/proofs.rs`.
+pub fn mock_harness(name: &str) -> HarnessMetadata {
+    let mut harness = mock_proof_harness(name, None, Some("sample_crate"), None);
+    harness.original_file = "/work/sample/src/proofs.rs".to_string();
+    harness.original_start_line = 12;
+    harness.original_end_line = 20;
+    harness
+}
+
+/// A check of function `foo` at `src/lib.rs:42:5`, described as `"{class} {id}"`.
+pub fn mock_property(class: &str, id: u32, status: CheckStatus) -> Property {
+    Property {
+        property_id: PropertyId { id, fn_name: Some("foo".to_string()), class: class.to_string() },
+        status,
+        description: format!("{class} {id}"),
+        source_location: SourceLocation {
+            file: Some("src/lib.rs".to_string()),
+            function: Some("foo".to_string()),
+            line: Some("42".to_string()),
+            column: Some("5".to_string()),
+        },
+        reach: None,
+        trace: None,
+    }
+}
+
+/// A [VerificationResult::mock_success] with the given status and checks.
+pub fn mock_result(
+    status: VerificationStatus,
+    results: Result<Vec<Property>, ExitStatus>,
+) -> VerificationResult {
+    let mut result = VerificationResult::mock_success();
+    result.status = status;
+    result.results = results;
+    result
+}
@@ -0,0 +1,178 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+/// Tests for the run_diff module
+use crate::frontend::run_diff::{RuntimeChange, diff_exports, diff_runs};
+use serde_json::{Value, json};
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+/// A minimal export with the given `(harness, status, duration_ms)` results.
+fn export(schema_version: u64, results: &[(&str, &str, u64)]) -> Value {
+    let results: Vec<_> = results
+        .iter()
+        .map(|(harness, status, duration_ms)| {
+            json!({ "harness_id": harness, "status": status, "duration_ms": duration_ms })
+        })
+        .collect();
+    json!({
+        "schema_version": schema_version,
+        "verification_results": { "summary": {}, "results": results },
+    })
+}
+
+fn write_fixture(dir: &TempDir, name: &str, content: &str) -> PathBuf {
+    let path = dir.path().join(name);
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+#[test]
+fn test_diff_runs() {
+    let dir = TempDir::new().unwrap();
+    let old = export(
+        16,
+        &[
+            ("check_fixed", "Failure", 1000),
+            ("check_broken", "Success", 1000),
+            ("check_slower", "Success", 1000),
+            ("check_faster", "Failure", 1000),
+            ("check_noise", "Success", 10),
+            ("check_removed", "Success", 1000),
+        ],
+    );
+    let new = export(
+        16,
+        &[
+            ("check_fixed", "Success", 1100),
+            ("check_broken", "Failure", 1000),
+            ("check_slower", "Success", 2500),
+            ("check_faster", "Failure", 500),
+            ("check_noise", "Success", 30),
+            ("check_added", "Failure", 1000),
+        ],
+    );
+    let old = write_fixture(&dir, "old.json", &serde_json::to_string_pretty(&old).unwrap());
+    let new = write_fixture(&dir, "new.json", &serde_json::to_string_pretty(&new).unwrap());
+
+    let diff = diff_runs(&old, &new, 20.0).unwrap();
+    assert_eq!(diff.old_schema_version, Some(16));
+    assert_eq!(diff.warning, None);
+    assert_eq!(diff.newly_failing, vec!["check_broken"]);
+    assert_eq!(diff.newly_passing, vec!["check_fixed"]);
+    assert_eq!(diff.only_in_old, vec!["check_removed"]);
+    assert_eq!(diff.only_in_new, vec!["check_added"]);
+    // `check_fixed` is below the threshold and `check_noise` below the minimum change.
+    assert_eq!(
+        diff.runtime_changes,
+        vec![
+            RuntimeChange {
+                harness: "check_faster".to_string(),
+                old_duration_ms: 1000,
+                new_duration_ms: 500,
+                change_percent: Some(-50.0),
+            },
+            RuntimeChange {
+                harness: "check_slower".to_string(),
+                old_duration_ms: 1000,
+                new_duration_ms: 2500,
+                change_percent: Some(150.0),
+            },
+        ]
+    );
+
+    let diff = diff_runs(&old, &new, 200.0).unwrap();
+    assert!(diff.runtime_changes.is_empty());
+
+    // The diff itself is exported as JSON.
+    let diff_json = serde_json::to_value(&diff).unwrap();
+    assert_eq!(diff_json["newly_failing"], json!(["check_broken"]));
+}
+
+#[test]
+fn test_diff_runs_schema_mismatch() {
+    let dir = TempDir::new().unwrap();
+    let old = export(15, &[("check_foo", "Success", 0)]);
+    let new = export(16, &[("check_foo", "Success", 200)]);
+    let old = write_fixture(&dir, "old.json", &old.to_string());
+    let new = write_fixture(&dir, "new.json", &new.to_string());
+
+    let diff = diff_runs(&old, &new, 20.0).unwrap();
+    assert_eq!((diff.old_schema_version, diff.new_schema_version), (Some(15), Some(16)));
+    assert!(diff.warning.unwrap().contains("(15 and 16)"));
+    assert!(diff.newly_failing.is_empty());
+    // The relative change from a zero runtime is unknown.
+    assert_eq!(diff.runtime_changes[0].change_percent, None);
+}
+
+#[test]
+fn test_diff_runs_streaming_export() {
+    let dir = TempDir::new().unwrap();
+    let old = export(16, &[("check_foo", "Success", 100)]);
+    let new = export(16, &[("check_foo", "Failure", 100)]);
+    let records: Vec<_> = ["schema_version", "verification_results"]
+        .iter()
+        .map(|key| json!({ "key": key, "value": new[key] }).to_string())
+        .collect();
+    let old = write_fixture(&dir, "old.json", &old.to_string());
+    let new = write_fixture(&dir, "new.ndjson", &(records.join("\n") + "\n"));
+
+    let diff = diff_runs(&old, &new, 20.0).unwrap();
+    assert_eq!(diff.new_schema_version, Some(16));
+    assert_eq!(diff.newly_failing, vec!["check_foo"]);
+
+    let invalid = write_fixture(&dir, "invalid.json", "[1, 2]\n");
+    assert!(diff_runs(&old, &invalid, 20.0).is_err());
+    assert!(diff_runs(&old, &dir.path().join("missing.json"), 20.0).is_err());
+}
+
+#[test]
+fn test_baseline_regressions() {
+    let baseline = export(
+        16,
+        &[
+            ("check_broken", "Success", 1000),
+            ("check_fixed", "Failure", 1000),
+            ("check_slower", "Success", 1000),
+            ("check_faster", "Success", 1000),
+            ("check_renamed", "Success", 1000),
+        ],
+    );
+    let current = export(
+        20,
+        &[
+            ("check_broken", "Timeout", 1000),
+            ("check_fixed", "Success", 1000),
+            ("check_slower", "Success", 3000),
+            ("check_faster", "Success", 200),
+            ("check_new_name", "Success", 1000),
+        ],
+    );
+
+    let diff = diff_exports(&baseline, &current, 20.0);
+    assert!(diff.warning.is_some());
+    // A renamed harness is removed and added, and a faster one is not a regression.
+    assert_eq!(diff.regressions(), vec!["check_broken", "check_slower"]);
+    let rendered = diff.render();
+    assert!(rendered.contains("Newly failing: check_broken"));
+    assert!(rendered.contains("Added: check_new_name"));
+    assert!(rendered.contains("Removed: check_renamed"));
+    assert!(rendered.contains("Runtime of check_slower: 1000ms -> 3000ms (+200.0%)"));
+
+    let unchanged = diff_exports(&baseline, &baseline, 20.0);
+    assert!(unchanged.regressions().is_empty());
+    assert_eq!(unchanged.render(), "No differences");
+}
+
+#[test]
+fn test_diff_skipped_harnesses() {
+    // Harnesses skipped by `--fail-fast` are neither newly failing nor faster.
+    let old = export(23, &[("check_a", "Success", 1000), ("check_b", "Failure", 1000)]);
+    let new = export(23, &[("check_a", "Skipped", 0), ("check_b", "Skipped", 0)]);
+    let diff = diff_exports(&old, &new, 20.0);
+    assert!(diff.newly_failing.is_empty());
+    assert!(diff.newly_passing.is_empty());
+    assert!(diff.runtime_changes.is_empty());
+    assert!(diff.regressions().is_empty());
+    assert!(diff.only_in_old.is_empty() && diff.only_in_new.is_empty());
+}
@@ -0,0 +1,123 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+/// Tests for the sarif module
+use super::{mock_harness, mock_property, mock_result};
+use crate::call_cbmc::{ExitStatus, VerificationStatus};
+use crate::cbmc_output_parser::{CheckStatus, SourceLocation};
+use crate::frontend::sarif::{SARIF_SCHEMA, create_sarif_log};
+use crate::harness_runner::HarnessResult;
+use serde_json::{Value, json};
+use std::path::Path;
+
+fn sarif(results: &[HarnessResult]) -> Value {
+    json!(create_sarif_log(results, Path::new("/work/sample")))
+}
+
+#[test]
+fn test_sarif_log_for_failed_checks() {
+    let passing = mock_harness("proofs::check_pass");
+    let failing = mock_harness("proofs::check_fail");
+    let timeout = mock_harness("proofs::check_timeout");
+    let mut overflow = mock_property("arithmetic_overflow", 3, CheckStatus::Failure);
+    overflow.source_location.file = Some("/work/sample/src/math.rs".to_string());
+    let mut dereference = mock_property("pointer_dereference", 4, CheckStatus::Failure);
+    dereference.source_location =
+        SourceLocation { file: None, function: None, line: None, column: None };
+    let results = vec![
+        HarnessResult {
+            harness: &passing,
+            result: mock_result(
+                VerificationStatus::Success,
+                Ok(vec![mock_property("assertion", 1, CheckStatus::Success)]),
+            ),
+        },
+        HarnessResult {
+            harness: &failing,
+            result: mock_result(
+                VerificationStatus::Failure,
+                Ok(vec![
+                    mock_property("assertion", 1, CheckStatus::Success),
+                    mock_property("assertion", 2, CheckStatus::Failure),
+                    overflow,
+                    dereference,
+                ]),
+            ),
+        },
+        HarnessResult {
+            harness: &timeout,
+            result: mock_result(VerificationStatus::Failure, Err(ExitStatus::Timeout)),
+        },
+    ];
+
+    let log = sarif(&results);
+    assert_eq!(log["$schema"], SARIF_SCHEMA);
+    assert_eq!(log["version"], "2.1.0");
+    let run = &log["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "Kani");
+
+    // One rule per property class, and one result per failed check.
+    let rules: Vec<_> =
+        run["tool"]["driver"]["rules"].as_array().unwrap().iter().map(|r| &r["id"]).collect();
+    assert_eq!(rules, ["arithmetic_overflow", "assertion", "pointer_dereference"]);
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 3);
+    for result in results {
+        let rule = &run["tool"]["driver"]["rules"][result["ruleIndex"].as_u64().unwrap() as usize];
+        assert_eq!(rule["id"], result["ruleId"]);
+        assert_eq!(result["level"], "error");
+        assert_eq!(result["properties"]["harness"], "proofs::check_fail");
+    }
+
+    assert_eq!(
+        results[0]["message"]["text"],
+        "Check `foo.assertion.2` failed in harness `proofs::check_fail`: assertion 2"
+    );
+    assert_eq!(
+        results[0]["locations"][0],
+        json!({
+            "physicalLocation": {
+                "artifactLocation": { "uri": "src/lib.rs" },
+                "region": { "startLine": 42, "startColumn": 5 }
+            },
+            "logicalLocations": [{ "fullyQualifiedName": "foo", "kind": "function" }]
+        })
+    );
+    // Absolute paths under the base directory are made relative.
+    assert_eq!(
+        results[1]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+        "src/math.rs"
+    );
+    // Checks without a source location are reported at the harness.
+    assert_eq!(
+        results[2]["locations"][0],
+        json!({
+            "physicalLocation": {
+                "artifactLocation": { "uri": "src/proofs.rs" },
+                "region": { "startLine": 12 }
+            }
+        })
+    );
+}
+
+#[test]
+fn test_sarif_log_skips_expected_failures() {
+    let xfail = mock_harness("proofs::check_xfail");
+    let mut check = mock_property("assertion", 1, CheckStatus::Failure);
+    check.source_location.file = Some("/other/src/lib.rs".to_string());
+    let mut expected = mock_result(VerificationStatus::Failure, Ok(vec![check]));
+    let results = vec![HarnessResult { harness: &xfail, result: expected }];
+    let log = sarif(&results);
+    assert_eq!(log["runs"][0]["results"][0]["ruleId"], "assertion");
+    // Files outside of the base directory are referenced by their absolute path.
+    assert_eq!(
+        log["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+        "file:///other/src/lib.rs"
+    );
+
+    expected = results.into_iter().next().unwrap().result;
+    expected.expected_failure = Some(String::new());
+    let log = sarif(&[HarnessResult { harness: &xfail, result: expected }]);
+    assert_eq!(log["runs"][0]["results"], json!([]));
+    assert_eq!(log["runs"][0]["tool"]["driver"]["rules"], json!([]));
+}
@@ -6,12 +6,15 @@
 /// and the json_handler module
 use crate::call_cbmc::{ExitStatus, FailedProperties, VerificationResult, VerificationStatus};
 use crate::cbmc_output_parser::{CheckStatus, Property, PropertyId, SourceLocation};
-use crate::frontend::JsonHandler;
+use crate::error::ErrorCategory;
 use crate::frontend::schema_utils::{
     add_runner_results_to_json, create_harness_metadata_json, create_metadata_json,
     create_project_metadata_json, create_verification_result_json,
     create_verification_summary_json,
 };
+use crate::frontend::{
+    DRIVER_VERSION_KEY, ExportMode, JsonHandler, SCHEMA_VERSION, SCHEMA_VERSION_KEY,
+};
 use crate::harness_runner::HarnessResult;
 use crate::project::Project;
 use kani_metadata::{HarnessAttributes, HarnessKind, HarnessMetadata, KaniMetadata};
@@ -62,7 +65,7 @@ fn test_create_harness_metadata_json() {
         is_automatically_generated: false,
     };
 
-    let json = create_harness_metadata_json(&harness);
+    let json = create_harness_metadata_json(&harness, Some("crate::mod::*".to_string()));
 
     assert_eq!(json["pretty_name"], "crate::mod::my_harness");
     assert_eq!(json["crate_name"], "sample_crate");
@@ -71,6 +74,225 @@ fn test_create_harness_metadata_json() {
     assert_eq!(json["source"]["end_line"], 20);
     assert_eq!(json["has_loop_contracts"], true);
     assert_eq!(json["is_automatically_generated"], false);
+    assert_eq!(json["selected_by"], "crate::mod::*");
+    assert!(json["target_function"].is_null());
+}
+
+/// A harness named `name` of `kind` that assumes the contracts of `verified_stubs`.
+fn contract_harness(name: &str, kind: HarnessKind, verified_stubs: &[&str]) -> HarnessMetadata {
+    let mut attributes = HarnessAttributes::new(kind);
+    attributes.verified_stubs = verified_stubs.iter().map(|f| f.to_string()).collect();
+    HarnessMetadata {
+        pretty_name: name.to_string(),
+        mangled_name: name.to_string(),
+        crate_name: "sample_crate".to_string(),
+        original_file: "src/lib.rs".to_string(),
+        original_start_line: 1,
+        original_end_line: 5,
+        goto_file: None,
+        attributes,
+        contract: None,
+        has_loop_contracts: false,
+        is_automatically_generated: false,
+    }
+}
+
+#[test]
+fn test_harness_stubs_and_contract_json() {
+    let mut harness = contract_harness("check_caller", HarnessKind::Proof, &["crate::checked_div"]);
+    harness.attributes.stubs = vec![kani_metadata::Stub {
+        original: "std::fs::read".to_string(),
+        replacement: "crate::mock_read".to_string(),
+    }];
+    let json = create_harness_metadata_json(&harness, None);
+    assert_eq!(json["attributes"]["kind"], "Proof");
+    assert_eq!(json["attributes"]["stubs"][0]["original"], "std::fs::read");
+    assert_eq!(json["attributes"]["stubs"][0]["replacement"], "crate::mock_read");
+    assert_eq!(json["attributes"]["verified_stubs"][0], "crate::checked_div");
+    assert!(json["contract"]["checked_function"].is_null());
+
+    let target_fn = "crate::checked_div".to_string();
+    let harness = contract_harness("check_div", HarnessKind::ProofForContract { target_fn }, &[]);
+    let json = create_harness_metadata_json(&harness, None);
+    assert_eq!(json["attributes"]["kind"], "ProofForContract");
+    assert_eq!(json["contract"]["checked_function"], "crate::checked_div");
+}
+
+#[test]
+fn test_contract_dependencies() {
+    use crate::frontend::{ContractDependencyStatus, create_contract_dependencies};
+
+    let for_contract = |name, target: &str| {
+        contract_harness(name, HarnessKind::ProofForContract { target_fn: target.into() }, &[])
+    };
+    let caller = contract_harness(
+        "check_caller",
+        HarnessKind::Proof,
+        &["crate::div", "crate::mul", "crate::sub", "crate::add"],
+    );
+    let div = for_contract("check_div", "crate::div");
+    let mul_ok = for_contract("check_mul_small", "crate::mul");
+    let mul_fail = for_contract("check_mul_large", "crate::mul");
+    let sub = for_contract("check_sub", "crate::sub");
+    let result = |harness, status| {
+        let mut result = VerificationResult::mock_success();
+        result.status = status;
+        HarnessResult { harness, result }
+    };
+    let results = vec![
+        result(&caller, VerificationStatus::Success),
+        result(&div, VerificationStatus::Success),
+        result(&mul_ok, VerificationStatus::Success),
+        result(&mul_fail, VerificationStatus::Failure),
+        result(&sub, VerificationStatus::Skipped),
+    ];
+
+    let dependencies = create_contract_dependencies(&results);
+    let summary: Vec<_> = dependencies
+        .iter()
+        .map(|d| (d.harness_id.as_str(), d.function.as_str(), d.checked_by.clone(), d.status))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (
+                "check_caller",
+                "crate::div",
+                vec!["check_div".to_string()],
+                ContractDependencyStatus::Verified
+            ),
+            (
+                "check_caller",
+                "crate::mul",
+                vec!["check_mul_small".to_string(), "check_mul_large".to_string()],
+                ContractDependencyStatus::Failed
+            ),
+            // The checking harness was selected, but skipped by `--fail-fast`.
+            (
+                "check_caller",
+                "crate::sub",
+                vec!["check_sub".to_string()],
+                ContractDependencyStatus::NotVerifiedInThisRun
+            ),
+            // The checking harness was not selected.
+            ("check_caller", "crate::add", vec![], ContractDependencyStatus::NotVerifiedInThisRun),
+        ]
+    );
+    assert_eq!(serde_json::json!(dependencies[3])["status"], "not_verified_in_this_run");
+}
+
+#[test]
+fn test_automatic_harness_target_function() {
+    let harness = HarnessMetadata {
+        pretty_name: "crate::mod::checked_add".to_string(),
+        mangled_name: "mangled::checked_add".to_string(),
+        crate_name: "sample_crate".to_string(),
+        original_file: "src/lib.rs".to_string(),
+        original_start_line: 3,
+        original_end_line: 5,
+        goto_file: None,
+        attributes: HarnessAttributes::new(HarnessKind::Proof),
+        contract: None,
+        has_loop_contracts: false,
+        is_automatically_generated: true,
+    };
+
+    let json = create_harness_metadata_json(&harness, None);
+    assert_eq!(json["is_automatically_generated"], true);
+    assert_eq!(json["target_function"], "crate::mod::checked_add");
+}
+
+#[test]
+fn test_create_skipped_functions() {
+    use crate::frontend::schema_utils::create_skipped_functions;
+    use kani_metadata::{AutoHarnessMetadata, AutoHarnessSkipReason};
+
+    let mut project = Project::default();
+    let metadata = |autoharness_md| KaniMetadata {
+        crate_name: "sample_crate".to_string(),
+        proof_harnesses: vec![],
+        test_harnesses: vec![],
+        unsupported_features: vec![],
+        contracted_functions: vec![],
+        autoharness_md,
+    };
+    project.metadata.push(metadata(None));
+    assert_eq!(create_skipped_functions(&project), None);
+
+    let autoharness_md = AutoHarnessMetadata {
+        chosen: ["lib::add".to_string()].into(),
+        skipped: [
+            ("lib::generic".to_string(), AutoHarnessSkipReason::GenericFn),
+            ("lib::harness".to_string(), AutoHarnessSkipReason::KaniImpl),
+            (
+                "lib::parse".to_string(),
+                AutoHarnessSkipReason::MissingArbitraryImpl(vec![(
+                    "s".to_string(),
+                    "&str".to_string(),
+                )]),
+            ),
+            ("net::connect".to_string(), AutoHarnessSkipReason::UserFilter),
+        ]
+        .into(),
+        arguments: Default::default(),
+    };
+    project.metadata.push(metadata(Some(autoharness_md)));
+
+    let skipped = create_skipped_functions(&project).unwrap();
+    let functions: Vec<_> = skipped.iter().map(|f| f.function.as_str()).collect();
+    assert_eq!(functions, ["lib::generic", "lib::parse", "net::connect"]);
+    assert_eq!(skipped[0].reason, "Generic Function");
+    assert!(skipped[0].missing_arbitrary.is_empty());
+    assert_eq!(skipped[1].reason, "Missing Arbitrary implementation for argument(s)");
+    assert_eq!(skipped[1].missing_arbitrary, ["s: &str"]);
+    assert_eq!(skipped[2].reason, "Did not match provided filters");
+    assert!(skipped.iter().all(|f| f.crate_name == "sample_crate"));
+}
+
+#[test]
+fn test_create_warnings() {
+    use crate::frontend::schema_utils::create_warnings;
+    use crate::frontend::{MODE_KEY, VerificationMode, WARNINGS_KEY};
+    use kani_metadata::{Location, UnsupportedFeature};
+
+    let location = |filename: &str, start_line| Location { filename: filename.into(), start_line };
+    let metadata = |crate_name: &str, unsupported_features| KaniMetadata {
+        crate_name: crate_name.to_string(),
+        proof_harnesses: vec![],
+        test_harnesses: vec![],
+        unsupported_features,
+        contracted_functions: vec![],
+        autoharness_md: None,
+    };
+    let mut project = Project::default();
+    assert!(create_warnings(&project).is_empty());
+
+    project.metadata.push(metadata(
+        "sample_crate",
+        vec![
+            UnsupportedFeature {
+                feature: "foreign function".to_string(),
+                locations: [location("src/lib.rs", 40), location("src/lib.rs", 9)].into(),
+            },
+            UnsupportedFeature {
+                feature: "caller_location".to_string(),
+                locations: [location("src/main.rs", 3)].into(),
+            },
+        ],
+    ));
+    project.metadata.push(metadata("other_crate", vec![]));
+    let warnings = create_warnings(&project);
+    let constructs: Vec<_> = warnings.iter().map(|w| w.construct.as_str()).collect();
+    assert_eq!(constructs, ["caller_location", "foreign function"]);
+    assert_eq!(warnings[1].locations, ["src/lib.rs:9", "src/lib.rs:40"]);
+    assert!(warnings.iter().all(|w| w.crate_name == "sample_crate"));
+
+    let dir = tempfile::tempdir().unwrap();
+    let mut handler = JsonHandler::new(Some(dir.path().join("results.json")));
+    handler.add_item(MODE_KEY, serde_json::json!(VerificationMode::Full));
+    handler.add_item(WARNINGS_KEY, serde_json::json!(warnings));
+    handler.export().unwrap();
+    assert_eq!(handler.validate().unwrap().warnings, warnings);
 }
 
 #[test]
@@ -134,6 +356,13 @@ fn test_create_verification_result_json() {
         generated_concrete_test: false,
         coverage_results: None,
         cbmc_stats: None,
+        auto_unwind: None,
+        smoke_test: None,
+        timed_out: false,
+        trace_rerun: None,
+        expected_failure: None,
+        cached: false,
+        loop_contracts: None,
     };
 
     let harness_result = HarnessResult { harness: &harness, result: verification_result };
@@ -146,6 +375,10 @@ fn test_create_verification_result_json() {
     assert_eq!(json["checks"][0]["function"], "foo");
     assert_eq!(json["checks"][1]["function"], "bar");
     assert_eq!(json["checks"][1]["location"]["file"], "src/main.rs");
+    assert_eq!(json["checks"][1]["status"], "FAILURE");
+    assert_eq!(json["checks"][1]["kind"], "assertion");
+    assert_eq!(json["check_counts"]["passed"], 1);
+    assert_eq!(json["check_counts"]["failed"], 1);
 
     // Optional extra check
     assert!(json["duration_ms"].as_u64().unwrap() >= 100);
@@ -207,6 +440,13 @@ fn test_add_runner_results_to_json_real() {
         generated_concrete_test: false,
         coverage_results: None,
         cbmc_stats: None,
+        auto_unwind: None,
+        smoke_test: None,
+        timed_out: false,
+        trace_rerun: None,
+        expected_failure: None,
+        cached: false,
+        loop_contracts: None,
     };
 
     let harness_result = HarnessResult { harness: &harness, result: verification_result };
@@ -218,3 +458,889 @@ fn test_add_runner_results_to_json_real() {
     assert_eq!(summary["status"], "Failed");
     assert_eq!(summary["failed"], 1);
 }
+
+#[test]
+fn test_summary_with_fail_fast() {
+    let harness = |name: &str| HarnessMetadata {
+        pretty_name: name.into(),
+        mangled_name: name.replace("::", "_"),
+        crate_name: "sample".into(),
+        original_file: "src/lib.rs".into(),
+        original_start_line: 1,
+        original_end_line: 5,
+        goto_file: None,
+        attributes: HarnessAttributes::new(HarnessKind::Proof),
+        contract: None,
+        has_loop_contracts: false,
+        is_automatically_generated: false,
+    };
+    let failing = harness("check_failing");
+    let skipped = harness("check_skipped");
+    let excluded = harness("check_excluded");
+    let mut failure = VerificationResult::mock_success();
+    failure.status = VerificationStatus::Failure;
+    let results = [
+        HarnessResult { harness: &failing, result: failure },
+        HarnessResult { harness: &skipped, result: VerificationResult::skipped() },
+        HarnessResult { harness: &excluded, result: VerificationResult::excluded() },
+    ];
+
+    let json = create_verification_summary_json(&results, 3, "completed_with_fail_fast");
+    assert_eq!(json["summary"]["total_harnesses"], 3);
+    assert_eq!(json["summary"]["executed"], 1);
+    assert_eq!(json["summary"]["failed"], 1);
+    assert_eq!(json["summary"]["skipped"], 1);
+    assert_eq!(json["summary"]["excluded"], 1);
+    // Skipped and excluded harnesses are still listed, so they are not mistaken for missing ones.
+    assert_eq!(json["results"][1]["harness_id"], "check_skipped");
+    assert_eq!(json["results"][1]["status"], "Skipped");
+    assert_eq!(json["results"][2]["harness_id"], "check_excluded");
+    assert_eq!(json["results"][2]["status"], "Excluded");
+    assert_eq!(json["results"][2]["duration_ms"], 0);
+}
+
+#[test]
+fn test_json_handler_streaming_flushes_each_detail() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("results.ndjson");
+    let mut handler = JsonHandler::new_streaming(path.clone()).unwrap();
+
+    // The version records are written first.
+    let read_records = || -> Vec<serde_json::Value> {
+        let content = std::fs::read_to_string(&path).unwrap();
+        content.lines().map(|line| serde_json::from_str(line).unwrap()).collect()
+    };
+    assert_eq!(read_records().len(), 2);
+
+    for i in 0..3 {
+        handler.add_harness_detail("harness_metadata", serde_json::json!({ "id": i }));
+
+        // The file must be readable as NDJSON after every flush.
+        let records = read_records();
+        assert_eq!(records.len(), i + 3);
+        assert_eq!(records[i + 2]["key"], "harness_metadata");
+        assert_eq!(records[i + 2]["value"]["id"], i);
+    }
+
+    // The in-memory view is still maintained, and `export()` does not clobber the stream.
+    assert_eq!(handler.data["harness_metadata"].as_array().unwrap().len(), 3);
+    handler.export().unwrap();
+    assert_eq!(read_records().len(), 5);
+}
+
+/// Export a run with `mode` to `path` in merge mode and return the content of the file.
+fn export_merged_run(path: &std::path::Path, mode: &str) -> Vec<serde_json::Value> {
+    let mut handler =
+        JsonHandler::for_export(Some(path.to_path_buf()), ExportMode::Merge).unwrap().unwrap();
+    handler.add_item("mode", serde_json::json!(mode));
+    handler.export().unwrap();
+    let content = std::fs::read_to_string(path).unwrap();
+    serde_json::from_str::<serde_json::Value>(&content).unwrap().as_array().unwrap().clone()
+}
+
+#[test]
+fn test_json_handler_merge_into_missing_or_empty_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let missing = dir.path().join("missing.json");
+    let runs = export_merged_run(&missing, "full");
+    assert_eq!(runs.len(), 1);
+    assert!(runs[0]["timestamp"].is_string());
+    assert_eq!(runs[0]["run"]["mode"], "full");
+    assert_eq!(runs[0]["run"][SCHEMA_VERSION_KEY], SCHEMA_VERSION);
+
+    let empty = dir.path().join("empty.json");
+    std::fs::write(&empty, "").unwrap();
+    assert_eq!(export_merged_run(&empty, "full").len(), 1);
+}
+
+#[test]
+fn test_json_handler_merge_appends_run() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("runs.json");
+    export_merged_run(&path, "full");
+    export_merged_run(&path, "quick");
+    let runs = export_merged_run(&path, "full");
+    let modes: Vec<_> = runs.iter().map(|run| run["run"]["mode"].as_str().unwrap()).collect();
+    assert_eq!(modes, ["full", "quick", "full"]);
+
+    // A regular export is not an array of runs, so merging into it fails instead of dropping it.
+    let regular = dir.path().join("regular.json");
+    let handler =
+        JsonHandler::for_export(Some(regular.clone()), ExportMode::Buffered).unwrap().unwrap();
+    handler.export().unwrap();
+    let mut handler =
+        JsonHandler::for_export(Some(regular.clone()), ExportMode::Merge).unwrap().unwrap();
+    handler.add_item("mode", serde_json::json!("full"));
+    let err = handler.export().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_json_handler_concurrent_merges_keep_every_run() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("runs.json");
+    std::thread::scope(|scope| {
+        for _ in 0..8 {
+            scope.spawn(|| export_merged_run(&path, "full"));
+        }
+    });
+    let content = std::fs::read_to_string(&path).unwrap();
+    let runs: Vec<serde_json::Value> = serde_json::from_str(&content).unwrap();
+    assert_eq!(runs.len(), 8);
+}
+
+#[test]
+fn test_json_handler_version_fields_on_empty_run() {
+    let handler = JsonHandler::new(None);
+    assert_eq!(handler.data[SCHEMA_VERSION_KEY], SCHEMA_VERSION);
+    assert_eq!(handler.data[DRIVER_VERSION_KEY], crate::version::KANI_VERSION);
+}
+
+#[test]
+fn test_exported_json_lists_failing_check() {
+    let harness = HarnessMetadata {
+        pretty_name: "crate::failing_harness".to_string(),
+        mangled_name: "failing_harness".to_string(),
+        crate_name: "sample_crate".to_string(),
+        original_file: "src/lib.rs".to_string(),
+        original_start_line: 1,
+        original_end_line: 5,
+        goto_file: None,
+        attributes: HarnessAttributes::new(HarnessKind::Proof),
+        contract: None,
+        has_loop_contracts: false,
+        is_automatically_generated: false,
+    };
+    let property = |id, class: &str, status, line: &str| Property {
+        property_id: PropertyId { id, fn_name: Some("check".to_string()), class: class.into() },
+        status,
+        description: format!("{class} {id}"),
+        source_location: SourceLocation {
+            file: Some("src/lib.rs".to_string()),
+            function: Some("check".to_string()),
+            line: Some(line.to_string()),
+            column: Some("5".to_string()),
+        },
+        reach: None,
+        trace: None,
+    };
+    let verification_result = VerificationResult {
+        status: VerificationStatus::Failure,
+        failed_properties: FailedProperties::Other,
+        results: Ok(vec![
+            property(1, "assertion", CheckStatus::Failure, "3"),
+            property(2, "cover", CheckStatus::Satisfied, "4"),
+        ]),
+        runtime: Duration::from_millis(10),
+        generated_concrete_test: false,
+        coverage_results: None,
+        cbmc_stats: None,
+        auto_unwind: None,
+        smoke_test: None,
+        timed_out: false,
+        trace_rerun: None,
+        expected_failure: None,
+        cached: false,
+        loop_contracts: None,
+    };
+    let harness_result = HarnessResult { harness: &harness, result: verification_result };
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("results.json");
+    let mut handler = JsonHandler::new(Some(path.clone()));
+    add_runner_results_to_json(&mut handler, &[harness_result], 1, "completed");
+    handler.export().unwrap();
+
+    let exported: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    let result = &exported["verification_results"]["results"][0];
+    let checks = result["checks"].as_array().unwrap();
+    let failed: Vec<_> = checks.iter().filter(|c| c["status"] == "FAILURE").collect();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0]["kind"], "assertion");
+    assert_eq!(failed[0]["location"]["file"], "src/lib.rs");
+    assert_eq!(failed[0]["location"]["line"], "3");
+    assert_eq!(failed[0]["location"]["origin"], "user");
+    assert_eq!(checks[1]["kind"], "cover");
+    assert_eq!(result["check_counts"]["failed"], 1);
+    assert_eq!(result["check_counts"]["satisfied"], 1);
+    assert_eq!(result["codegen_stats"]["model_functions"], 0);
+}
+
+#[test]
+fn test_create_codegen_stats_json_counts_model_functions() {
+    use crate::frontend::schema_utils::create_codegen_stats_json;
+
+    let property = |id, function: &str, file: &str| Property {
+        property_id: PropertyId {
+            id,
+            fn_name: Some(function.to_string()),
+            class: "assertion".to_string(),
+        },
+        status: CheckStatus::Success,
+        description: String::new(),
+        source_location: SourceLocation {
+            file: Some(file.to_string()),
+            function: Some(function.to_string()),
+            line: Some("1".to_string()),
+            column: None,
+        },
+        reach: None,
+        trace: None,
+    };
+    let models = "/kani/library/kani_core/src/models.rs";
+    let properties = vec![
+        property(1, "size_of_val_raw", models),
+        property(2, "size_of_val_raw", models),
+        property(3, "memcmp", "<builtin-library-memcmp>"),
+        property(4, "check", "src/lib.rs"),
+    ];
+    assert_eq!(create_codegen_stats_json(&properties)["model_functions"], 2);
+    assert_eq!(create_codegen_stats_json(&[])["model_functions"], 0);
+}
+
+#[test]
+fn test_export_round_trips_through_typed_structures() {
+    use crate::args::resolved_config::ResolvedArg;
+    use crate::args_toml::ArgSource;
+    use crate::call_cbmc::CbmcStats;
+    use crate::frontend::schema_utils::{create_exported_cbmc_run, create_exported_harness};
+    use crate::frontend::{
+        CBMC_KEY, COVERAGE_KEY, ExportedCoverage, ExportedSession, ExportedSkippedFunction,
+        HARNESS_METADATA_KEY, INVOCATION_KEY, MODE_KEY, SESSION_KEY, SKIPPED_FUNCTIONS_KEY,
+        VerificationMode,
+    };
+    use clap::Parser;
+
+    // No goto file, no solver attribute, no contract.
+    let harness = HarnessMetadata {
+        pretty_name: "crate::edge_harness".to_string(),
+        mangled_name: "edge_harness".to_string(),
+        crate_name: "sample_crate".to_string(),
+        original_file: "src/lib.rs".to_string(),
+        original_start_line: 1,
+        original_end_line: 3,
+        goto_file: None,
+        attributes: HarnessAttributes::new(HarnessKind::Proof),
+        contract: None,
+        has_loop_contracts: false,
+        is_automatically_generated: false,
+    };
+    let args = crate::args::StandaloneArgs::try_parse_from(["kani", "x.rs"]).unwrap().verify_opts;
+
+    // The harness did not run at all, so there are no CBMC statistics.
+    let not_run = create_exported_cbmc_run(&harness, None, None, &args);
+    assert_eq!(not_run.cbmc_stats, None);
+    assert_eq!(not_run.configuration.solver, "Cadical");
+
+    // The harness ran and CBMC reported only some of the statistics.
+    let verification_result = VerificationResult {
+        status: VerificationStatus::Success,
+        failed_properties: FailedProperties::None,
+        results: Ok(vec![]),
+        runtime: Duration::from_millis(10),
+        generated_concrete_test: false,
+        coverage_results: None,
+        cbmc_stats: Some(CbmcStats { vccs_generated: Some(3), ..Default::default() }),
+        auto_unwind: None,
+        smoke_test: None,
+        timed_out: false,
+        trace_rerun: None,
+        expected_failure: None,
+        cached: false,
+        loop_contracts: None,
+    };
+    let harness_result = HarnessResult { harness: &harness, result: verification_result };
+    let ran = create_exported_cbmc_run(&harness, Some(&harness_result), None, &args);
+
+    let dir = tempfile::tempdir().unwrap();
+    let mut handler = JsonHandler::new(Some(dir.path().join("results.json")));
+    handler.add_item(MODE_KEY, serde_json::json!(VerificationMode::Quick));
+    let session = ExportedSession {
+        config: vec![ResolvedArg {
+            name: "quick".to_string(),
+            value: serde_json::Value::Null,
+            source: ArgSource::Cli,
+        }],
+    };
+    handler.add_item(SESSION_KEY, serde_json::json!(session));
+    handler.add_item(INVOCATION_KEY, serde_json::json!(["cargo", "kani", "--quick"]));
+    let skipped = ExportedSkippedFunction {
+        crate_name: "sample_crate".to_string(),
+        function: "crate::takes_closure".to_string(),
+        reason: "Missing Arbitrary implementation for argument(s)".to_string(),
+        missing_arbitrary: vec!["f: F".to_string()],
+    };
+    handler.add_item(SKIPPED_FUNCTIONS_KEY, serde_json::json!([skipped]));
+    handler.add_harness_detail(
+        HARNESS_METADATA_KEY,
+        serde_json::json!(create_exported_harness(&harness)),
+    );
+    handler.add_harness_detail(CBMC_KEY, serde_json::json!(not_run));
+    handler.add_harness_detail(CBMC_KEY, serde_json::json!(ran));
+    let coverage =
+        ExportedCoverage { enabled: false, output_dir: None, timestamp: None, harnesses: vec![] };
+    handler.add_item(COVERAGE_KEY, serde_json::json!(coverage));
+    handler.export().unwrap();
+
+    let exported = handler.validate().unwrap();
+    assert_eq!(exported.schema_version, SCHEMA_VERSION);
+    assert_eq!(exported.kani_driver_version, crate::version::KANI_VERSION);
+    assert_eq!(exported.mode, VerificationMode::Quick);
+    assert_eq!(exported.session, Some(session));
+    assert_eq!(exported.invocation, ["cargo", "kani", "--quick"]);
+    assert_eq!(exported.skipped_functions, vec![skipped]);
+    assert_eq!(handler.data[MODE_KEY], "quick");
+    assert_eq!(exported.harness_metadata, vec![create_exported_harness(&harness)]);
+    assert_eq!(exported.harness_metadata[0].goto_file, None);
+    assert_eq!(exported.cbmc, vec![not_run, ran]);
+    assert_eq!(exported.cbmc[1].cbmc_stats.as_ref().unwrap().vccs_generated, Some(3));
+    assert_eq!(exported.cbmc[1].cbmc_stats.as_ref().unwrap().runtime_solver_s, None);
+    assert_eq!(exported.coverage, Some(coverage));
+}
+
+#[test]
+fn test_cbmc_check_counts_match_parsed_output() {
+    use crate::cbmc_output_parser::{ParserItem, extract_results};
+    use crate::frontend::CBMC_KEY;
+    use crate::frontend::schema_utils::create_exported_cbmc_run;
+    use clap::Parser;
+
+    let property = |name: &str, status: &str| {
+        format!(
+            r#"{{ "description": "{name}", "property": "check.{name}", "status": "{status}",
+                "sourceLocation": {{ "file": "src/lib.rs", "function": "check", "line": "3" }} }}"#
+        )
+    };
+    let properties = [
+        property("assertion.1", "SUCCESS"),
+        property("assertion.2", "FAILURE"),
+        property("assertion.3", "SUCCESS"),
+        property("arithmetic_overflow.1", "FAILURE"),
+        property("pointer_dereference.1", "SUCCESS"),
+        property("cover.1", "SATISFIED"),
+    ];
+    let output = format!(r#"{{ "result": [{}] }}"#, properties.join(","));
+    let item: ParserItem = serde_json::from_str(&output).unwrap();
+    let (_, results) = extract_results(vec![item]);
+
+    let harness = HarnessMetadata {
+        pretty_name: "crate::multi_check".to_string(),
+        mangled_name: "multi_check".to_string(),
+        crate_name: "sample_crate".to_string(),
+        original_file: "src/lib.rs".to_string(),
+        original_start_line: 1,
+        original_end_line: 5,
+        goto_file: None,
+        attributes: HarnessAttributes::new(HarnessKind::Proof),
+        contract: None,
+        has_loop_contracts: false,
+        is_automatically_generated: false,
+    };
+    let mut verification_result = VerificationResult::mock_success();
+    verification_result.status = VerificationStatus::Failure;
+    verification_result.results = Ok(results.unwrap());
+    let harness_result = HarnessResult { harness: &harness, result: verification_result };
+    let args = crate::args::StandaloneArgs::try_parse_from(["kani", "x.rs"]).unwrap().verify_opts;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("results.json");
+    let mut handler = JsonHandler::new(Some(path.clone()));
+    let cbmc_run = create_exported_cbmc_run(&harness, Some(&harness_result), None, &args);
+    handler.add_harness_detail(CBMC_KEY, serde_json::json!(cbmc_run));
+    handler.export().unwrap();
+
+    let exported: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    let count = |status| properties.iter().filter(|p| p.contains(status)).count();
+    assert_eq!(exported[CBMC_KEY][0]["checks_passed"], count("\"SUCCESS\""));
+    assert_eq!(exported[CBMC_KEY][0]["checks_failed"], count("\"FAILURE\""));
+    assert_eq!(exported[CBMC_KEY][0]["checks_passed"], 3);
+    assert_eq!(exported[CBMC_KEY][0]["checks_failed"], 2);
+
+    // A harness that did not run has no counts.
+    let not_run = create_exported_cbmc_run(&harness, None, None, &args);
+    assert_eq!((not_run.checks_passed, not_run.checks_failed), (None, None));
+}
+
+#[test]
+fn test_run_summary_matches_harness_results() {
+    use crate::frontend::schema_utils::create_run_summary;
+    use crate::frontend::{ExportedRunSummary, MODE_KEY, RUN_SUMMARY_KEY, VerificationMode};
+
+    let harness = |name: &str| HarnessMetadata {
+        pretty_name: name.to_string(),
+        mangled_name: name.to_string(),
+        crate_name: "sample_crate".to_string(),
+        original_file: "src/lib.rs".to_string(),
+        original_start_line: 1,
+        original_end_line: 5,
+        goto_file: None,
+        attributes: HarnessAttributes::new(HarnessKind::Proof),
+        contract: None,
+        has_loop_contracts: false,
+        is_automatically_generated: false,
+    };
+    let result = |status, runtime_ms| VerificationResult {
+        status,
+        failed_properties: FailedProperties::None,
+        results: Ok(vec![]),
+        runtime: Duration::from_millis(runtime_ms),
+        generated_concrete_test: false,
+        coverage_results: None,
+        cbmc_stats: None,
+        auto_unwind: None,
+        smoke_test: None,
+        timed_out: false,
+        trace_rerun: None,
+        expected_failure: None,
+        cached: false,
+        loop_contracts: None,
+    };
+    let passing = harness("crate::passing");
+    let failing = harness("crate::failing");
+    let skipped = harness("crate::skipped");
+    let excluded = harness("crate::excluded");
+    let results = vec![
+        HarnessResult { harness: &passing, result: result(VerificationStatus::Success, 100) },
+        HarnessResult { harness: &failing, result: result(VerificationStatus::Failure, 250) },
+        HarnessResult { harness: &skipped, result: VerificationResult::skipped() },
+        HarnessResult { harness: &excluded, result: VerificationResult::excluded() },
+    ];
+
+    let summary = create_run_summary(&results, true);
+    assert_eq!(
+        summary,
+        ExportedRunSummary {
+            total_harnesses: 4,
+            successes: 1,
+            failures: 1,
+            skipped: 1,
+            excluded: 1,
+            total_runtime_ms: 350,
+            coverage_enabled: true,
+        }
+    );
+
+    let dir = tempfile::tempdir().unwrap();
+    let mut handler = JsonHandler::new(Some(dir.path().join("results.json")));
+    handler.add_item(MODE_KEY, serde_json::json!(VerificationMode::Full));
+    handler.add_item(RUN_SUMMARY_KEY, serde_json::json!(summary));
+    handler.export().unwrap();
+    assert_eq!(handler.validate().unwrap().run_summary, Some(summary));
+}
+
+#[test]
+fn test_run_info_without_verification() {
+    use crate::frontend::schema_utils::create_run_info;
+    use crate::frontend::{ExportedStatusCounts, MODE_KEY, VerificationMode};
+
+    let mut project = Project::default();
+    project.metadata.push(KaniMetadata {
+        crate_name: "sample_crate".to_string(),
+        proof_harnesses: vec![],
+        test_harnesses: vec![],
+        unsupported_features: vec![],
+        contracted_functions: vec![],
+        autoharness_md: None,
+    });
+    let command_line = vec!["kani".to_string(), "src/lib.rs".to_string()];
+
+    let skipped = create_run_info(&project, command_line.clone(), None, Duration::ZERO);
+    assert!(skipped.verification_skipped);
+    assert!(!skipped.no_harnesses_selected);
+    assert_eq!(skipped.targets, vec!["sample_crate".to_string()]);
+    assert!(skipped.packages.is_empty());
+
+    let empty = create_run_info(&project, command_line.clone(), Some(&[]), Duration::from_secs(2));
+    assert!(!empty.verification_skipped);
+    assert!(empty.no_harnesses_selected);
+    assert_eq!(empty.total_harnesses, 0);
+    assert_eq!(empty.status_counts, ExportedStatusCounts::default());
+    assert_eq!(empty.wall_clock_ms, 2000);
+    assert_eq!(empty.cumulative_solver_time_s, None);
+    assert_eq!(empty.command_line, command_line);
+
+    let dir = tempfile::tempdir().unwrap();
+    let mut handler = JsonHandler::new(Some(dir.path().join("results.json")));
+    handler.add_item(MODE_KEY, serde_json::json!(VerificationMode::Full));
+    handler.set_run_summary(&empty);
+    handler.export().unwrap();
+    assert_eq!(handler.validate().unwrap().run, Some(empty));
+}
+
+#[test]
+fn test_verification_result_json_flags_nondet_uninit_reads() {
+    use crate::cbmc_output_parser::TraceItem;
+
+    let harness = HarnessMetadata {
+        pretty_name: "crate::uninit_harness".to_string(),
+        mangled_name: "uninit_harness".to_string(),
+        crate_name: "sample_crate".to_string(),
+        original_file: "src/lib.rs".to_string(),
+        original_start_line: 1,
+        original_end_line: 5,
+        goto_file: None,
+        attributes: HarnessAttributes::new(HarnessKind::Proof),
+        contract: None,
+        has_loop_contracts: false,
+        is_automatically_generated: false,
+    };
+    let location = |line: &str| SourceLocation {
+        file: Some("src/lib.rs".to_string()),
+        function: Some("check".to_string()),
+        line: Some(line.to_string()),
+        column: Some("5".to_string()),
+    };
+    let step = |step_type: &str| TraceItem {
+        step_type: step_type.to_string(),
+        assignment_type: Some("variable".to_string()),
+        hidden: false,
+        lhs: Some("x".to_string()),
+        source_location: Some(location("2")),
+        value: None,
+    };
+    let property = |id, trace| Property {
+        property_id: PropertyId {
+            id,
+            fn_name: Some("check".to_string()),
+            class: "assertion".into(),
+        },
+        status: CheckStatus::Failure,
+        description: format!("assertion {id}"),
+        source_location: location("3"),
+        reach: None,
+        trace: Some(trace),
+    };
+    let verification_result = VerificationResult {
+        status: VerificationStatus::Failure,
+        failed_properties: FailedProperties::Other,
+        results: Ok(vec![
+            property(1, vec![step("declaration"), step("assignment")]),
+            property(2, vec![step("assignment")]),
+        ]),
+        runtime: Duration::from_millis(10),
+        generated_concrete_test: false,
+        coverage_results: None,
+        cbmc_stats: None,
+        auto_unwind: None,
+        smoke_test: None,
+        timed_out: false,
+        trace_rerun: None,
+        expected_failure: None,
+        cached: false,
+        loop_contracts: None,
+    };
+    let harness_result = HarnessResult { harness: &harness, result: verification_result };
+
+    let json = create_verification_result_json(&harness_result);
+    assert_eq!(json["checks"][0]["nondet_uninit_read"], true);
+    assert_eq!(json["checks"][1]["nondet_uninit_read"], false);
+    assert_eq!(json["nondet_uninit_reads"], 1);
+}
+
+#[test]
+fn test_records_are_only_built_for_an_export() {
+    let harnesses: Vec<_> = (0..5000)
+        .map(|i| HarnessMetadata {
+            pretty_name: format!("crate::harness_{i}"),
+            mangled_name: format!("harness_{i}"),
+            crate_name: "sample_crate".to_string(),
+            original_file: "src/lib.rs".to_string(),
+            original_start_line: 1,
+            original_end_line: 5,
+            goto_file: None,
+            attributes: HarnessAttributes::new(HarnessKind::Proof),
+            contract: None,
+            has_loop_contracts: false,
+            is_automatically_generated: false,
+        })
+        .collect();
+    let results: Vec<_> = harnesses
+        .iter()
+        .map(|harness| HarnessResult {
+            harness,
+            result: VerificationResult {
+                status: VerificationStatus::Success,
+                failed_properties: FailedProperties::None,
+                results: Ok(vec![]),
+                runtime: Duration::from_millis(1),
+                generated_concrete_test: false,
+                coverage_results: None,
+                cbmc_stats: None,
+                auto_unwind: None,
+                smoke_test: None,
+                timed_out: false,
+                trace_rerun: None,
+                expected_failure: None,
+                cached: false,
+                loop_contracts: None,
+            },
+        })
+        .collect();
+
+    // Without an export path there is no handler, so there is nothing to build records for.
+    assert!(JsonHandler::for_export(None, ExportMode::Buffered).unwrap().is_none());
+    assert!(JsonHandler::for_export(None, ExportMode::Streaming).unwrap().is_none());
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("results.json");
+    let mut handler = JsonHandler::for_export(Some(path), ExportMode::Buffered).unwrap().unwrap();
+    assert!(!handler.is_streaming());
+    add_runner_results_to_json(&mut handler, &results, results.len(), "completed");
+    let exported = handler.data["verification_results"]["results"].as_array().unwrap();
+    assert_eq!(exported.len(), 5000);
+}
+
+#[test]
+fn test_coverage_summary_per_harness() {
+    use crate::coverage::cov_results::{
+        CoverageCheck, CoverageRegion, CoverageResults, CoverageTerm, FileCoverage, HarnessCoverage,
+    };
+    use crate::frontend::create_exported_coverage;
+    use std::collections::BTreeMap;
+
+    let check = |file: &str, line, status| {
+        CoverageCheck::new(
+            "main".to_string(),
+            CoverageTerm::Counter(0),
+            CoverageRegion { file: file.to_string(), start: (line, 1), end: (line, 10) },
+            status,
+        )
+    };
+    let results = CoverageResults::new(BTreeMap::from([
+        (
+            "src/lib.rs".to_string(),
+            vec![
+                check("src/lib.rs", 1, CheckStatus::Covered),
+                // The same region reported twice counts once, and is covered if either check is.
+                check("src/lib.rs", 2, CheckStatus::Uncovered),
+                check("src/lib.rs", 2, CheckStatus::Covered),
+                check("src/lib.rs", 3, CheckStatus::Uncovered),
+            ],
+        ),
+        ("src/main.rs".to_string(), vec![check("src/main.rs", 1, CheckStatus::Uncovered)]),
+    ]));
+    let files = results.file_summaries();
+    assert_eq!(
+        files,
+        vec![
+            FileCoverage { file: "src/lib.rs".to_string(), covered_regions: 2, total_regions: 3 },
+            FileCoverage { file: "src/main.rs".to_string(), covered_regions: 0, total_regions: 1 },
+        ]
+    );
+
+    // One harness produced coverage results and the other did not.
+    let harnesses = vec![
+        HarnessCoverage { harness: "covered".to_string(), files, error: None },
+        HarnessCoverage {
+            harness: "timed_out".to_string(),
+            files: vec![],
+            error: Some("no coverage results were produced".to_string()),
+        },
+    ];
+    let dir = tempfile::tempdir().unwrap();
+    let coverage = create_exported_coverage(true, Some(dir.path()), harnesses.clone());
+    assert!(coverage.enabled);
+    assert_eq!(coverage.output_dir, Some(dir.path().display().to_string()));
+    assert!(coverage.timestamp.unwrap().contains('T'));
+    assert_eq!(coverage.harnesses, harnesses);
+
+    let disabled = create_exported_coverage(false, None, vec![]);
+    assert_eq!((disabled.output_dir, disabled.timestamp), (None, None));
+}
+
+#[test]
+fn test_partial_export_after_error() {
+    use crate::frontend::schema_utils::create_exported_harness;
+    use crate::frontend::{HARNESS_METADATA_KEY, MODE_KEY, VerificationMode, export_on_error};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("results.json");
+    let mut handler = JsonHandler::new(Some(path.clone()));
+    handler.add_item(MODE_KEY, serde_json::json!(VerificationMode::Full));
+
+    // The first harness is recorded, then the run fails before the second one.
+    let harness = HarnessMetadata {
+        pretty_name: "crate::first_harness".to_string(),
+        mangled_name: "first_harness".to_string(),
+        crate_name: "sample_crate".to_string(),
+        original_file: "src/lib.rs".to_string(),
+        original_start_line: 1,
+        original_end_line: 5,
+        goto_file: None,
+        attributes: HarnessAttributes::new(HarnessKind::Proof),
+        contract: None,
+        has_loop_contracts: false,
+        is_automatically_generated: false,
+    };
+    let run = |handler: &mut JsonHandler| -> anyhow::Result<()> {
+        handler.add_harness_detail(
+            HARNESS_METADATA_KEY,
+            serde_json::json!(create_exported_harness(&harness)),
+        );
+        anyhow::bail!("CBMC could not be started")
+    };
+    let result = run(&mut handler);
+    let err = export_on_error(Some(&mut handler), result).unwrap_err();
+    assert_eq!(err.to_string(), "CBMC could not be started");
+
+    assert!(path.exists());
+    let exported = handler.validate().unwrap();
+    assert_eq!(exported.harness_metadata, vec![create_exported_harness(&harness)]);
+    assert_eq!(exported.run_error.as_deref(), Some("CBMC could not be started"));
+    let error = exported.error.unwrap();
+    assert_eq!(error.category, ErrorCategory::Internal);
+    assert_eq!(error.messages, ["CBMC could not be started"]);
+    assert_eq!(error.command, None);
+
+    // A successful result is passed through without exporting anything.
+    let other = dir.path().join("other.json");
+    let mut handler = JsonHandler::new(Some(other.clone()));
+    assert_eq!(export_on_error(Some(&mut handler), Ok(3)).unwrap(), 3);
+    assert!(!other.exists());
+}
+
+#[test]
+fn test_process_harness_results_matches_two_passes() {
+    use crate::frontend::schema_utils::{
+        create_error_details_json, create_exported_cbmc_run, create_property_details_json,
+        process_harness_results,
+    };
+    use crate::frontend::{CBMC_KEY, ERROR_DETAILS_KEY, PROPERTY_DETAILS_KEY};
+    use clap::Parser;
+
+    let args = crate::args::StandaloneArgs::try_parse_from(["kani", "x.rs"]).unwrap().verify_opts;
+    let harnesses: Vec<HarnessMetadata> = (0..300)
+        .map(|i| HarnessMetadata {
+            pretty_name: format!("check_{i}"),
+            mangled_name: format!("check_{i}_mangled"),
+            crate_name: "sample".into(),
+            original_file: "src/lib.rs".into(),
+            original_start_line: i,
+            original_end_line: i + 5,
+            goto_file: None,
+            attributes: HarnessAttributes::new(HarnessKind::Proof),
+            contract: None,
+            has_loop_contracts: false,
+            is_automatically_generated: false,
+        })
+        .collect();
+    // Every seventh harness has no result, and the results are not in the order of the harnesses.
+    let results: Vec<HarnessResult> = harnesses
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(i, _)| i % 7 != 0)
+        .map(|(i, harness)| {
+            let mut result = VerificationResult::mock_success();
+            result.runtime = Duration::from_millis(i as u64);
+            match i % 5 {
+                1 => {
+                    result.status = VerificationStatus::Failure;
+                    result.failed_properties = FailedProperties::PanicsOnly;
+                }
+                2 => {
+                    result.status = VerificationStatus::Failure;
+                    result.results = Err(ExitStatus::Timeout);
+                    result.timed_out = true;
+                }
+                3 => {
+                    result.status = VerificationStatus::Failure;
+                    result.results = Err(ExitStatus::Other(i as i32));
+                }
+                4 => result = VerificationResult::skipped(),
+                _ => {}
+            }
+            HarnessResult { harness, result }
+        })
+        .collect();
+    let harness_refs: Vec<&HarnessMetadata> = harnesses.iter().collect();
+
+    let dir = tempfile::tempdir().unwrap();
+    let single_path = dir.path().join("single.ndjson");
+    let mut single = JsonHandler::new_streaming(single_path.clone()).unwrap();
+    process_harness_results(&mut single, &harness_refs, &results, None, &args).unwrap();
+
+    // The two passes of the previous implementation.
+    let two_passes_path = dir.path().join("two_passes.ndjson");
+    let mut two_passes = JsonHandler::new_streaming(two_passes_path.clone()).unwrap();
+    for h in &harness_refs {
+        if let Some(result) = results.iter().find(|r| r.harness.pretty_name == h.pretty_name) {
+            two_passes.add_item(ERROR_DETAILS_KEY, create_error_details_json(result));
+            two_passes
+                .add_harness_detail(PROPERTY_DETAILS_KEY, create_property_details_json(result));
+        }
+    }
+    for h in &harness_refs {
+        let result = results.iter().find(|r| r.harness.pretty_name == h.pretty_name);
+        two_passes.add_harness_detail(
+            CBMC_KEY,
+            serde_json::json!(create_exported_cbmc_run(h, result, None, &args)),
+        );
+    }
+
+    assert_eq!(single.data[CBMC_KEY].as_array().unwrap().len(), 300);
+    assert_eq!(
+        serde_json::to_string(&single.data).unwrap(),
+        serde_json::to_string(&two_passes.data).unwrap()
+    );
+    assert_eq!(
+        std::fs::read_to_string(single_path).unwrap(),
+        std::fs::read_to_string(two_passes_path).unwrap()
+    );
+}
+
+#[test]
+fn test_exported_harness_order_is_deterministic() {
+    use crate::frontend::schema_utils::process_harness_results;
+    use crate::frontend::{CBMC_KEY, HARNESS_METADATA_KEY};
+    use crate::metadata::sort_harnesses_by_name;
+    use clap::Parser;
+
+    let args = crate::args::StandaloneArgs::try_parse_from(["kani", "x.rs"]).unwrap().verify_opts;
+    let harnesses: Vec<HarnessMetadata> = ["b::check", "a::check", "check_z", "check_a"]
+        .iter()
+        .enumerate()
+        .map(|(i, name)| HarnessMetadata {
+            pretty_name: name.to_string(),
+            mangled_name: name.replace("::", "_"),
+            crate_name: if i % 2 == 0 { "krate_b" } else { "krate_a" }.into(),
+            original_file: "src/lib.rs".into(),
+            original_start_line: i,
+            original_end_line: i + 5,
+            goto_file: None,
+            attributes: HarnessAttributes::new(HarnessKind::Proof),
+            contract: None,
+            has_loop_contracts: false,
+            is_automatically_generated: false,
+        })
+        .collect();
+    let results: Vec<HarnessResult> = harnesses
+        .iter()
+        .map(|harness| HarnessResult { harness, result: VerificationResult::mock_success() })
+        .collect();
+
+    let export = |mut order: Vec<&HarnessMetadata>| {
+        sort_harnesses_by_name(&mut order);
+        let mut handler = JsonHandler::new(None);
+        for h in &order {
+            handler.add_harness_detail(HARNESS_METADATA_KEY, create_harness_metadata_json(h, None));
+        }
+        process_harness_results(&mut handler, &order, &results, None, &args).unwrap();
+        handler.data
+    };
+    let expected = export(harnesses.iter().collect());
+    let ids: Vec<_> = expected[CBMC_KEY]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|run| run["harness_id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, ["a::check", "check_a", "b::check", "check_z"]);
+    for rotation in 1..harnesses.len() {
+        let mut shuffled: Vec<_> = harnesses.iter().rev().collect();
+        shuffled.rotate_left(rotation);
+        assert_eq!(export(shuffled), expected);
+    }
+}
@@ -1,18 +1,31 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use anyhow::{Error, Result, bail};
+use anyhow::Result;
 use kani_metadata::{ArtifactType, HarnessKind, HarnessMetadata};
 use rayon::prelude::*;
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::Write;
+use std::panic::AssertUnwindSafe;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
 
 use crate::args::{NumThreads, OutputFormat};
-use crate::call_cbmc::{VerificationResult, VerificationStatus};
-use crate::frontend::{JsonHandler, schema_utils::add_runner_results_to_json};
+use crate::call_cbmc::{AutoUnwind, VerificationResult, VerificationStatus, resolve_unwind_value};
+use crate::call_goto_synthesizer::LoopContractSynthesis;
+use crate::error::KaniDriverError;
+use crate::frontend::schema_utils::{add_runner_results_to_json, create_verification_result_json};
+use crate::frontend::{
+    ExportedPlannedCommands, HARNESS_RESULT_KEY, JsonHandler, harness_events, summary_event,
+};
 use crate::project::Project;
-use crate::session::{BUG_REPORT_URL, KaniSession};
+use crate::result_cache;
+use crate::session::{BUG_REPORT_URL, KaniSession, QUICK_UNWIND_DEFAULT};
+use crate::smoke_test::{SmokeTest, SmokeTestStatus};
+use crate::util::render_command;
 
 use std::env::current_dir;
 use std::path::PathBuf;
@@ -35,17 +48,42 @@ pub(crate) struct HarnessResult<'pr> {
     pub result: VerificationResult,
 }
 
-#[derive(Debug)]
-struct FailFastHarnessInfo {
-    pub index_to_failing_harness: usize,
-    pub result: VerificationResult,
+/// Prints the output of each harness in the order of the harnesses, even if they finish out of
+/// order when verified in parallel.
+#[derive(Default)]
+struct OrderedOutput {
+    /// Index of the next harness whose output should be printed.
+    next: usize,
+    /// Output of harnesses that finished before some harness that precedes them.
+    /// `None` if the harness did not produce any output.
+    pending: BTreeMap<usize, Option<String>>,
 }
 
-impl std::error::Error for FailFastHarnessInfo {}
+impl OrderedOutput {
+    /// Record the output of the harness at `index` and print all the output that is now in order.
+    fn push(&mut self, index: usize, output: Option<String>) {
+        self.pending.insert(index, output);
+        while let Some(output) = self.pending.remove(&self.next) {
+            if let Some(output) = output {
+                println!("{output}");
+            }
+            self.next += 1;
+        }
+    }
 
-impl std::fmt::Display for FailFastHarnessInfo {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "harness failed")
+    /// Print the output that is still pending, e.g. because verification stopped early.
+    fn flush(&mut self) {
+        for output in std::mem::take(&mut self.pending).into_values().flatten() {
+            println!("{output}");
+        }
+    }
+}
+
+/// Concatenate the lines of output of a harness, if it has any.
+fn join_output(first: Option<String>, second: Option<String>) -> Option<String> {
+    match (first, second) {
+        (Some(first), Some(second)) => Some(format!("{first}\n{second}")),
+        (first, second) => first.or(second),
     }
 }
 
@@ -58,6 +96,12 @@ pub(crate) fn check_all_harnesses(
         mut json_handler: Option<&mut JsonHandler>,
     ) -> Result<Vec<HarnessResult<'pr>>> {
         let sorted_harnesses = crate::metadata::sort_harnesses_by_loc(harnesses);
+        let verified: Vec<_> = sorted_harnesses
+            .iter()
+            .copied()
+            .filter(|h| !self.sess.is_excluded(h) && self.sess.cached_unsupported(h).is_none())
+            .collect();
+        let smoke_tests = self.sess.smoke_test(self.project, &verified)?;
         let pool = {
             let mut builder = rayon::ThreadPoolBuilder::new();
             match self.sess.args.jobs() {
@@ -73,83 +117,219 @@ pub(crate) fn check_all_harnesses(
             builder.build()?
         };
 
+        // In streaming mode, each harness result is reported to the handler as soon as it is
+        // available instead of only once all harnesses finished.
+        let progress_handler =
+            json_handler.as_deref_mut().filter(|handler| handler.is_streaming()).map(Mutex::new);
+        let output = Mutex::new(OrderedOutput::default());
+
+        // With `--fail-fast`, harnesses that have not started yet when a harness fails the run are
+        // skipped. The harnesses that are already being verified are allowed to finish.
+        let stopped = AtomicBool::new(false);
+        // Harnesses are taken from the schedule one at a time as threads become available, so the
+        // slowest ones start first. Their index is still the declaration order, which is the order
+        // their output is printed in.
+        let schedule = self.sess.harness_schedule(self.project, &sorted_harnesses);
+        let run_start = Instant::now();
         let results = pool.install(|| -> Result<Vec<HarnessResult<'pr>>> {
-            sorted_harnesses
-                .par_iter()
-                .enumerate()
+            schedule
+                .into_iter()
+                .par_bridge()
                 .map(|(idx, harness)| -> Result<HarnessResult<'pr>> {
-                    let goto_file =
-                        self.project.get_harness_artifact(harness, ArtifactType::Goto).unwrap();
-
-                    self.sess.instrument_model(goto_file, goto_file, self.project, harness)?;
-
-                    if self.sess.args.synthesize_loop_contracts {
-                        self.sess.synthesize_loop_contracts(goto_file, goto_file, harness)?;
-                    }
-
-                    let result = self.sess.check_harness(goto_file, harness)?;
-                    if self.sess.args.fail_fast && result.status == VerificationStatus::Failure {
-                        Err(Error::new(FailFastHarnessInfo {
-                            index_to_failing_harness: idx,
-                            result,
-                        }))
+                    let start_time = Instant::now();
+                    let result = if self.sess.is_excluded(harness) {
+                        output.lock().unwrap().push(idx, None);
+                        VerificationResult::excluded()
+                    } else if stopped.load(Ordering::Relaxed)
+                        || self.sess.cached_unsupported(harness).is_some()
+                    {
+                        output.lock().unwrap().push(idx, None);
+                        VerificationResult::skipped()
                     } else {
-                        Ok(HarnessResult { harness, result })
+                        let smoke_test = smoke_tests.get(&harness.pretty_name).cloned();
+                        // A panic while checking one harness should not lose the results of the
+                        // others.
+                        let checked = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                            self.check_harness(idx, harness, smoke_test, &output)
+                        }));
+                        match checked {
+                            Ok(result) => result?,
+                            Err(_) => {
+                                let result = VerificationResult::driver_panic(start_time.elapsed());
+                                let thread_index =
+                                    rayon::current_thread_index().unwrap_or_default();
+                                let rendered = join_output(
+                                    self.sess.checking_message(harness, thread_index),
+                                    self.sess.process_output(&result, harness, thread_index),
+                                );
+                                // The thread that panicked may have poisoned the lock.
+                                output
+                                    .lock()
+                                    .unwrap_or_else(PoisonError::into_inner)
+                                    .push(idx, rendered);
+                                result
+                            }
+                        }
+                    };
+                    if self.sess.args.fail_fast && result.fails_run(self.sess.args.strict_xfail) {
+                        stopped.store(true, Ordering::Relaxed);
+                    }
+                    let harness_result = HarnessResult { harness, result };
+                    if self.sess.args.output_format == OutputFormat::Json {
+                        println!("{}", harness_events(&harness_result));
                     }
+                    if let Some(handler) = &progress_handler {
+                        handler.lock().unwrap().add_harness_detail(
+                            HARNESS_RESULT_KEY,
+                            create_verification_result_json(&harness_result),
+                        );
+                    }
+                    Ok(harness_result)
                 })
                 .collect::<Result<Vec<_>>>()
         });
-        match results {
-            Ok(results) => {
-                if let Some(handler) = json_handler.as_deref_mut() {
-                    add_runner_results_to_json(handler, &results, harnesses.len(), "completed");
+        let elapsed = run_start.elapsed();
+        output.into_inner().unwrap_or_else(PoisonError::into_inner).flush();
+        // The results are reported by harness name, independently of the verification order.
+        let mut results = results?;
+        self.sess.print_parallel_efficiency(&results, elapsed, pool.current_num_threads());
+        results.sort_by(|r1, r2| crate::metadata::cmp_harnesses_by_name(r1.harness, r2.harness));
+        if let Some(handler) = json_handler {
+            let status =
+                if stopped.into_inner() { "completed_with_fail_fast" } else { "completed" };
+            add_runner_results_to_json(handler, &results, harnesses.len(), status);
+        }
+        Ok(results)
+    }
+
+    /// Implement `--dry-run`: build the commands that would verify each harness in `harnesses`,
+    /// without running them. The harnesses are in the order they would be verified in.
+    pub(crate) fn plan_all_harnesses(
+        &self,
+        harnesses: &'pr [&HarnessMetadata],
+    ) -> Result<Vec<ExportedPlannedCommands>> {
+        let sorted_harnesses = crate::metadata::sort_harnesses_by_loc(harnesses);
+        self.sess
+            .harness_schedule(self.project, &sorted_harnesses)
+            .into_iter()
+            .map(|(_, harness)| harness)
+            .filter(|harness| !self.sess.is_excluded(harness))
+            .map(|harness| {
+                let goto_file =
+                    self.project.get_harness_artifact(harness, ArtifactType::Goto).unwrap();
+                let mut commands = self.sess.instrument_model_commands(
+                    goto_file,
+                    goto_file,
+                    self.project,
+                    harness,
+                )?;
+                if self.sess.args.synthesize_loop_contracts {
+                    commands
+                        .push(self.sess.goto_synthesizer_command(goto_file, goto_file, harness)?);
                 }
-                Ok(results)
-            }
-            Err(err) => {
-                if err.is::<FailFastHarnessInfo>() {
-                    let failed = err.downcast::<FailFastHarnessInfo>().unwrap();
-                    let result = vec![HarnessResult {
-                        harness: sorted_harnesses[failed.index_to_failing_harness],
-                        result: failed.result,
-                    }];
-
-                    if let Some(handler) = json_handler {
-                        add_runner_results_to_json(
-                            handler,
-                            &result,
-                            harnesses.len(),
-                            "completed_with_fail_fast",
-                        );
-                    }
+                let mut commands: Vec<String> = commands
+                    .iter()
+                    .map(|cmd| render_command(cmd).to_string_lossy().into_owned())
+                    .collect();
+                let unwind = resolve_unwind_value(&self.sess.args, harness);
+                let cbmc = self.sess.cbmc_command(
+                    goto_file,
+                    harness,
+                    unwind,
+                    self.sess.traced_checks(),
+                )?;
+                commands.push(render_command(cbmc.as_std()).to_string_lossy().into_owned());
+                Ok(ExportedPlannedCommands { harness: harness.pretty_name.clone(), commands })
+            })
+            .collect()
+    }
 
-                    Ok(result)
+    /// Instrument and verify the harness at `index`, printing its output in harness order.
+    /// If its smoke test failed, the harness is only verified with `--smoke-then-verify`.
+    fn check_harness(
+        &self,
+        index: usize,
+        harness: &'pr HarnessMetadata,
+        smoke_test: Option<SmokeTest>,
+        output: &Mutex<OrderedOutput>,
+    ) -> Result<VerificationResult> {
+        if let Some(smoke_test) = &smoke_test
+            && smoke_test.status == SmokeTestStatus::Failure
+            && !self.sess.args.smoke_then_verify
+        {
+            let mut result =
+                VerificationResult::smoke_test_failure(smoke_test.clone(), Duration::ZERO);
+            result.expected_failure =
+                self.sess.args.xfail_reason(&harness.pretty_name).map(str::to_string);
+            let thread_index = rayon::current_thread_index().unwrap_or_default();
+            output
+                .lock()
+                .unwrap()
+                .push(index, self.sess.process_output(&result, harness, thread_index));
+            return Ok(result);
+        }
+        let thread_index = rayon::current_thread_index().unwrap_or_default();
+        let mut checking = self.sess.checking_message(harness, thread_index);
+        // With the old output format, CBMC prints directly to the terminal, so its output cannot be
+        // ordered and the message must precede it.
+        if self.sess.args.output_format == OutputFormat::Old
+            && let Some(checking) = checking.take()
+        {
+            println!("{checking}");
+        }
+        let goto_file = self.project.get_harness_artifact(harness, ArtifactType::Goto).unwrap();
+        let cache_dir = result_cache::cache_dir(&self.sess.args, self.project);
+        let checked = self
+            .sess
+            .instrument_model(goto_file, goto_file, self.project, harness)
+            .and_then(|_| {
+                let loop_contracts = if self.sess.args.synthesize_loop_contracts {
+                    Some(self.sess.synthesize_loop_contracts(goto_file, goto_file, harness)?)
                 } else {
-                    Err(err)
-                }
+                    None
+                };
+                self.sess.check_harness(
+                    goto_file,
+                    harness,
+                    smoke_test,
+                    loop_contracts,
+                    cache_dir.as_deref(),
+                )
+            });
+        match checked {
+            Ok((mut result, rendered)) => {
+                output.lock().unwrap().push(index, join_output(checking, rendered));
+                self.sess.gen_and_add_concrete_playback(harness, &mut result)?;
+                Ok(result)
+            }
+            Err(err) => {
+                output.lock().unwrap().push(index, checking);
+                Err(err)
             }
         }
     }
 }
 
 impl KaniSession {
+    /// Write the output of a harness to a file if requested, and return the output to print.
     fn process_output(
         &self,
         result: &VerificationResult,
         harness: &HarnessMetadata,
         thread_index: usize,
-    ) {
-        if self.should_print_output() {
-            if self.args.output_into_files {
-                self.write_output_to_file(result, harness, thread_index);
-            }
+    ) -> Option<String> {
+        if !self.should_print_output() {
+            return None;
+        }
+        if self.args.output_into_files {
+            self.write_output_to_file(result, harness, thread_index);
+        }
 
-            let output = result.render(&self.args.output_format, harness.attributes.should_panic);
-            if rayon::current_num_threads() > 1 {
-                println!("Thread {thread_index}: {output}");
-            } else {
-                println!("{output}");
-            }
+        let output = result.render(&self.args.output_format, harness.attributes.should_panic);
+        if rayon::current_num_threads() > 1 {
+            Some(format!("Thread {thread_index}: {output}"))
+        } else {
+            Some(output)
         }
     }
 
@@ -186,68 +366,163 @@ fn write_output_to_file(
     }
 
     fn result_output_dir(&self) -> Result<PathBuf> {
-        let target_dir = self.args.target_dir.clone().map_or_else(current_dir, Ok)?;
+        let target_dir = self
+            .args
+            .target_dir
+            .clone()
+            .or_else(|| crate::project_root::get().map(Path::to_path_buf))
+            .map_or_else(current_dir, Ok)?;
         Ok(target_dir.join("result_output_dir")) //Hardcode output to result_output_dir, may want to make it adjustable?
     }
 
-    /// Run the verification process for a single harness
+    /// The message announcing that `harness` is being checked, unless the output is quiet.
+    fn checking_message(&self, harness: &HarnessMetadata, thread_index: usize) -> Option<String> {
+        if self.args.common_args.quiet {
+            return None;
+        }
+        // If the harness is automatically generated, pretty_name refers to the function under verification.
+        let msg = if harness.is_automatically_generated {
+            if matches!(harness.attributes.kind, HarnessKind::Proof) {
+                format!(
+                    "Autoharness: Checking function {} against all possible inputs...",
+                    harness.pretty_name
+                )
+            } else {
+                format!(
+                    "Autoharness: Checking function {}'s contract against all possible inputs...",
+                    harness.pretty_name
+                )
+            }
+        } else {
+            format!("Checking harness {}...", harness.pretty_name)
+        };
+
+        if rayon::current_num_threads() > 1 {
+            Some(format!("Thread {thread_index}: {msg}"))
+        } else {
+            Some(msg)
+        }
+    }
+
+    /// Run the verification process for a single harness.
+    /// With `--incremental`, `cache_dir` holds the results of previous runs, which are reused
+    /// instead of running CBMC if the harness and its flags are unchanged.
+    /// Returns the verification result along with the output that should be printed for it,
+    /// which is printed by the caller so the output of parallel harnesses does not interleave.
     pub(crate) fn check_harness(
         &self,
         binary: &Path,
         harness: &HarnessMetadata,
-    ) -> Result<VerificationResult> {
+        smoke_test: Option<SmokeTest>,
+        loop_contracts: Option<LoopContractSynthesis>,
+        cache_dir: Option<&Path>,
+    ) -> Result<(VerificationResult, Option<String>)> {
         let thread_index = rayon::current_thread_index().unwrap_or_default();
-        if !self.args.common_args.quiet {
-            // If the harness is automatically generated, pretty_name refers to the function under verification.
-            let mut msg = if harness.is_automatically_generated {
-                if matches!(harness.attributes.kind, HarnessKind::Proof) {
-                    format!(
-                        "Autoharness: Checking function {} against all possible inputs...",
-                        harness.pretty_name
-                    )
-                } else {
-                    format!(
-                        "Autoharness: Checking function {}'s contract against all possible inputs...",
-                        harness.pretty_name
-                    )
-                }
-            } else {
-                format!("Checking harness {}...", harness.pretty_name)
-            };
-
-            if rayon::current_num_threads() > 1 {
-                msg = format!("Thread {thread_index}: {msg}");
-            }
-
-            println!("{msg}");
+        let unwind = resolve_unwind_value(&self.args, harness);
+        let cache = match cache_dir {
+            Some(dir) => self.result_cache_key(binary, harness, unwind)?.map(|key| (dir, key)),
+            None => None,
+        };
+        if let Some(mut result) =
+            cache.as_ref().and_then(|(dir, key)| self.cached_result(dir, harness, key))
+        {
+            result.smoke_test = smoke_test;
+            result.loop_contracts = loop_contracts;
+            result.expected_failure =
+                self.args.xfail_reason(&harness.pretty_name).map(str::to_string);
+            let output = self.process_output(&result, harness, thread_index);
+            return Ok((result, output));
         }
 
-        let mut result = self.with_timer(|| self.run_cbmc(binary, harness), "run_cbmc")?;
+        let mut result = self.with_timer(|| self.run_cbmc(binary, harness, unwind), "run_cbmc")?;
+        let mut retries = None;
+        if self.args.auto_unwind
+            && let Some(unwind) = unwind
+        {
+            let retried = self.retry_with_larger_unwind(binary, harness, result, unwind)?;
+            result = retried.0;
+            retries = retried.1;
+        }
+        if let Some((dir, key)) = &cache {
+            self.cache_result(dir, harness, key, &result);
+        }
+        // Produce the traces with the unwind value of the result, which `--auto-unwind` may have raised.
+        let unwind = result.auto_unwind.map_or(unwind, |auto_unwind| Some(auto_unwind.unwind));
+        self.with_timer(
+            || self.add_failure_traces(binary, harness, unwind, &mut result),
+            "add_failure_traces",
+        )?;
+        result.smoke_test = smoke_test;
+        result.loop_contracts = loop_contracts;
+        result.expected_failure = self.args.xfail_reason(&harness.pretty_name).map(str::to_string);
+        let output = self.process_output(&result, harness, thread_index);
+        Ok((result, join_output(retries, output)))
+    }
 
-        self.process_output(&result, harness, thread_index);
-        self.gen_and_add_concrete_playback(harness, &mut result)?;
-        Ok(result)
+    /// Implement `--auto-unwind`: as long as the only failures of the harness are unwinding
+    /// assertions, verify it again with twice the unwind value, up to `--auto-unwind-max`.
+    /// The runtime of the returned result includes all attempts. The result is returned along with
+    /// the messages announcing each retry, unless the output is quiet.
+    fn retry_with_larger_unwind(
+        &self,
+        binary: &Path,
+        harness: &HarnessMetadata,
+        mut result: VerificationResult,
+        mut unwind: u32,
+    ) -> Result<(VerificationResult, Option<String>)> {
+        let cap = self.args.auto_unwind_cap();
+        let mut attempts = 1;
+        let mut retries = None;
+        while result.fails_only_unwinding_assertions() && unwind < cap {
+            unwind = unwind.saturating_mul(2).clamp(1, cap);
+            attempts += 1;
+            if !self.args.common_args.quiet {
+                let retry = format!(
+                    "Unwinding assertions failed for harness {}. Retrying with unwind {unwind}...",
+                    harness.pretty_name
+                );
+                retries = join_output(retries, Some(retry));
+            }
+            let previous_runtime = result.runtime;
+            result =
+                self.with_timer(|| self.run_cbmc(binary, harness, Some(unwind)), "run_cbmc")?;
+            result.runtime += previous_runtime;
+        }
+        let reached_cap = result.fails_only_unwinding_assertions();
+        result.auto_unwind = Some(AutoUnwind { unwind, attempts, reached_cap });
+        Ok((result, retries))
     }
 
-    /// Concludes a session by printing a summary report and exiting the process with an
-    /// error code (if applicable).
+    /// Concludes a session by printing a summary report, and fails with
+    /// [KaniDriverError::VerificationFailed] if any harness failed.
     ///
-    /// Note: Takes `self` "by ownership". This function wants to be able to drop before
-    /// exiting with an error code, if needed.
+    /// Note: Takes `self` "by ownership", so the session is dropped before the process exits.
     pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result<()> {
+        if self.args.output_format == OutputFormat::Json {
+            println!("{}", summary_event(results, self.args.coverage));
+            if results.iter().any(|r| r.result.fails_run(self.args.strict_xfail)) {
+                return Err(KaniDriverError::VerificationFailed.into());
+            }
+            return Ok(());
+        }
         if self.args.common_args.quiet {
             return Ok(());
         }
 
         let (automatic, manual): (Vec<_>, Vec<_>) =
             results.iter().partition(|r| r.harness.is_automatically_generated);
-
+        // The harnesses that `--with-contract-dependencies` added to the selection.
+        let dependencies = manual.iter().filter(|r| self.is_contract_dependency(r.harness)).count();
+
+        // Harnesses skipped by `--fail-fast` or excluded are neither successes nor failures.
+        let (skipped, manual): (Vec<_>, Vec<_>) =
+            manual.into_iter().partition(|r| r.result.status == VerificationStatus::Skipped);
+        let (excluded, manual): (Vec<_>, Vec<_>) =
+            manual.into_iter().partition(|r| r.result.status == VerificationStatus::Excluded);
         let (successes, failures): (Vec<_>, Vec<_>) =
             manual.into_iter().partition(|r| r.result.status == VerificationStatus::Success);
 
-        let succeeding = successes.len();
-        let failing = failures.len();
-        let total = succeeding + failing;
+        let total = successes.len() + failures.len() + skipped.len() + excluded.len();
 
         if self.args.concrete_playback.is_some() {
             if failures.is_empty() {
@@ -261,16 +536,90 @@ pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result
             }
         }
 
+        if self.args.quick {
+            println!(
+                "QUICK CHECK (NOT SOUND): loops were unwound at most {} times and unwinding, \
+                overflow and memory safety checks were disabled. Passing harnesses are not proofs.",
+                self.args.default_unwind.unwrap_or(QUICK_UNWIND_DEFAULT)
+            );
+        }
         println!("Manual Harness Summary:");
 
+        // Harnesses marked with `--xfail` are reported apart from the other results.
+        let (xfailed, failures): (Vec<_>, Vec<_>) =
+            failures.into_iter().partition(|r| r.result.expected_failure.is_some());
+        let (xpassed, successes): (Vec<_>, Vec<_>) =
+            successes.into_iter().partition(|r| r.result.expected_failure.is_some());
+        let succeeding = successes.len();
+        let failing = failures.len();
+
+        for xfail in xfailed.iter() {
+            println!("Expected failure (XFAIL) for - {}", xfail.harness.pretty_name);
+        }
+        for xpass in xpassed.iter() {
+            println!("Unexpected pass (XPASS) for - {}", xpass.harness.pretty_name);
+        }
         for failure in failures.iter() {
-            println!("Verification failed for - {}", failure.harness.pretty_name);
+            match failure.result.auto_unwind {
+                Some(AutoUnwind { unwind, reached_cap: true, .. }) => println!(
+                    "Verification failed for - {} (unwinding assertions fail with unwind {unwind})",
+                    failure.harness.pretty_name
+                ),
+                _ if failure.result.timed_out => {
+                    println!("Verification timed out for - {}", failure.harness.pretty_name)
+                }
+                _ => println!("Verification failed for - {}", failure.harness.pretty_name),
+            }
+        }
+        for skipped in skipped.iter() {
+            println!("Verification skipped for - {}", skipped.harness.pretty_name);
+        }
+        for excluded in excluded.iter() {
+            println!("Verification excluded (EXCLUDED) for - {}", excluded.harness.pretty_name);
+        }
+        for success in successes.iter() {
+            if success.result.cached {
+                println!("Verified {} (cached)", success.harness.pretty_name);
+            } else if let Some(AutoUnwind { unwind, attempts, .. }) = success.result.auto_unwind
+                && attempts > 1
+            {
+                println!(
+                    "Verified {} with unwind {unwind} after {attempts} attempts",
+                    success.harness.pretty_name
+                );
+            }
         }
 
         if total > 0 {
+            let label = if self.args.quick { " (quick check, NOT SOUND)" } else { "" };
+            let xfail_counts = if self.args.xfail.is_empty() {
+                String::new()
+            } else {
+                format!(
+                    " {} expected failures (XFAIL), {} unexpected passes (XPASS),",
+                    xfailed.len(),
+                    xpassed.len()
+                )
+            };
+            let skipped_count = if skipped.is_empty() {
+                String::new()
+            } else {
+                format!(" {} skipped,", skipped.len())
+            };
+            let excluded_count = if excluded.is_empty() {
+                String::new()
+            } else {
+                format!(" {} excluded,", excluded.len())
+            };
+            let cached = successes.iter().filter(|r| r.result.cached).count();
+            let cached_count =
+                if cached == 0 { String::new() } else { format!(" ({cached} cached)") };
             println!(
-                "Complete - {succeeding} successfully verified harnesses, {failing} failures, {total} total."
+                "Complete - {succeeding} successfully verified harnesses{cached_count}, {failing} failures,{xfail_counts}{skipped_count}{excluded_count} {total} total.{label}"
             );
+            if dependencies > 0 {
+                println!("{}", crate::metadata::expansion_message(total - dependencies, total));
+            }
         } else {
             match self.args.harnesses.as_slice() {
                 [] =>
@@ -281,11 +630,8 @@ pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result
                         "No proof harnesses (functions with #[kani::proof]) were found to verify."
                     )
                 }
-                [harness] => {
-                    bail!("no harnesses matched the harness filter: `{harness}`")
-                }
                 harnesses => {
-                    bail!("no harnesses matched the harness filters: `{}`", harnesses.join("`, `"))
+                    return Err(KaniDriverError::no_harnesses_matched(harnesses).into());
                 }
             };
         }
@@ -300,10 +646,10 @@ pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result
             0
         };
 
-        if failing + autoharness_failing > 0 {
+        let strict_xpassing = if self.args.strict_xfail { xpassed.len() } else { 0 };
+        if failing + strict_xpassing + autoharness_failing > 0 {
             // Failure exit code without additional error message
-            drop(self);
-            std::process::exit(1);
+            return Err(KaniDriverError::VerificationFailed.into());
         }
 
         Ok(())
@@ -316,3 +662,35 @@ fn show_coverage_summary(&self) -> Result<()> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_ordered_output() {
+        let mut output = OrderedOutput::default();
+        output.push(2, Some("third".to_string()));
+        output.push(1, None);
+        assert_eq!(output.next, 0);
+        assert_eq!(output.pending.len(), 2);
+
+        // The first harness finished, so all harnesses up to the third one are printed.
+        output.push(0, Some("first".to_string()));
+        assert_eq!(output.next, 3);
+        assert!(output.pending.is_empty());
+
+        output.push(4, Some("fifth".to_string()));
+        output.flush();
+        assert!(output.pending.is_empty());
+    }
+
+    #[test]
+    fn check_join_output() {
+        let line = |s: &str| Some(s.to_string());
+        assert_eq!(join_output(line("Checking"), line("Result")), line("Checking\nResult"));
+        assert_eq!(join_output(line("Checking"), None), line("Checking"));
+        assert_eq!(join_output(None, line("Result")), line("Result"));
+        assert_eq!(join_output(None, None), None);
+    }
+}
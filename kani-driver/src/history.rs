@@ -0,0 +1,267 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! This module implements the local run history used by the `history` subcommand.
+//!
+//! Every verification run of a cargo project appends one summary record to
+//! `<target-dir>/kani/history.jsonl`. The file never leaves the machine and is capped to the
+//! [MAX_HISTORY_RECORDS] most recent runs.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use comfy_table::Table as PrettyTable;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+use crate::InvocationType;
+use crate::args::VerificationArgs;
+use crate::args::history_args::CargoHistoryArgs;
+use crate::call_cbmc::VerificationStatus;
+use crate::harness_runner::HarnessResult;
+use crate::project::Project;
+use crate::session::KaniSession;
+use crate::version::print_kani_version;
+
+/// Maximum number of runs kept in the history file. Older runs are trimmed first.
+const MAX_HISTORY_RECORDS: usize = 500;
+/// Number of runs displayed by `cargo kani history`.
+const DISPLAYED_RECORDS: usize = 20;
+pub const HISTORY_FILENAME: &str = "history.jsonl";
+
+/// Summary of one verification run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunRecord {
+    /// When the run finished, in RFC 3339 format.
+    pub timestamp: String,
+    pub duration_ms: u64,
+    /// The arguments Kani was invoked with.
+    pub flags: Vec<String>,
+    pub successes: usize,
+    pub failures: usize,
+    pub harnesses: Vec<HarnessRecord>,
+    /// The absolute path of the `--export-json` file of the run, if it was exported.
+    #[serde(default)]
+    pub export: Option<PathBuf>,
+}
+
+/// Outcome of one harness in a given run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HarnessRecord {
+    pub name: String,
+    pub status: String,
+    /// How long the harness took to verify, if it was verified.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+}
+
+impl RunRecord {
+    pub fn new(
+        results: &[HarnessResult<'_>],
+        duration: Duration,
+        export: Option<PathBuf>,
+    ) -> RunRecord {
+        let harnesses: Vec<_> = results
+            .iter()
+            .map(|r| HarnessRecord {
+                name: r.harness.pretty_name.clone(),
+                status: r.result.status.to_string(),
+                duration_ms: (!matches!(
+                    r.result.status,
+                    VerificationStatus::Skipped | VerificationStatus::Excluded
+                ))
+                .then_some(r.result.runtime.as_millis() as u64),
+            })
+            .collect();
+        let count = |status| results.iter().filter(|r| r.result.status == status).count();
+        RunRecord {
+            timestamp: OffsetDateTime::now_utc().format(&Rfc3339).unwrap_or_default(),
+            duration_ms: duration.as_millis() as u64,
+            flags: invocation_flags(),
+            successes: count(VerificationStatus::Success),
+            failures: count(VerificationStatus::Failure),
+            harnesses,
+            export,
+        }
+    }
+}
+
+/// The user supplied arguments, without the binary name and the `kani` cargo subcommand.
+fn invocation_flags() -> Vec<String> {
+    let mut args = std::env::args().skip(1).peekable();
+    args.next_if(|arg| arg == "kani");
+    args.collect()
+}
+
+/// Compute the location of the history file for a cargo project.
+/// Returns `None` for standalone projects, which have no natural place to keep a history.
+pub fn history_file(args: &VerificationArgs, project: &Project) -> Option<PathBuf> {
+    let metadata = project.cargo_metadata.as_ref()?;
+    let target_dir =
+        args.target_dir.clone().unwrap_or_else(|| metadata.target_directory.clone().into());
+    Some(target_dir.join("kani").join(HISTORY_FILENAME))
+}
+
+/// Append `record` to the history file, dropping the oldest records if it grew above the cap.
+pub fn append_record(path: &Path, record: &RunRecord) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(record)?)?;
+    drop(file);
+
+    let content = fs::read_to_string(path)?;
+    if let Some(trimmed) = trim_records(&content, MAX_HISTORY_RECORDS) {
+        fs::write(path, trimmed)?;
+    }
+    Ok(())
+}
+
+/// Keep only the last `max` lines of `content`. Returns `None` if nothing needs to be trimmed.
+fn trim_records(content: &str, max: usize) -> Option<String> {
+    let lines: Vec<&str> = content.lines().filter(|line| !line.trim().is_empty()).collect();
+    if lines.len() <= max {
+        return None;
+    }
+    let mut trimmed = lines[lines.len() - max..].join("\n");
+    trimmed.push('\n');
+    Some(trimmed)
+}
+
+/// Read all records from the history file. Lines that cannot be parsed are skipped.
+pub fn read_records(path: &Path) -> Result<Vec<RunRecord>> {
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read run history `{}`", path.display()))?;
+    Ok(content.lines().filter_map(|line| serde_json::from_str(line).ok()).collect())
+}
+
+impl KaniSession {
+    /// Record the results of this run in the project history unless `--no-history` was given.
+    pub fn record_history(
+        &self,
+        project: &Project,
+        results: &[HarnessResult<'_>],
+        duration: Duration,
+    ) -> Result<()> {
+        if self.args.no_history {
+            return Ok(());
+        }
+        let Some(path) = history_file(&self.args, project) else { return Ok(()) };
+        let export = self.args.export_json.as_deref().map(std::path::absolute).transpose()?;
+        append_record(&path, &RunRecord::new(results, duration, export))?;
+        crate::event_log::file_written(&path);
+        Ok(())
+    }
+}
+
+/// Implement `cargo kani history`.
+pub fn history_cargo(args: CargoHistoryArgs, mut verify_opts: VerificationArgs) -> Result<()> {
+    let quiet = args.common_args.quiet;
+    verify_opts.common_args = args.common_args;
+    let session = KaniSession::new(verify_opts)?;
+    if !quiet {
+        print_kani_version(InvocationType::CargoKani(vec![]));
+    }
+
+    let metadata = session.cargo_metadata(env!("TARGET"))?;
+    let target_dir =
+        session.args.target_dir.clone().unwrap_or_else(|| metadata.target_directory.clone().into());
+    let path = target_dir.join("kani").join(HISTORY_FILENAME);
+
+    if args.clear {
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
+        if !quiet {
+            println!("Cleared run history `{}`", path.display());
+        }
+        return Ok(());
+    }
+
+    let records = read_records(&path)?;
+    if records.is_empty() {
+        println!("No run history found for this package.");
+        return Ok(());
+    }
+
+    let mut table = PrettyTable::new();
+    if let Some(harness) = &args.harness {
+        let rows: Vec<_> = records
+            .iter()
+            .filter_map(|record| {
+                let entry = record.harnesses.iter().find(|h| &h.name == harness)?;
+                Some(vec![
+                    record.timestamp.clone(),
+                    format_duration(record.duration_ms),
+                    entry.status.clone(),
+                ])
+            })
+            .collect();
+        if rows.is_empty() {
+            println!("No run history found for harness `{harness}`.");
+            return Ok(());
+        }
+        table
+            .set_header(vec!["Date", "Duration (s)", "Status"])
+            .add_rows(rows[rows.len().saturating_sub(DISPLAYED_RECORDS)..].to_vec());
+    } else {
+        table.set_header(vec!["Date", "Duration (s)", "Successes", "Failures", "Flags"]);
+        for record in &records[records.len().saturating_sub(DISPLAYED_RECORDS)..] {
+            table.add_row(vec![
+                record.timestamp.clone(),
+                format_duration(record.duration_ms),
+                record.successes.to_string(),
+                record.failures.to_string(),
+                record.flags.join(" "),
+            ]);
+        }
+    }
+    println!("{table}");
+    Ok(())
+}
+
+fn format_duration(duration_ms: u64) -> String {
+    format!("{:.3}", duration_ms as f64 / 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_trim_records() {
+        let content = "a\nb\nc\n";
+        assert_eq!(trim_records(content, 3), None);
+        assert_eq!(trim_records(content, 2), Some("b\nc\n".to_string()));
+        assert_eq!(trim_records(content, 1), Some("c\n".to_string()));
+    }
+
+    #[test]
+    fn check_append_and_read_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("kani").join(HISTORY_FILENAME);
+        let record = RunRecord {
+            timestamp: "2025-01-01T00:00:00Z".to_string(),
+            duration_ms: 1500,
+            flags: vec!["--harness".to_string(), "foo".to_string()],
+            successes: 1,
+            failures: 0,
+            harnesses: vec![HarnessRecord {
+                name: "foo".to_string(),
+                status: "SUCCESS".into(),
+                duration_ms: Some(1200),
+            }],
+            export: Some(PathBuf::from("/work/foo/run.json")),
+        };
+        append_record(&path, &record).unwrap();
+        append_record(&path, &record).unwrap();
+        assert_eq!(read_records(&path).unwrap(), vec![record.clone(), record]);
+    }
+}
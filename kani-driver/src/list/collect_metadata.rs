@@ -8,6 +8,7 @@
     InvocationType,
     args::{
         VerificationArgs,
+        common::Verbosity,
         list_args::{CargoListArgs, StandaloneListArgs},
     },
     list::output::output_list_results,
@@ -72,6 +73,7 @@ pub fn process_metadata(metadata: Vec<KaniMetadata>) -> BTreeSet<ListMetadata> {
 
 pub fn list_cargo(args: CargoListArgs, mut verify_opts: VerificationArgs) -> Result<()> {
     let quiet = args.common_args.quiet;
+    let verbose = args.common_args.verbose();
     verify_opts.common_args = args.common_args;
     let mut session = KaniSession::new(verify_opts)?;
     if !quiet {
@@ -81,11 +83,18 @@ pub fn list_cargo(args: CargoListArgs, mut verify_opts: VerificationArgs) -> Res
     let project = cargo_project(&mut session, false)?;
     let list_metadata = process_metadata(project.metadata);
 
-    output_list_results(list_metadata, args.format, quiet)
+    output_list_results(
+        list_metadata,
+        args.format,
+        quiet,
+        verbose,
+        session.args.export_json.as_deref(),
+    )
 }
 
 pub fn list_standalone(args: StandaloneListArgs, mut verify_opts: VerificationArgs) -> Result<()> {
     let quiet = args.common_args.quiet;
+    let verbose = args.common_args.verbose();
     verify_opts.common_args = args.common_args;
     let session = KaniSession::new(verify_opts)?;
     if !quiet {
@@ -100,5 +109,11 @@ pub fn list_standalone(args: StandaloneListArgs, mut verify_opts: VerificationAr
 
     let list_metadata = process_metadata(project.metadata);
 
-    output_list_results(list_metadata, args.format, quiet)
+    output_list_results(
+        list_metadata,
+        args.format,
+        quiet,
+        verbose,
+        session.args.export_json.as_deref(),
+    )
 }
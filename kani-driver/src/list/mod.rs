@@ -13,7 +13,7 @@
 
 /// Metadata for the list subcommand for a given crate.
 /// It is important that crate_name is the first field so that `Ord` orders two ListMetadata objects by crate name.
-#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ListMetadata {
     crate_name: String,
     // Files mapped to their #[kani::proof] harnesses
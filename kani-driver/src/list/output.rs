@@ -17,24 +17,37 @@
 };
 use anyhow::Result;
 use comfy_table::Table as PrettyTable;
+use kani_metadata::ContractClause;
 use serde_json::json;
 use to_markdown_table::MarkdownTable;
 
 // Represents the version of our JSON file format.
 // Increment this version (according to semantic versioning rules) whenever the JSON output format changes.
-const FILE_VERSION: &str = "0.1";
+const FILE_VERSION: &str = "0.2";
 const OUTPUT_FILENAME: &str = "kani-list";
 
 /// Output the results of the list subcommand.
+/// If `verbose` is set, the pretty and Markdown tables also show the clauses of each contract.
+/// The JSON output always contains the clauses.
+/// With `--export-json`, the JSON output is also written to `export_json`, which replaces the
+/// default `kani-list.json` file of the JSON format.
 pub fn output_list_results(
     list_metadata: BTreeSet<ListMetadata>,
     format: Format,
     quiet: bool,
+    verbose: bool,
+    export_json: Option<&Path>,
 ) -> Result<()> {
+    if let Some(path) = export_json {
+        json(list_metadata.clone(), quiet, path)?;
+    }
     match format {
-        Format::Pretty => pretty(list_metadata),
-        Format::Markdown => markdown(list_metadata, quiet),
-        Format::Json => json(list_metadata, quiet),
+        Format::Pretty => pretty(list_metadata, verbose.then_some("\n")),
+        Format::Markdown => markdown(list_metadata, quiet, verbose.then_some("<br>")),
+        Format::Json if export_json.is_some() => Ok(()),
+        Format::Json => {
+            json(list_metadata, quiet, &Path::new(OUTPUT_FILENAME).with_extension("json"))
+        }
     }
 }
 
@@ -50,9 +63,12 @@ fn markdown_constructor(header: Vec<String>, rows: Vec<Vec<String>>) -> Result<M
 
 /// Construct the "Contracts" and "Standard Harnesses" tables.
 /// `table_constructor` is a function that, given the header and rows for the tables, creates a particular kind of table.
+/// If `clause_separator` is set, the contracts table gets a column with the contract clauses,
+/// separated by `clause_separator`.
 fn construct_output<T: Display>(
     list_metadata: BTreeSet<ListMetadata>,
     table_constructor: fn(Vec<String>, Vec<Vec<String>>) -> Result<T>,
+    clause_separator: Option<&str>,
 ) -> Result<(String, String)> {
     let contract_output = {
         const CONTRACTS_SECTION: &str = "Contracts:";
@@ -60,7 +76,7 @@ fn construct_output<T: Display>(
         let contract_table = if list_metadata.iter().all(|md| md.contracted_functions.is_empty()) {
             None
         } else {
-            let (header, rows) = construct_contracts_table(&list_metadata);
+            let (header, rows) = construct_contracts_table(&list_metadata, clause_separator);
             let t = table_constructor(header, rows)?;
             Some(t)
         };
@@ -80,8 +96,9 @@ fn construct_output<T: Display>(
 }
 
 /// Print results to the terminal.
-fn pretty(list_metadata: BTreeSet<ListMetadata>) -> Result<()> {
-    let (contract_output, standard_output) = construct_output(list_metadata, pretty_constructor)?;
+fn pretty(list_metadata: BTreeSet<ListMetadata>, clause_separator: Option<&str>) -> Result<()> {
+    let (contract_output, standard_output) =
+        construct_output(list_metadata, pretty_constructor, clause_separator)?;
     println!("{contract_output}");
     println!("{standard_output}");
 
@@ -89,8 +106,13 @@ fn pretty(list_metadata: BTreeSet<ListMetadata>) -> Result<()> {
 }
 
 /// Output results to a Markdown file.
-fn markdown(list_metadata: BTreeSet<ListMetadata>, quiet: bool) -> Result<()> {
-    let (contract_output, standard_output) = construct_output(list_metadata, markdown_constructor)?;
+fn markdown(
+    list_metadata: BTreeSet<ListMetadata>,
+    quiet: bool,
+    clause_separator: Option<&str>,
+) -> Result<()> {
+    let (contract_output, standard_output) =
+        construct_output(list_metadata, markdown_constructor, clause_separator)?;
 
     let out_path = Path::new(OUTPUT_FILENAME).with_extension("md");
     let mut out_file = File::create(&out_path).unwrap();
@@ -102,10 +124,9 @@ fn markdown(list_metadata: BTreeSet<ListMetadata>, quiet: bool) -> Result<()> {
     Ok(())
 }
 
-/// Output results as a JSON file.
-fn json(list_metadata: BTreeSet<ListMetadata>, quiet: bool) -> Result<()> {
-    let out_path = Path::new(OUTPUT_FILENAME).with_extension("json");
-    let out_file = File::create(&out_path).unwrap();
+/// Output results as a JSON file at `out_path`.
+fn json(list_metadata: BTreeSet<ListMetadata>, quiet: bool, out_path: &Path) -> Result<()> {
+    let out_file = File::create(out_path)?;
     let writer = BufWriter::new(out_file);
 
     let combined_md = merge_list_metadata(list_metadata);
@@ -136,19 +157,24 @@ fn json(list_metadata: BTreeSet<ListMetadata>, quiet: bool) -> Result<()> {
 /// Returns a tuple of the table header and the rows.
 fn construct_contracts_table(
     list_metadata: &BTreeSet<ListMetadata>,
+    clause_separator: Option<&str>,
 ) -> (Vec<String>, Vec<Vec<String>>) {
     const NO_HARNESSES_MSG: &str = "NONE";
     const CRATE_NAME: &str = "Crate";
     const FUNCTION_HEADER: &str = "Function";
     const CONTRACT_HARNESSES_HEADER: &str = "Contract Harnesses (#[kani::proof_for_contract])";
+    const CLAUSES_HEADER: &str = "Clauses";
     const TOTALS_HEADER: &str = "Total";
 
-    let header = vec![
+    let mut header = vec![
         String::new(),
         CRATE_NAME.to_string(),
         FUNCTION_HEADER.to_string(),
         CONTRACT_HARNESSES_HEADER.to_string(),
     ];
+    if clause_separator.is_some() {
+        header.push(CLAUSES_HEADER.to_string());
+    }
 
     let mut rows: Vec<Vec<String>> = vec![];
     let mut functions_under_contract_total = 0;
@@ -162,23 +188,39 @@ fn construct_contracts_table(
             } else {
                 row.push(cf.harnesses.join(", "));
             }
+            if let Some(separator) = clause_separator {
+                row.push(format_clauses(&cf.clauses, separator));
+            }
             rows.push(row);
         }
         functions_under_contract_total += crate_md.contracted_functions.len();
         contract_harnesses_total += crate_md.contract_harnesses_count;
     }
 
-    let totals_row = vec![
+    let mut totals_row = vec![
         TOTALS_HEADER.to_string(),
         String::new(),
         functions_under_contract_total.to_string(),
         contract_harnesses_total.to_string(),
     ];
+    if clause_separator.is_some() {
+        totals_row.push(String::new());
+    }
     rows.push(totals_row);
 
     (header, rows)
 }
 
+/// Format each clause as `<kind>: <text>` on a single line, so multi-line clauses don't break
+/// the table layout, and join them with `separator`.
+fn format_clauses(clauses: &[ContractClause], separator: &str) -> String {
+    clauses
+        .iter()
+        .map(|clause| format!("{}: {}", clause.kind, clause.normalized_text()))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
 fn construct_standard_table(
     list_metadata: &BTreeSet<ListMetadata>,
 ) -> (Vec<String>, Vec<Vec<String>>) {
@@ -1,32 +1,46 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 use std::ffi::OsString;
+use std::path::Path;
 use std::process::ExitCode;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use autoharness::{autoharness_cargo, autoharness_standalone};
-use time::{OffsetDateTime, format_description};
 
-use args::{CargoKaniSubcommand, check_is_valid};
-use args_toml::join_args;
+use args::resolved_config::{ResolvedArg, resolve_config};
+use args::{CargoKaniSubcommand, VerificationArgs, check_is_valid};
+use args_toml::{ArgSource, SourcedArg, join_args};
 
 use crate::args::StandaloneSubcommand;
+use crate::baseline::baseline_cargo;
 use crate::concrete_playback::playback::{playback_cargo, playback_standalone};
+use crate::error::{ErrorCategory, KaniDriverError};
 use crate::frontend::{
-    JsonHandler, create_harness_metadata_json, create_metadata_json, create_project_metadata_json,
-    process_cbmc_results, process_harness_results,
+    BASELINE_DIFF_KEY, CONTRACT_DEPENDENCIES_KEY, COVERAGE_KEY, ExportMode,
+    ExportedPlannedCommands, ExportedSession, HARNESS_METADATA_KEY, INVOCATION_KEY, JsonHandler,
+    METADATA_KEY, MODE_KEY, PLANNED_COMMANDS_KEY, PROJECT_KEY, RUN_SUMMARY_KEY, RunDiff,
+    SESSION_KEY, SKIPPED_FUNCTIONS_KEY, VerificationMode, WARNINGS_KEY, compare_with_baseline,
+    create_contract_dependencies, create_exported_coverage, create_harness_metadata_json,
+    create_metadata_json, create_project_metadata_json, create_run_info, create_run_summary,
+    create_skipped_functions, create_warnings, diff_exported_runs, export_on_error,
+    process_harness_results, write_junit_report, write_sarif_report,
 };
+use crate::history::history_cargo;
 use crate::list::collect_metadata::{list_cargo, list_standalone};
+use crate::new_proof::new_proof_cargo;
 use crate::project::Project;
-use crate::session::KaniSession;
+use crate::session::{BUG_REPORT_URL, KaniSession};
 use crate::version::print_kani_version;
-use clap::Parser;
+use clap::{CommandFactory, Parser};
+use kani_metadata::HarnessMetadata;
 use serde_json::json;
 use tracing::debug;
 
 mod args;
 mod args_toml;
 mod autoharness;
+mod baseline;
 mod call_cargo;
 mod call_cbmc;
 mod call_goto_cc;
@@ -37,13 +51,21 @@
 mod cbmc_property_renderer;
 mod concrete_playback;
 mod coverage;
+mod error;
+mod event_log;
 mod harness_runner;
+mod history;
 mod list;
 mod metadata;
+mod new_proof;
 mod project;
+mod project_root;
+mod result_cache;
+mod schedule;
 
 mod frontend;
 mod session;
+mod smoke_test;
 mod util;
 mod version;
 
@@ -62,8 +84,19 @@ fn main() -> ExitCode {
         // We are using the debug format for now to print the all the context.
         // We should consider creating a standard for error reporting.
         debug!(?error, "main_failure");
-        util::error(&format!("{error:#}"));
-        ExitCode::FAILURE
+        if error::should_report(&error) {
+            util::error(&format!("{error:#}"));
+        }
+        if error::category(&error) == ErrorCategory::Internal
+            && let Some(path) = event_log::path()
+        {
+            eprintln!(
+                "The event log of this run is at `{}`. Please include it when filing a bug report \
+                at {BUG_REPORT_URL}",
+                path.display()
+            );
+        }
+        ExitCode::from(error::exit_code(&error))
     } else {
         ExitCode::SUCCESS
     }
@@ -72,114 +105,388 @@ fn main() -> ExitCode {
 /// The main function for the `cargo kani` command.
 fn cargokani_main(input_args: Vec<OsString>) -> Result<()> {
     let input_args = join_args(input_args)?;
-    let args = args::CargoKaniArgs::parse_from(&input_args);
+    let mut args = args::CargoKaniArgs::parse_from(input_args.iter().map(|arg| &arg.arg));
     check_is_valid(&args);
+    let invocation = Invocation::new::<args::CargoKaniArgs>(&input_args)?;
+    if args.verify_opts.print_config {
+        return invocation.print_config();
+    }
+    // Outside of a cargo project, the build reports the error.
+    if let Ok(root) =
+        project_root::cargo_project_root(args.verify_opts.cargo.manifest_path.as_deref())
+    {
+        project_root::init(root, &mut args.verify_opts);
+    }
 
+    let export_json = args.verify_opts.export_json.clone();
+    let mode = export_mode(&args.verify_opts);
     let mut session = match args.command {
         Some(CargoKaniSubcommand::Autoharness(autoharness_args)) => {
-            return autoharness_cargo(*autoharness_args);
+            return autoharness_cargo(*autoharness_args, invocation);
+        }
+        Some(CargoKaniSubcommand::Baseline(baseline_args)) => {
+            return baseline_cargo(*baseline_args, args.verify_opts);
+        }
+        Some(CargoKaniSubcommand::Diff(diff_args)) => return diff_exported_runs(*diff_args),
+        Some(CargoKaniSubcommand::History(history_args)) => {
+            return history_cargo(*history_args, args.verify_opts);
         }
         Some(CargoKaniSubcommand::List(list_args)) => {
             return list_cargo(*list_args, args.verify_opts);
         }
+        Some(CargoKaniSubcommand::NewProof(new_proof_args)) => {
+            return new_proof_cargo(*new_proof_args, args.verify_opts);
+        }
         Some(CargoKaniSubcommand::Playback(args)) => {
             return playback_cargo(*args);
         }
-        None => session::KaniSession::new(args.verify_opts)?,
+        None => {
+            let session = session::KaniSession::new(args.verify_opts);
+            export_early_error(export_json.as_deref(), mode, &invocation, session)?
+        }
     };
 
     if !session.args.common_args.quiet {
-        print_kani_version(InvocationType::CargoKani(input_args));
+        print_kani_version(InvocationType::CargoKani(
+            input_args.into_iter().map(|arg| arg.arg).collect(),
+        ));
     }
 
-    let project = project::cargo_project(&mut session, false)?;
-    if session.args.only_codegen { Ok(()) } else { verify_project(project, session) }
+    event_log::phase("build");
+    let project = project::cargo_project(&mut session, false);
+    let project = export_early_error(export_json.as_deref(), mode, &invocation, project)?;
+    if session.args.only_codegen {
+        export_skipped_run(&project, &session, invocation, None)
+    } else if session.args.dry_run {
+        dry_run_project(&project, &session, invocation)
+    } else {
+        verify_project(project, session, invocation)
+    }
 }
 
 /// The main function for the `kani` command.
 fn standalone_main() -> Result<()> {
     let args = args::StandaloneArgs::parse();
     check_is_valid(&args);
+    let input_args: Vec<_> =
+        std::env::args_os().map(|arg| SourcedArg::new(arg, ArgSource::Cli)).collect();
+    let invocation = Invocation::new::<args::StandaloneArgs>(&input_args)?;
+    if args.verify_opts.print_config {
+        return invocation.print_config();
+    }
 
     let (session, project) = match args.command {
         Some(StandaloneSubcommand::Autoharness(args)) => {
-            return autoharness_standalone(*args);
+            return autoharness_standalone(*args, invocation);
         }
+        Some(StandaloneSubcommand::Diff(args)) => return diff_exported_runs(*args),
         Some(StandaloneSubcommand::Playback(args)) => return playback_standalone(*args),
         Some(StandaloneSubcommand::List(list_args)) => {
             return list_standalone(*list_args, args.verify_opts);
         }
         Some(StandaloneSubcommand::VerifyStd(args)) => {
-            let session = KaniSession::new(args.verify_opts)?;
+            let export_json = args.verify_opts.export_json.clone();
+            let mode = export_mode(&args.verify_opts);
+            let session = KaniSession::new(args.verify_opts);
+            let session = export_early_error(export_json.as_deref(), mode, &invocation, session)?;
             if !session.args.common_args.quiet {
                 print_kani_version(InvocationType::Standalone);
             }
 
-            let project = project::std_project(&args.std_path, &session)?;
+            event_log::phase("build");
+            let project = project::std_project(&args.std_path, &session);
+            let project = export_early_error(export_json.as_deref(), mode, &invocation, project)?;
             (session, project)
         }
         None => {
-            let session = KaniSession::new(args.verify_opts)?;
+            let input = args.input.unwrap();
+            let mut verify_opts = args.verify_opts;
+            // If the input file does not exist, building the project reports the error.
+            if let Ok(root) = project_root::standalone_project_root(&input) {
+                project_root::init(root, &mut verify_opts);
+            }
+            let export_json = verify_opts.export_json.clone();
+            let mode = export_mode(&verify_opts);
+            let session = KaniSession::new(verify_opts);
+            let session = export_early_error(export_json.as_deref(), mode, &invocation, session)?;
             if !session.args.common_args.quiet {
                 print_kani_version(InvocationType::Standalone);
             }
 
-            let project =
-                project::standalone_project(&args.input.unwrap(), args.crate_name, &session)?;
+            event_log::phase("build");
+            let project = project::standalone_project(&input, args.crate_name, &session);
+            let project = export_early_error(export_json.as_deref(), mode, &invocation, project)?;
             (session, project)
         }
     };
-    if session.args.only_codegen { Ok(()) } else { verify_project(project, session) }
+    if session.args.only_codegen {
+        export_skipped_run(&project, &session, invocation, None)
+    } else if session.args.dry_run {
+        dry_run_project(&project, &session, invocation)
+    } else {
+        verify_project(project, session, invocation)
+    }
+}
+
+/// How Kani was invoked, as recorded in the `--export-json` file.
+struct Invocation {
+    /// The arguments of the driver process, exactly as it was started.
+    argv: Vec<String>,
+    /// The effective command line, including the arguments read from `Cargo.toml`.
+    command_line: Vec<String>,
+    /// The resolved value and source of every argument.
+    config: Vec<ResolvedArg>,
+}
+
+impl Invocation {
+    fn new<T: CommandFactory>(input_args: &[SourcedArg]) -> Result<Invocation> {
+        Ok(Invocation {
+            argv: std::env::args_os().map(|arg| arg.to_string_lossy().into_owned()).collect(),
+            command_line: input_args
+                .iter()
+                .map(|arg| arg.arg.to_string_lossy().into_owned())
+                .collect(),
+            config: resolve_config(T::command(), input_args)?,
+        })
+    }
+
+    /// Implement `--print-config`.
+    fn print_config(&self) -> Result<()> {
+        println!("{}", serde_json::to_string_pretty(&self.config)?);
+        Ok(())
+    }
+}
+
+/// With `--export-json`, export `result` if it is an error that stops the run before any harness
+/// is verified, e.g. a compilation error, so tools can still tell what went wrong. Errors during
+/// verification are exported by [verify_project] along with the results gathered so far.
+fn export_early_error<T>(
+    export_json: Option<&Path>,
+    mode: ExportMode,
+    invocation: &Invocation,
+    result: Result<T>,
+) -> Result<T> {
+    let Some(path) = export_json.filter(|_| result.is_err()) else { return result };
+    match JsonHandler::for_export(Some(path.to_path_buf()), mode) {
+        Ok(mut handler) => {
+            if let Some(handler) = &mut handler {
+                handler.add_item(METADATA_KEY, create_metadata_json());
+                let config = invocation.config.clone();
+                handler.add_item(SESSION_KEY, json!(ExportedSession { config }));
+                handler.add_item(INVOCATION_KEY, json!(invocation.argv));
+            }
+            export_on_error(handler.as_mut(), result)
+        }
+        Err(export_err) => {
+            util::warning(&format!("Failed to export JSON after an error: {export_err}"));
+            result
+        }
+    }
+}
+
+/// Create the handler for the `--export-json` file, if any.
+fn json_handler(session: &KaniSession) -> Result<Option<JsonHandler>> {
+    Ok(JsonHandler::for_export(session.args.export_json.clone(), export_mode(&session.args))?)
+}
+
+/// How the `--export-json` file is written.
+fn export_mode(args: &VerificationArgs) -> ExportMode {
+    ExportMode::from_flags(args.export_json_streaming, args.export_json_merge)
+}
+
+/// With `--only-codegen` and `--dry-run`, still export the project and a run section stating that
+/// verification was skipped, along with the `planned_commands` of `--dry-run`.
+fn export_skipped_run(
+    project: &Project,
+    session: &KaniSession,
+    invocation: Invocation,
+    planned_commands: Option<Vec<ExportedPlannedCommands>>,
+) -> Result<()> {
+    let Some(mut handler) = json_handler(session)? else { return Ok(()) };
+    let mode = if session.args.quick { VerificationMode::Quick } else { VerificationMode::Full };
+    handler.add_item(MODE_KEY, json!(mode));
+    handler.add_item(METADATA_KEY, create_metadata_json());
+    handler.add_item(PROJECT_KEY, create_project_metadata_json(project));
+    handler.add_item(SESSION_KEY, json!(ExportedSession { config: invocation.config }));
+    handler.add_item(INVOCATION_KEY, json!(invocation.argv));
+    if let Some(skipped) = create_skipped_functions(project) {
+        handler.add_item(SKIPPED_FUNCTIONS_KEY, json!(skipped));
+    }
+    handler.add_item(WARNINGS_KEY, json!(create_warnings(project)));
+    if let Some(planned_commands) = planned_commands {
+        handler.add_item(PLANNED_COMMANDS_KEY, json!(planned_commands));
+    }
+    handler.set_run_summary(&create_run_info(
+        project,
+        invocation.command_line,
+        None,
+        Duration::ZERO,
+    ));
+    Ok(handler.export()?)
+}
+
+/// Implement `--dry-run`: print the commands that would verify the selected harnesses instead of
+/// running them.
+fn dry_run_project(project: &Project, session: &KaniSession, invocation: Invocation) -> Result<()> {
+    event_log::phase("plan");
+    let harnesses = session.determine_targets(project);
+    let export_json = session.args.export_json.as_deref();
+    let mode = export_mode(&session.args);
+    let harnesses = export_early_error(export_json, mode, &invocation, harnesses)?;
+    let runner = harness_runner::HarnessRunner { sess: session, project };
+    let planned_commands = runner.plan_all_harnesses(&harnesses)?;
+    if session.args.jobs().will_multithread() && !planned_commands.is_empty() {
+        println!("Harnesses are listed in verification order, slowest in previous runs first.");
+    }
+    for planned in &planned_commands {
+        println!("Commands for harness {}:", planned.harness);
+        for command in &planned.commands {
+            println!("  {command}");
+        }
+    }
+    export_skipped_run(project, session, invocation, Some(planned_commands))
 }
 
 /// Run verification on the given project.
-fn verify_project(project: Project, session: KaniSession) -> Result<()> {
+fn verify_project(project: Project, session: KaniSession, invocation: Invocation) -> Result<()> {
     debug!(?project, "verify_project");
-    let mut handler = JsonHandler::new(session.args.export_json.clone());
-    let harnesses = session.determine_targets(project.get_all_harnesses())?;
+    let start_time = Instant::now();
+    // Without `--export-json` there is no handler, and none of the exported records are built.
+    let mut handler = json_handler(&session)?;
+    // Anything gathered before an error is still exported, so it is not lost.
+    let harnesses = session.determine_targets(&project);
+    let mut harnesses = export_on_error(handler.as_mut(), harnesses)?;
+    // The harnesses are exported and summarized in the same order across runs.
+    metadata::sort_harnesses_by_name(&mut harnesses);
     debug!(n = harnesses.len(), ?harnesses, "verify_project");
+    let verified =
+        verify_and_export(&project, &session, &harnesses, invocation, handler.as_mut(), start_time);
+    let (results, baseline_diff) = export_on_error(handler.as_mut(), verified)?;
+    if let Some(path) = &session.args.junit_xml {
+        write_junit_report(path, &results)?;
+        event_log::file_written(path);
+    }
+    if let Some(path) = &session.args.sarif {
+        write_sarif_report(path, &results)?;
+        event_log::file_written(path);
+    }
 
-    // Add project and export run metadata using frontend utility
-    handler.add_item("metadata", create_metadata_json());
-    handler.add_item("project", create_project_metadata_json(&project));
+    if let Err(err) = session.record_history(&project, &results, start_time.elapsed()) {
+        util::warning(&format!("Failed to record run history: {err:#}"));
+    }
+    session.record_unsupported_functions(&results);
 
-    // Add harness metadata using frontend utility
-    for h in &harnesses {
-        handler.add_harness_detail("harness_metadata", create_harness_metadata_json(h));
+    if let Some(diff) = &baseline_diff
+        && !session.args.common_args.quiet
+    {
+        if let Some(warning) = &diff.warning {
+            util::warning(warning);
+        }
+        println!("\nComparison with the baseline run:\n{}", diff.render());
+    }
+    let fail_on_regression = session.args.fail_on_regression;
+    event_log::phase("summary");
+    session.print_final_summary(&results)?;
+    if fail_on_regression
+        && let Some(diff) = &baseline_diff
+        && !diff.regressions().is_empty()
+    {
+        let regressions = diff.regressions().into_iter().map(str::to_string).collect();
+        return Err(KaniDriverError::Regressions(regressions).into());
     }
+    Ok(())
+}
 
-    // Verification
-    let runner = harness_runner::HarnessRunner { sess: &session, project: &project };
-    let results = runner.check_all_harnesses(&harnesses, Some(&mut handler))?;
-
-    // Process harness results and add additional metadata using frontend utility function
-    process_harness_results(&mut handler, &harnesses, &results)?;
-    process_cbmc_results(&mut handler, &harnesses, &results, &session)?;
-
-    if session.args.coverage {
-        // We generate a timestamp to save the coverage data in a folder named
-        // `kanicov_<date>` where `<date>` is the current date based on `format`
-        // below. The purpose of adding timestamps to the folder name is to make
-        // coverage results easily identifiable. Using a timestamp makes
-        // coverage results not only distinguishable, but also easy to relate to
-        // verification runs. We expect this to be particularly helpful for
-        // users in a proof debugging session, who are usually interested in the
-        // most recent results.
-        let time_now = OffsetDateTime::now_utc();
-        let format = format_description::parse("[year]-[month]-[day]_[hour]-[minute]").unwrap();
-        let timestamp = time_now.format(&format).unwrap();
-
-        session.save_coverage_metadata(&project, &timestamp)?;
-        session.save_coverage_results(&project, &results, &timestamp)?;
-
-        handler.add_item("coverage", json!({"enabled": true}));
-    } else {
-        handler.add_item("coverage", json!({"enabled": false}));
+/// Verify `harnesses`, save their coverage results and compare them with `--compare-baseline`.
+/// Everything gathered is added to `handler`, which is exported once all harnesses are processed.
+fn verify_and_export<'a>(
+    project: &'a Project,
+    session: &KaniSession,
+    harnesses: &'a [&'a HarnessMetadata],
+    invocation: Invocation,
+    mut handler: Option<&mut JsonHandler>,
+    start_time: Instant,
+) -> Result<(Vec<harness_runner::HarnessResult<'a>>, Option<RunDiff>)> {
+    // Read the baseline up front, so a missing baseline is reported before spending time on
+    // verification.
+    let baseline = session
+        .args
+        .compare_baseline
+        .as_deref()
+        .map(|baseline| session.read_baseline(project, baseline, &invocation.config))
+        .transpose()?;
+
+    if let Some(handler) = handler.as_deref_mut() {
+        // Add project and export run metadata using frontend utility
+        let mode =
+            if session.args.quick { VerificationMode::Quick } else { VerificationMode::Full };
+        handler.add_item(MODE_KEY, json!(mode));
+        handler.add_item(METADATA_KEY, create_metadata_json());
+        handler.add_item(PROJECT_KEY, create_project_metadata_json(project));
+        handler.add_item(SESSION_KEY, json!(ExportedSession { config: invocation.config }));
+        handler.add_item(INVOCATION_KEY, json!(invocation.argv));
+        if let Some(skipped) = create_skipped_functions(project) {
+            handler.add_item(SKIPPED_FUNCTIONS_KEY, json!(skipped));
+        }
+        handler.add_item(WARNINGS_KEY, json!(create_warnings(project)));
+
+        // Add harness metadata using frontend utility
+        for h in harnesses {
+            let selected_by = session.harness_selector(h);
+            handler.add_harness_detail(
+                HARNESS_METADATA_KEY,
+                create_harness_metadata_json(h, selected_by),
+            );
+        }
     }
 
-    handler.export()?;
+    // Pick the coverage directory up front, so a clash with an existing `--coverage-out`
+    // directory is reported before spending time on verification.
+    let coverage_dir =
+        if session.args.coverage { Some(session.coverage_dir(project)?) } else { None };
+
+    // Verification
+    event_log::phase("verify");
+    let runner = harness_runner::HarnessRunner { sess: session, project };
+    let results = runner.check_all_harnesses(harnesses, handler.as_deref_mut())?;
+
+    let mut harness_coverage = vec![];
+    if let Some(outdir) = &coverage_dir {
+        session.save_coverage_metadata(project, outdir)?;
+        harness_coverage = session.save_coverage_results(&results, outdir)?;
+    }
 
-    session.print_final_summary(&results)
+    let baseline_diff = baseline.as_ref().map(|baseline| {
+        compare_with_baseline(baseline, &results, session.args.baseline_runtime_threshold)
+    });
+
+    if let Some(handler) = handler {
+        event_log::phase("export");
+        // Process harness results and add additional metadata using frontend utility function
+        let cbmc_info = session.get_cbmc_info().ok();
+        process_harness_results(handler, harnesses, &results, cbmc_info.as_ref(), &session.args)?;
+        handler.add_item(CONTRACT_DEPENDENCIES_KEY, json!(create_contract_dependencies(&results)));
+
+        let coverage = create_exported_coverage(
+            session.args.coverage,
+            coverage_dir.as_deref(),
+            harness_coverage,
+        );
+        handler.add_item(COVERAGE_KEY, json!(coverage));
+        if let Some(diff) = &baseline_diff {
+            handler.add_item(BASELINE_DIFF_KEY, json!(diff));
+        }
+        handler
+            .add_item(RUN_SUMMARY_KEY, json!(create_run_summary(&results, session.args.coverage)));
+        handler.set_run_summary(&create_run_info(
+            project,
+            invocation.command_line,
+            Some(&results),
+            start_time.elapsed(),
+        ));
+        handler.export()?;
+    }
+    Ok((results, baseline_diff))
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -201,12 +508,12 @@ fn determine_invocation_type(mut args: Vec<OsString>) -> InvocationType {
     }
     // Case 2: if 'kani' is the name we're invoked as, then we're being invoked standalone
     // Note: we care about argv0 here, NOT std::env::current_exe(), as the later will be resolved
-    else if Some("kani".into()) == exe {
+    else if is_invoked_as(&exe, "kani") {
         InvocationType::Standalone
     }
     // Case 3: if 'cargo-kani' is the name we're invoked as, then the user is directly invoking
     // 'cargo-kani' instead of 'cargo kani', and we shouldn't alter arguments.
-    else if Some("cargo-kani".into()) == exe {
+    else if is_invoked_as(&exe, "cargo-kani") {
         InvocationType::CargoKani(args)
     }
     // Case 4: default fallback, act like standalone
@@ -215,10 +522,40 @@ fn determine_invocation_type(mut args: Vec<OsString>) -> InvocationType {
     }
 }
 
+/// Whether the executable basename `exe` is `name`, possibly followed by a version, e.g.
+/// `kani-0.55` or `cargo-kani-v0.55.0`, as for versioned installs and symlinks.
+fn is_invoked_as(exe: &Option<OsString>, name: &str) -> bool {
+    let Some(suffix) = exe.as_ref().and_then(|exe| exe.to_str()?.strip_prefix(name)) else {
+        return false;
+    };
+    let Some(version) = suffix.strip_prefix(['-', '_']) else {
+        return suffix.is_empty();
+    };
+    let version = version.strip_prefix('v').unwrap_or(version);
+    version.starts_with(|c: char| c.is_ascii_digit())
+        && version.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+'))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn check_invocation_records_arguments() {
+        let input_args: Vec<_> = ["cargo-kani", "--harness", "check_a", "--export-json", "a.json"]
+            .into_iter()
+            .map(|arg| SourcedArg::new(arg, ArgSource::Cli))
+            .collect();
+        let invocation = Invocation::new::<args::CargoKaniArgs>(&input_args).unwrap();
+        assert_eq!(
+            invocation.command_line,
+            ["cargo-kani", "--harness", "check_a", "--export-json", "a.json"]
+        );
+        // The process arguments are recorded as they are, whatever the parsed arguments are.
+        let argv: Vec<String> = std::env::args().collect();
+        assert_eq!(invocation.argv, argv);
+    }
+
     #[test]
     fn check_invocation_type() {
         // conversions to/from OsString are rough, simplify the test code below
@@ -238,6 +575,36 @@ fn x(args: Vec<&str>) -> Vec<OsString> {
         );
         // Case 2: 'kani'
         assert_eq!(determine_invocation_type(x(vec!["kani", "foo"])), InvocationType::Standalone);
+        // Versioned, symlinked and Windows names
+        assert_eq!(
+            determine_invocation_type(x(vec!["/usr/bin/cargo-kani-0.55", "foo"])),
+            InvocationType::CargoKani(x(vec!["/usr/bin/cargo-kani-0.55", "foo"]))
+        );
+        assert_eq!(
+            determine_invocation_type(x(vec!["cargo-kani.exe", "foo"])),
+            InvocationType::CargoKani(x(vec!["cargo-kani.exe", "foo"]))
+        );
+        assert_eq!(
+            determine_invocation_type(x(vec!["cargo-kani-v0.55.0-nightly.exe", "foo"])),
+            InvocationType::CargoKani(x(vec!["cargo-kani-v0.55.0-nightly.exe", "foo"]))
+        );
+        assert_eq!(
+            determine_invocation_type(x(vec!["kani-0.55", "foo"])),
+            InvocationType::Standalone
+        );
+        // 'cargo kani' is recognized before the executable name
+        assert_eq!(
+            determine_invocation_type(x(vec!["kani-0.55", "kani", "foo"])),
+            InvocationType::CargoKani(x(vec!["kani-0.55", "foo"]))
+        );
+        // Names that only start with 'cargo-kani' are not versions
+        assert_eq!(
+            determine_invocation_type(x(vec!["cargo-kani-driver", "foo"])),
+            InvocationType::Standalone
+        );
+        assert!(!is_invoked_as(&Some("cargo-kanix".into()), "cargo-kani"));
+        assert!(!is_invoked_as(&Some("cargo-kani-".into()), "cargo-kani"));
+        assert!(is_invoked_as(&Some("cargo-kani_1".into()), "cargo-kani"));
         // default
         assert_eq!(determine_invocation_type(x(vec!["foo"])), InvocationType::Standalone);
         // weird case can be handled
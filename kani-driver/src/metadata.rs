@@ -1,16 +1,21 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use anyhow::{Result, bail};
+use anyhow::Result;
+use regex::Regex;
 use std::path::Path;
 
 use kani_metadata::{
-    HarnessMetadata, InternedString, TraitDefinedMethod, VtableCtxResults, find_proof_harnesses,
+    ContractedFunction, HarnessKind, HarnessMetadata, InternedString, TraitDefinedMethod,
+    VtableCtxResults, find_proof_harnesses,
 };
-use std::collections::{BTreeSet, HashMap};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs::File;
 use std::io::{BufReader, BufWriter};
 
+use crate::error::KaniDriverError;
+use crate::project::Project;
 use crate::session::KaniSession;
 use serde::Deserialize;
 
@@ -25,8 +30,11 @@ pub fn collect_and_link_function_pointer_restrictions(
     let mut per_crate_restrictions = Vec::new();
 
     if md.is_dir() {
-        for element in path.read_dir()? {
-            let path = element?.path();
+        // Read the files in a fixed order, so the linked restrictions are the same on every run.
+        let mut paths =
+            path.read_dir()?.map(|element| Ok(element?.path())).collect::<Result<Vec<_>>>()?;
+        paths.sort();
+        for path in paths {
             if path.as_os_str().to_str().unwrap().ends_with(".restrictions.json") {
                 let restrictions = from_json(&path)?;
                 per_crate_restrictions.push(restrictions);
@@ -43,7 +51,8 @@ pub fn collect_and_link_function_pointer_restrictions(
     link_function_pointer_restrictions(per_crate_restrictions, output_filename)
 }
 
-/// Collect all vtable restriction metadata together, and write one combined output in CBMC's format
+/// Collect all vtable restriction metadata together, and write one combined output in CBMC's format.
+/// The call sites are sorted, so the output only depends on the restrictions.
 fn link_function_pointer_restrictions(
     data_per_crate: Vec<VtableCtxResults>,
     output_filename: &Path,
@@ -59,7 +68,7 @@ fn link_function_pointer_restrictions(
     }
 
     // Emit a restriction for every call site
-    let mut output = HashMap::new();
+    let mut output = BTreeMap::new();
     for crate_data in data_per_crate {
         for call_site in crate_data.call_sites {
             // CBMC Now supports referencing callsites by label:
@@ -88,13 +97,134 @@ pub fn from_json<T: for<'a> Deserialize<'a>>(path: &Path) -> Result<T> {
     Ok(obj)
 }
 
+/// A harness selector given by `--harness-pattern` (glob) or `--harness-regex`.
+#[derive(Debug, Clone)]
+pub struct HarnessPattern {
+    /// The pattern as provided by the user.
+    source: String,
+    regex: Regex,
+}
+
+impl HarnessPattern {
+    /// A glob pattern that must match the whole harness name. `*` matches any sequence of
+    /// characters (including `::`) and `?` matches a single character.
+    pub fn glob(pattern: &str) -> Result<Self, regex::Error> {
+        let mut anchored = String::from("^");
+        for c in pattern.chars() {
+            match c {
+                '*' => anchored.push_str(".*"),
+                '?' => anchored.push('.'),
+                _ => anchored.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+        anchored.push('$');
+        Ok(HarnessPattern { source: pattern.to_string(), regex: Regex::new(&anchored)? })
+    }
+
+    /// A regular expression that must match some part of the harness name.
+    pub fn regex(pattern: &str) -> Result<Self, regex::Error> {
+        Ok(HarnessPattern { source: pattern.to_string(), regex: Regex::new(pattern)? })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Whether the pattern matches the fully qualified or the unqualified name of the harness.
+    /// For automatic harnesses, this is the name of the function under verification.
+    pub fn is_match(&self, harness: &HarnessMetadata) -> bool {
+        let name = matched_name(harness);
+        let unqualified = name.rsplit_once("::").map_or(name, |(_, unqualified)| unqualified);
+        self.regex.is_match(name) || self.regex.is_match(unqualified)
+    }
+}
+
+/// The name that harness patterns match: the harness name for manual harnesses, and the name of
+/// the function under verification for automatic harnesses.
+fn matched_name(harness: &HarnessMetadata) -> &str {
+    match &harness.attributes.kind {
+        HarnessKind::ProofForContract { target_fn } if harness.is_automatically_generated => {
+            target_fn
+        }
+        _ => &harness.pretty_name,
+    }
+}
+
 impl KaniSession {
+    /// The `--harness` filter or harness pattern that selected `harness`, if any.
+    /// If several of them match, the first `--harness` filter is reported, then the first pattern.
+    pub fn harness_selector(&self, harness: &HarnessMetadata) -> Option<String> {
+        let by_filter = self.args.harnesses.iter().find(|filter| {
+            !find_proof_harnesses(&BTreeSet::from([*filter]), [harness], self.args.exact).is_empty()
+        });
+        if let Some(filter) = by_filter {
+            return Some(filter.clone());
+        }
+        let pattern = self.harness_patterns.iter().find(|pattern| pattern.is_match(harness));
+        pattern.map(|pattern| pattern.source.clone())
+    }
+
+    /// Whether `harness` matches an `--exclude-harness` pattern, in which case it is reported as
+    /// excluded instead of being verified.
+    pub fn is_excluded(&self, harness: &HarnessMetadata) -> bool {
+        self.exclusion_patterns.iter().any(|pattern| pattern.is_match(harness))
+    }
+
+    /// Whether `harness` was only selected because `--with-contract-dependencies` added it.
+    pub fn is_contract_dependency(&self, harness: &HarnessMetadata) -> bool {
+        let filtered = !self.args.harnesses.is_empty()
+            || !self.args.harness_patterns.is_empty()
+            || !self.args.harness_regexes.is_empty();
+        self.args.with_contract_dependencies && filtered && self.harness_selector(harness).is_none()
+    }
+
     /// Determine which function to use as entry point, based on command-line arguments and kani-metadata.
-    pub fn determine_targets<'a>(
+    /// The harnesses matching `--exclude-harness` are kept, so they are reported as excluded rather
+    /// than vanishing silently, see [KaniSession::is_excluded].
+    pub fn determine_targets<'a>(&self, project: &'a Project) -> Result<Vec<&'a HarnessMetadata>> {
+        let all_harnesses = project.get_all_harnesses();
+        let included = self.included_targets(all_harnesses.clone())?;
+        if included.is_empty() && !self.args.harnesses.is_empty() {
+            return Err(KaniDriverError::no_harnesses_matched(&self.args.harnesses).into());
+        }
+        check_exclusions(&self.exclusion_patterns, &included)?;
+        if !self.args.with_contract_dependencies {
+            return Ok(included);
+        }
+
+        let contracted_functions: Vec<&ContractedFunction> =
+            project.metadata.iter().flat_map(|md| &md.contracted_functions).collect();
+        let selected = included.len();
+        let expanded = add_contract_dependencies(included, &all_harnesses, &contracted_functions)?;
+        if expanded.len() > selected && !self.args.common_args.quiet {
+            println!("[Kani] info: {}", expansion_message(selected, expanded.len()));
+        }
+        Ok(expanded)
+    }
+
+    /// The harnesses selected by `--harness` and the harness patterns, or all of them.
+    fn included_targets<'a>(
         &self,
         compiler_filtered_harnesses: Vec<&'a HarnessMetadata>,
     ) -> Result<Vec<&'a HarnessMetadata>> {
         let harness_filters = BTreeSet::from_iter(self.args.harnesses.iter());
+        if !self.harness_patterns.is_empty() {
+            return select_by_patterns(
+                &self.harness_patterns,
+                &harness_filters,
+                compiler_filtered_harnesses,
+                self.args.exact,
+            );
+        }
+
+        // The compiler generates all harnesses for `--with-contract-dependencies`, so the
+        // contract harnesses can be added to the selection.
+        let compiler_filtered_harnesses =
+            if self.args.compiler_selects_harnesses() || harness_filters.is_empty() {
+                compiler_filtered_harnesses
+            } else {
+                find_proof_harnesses(&harness_filters, compiler_filtered_harnesses, self.args.exact)
+            };
 
         // For dev builds, re-filter the harnesses to double check filtering in the compiler
         // and ensure we're doing the minimal harness codegen possible. That filtering happens in
@@ -122,15 +252,149 @@ pub fn determine_targets<'a>(
                 .collect::<Vec<String>>()
                 .join("`, `");
 
-            bail!(
+            return Err(KaniDriverError::NoHarnessesMatched(format!(
                 "Failed to match the following harness(es):\n{joined_string}\nPlease specify the fully-qualified name of a harness.",
-            );
+            ))
+            .into());
         }
 
         Ok(compiler_filtered_harnesses)
     }
 }
 
+/// Select the harnesses that match any of the `patterns` or any of the `--harness` filters.
+/// The compiler does not filter harnesses when patterns are used, so all harnesses are given here.
+fn select_by_patterns<'a>(
+    patterns: &[HarnessPattern],
+    harness_filters: &BTreeSet<&String>,
+    all_harnesses: Vec<&'a HarnessMetadata>,
+    exact: bool,
+) -> Result<Vec<&'a HarnessMetadata>> {
+    let by_filter = if harness_filters.is_empty() {
+        vec![]
+    } else {
+        find_proof_harnesses(harness_filters, all_harnesses.iter(), exact)
+    };
+    let selected: Vec<_> = all_harnesses
+        .iter()
+        .copied()
+        .filter(|h| by_filter.contains(h) || patterns.iter().any(|p| p.is_match(h)))
+        .collect();
+
+    if selected.is_empty() {
+        let mut msg = String::from("no harnesses matched the harness pattern(s):");
+        for pattern in patterns {
+            msg.push_str(&format!("\n  `{}`", pattern.as_str()));
+            let near_misses = near_misses(pattern.as_str(), &all_harnesses);
+            if !near_misses.is_empty() {
+                msg.push_str(&format!(" (did you mean `{}`?)", near_misses.join("`, `")));
+            }
+        }
+        return Err(KaniDriverError::NoHarnessesMatched(msg).into());
+    }
+    Ok(selected)
+}
+
+/// Excluding every harness that was selected is an error, since there would be nothing to verify.
+fn check_exclusions(exclusions: &[HarnessPattern], included: &[&HarnessMetadata]) -> Result<()> {
+    if !exclusions.is_empty()
+        && !included.is_empty()
+        && included.iter().all(|h| exclusions.iter().any(|pattern| pattern.is_match(h)))
+    {
+        let patterns: Vec<&str> = exclusions.iter().map(HarnessPattern::as_str).collect();
+        return Err(KaniDriverError::InvalidArguments(format!(
+            "all {} selected harness(es) are excluded by the `--exclude-harness` pattern(s): `{}`",
+            included.len(),
+            patterns.join("`, `")
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// Add the `#[kani::proof_for_contract]` harnesses of the contracts that `selected` assumes with
+/// `#[kani::stub_verified]`, then the ones these harnesses assume, and so on. The contracts are
+/// found by the fully qualified name of their function. An assumed contract without a harness is
+/// an error, since the selection would not verify everything it relies on.
+fn add_contract_dependencies<'a>(
+    selected: Vec<&'a HarnessMetadata>,
+    all_harnesses: &[&'a HarnessMetadata],
+    contracted_functions: &[&ContractedFunction],
+) -> Result<Vec<&'a HarnessMetadata>> {
+    let mut expanded = selected;
+    let mut next = 0;
+    while let Some(&harness) = expanded.get(next) {
+        next += 1;
+        for function in &harness.attributes.verified_stubs {
+            let contract_harnesses: Vec<&'a HarnessMetadata> = contracted_functions
+                .iter()
+                .filter(|contracted| &contracted.function == function)
+                .flat_map(|contracted| &contracted.harnesses)
+                .filter_map(|name| all_harnesses.iter().copied().find(|h| &h.pretty_name == name))
+                .collect();
+            if contract_harnesses.is_empty() {
+                return Err(KaniDriverError::InvalidArguments(format!(
+                    "the contract of `{function}` is assumed by `{}` with `stub_verified`, but it \
+                    has no `#[kani::proof_for_contract]` harness, so `--with-contract-dependencies` \
+                    cannot verify it",
+                    harness.pretty_name
+                ))
+                .into());
+            }
+            for dependency in contract_harnesses {
+                if !expanded.contains(&dependency) {
+                    expanded.push(dependency);
+                }
+            }
+        }
+    }
+    Ok(expanded)
+}
+
+/// How `--with-contract-dependencies` changed the selection of harnesses.
+pub fn expansion_message(selected: usize, expanded: usize) -> String {
+    format!(
+        "Selection expanded from {selected} to {expanded} harnesses to cover assumed contracts."
+    )
+}
+
+/// Maximum number of similar harness names suggested when a pattern does not match anything.
+const MAX_NEAR_MISSES: usize = 3;
+
+/// Harness names that are close to `pattern`, ignoring its wildcards, sorted by similarity.
+fn near_misses<'a>(pattern: &str, harnesses: &[&'a HarnessMetadata]) -> Vec<&'a str> {
+    let literal: String =
+        pattern.chars().filter(|c| c.is_alphanumeric() || "_:".contains(*c)).collect();
+    let max_distance = (literal.len() / 3).max(2);
+    let mut candidates: Vec<_> = harnesses
+        .iter()
+        .map(|h| {
+            let distance = edit_distance(&literal, &h.pretty_name)
+                .min(edit_distance(&literal, h.get_harness_name_unqualified()));
+            (distance, h.pretty_name.as_str())
+        })
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect();
+    candidates.sort();
+    candidates.dedup();
+    candidates.into_iter().take(MAX_NEAR_MISSES).map(|(_, name)| name).collect()
+}
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
 /// Sort harnesses such that for two harnesses in the same file, it is guaranteed that later
 /// appearing harnesses get processed earlier.
 /// This is necessary for the concrete playback feature (with in-place unit test modification)
@@ -146,9 +410,23 @@ pub fn sort_harnesses_by_loc<'a>(harnesses: &[&'a HarnessMetadata]) -> Vec<&'a H
     harnesses_clone
 }
 
+/// Sort harnesses by crate and name, the order in which their details and results are reported.
+/// Unlike the order in which the metadata of the crates is collected, this order is the same
+/// across runs.
+pub fn sort_harnesses_by_name(harnesses: &mut [&HarnessMetadata]) {
+    harnesses.sort_by(|harness1, harness2| cmp_harnesses_by_name(harness1, harness2));
+}
+
+/// Compare harnesses by crate and name.
+pub fn cmp_harnesses_by_name(harness1: &HarnessMetadata, harness2: &HarnessMetadata) -> Ordering {
+    (&harness1.crate_name, &harness1.pretty_name)
+        .cmp(&(&harness2.crate_name, &harness2.pretty_name))
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
+    use crate::error::ErrorCategory;
     use kani_metadata::{HarnessAttributes, HarnessKind};
     use std::path::PathBuf;
 
@@ -175,6 +453,38 @@ pub fn mock_proof_harness(
         }
     }
 
+    #[test]
+    fn check_sort_harnesses_by_name() {
+        let harnesses = [
+            mock_proof_harness("check_b", None, Some("crate_a"), None),
+            mock_proof_harness("check_a", None, Some("crate_b"), None),
+            mock_proof_harness("module::check_a", None, Some("crate_a"), None),
+            mock_proof_harness("check_a", None, Some("crate_a"), None),
+        ];
+        let mut expected = harnesses.iter().collect::<Vec<_>>();
+        sort_harnesses_by_name(&mut expected);
+        let names: Vec<_> =
+            expected.iter().map(|h| format!("{}::{}", h.crate_name, h.pretty_name)).collect();
+        assert_eq!(
+            names,
+            [
+                "crate_a::check_a",
+                "crate_a::check_b",
+                "crate_a::module::check_a",
+                "crate_b::check_a"
+            ]
+        );
+
+        // The order doesn't depend on the order of the input.
+        for rotation in 0..harnesses.len() {
+            let mut shuffled = harnesses.iter().collect::<Vec<_>>();
+            shuffled.rotate_left(rotation);
+            shuffled.swap(0, 2);
+            sort_harnesses_by_name(&mut shuffled);
+            assert_eq!(shuffled, expected);
+        }
+    }
+
     #[test]
     fn check_find_proof_harness_without_exact() {
         let harnesses = [
@@ -260,4 +570,169 @@ fn check_find_proof_harness_with_exact() {
             "module::not_check_three"
         );
     }
+
+    #[test]
+    fn check_harness_patterns() {
+        let harness = mock_proof_harness("parser::check_u8_roundtrip", None, None, None);
+        assert!(HarnessPattern::glob("parser::*").unwrap().is_match(&harness));
+        assert!(HarnessPattern::glob("check_u?_roundtrip").unwrap().is_match(&harness));
+        assert!(!HarnessPattern::glob("parser").unwrap().is_match(&harness));
+        assert!(!HarnessPattern::glob("lexer::*").unwrap().is_match(&harness));
+        assert!(HarnessPattern::regex("check_.*_roundtrip").unwrap().is_match(&harness));
+        assert!(!HarnessPattern::regex("^check_.*_parse$").unwrap().is_match(&harness));
+        assert!(HarnessPattern::regex("check_(").is_err());
+    }
+
+    #[test]
+    fn check_harness_patterns_match_automatic_harness_targets() {
+        let mut harness = mock_proof_harness("parser::parse_u8", None, None, None);
+        harness.mangled_name = "kani_autoharness_intrinsic".into();
+        harness.is_automatically_generated = true;
+        assert!(HarnessPattern::glob("parser::*").unwrap().is_match(&harness));
+        assert!(HarnessPattern::glob("parse_u?").unwrap().is_match(&harness));
+        assert!(!HarnessPattern::regex("autoharness").unwrap().is_match(&harness));
+
+        harness.attributes.kind =
+            HarnessKind::ProofForContract { target_fn: "parser::checked_parse".into() };
+        assert!(HarnessPattern::glob("checked_*").unwrap().is_match(&harness));
+        assert!(!HarnessPattern::glob("parse_u8").unwrap().is_match(&harness));
+    }
+
+    #[test]
+    fn check_select_by_patterns() {
+        let harnesses = [
+            mock_proof_harness("parser::check_one", None, None, None),
+            mock_proof_harness("parser::check_two", None, None, None),
+            mock_proof_harness("lexer::check_three", None, None, None),
+        ];
+        let ref_harnesses = harnesses.iter().collect::<Vec<_>>();
+        let patterns = [HarnessPattern::glob("parser::*").unwrap()];
+        let three = "check_three".to_string();
+
+        let selected =
+            select_by_patterns(&patterns, &BTreeSet::new(), ref_harnesses.clone(), false).unwrap();
+        assert_eq!(selected, vec![&harnesses[0], &harnesses[1]]);
+
+        // Patterns and `--harness` filters are combined.
+        let selected =
+            select_by_patterns(&patterns, &BTreeSet::from([&three]), ref_harnesses.clone(), false)
+                .unwrap();
+        assert_eq!(selected.len(), 3);
+
+        // Near misses are suggested when nothing matches.
+        let patterns = [HarnessPattern::glob("parser::check_on").unwrap()];
+        let err = select_by_patterns(&patterns, &BTreeSet::new(), ref_harnesses, false)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("`parser::check_on`"), "{err}");
+        assert!(err.contains("did you mean `parser::check_one`"), "{err}");
+    }
+
+    #[test]
+    fn check_exclusions_of_selected_harnesses() {
+        let harnesses = [
+            mock_proof_harness("foo", None, None, None),
+            mock_proof_harness("parser::check_slow", None, None, None),
+        ];
+        let foo = "foo".to_string();
+        // `--harness foo --exclude-harness foo` leaves nothing to verify.
+        let included = find_proof_harnesses(&BTreeSet::from([&foo]), &harnesses, false);
+        let exclusions = [HarnessPattern::glob("foo").unwrap()];
+        let err = check_exclusions(&exclusions, &included).unwrap_err();
+        assert_eq!(crate::error::category(&err), ErrorCategory::InvalidArguments);
+        let err = err.to_string();
+        assert!(err.contains("all 1 selected harness(es) are excluded"), "{err}");
+        assert!(err.contains("`foo`"), "{err}");
+
+        // Excluding only some of the harnesses is fine, and so is an exclusion that matches none.
+        let all = harnesses.iter().collect::<Vec<_>>();
+        assert!(check_exclusions(&exclusions, &all).is_ok());
+        assert!(check_exclusions(&[HarnessPattern::glob("*::check_*").unwrap()], &all).is_ok());
+        assert!(check_exclusions(&[HarnessPattern::glob("bar").unwrap()], &included).is_ok());
+        assert!(check_exclusions(&[], &included).is_ok());
+    }
+
+    #[test]
+    fn check_contract_dependencies() {
+        let contract_harness = |name: &str, target: &str, stubs: &[&str]| {
+            let mut harness = mock_proof_harness(name, None, None, None);
+            harness.attributes.kind = HarnessKind::ProofForContract { target_fn: target.into() };
+            harness.attributes.verified_stubs = stubs.iter().map(|s| s.to_string()).collect();
+            harness
+        };
+        let contracted = |function: &str, harnesses: &[&str]| ContractedFunction {
+            function: function.into(),
+            file: "src/lib.rs".into(),
+            harnesses: harnesses.iter().map(|h| h.to_string()).collect(),
+            clauses: vec![],
+        };
+        let mut top = mock_proof_harness("top_level_proof", None, None, None);
+        top.attributes.verified_stubs = vec!["krate::parse".into(), "krate::check".into()];
+        let harnesses = [
+            top,
+            contract_harness("check_parse", "parse", &["krate::lex"]),
+            contract_harness("check_check", "check", &[]),
+            contract_harness("check_lex", "lex", &[]),
+            mock_proof_harness("unrelated", None, None, None),
+        ];
+        let all: Vec<_> = harnesses.iter().collect();
+        let functions = [
+            contracted("krate::parse", &["check_parse"]),
+            contracted("krate::check", &["check_check"]),
+            contracted("krate::lex", &["check_lex"]),
+        ];
+        let contracted_functions: Vec<_> = functions.iter().collect();
+
+        // The contract of `lex` is assumed by the harness of `parse`, so it is added as well.
+        let expanded =
+            add_contract_dependencies(vec![all[0]], &all, &contracted_functions).unwrap();
+        let names: Vec<_> = expanded.iter().map(|h| h.pretty_name.as_str()).collect();
+        assert_eq!(names, ["top_level_proof", "check_parse", "check_check", "check_lex"]);
+
+        // Harnesses that are already selected are not added twice.
+        let expanded =
+            add_contract_dependencies(vec![all[3], all[1]], &all, &contracted_functions).unwrap();
+        assert_eq!(expanded.len(), 2);
+
+        // An assumed contract without a harness can't be verified.
+        let err =
+            add_contract_dependencies(vec![all[0]], &all, &contracted_functions[1..]).unwrap_err();
+        assert_eq!(crate::error::category(&err), ErrorCategory::InvalidArguments);
+        let err = err.to_string();
+        assert!(err.contains("the contract of `krate::parse` is assumed by `top_level_proof`"));
+    }
+
+    #[test]
+    fn check_linked_restrictions_are_deterministic() {
+        let call_site = |function: &str, label: &str| {
+            format!(
+                r#"{{"trait_method":{{"trait_name":"Shape","vtable_idx":3}},"function_name":"{function}","label":"{label}"}}"#
+            )
+        };
+        let restrictions = |call_sites: &[String]| {
+            format!(
+                r#"{{"call_sites":[{}],"possible_methods":[{{"trait_method":{{"trait_name":"Shape","vtable_idx":3}},"possibilities":["area"]}}]}}"#,
+                call_sites.join(",")
+            )
+        };
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("b.restrictions.json"),
+            restrictions(&[call_site("main", "l2"), call_site("check", "l1")]),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("a.restrictions.json"),
+            restrictions(&[call_site("foo", "l0")]),
+        )
+        .unwrap();
+
+        let first = dir.path().join("first.json");
+        let second = dir.path().join("second.json");
+        collect_and_link_function_pointer_restrictions(dir.path(), &first).unwrap();
+        collect_and_link_function_pointer_restrictions(dir.path(), &second).unwrap();
+        let linked = std::fs::read_to_string(&first).unwrap();
+        assert_eq!(linked, std::fs::read_to_string(&second).unwrap());
+        assert_eq!(linked, r#"{"check.l1":["area"],"foo.l0":["area"],"main.l2":["area"]}"#);
+    }
 }
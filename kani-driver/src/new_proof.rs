@@ -0,0 +1,347 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the `new-proof` subcommand, which adds a proof harness skeleton for a function to
+//! the package.
+//!
+//! The function is resolved with the autoharness machinery: the compiler is invoked in
+//! autoharness mode with a pattern that only matches the function, and reports the arguments of
+//! the function and whether their types implement `kani::Arbitrary`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::args::VerificationArgs;
+use crate::args::new_proof_args::CargoNewProofArgs;
+use crate::error::KaniDriverError;
+use crate::project::cargo_project;
+use crate::session::KaniSession;
+use crate::version::print_kani_version;
+use crate::{InvocationType, util};
+use anyhow::{Context, Result};
+use kani_metadata::{AutoHarnessArgument, AutoHarnessSkipReason, KaniMetadata};
+
+/// A function that a harness skeleton is generated for.
+#[derive(Debug)]
+struct TargetFunction {
+    crate_name: String,
+    /// The path of the function in its crate, as reported by the compiler.
+    path: String,
+    arguments: Vec<AutoHarnessArgument>,
+}
+
+pub fn new_proof_cargo(args: CargoNewProofArgs, mut verify_opts: VerificationArgs) -> Result<()> {
+    let quiet = args.common_args.quiet;
+    verify_opts.common_args = args.common_args;
+    let mut session = KaniSession::new(verify_opts)?;
+    if !quiet {
+        print_kani_version(InvocationType::CargoKani(vec![]));
+    }
+
+    let function = args.function.strip_prefix("crate::").unwrap_or(&args.function);
+    session.enable_autoharness();
+    session.add_auto_harness_args(&[function_pattern(function)], &[]);
+    let project = cargo_project(&mut session, false)?;
+    let target = find_function(&project.metadata, function)?;
+
+    let metadata = project.cargo_metadata.as_ref().context("missing cargo metadata")?;
+    let crate_root = metadata
+        .workspace_packages()
+        .into_iter()
+        .flat_map(|package| &package.targets)
+        .find(|target_md| {
+            (target_md.is_lib() || target_md.is_bin())
+                && target_md.name.replace('-', "_") == target.crate_name
+        })
+        .map(|target_md| PathBuf::from(&target_md.src_path))
+        .with_context(|| format!("could not find the root of crate `{}`", target.crate_name))?;
+
+    let proofs_file = crate_root.parent().unwrap().join(&args.proofs_file);
+    let harness_name = harness_name(&target.path);
+    let harness = render_harness(&harness_name, &target);
+    add_harness(&crate_root, &proofs_file, &harness_name, &harness)?;
+
+    println!("Added harness `{harness_name}` to `{}`:\n\n{harness}", proofs_file.display());
+    println!("Verify it with:\n    cargo kani --harness {harness_name}");
+    Ok(())
+}
+
+/// The autoharness pattern that matches `function`. The compiler matches the patterns against
+/// the function paths prefixed with the crate name.
+fn function_pattern(function: &str) -> String {
+    format!("(^|::){}$", regex::escape(function))
+}
+
+/// Find the function with the given path, with or without the crate name.
+fn find_function(metadata: &[KaniMetadata], function: &str) -> Result<TargetFunction> {
+    let mut found = vec![];
+    for md in metadata {
+        let Some(autoharness_md) = &md.autoharness_md else { continue };
+        let is_target =
+            |path: &str| path == function || format!("{}::{path}", md.crate_name) == function;
+        for (path, arguments) in &autoharness_md.arguments {
+            if is_target(path) {
+                found.push(TargetFunction {
+                    crate_name: md.crate_name.clone(),
+                    path: path.clone(),
+                    arguments: arguments.clone(),
+                });
+            }
+        }
+        // Functions that are skipped before their arguments are checked can't be verified
+        // with a harness of this shape.
+        if let Some((path, reason)) = autoharness_md.skipped.iter().find(|(path, reason)| {
+            is_target(path)
+                && !matches!(
+                    reason,
+                    AutoHarnessSkipReason::MissingArbitraryImpl(_)
+                        | AutoHarnessSkipReason::UserFilter
+                )
+        }) {
+            return Err(KaniDriverError::InvalidArguments(format!(
+                "Cannot generate a harness for `{path}`: {reason}"
+            ))
+            .into());
+        }
+    }
+    match found.len() {
+        0 => Err(KaniDriverError::InvalidArguments(format!(
+            "Could not find a function `{function}` in this package"
+        ))
+        .into()),
+        1 => Ok(found.pop().unwrap()),
+        _ => Err(KaniDriverError::InvalidArguments(format!(
+            "`{function}` matches several functions; use the full path of one of: `{}`",
+            found
+                .iter()
+                .map(|target| format!("{}::{}", target.crate_name, target.path))
+                .collect::<Vec<_>>()
+                .join("`, `")
+        ))
+        .into()),
+    }
+}
+
+/// The name of the harness for the function with the given path, e.g. `check_parse` for
+/// `parser::parse` and `check_header_len` for `Header::len`.
+fn harness_name(path: &str) -> String {
+    let mut segments = path.rsplit("::");
+    let function = segments.next().unwrap_or(path);
+    let mut name = String::from("check_");
+    // Include the type of methods, which start with an uppercase letter by convention.
+    if let Some(ty) = segments.next().filter(|ty| ty.starts_with(char::is_uppercase)) {
+        for (idx, c) in ty.chars().filter(|c| c.is_alphanumeric()).enumerate() {
+            if c.is_uppercase() && idx > 0 {
+                name.push('_');
+            }
+            name.extend(c.to_lowercase());
+        }
+        name.push('_');
+    }
+    name.push_str(function);
+    name
+}
+
+/// Render the harness skeleton: a nondeterministic value for each argument, a call to the
+/// function and a placeholder assertion.
+fn render_harness(harness_name: &str, target: &TargetFunction) -> String {
+    let mut body = String::new();
+    let mut call_args = vec![];
+    for (idx, arg) in target.arguments.iter().enumerate() {
+        let name = match arg.name.as_str() {
+            "_" => format!("arg{idx}"),
+            "self" => "receiver".to_string(),
+            name => name.to_string(),
+        };
+        if !arg.arbitrary {
+            body.push_str(&format!(
+                "    // TODO: `{}` doesn't implement `kani::Arbitrary`; build a value for `{name}`.\n",
+                arg.ty
+            ));
+            body.push_str(&format!("    let {name}: {} = todo!();\n", arg.ty));
+            call_args.push(name);
+        } else if let Some(ty) = arg.ty.strip_prefix("&mut ") {
+            body.push_str(&format!("    let mut {name}: {ty} = kani::any();\n"));
+            call_args.push(format!("&mut {name}"));
+        } else if let Some(ty) = arg.ty.strip_prefix('&') {
+            body.push_str(&format!("    let {name}: {ty} = kani::any();\n"));
+            call_args.push(format!("&{name}"));
+        } else {
+            body.push_str(&format!("    let {name}: {} = kani::any();\n", arg.ty));
+            call_args.push(name);
+        }
+    }
+    let function = if target.path.starts_with('<') {
+        target.path.clone()
+    } else {
+        format!("crate::{}", target.path)
+    };
+    format!(
+        "#[kani::proof]\n\
+        fn {harness_name}() {{\n\
+        {body}    \
+        let _result = {function}({});\n    \
+        // TODO: Replace this placeholder with the properties that `{}` must satisfy.\n    \
+        assert!(true);\n\
+        }}\n",
+        call_args.join(", "),
+        target.path
+    )
+}
+
+/// Add `harness` to `proofs_file`. If the file doesn't exist, it's created and declared as a
+/// `#[cfg(kani)]` module in `crate_root`.
+fn add_harness(
+    crate_root: &Path,
+    proofs_file: &Path,
+    harness_name: &str,
+    harness: &str,
+) -> Result<()> {
+    if proofs_file.exists() {
+        let content = fs::read_to_string(proofs_file)?;
+        if content.contains(&format!("fn {harness_name}(")) {
+            return Err(KaniDriverError::InvalidArguments(format!(
+                "`{}` already has a harness named `{harness_name}`",
+                proofs_file.display()
+            ))
+            .into());
+        }
+        let separator = if content.ends_with('\n') { "\n" } else { "\n\n" };
+        fs::write(proofs_file, format!("{content}{separator}{harness}"))?;
+        return Ok(());
+    }
+
+    fs::create_dir_all(proofs_file.parent().unwrap())?;
+    fs::write(
+        proofs_file,
+        format!("//! Kani proof harnesses, verified with `cargo kani`.\n\n{harness}"),
+    )?;
+    let module = proofs_file.file_stem().unwrap().to_string_lossy();
+    if proofs_file.parent() == crate_root.parent() {
+        let root = fs::read_to_string(crate_root)?;
+        if !declares_module(&root, &module) {
+            let separator = if root.ends_with('\n') { "" } else { "\n" };
+            fs::write(crate_root, format!("{root}{separator}\n#[cfg(kani)]\nmod {module};\n"))?;
+        }
+    } else {
+        util::warning(&format!(
+            "Declare `{}` as a module gated by `#[cfg(kani)]` so that Kani finds the harness",
+            proofs_file.display()
+        ));
+    }
+    Ok(())
+}
+
+/// Whether `source` declares the module `module`, e.g. `mod proofs;` or `mod proofs {`.
+fn declares_module(source: &str, module: &str) -> bool {
+    source.lines().any(|line| {
+        let line = line.trim_start();
+        let line = line.strip_prefix("pub ").unwrap_or(line);
+        line.strip_prefix("mod ").is_some_and(|rest| {
+            rest.strip_prefix(module).is_some_and(|rest| {
+                rest.trim_start().starts_with(';') || rest.trim_start().starts_with('{')
+            })
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argument(name: &str, ty: &str, arbitrary: bool) -> AutoHarnessArgument {
+        AutoHarnessArgument { name: name.into(), ty: ty.into(), arbitrary }
+    }
+
+    #[test]
+    fn check_harness_name() {
+        assert_eq!(harness_name("parse"), "check_parse");
+        assert_eq!(harness_name("parser::parse"), "check_parse");
+        assert_eq!(harness_name("parser::HeaderMap::len"), "check_header_map_len");
+    }
+
+    #[test]
+    fn check_render_harness() {
+        let target = TargetFunction {
+            crate_name: "sample".into(),
+            path: "buf::Buffer::push".into(),
+            arguments: vec![
+                argument("self", "&mut buf::Buffer", true),
+                argument("value", "u8", true),
+                argument("_", "&u32", true),
+                argument("log", "std::fs::File", false),
+            ],
+        };
+        assert_eq!(
+            render_harness("check_buffer_push", &target),
+            "#[kani::proof]
+fn check_buffer_push() {
+    let mut receiver: buf::Buffer = kani::any();
+    let value: u8 = kani::any();
+    let arg2: u32 = kani::any();
+    // TODO: `std::fs::File` doesn't implement `kani::Arbitrary`; build a value for `log`.
+    let log: std::fs::File = todo!();
+    let _result = crate::buf::Buffer::push(&mut receiver, value, &arg2, log);
+    // TODO: Replace this placeholder with the properties that `buf::Buffer::push` must satisfy.
+    assert!(true);
+}
+"
+        );
+    }
+
+    #[test]
+    fn check_find_function() {
+        let md = KaniMetadata {
+            crate_name: "sample".into(),
+            proof_harnesses: vec![],
+            test_harnesses: vec![],
+            unsupported_features: vec![],
+            contracted_functions: vec![],
+            autoharness_md: Some(kani_metadata::AutoHarnessMetadata {
+                chosen: ["parser::parse".to_string()].into(),
+                skipped: [("parser::generic".to_string(), AutoHarnessSkipReason::GenericFn)].into(),
+                arguments: [("parser::parse".to_string(), vec![argument("s", "u8", true)])].into(),
+            }),
+        };
+        let metadata = [md];
+        assert_eq!(
+            find_function(&metadata, "sample::parser::parse").unwrap().path,
+            "parser::parse"
+        );
+        assert_eq!(find_function(&metadata, "parser::parse").unwrap().arguments.len(), 1);
+        let err = find_function(&metadata, "parser::generic").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Cannot generate a harness for `parser::generic`: Generic Function"
+        );
+        assert!(find_function(&metadata, "parser::missing").is_err());
+    }
+
+    #[test]
+    fn check_add_harness() {
+        let dir = tempfile::tempdir().unwrap();
+        let crate_root = dir.path().join("lib.rs");
+        fs::write(&crate_root, "pub fn parse() {}").unwrap();
+        let proofs_file = dir.path().join("proofs.rs");
+
+        add_harness(&crate_root, &proofs_file, "check_parse", "fn check_parse() {}\n").unwrap();
+        assert_eq!(
+            fs::read_to_string(&crate_root).unwrap(),
+            "pub fn parse() {}\n\n#[cfg(kani)]\nmod proofs;\n"
+        );
+        assert!(fs::read_to_string(&proofs_file).unwrap().ends_with("fn check_parse() {}\n"));
+
+        // Existing harnesses are never overwritten, and the module is only declared once.
+        assert!(add_harness(&crate_root, &proofs_file, "check_parse", "").is_err());
+        add_harness(&crate_root, &proofs_file, "check_len", "fn check_len() {}\n").unwrap();
+        let proofs = fs::read_to_string(&proofs_file).unwrap();
+        assert!(proofs.ends_with("fn check_parse() {}\n\nfn check_len() {}\n"));
+        assert_eq!(fs::read_to_string(&crate_root).unwrap().matches("mod proofs").count(), 1);
+    }
+
+    #[test]
+    fn check_declares_module() {
+        assert!(declares_module("#[cfg(kani)]\nmod proofs;\n", "proofs"));
+        assert!(declares_module("pub mod proofs {\n}\n", "proofs"));
+        assert!(!declares_module("mod proofs_util;\n", "proofs"));
+    }
+}
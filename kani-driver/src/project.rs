@@ -103,7 +103,11 @@ fn try_new(
                 let goto_path = convert_type(&symtab_out.path, symtab_out.typ, Goto);
 
                 // Link
-                session.link_goto_binary(&[symtab_out.to_path_buf()], &goto_path)?;
+                session.link_goto_binary(
+                    &[symtab_out.to_path_buf()],
+                    &goto_path,
+                    &crate_metadata.crate_name,
+                )?;
                 let goto = Artifact::try_new(&goto_path, Goto)?;
 
                 // All other harness artifacts that may have been generated as part of the build.
@@ -0,0 +1,121 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! This module determines the project root of a run: the workspace root for `cargo kani`, and the
+//! directory of the input file for `kani`.
+//!
+//! The relative output paths given on the command line, e.g. `--export-json`, are resolved against
+//! the project root instead of the current directory, so the driver places its files in the same
+//! location no matter which directory of the project it is invoked from.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+use anyhow::{Result, bail};
+
+use crate::args::VerificationArgs;
+use crate::args::common::Verbosity;
+
+/// The project root of this run, once it was detected.
+static PROJECT_ROOT: OnceLock<PathBuf> = OnceLock::new();
+
+/// The project root of this run, if it was detected.
+pub fn get() -> Option<&'static Path> {
+    PROJECT_ROOT.get().map(PathBuf::as_path)
+}
+
+/// Record `root` as the project root of this run, and resolve the relative output paths of `args`
+/// against it. The root is printed with `--verbose`.
+pub fn init(root: PathBuf, args: &mut VerificationArgs) {
+    if args.common_args.verbose() {
+        println!("[Kani] Project root: {}", root.display());
+    }
+    resolve_output_paths(args, &root);
+    let _ = PROJECT_ROOT.set(root);
+}
+
+/// The root of the cargo workspace that `manifest_path`, or the current directory if `None`,
+/// belongs to.
+pub fn cargo_project_root(manifest_path: Option<&Path>) -> Result<PathBuf> {
+    let manifest = cargo_locate_project(manifest_path, true)?;
+    Ok(manifest.parent().map(Path::to_path_buf).unwrap_or_default())
+}
+
+/// The directory of the input file of a standalone run.
+pub fn standalone_project_root(input: &Path) -> Result<PathBuf> {
+    Ok(input.canonicalize()?.parent().map(Path::to_path_buf).unwrap_or_default())
+}
+
+/// Find the manifest of the package at `manifest_path`, or of the current directory if `None`.
+/// With `workspace`, find the manifest of the root of its workspace instead.
+///
+/// `locate-project` produces a response like: `/full/path/to/src/cargo-kani/Cargo.toml`
+pub fn cargo_locate_project(manifest_path: Option<&Path>, workspace: bool) -> Result<PathBuf> {
+    if let Some(path) = manifest_path
+        && !workspace
+    {
+        return Ok(path.to_path_buf());
+    }
+    let mut cmd = Command::new("cargo");
+    cmd.args(["locate-project", "--message-format", "plain"]);
+    if workspace {
+        cmd.arg("--workspace");
+    }
+    if let Some(path) = manifest_path {
+        cmd.arg("--manifest-path").arg(path);
+    }
+    let output = cmd.output()?;
+    if !output.status.success() {
+        let err = std::str::from_utf8(&output.stderr)?;
+        bail!("{}", err);
+    }
+    let path = std::str::from_utf8(&output.stdout)?;
+    // A trim is essential: remove the trailing newline
+    Ok(path.trim().into())
+}
+
+/// Make the relative output paths of `args` relative to `root`.
+fn resolve_output_paths(args: &mut VerificationArgs, root: &Path) {
+    let paths = [
+        &mut args.coverage_out,
+        &mut args.event_log,
+        &mut args.export_json,
+        &mut args.junit_xml,
+        &mut args.sarif,
+        &mut args.target_dir,
+    ];
+    for path in paths.into_iter().flatten() {
+        if path.is_relative() {
+            *path = root.join(&*path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::StandaloneArgs;
+    use clap::Parser;
+
+    #[test]
+    fn check_output_paths_are_resolved() {
+        let args = "kani lib.rs --export-json out/run.json --junit-xml /tmp/junit.xml \
+            --target-dir target";
+        let mut args = StandaloneArgs::try_parse_from(args.split_whitespace()).unwrap().verify_opts;
+        resolve_output_paths(&mut args, Path::new("/workspace"));
+        assert_eq!(args.export_json, Some(PathBuf::from("/workspace/out/run.json")));
+        assert_eq!(args.junit_xml, Some(PathBuf::from("/tmp/junit.xml")));
+        assert_eq!(args.target_dir, Some(PathBuf::from("/workspace/target")));
+        assert_eq!(args.sarif, None);
+    }
+
+    #[test]
+    fn check_standalone_project_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("src").join("lib.rs");
+        std::fs::create_dir_all(input.parent().unwrap()).unwrap();
+        std::fs::write(&input, "").unwrap();
+        let root = standalone_project_root(&input).unwrap();
+        assert_eq!(root, dir.path().join("src").canonicalize().unwrap());
+    }
+}
@@ -0,0 +1,307 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! This module implements the result cache of `--incremental`.
+//!
+//! Every harness that verifies successfully stores its result in
+//! `<target-dir>/kani/cache/<harness>.json`, together with a key that covers the bytes of its
+//! instrumented goto binary and the CBMC flags it was verified with. A later `--incremental` run
+//! reuses the result instead of running CBMC if the key is unchanged. Failures are never cached,
+//! so they are always verified again and reported with up to date traces.
+
+use std::collections::hash_map::DefaultHasher;
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::hash::{Hash, Hasher};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use kani_metadata::HarnessMetadata;
+use serde::{Deserialize, Serialize};
+
+use crate::args::{OutputFormat, VerificationArgs};
+use crate::call_cbmc::{
+    AutoUnwind, CbmcStats, TracedChecks, VerificationResult, VerificationStatus,
+};
+use crate::cbmc_output_parser::{CheckStatus, Property, PropertyId, SourceLocation};
+use crate::project::Project;
+use crate::session::KaniSession;
+use crate::version::KANI_VERSION;
+
+const CACHE_DIRNAME: &str = "cache";
+
+/// The cached result of one harness.
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    /// The [cache_key] the result was produced with.
+    key: String,
+    properties: Vec<CachedProperty>,
+    cbmc_stats: Option<CbmcStats>,
+    auto_unwind: Option<AutoUnwind>,
+}
+
+/// A [Property] as stored in the cache. Traces are not stored, since successful harnesses only
+/// have traces with `--trace=always`, which is not cached.
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedProperty {
+    description: String,
+    fn_name: Option<String>,
+    class: String,
+    id: u32,
+    column: Option<String>,
+    file: Option<String>,
+    function: Option<String>,
+    line: Option<String>,
+    status: CheckStatus,
+    reach: Option<CheckStatus>,
+}
+
+impl From<&Property> for CachedProperty {
+    fn from(property: &Property) -> Self {
+        CachedProperty {
+            description: property.description.clone(),
+            fn_name: property.property_id.fn_name.clone(),
+            class: property.property_id.class.clone(),
+            id: property.property_id.id,
+            column: property.source_location.column.clone(),
+            file: property.source_location.file.clone(),
+            function: property.source_location.function.clone(),
+            line: property.source_location.line.clone(),
+            status: property.status,
+            reach: property.reach,
+        }
+    }
+}
+
+impl From<CachedProperty> for Property {
+    fn from(cached: CachedProperty) -> Self {
+        Property {
+            description: cached.description,
+            property_id: PropertyId { fn_name: cached.fn_name, class: cached.class, id: cached.id },
+            source_location: SourceLocation {
+                column: cached.column,
+                file: cached.file,
+                function: cached.function,
+                line: cached.line,
+            },
+            status: cached.status,
+            reach: cached.reach,
+            trace: None,
+        }
+    }
+}
+
+/// Compute the location of the result cache for a cargo project if `--incremental` is enabled.
+/// Returns `None` for standalone projects, which `--incremental` does not support.
+pub fn cache_dir(args: &VerificationArgs, project: &Project) -> Option<PathBuf> {
+    if !args.incremental {
+        return None;
+    }
+    let metadata = project.cargo_metadata.as_ref()?;
+    let target_dir =
+        args.target_dir.clone().unwrap_or_else(|| metadata.target_directory.clone().into());
+    Some(target_dir.join("kani").join(CACHE_DIRNAME))
+}
+
+/// Hash the content of `goto_file` together with `inputs`, e.g. the CBMC flags.
+/// [DefaultHasher] is only stable for a given toolchain, so the Kani version is part of the key.
+/// Two differently seeded hashes make up a 128 bit key.
+fn cache_key(goto_file: &Path, inputs: &[OsString]) -> Result<String> {
+    let mut hashers = [DefaultHasher::new(), DefaultHasher::new()];
+    for (seed, hasher) in hashers.iter_mut().enumerate() {
+        seed.hash(hasher);
+        KANI_VERSION.hash(hasher);
+    }
+    let mut file = File::open(goto_file)?;
+    let mut buffer = vec![0; 64 * 1024];
+    let mut len: u64 = 0;
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hashers.iter_mut().for_each(|hasher| hasher.write(&buffer[..read]));
+        len += read as u64;
+    }
+    for hasher in &mut hashers {
+        len.hash(hasher);
+        inputs.hash(hasher);
+    }
+    Ok(format!("{:016x}{:016x}", hashers[0].finish(), hashers[1].finish()))
+}
+
+fn entry_path(dir: &Path, harness: &HarnessMetadata) -> PathBuf {
+    dir.join(format!("{}.json", harness.mangled_name))
+}
+
+/// Read the result of `harness` from the cache if it was stored with `key`.
+/// Missing, outdated and unreadable entries are all cache misses.
+fn load_result(dir: &Path, harness: &HarnessMetadata, key: &str) -> Option<VerificationResult> {
+    let content = fs::read_to_string(entry_path(dir, harness)).ok()?;
+    let entry: CacheEntry = serde_json::from_str(&content).ok()?;
+    if entry.key != key {
+        return None;
+    }
+    VerificationResult::cached_success(
+        entry.properties.into_iter().map(Property::from).collect(),
+        harness.attributes.should_panic,
+        entry.cbmc_stats,
+        entry.auto_unwind,
+    )
+}
+
+/// Store `result` as the result of `harness` for `key`. Only successful results are stored;
+/// the entry of a harness that failed is removed so it is never reused.
+fn store_result(
+    dir: &Path,
+    harness: &HarnessMetadata,
+    key: &str,
+    result: &VerificationResult,
+) -> Result<()> {
+    let path = entry_path(dir, harness);
+    let properties = match &result.results {
+        Ok(properties) if result.status == VerificationStatus::Success && !result.timed_out => {
+            properties
+        }
+        _ => {
+            if path.exists() {
+                fs::remove_file(&path)?;
+            }
+            return Ok(());
+        }
+    };
+    let entry = CacheEntry {
+        key: key.to_string(),
+        properties: properties.iter().map(CachedProperty::from).collect(),
+        cbmc_stats: result.cbmc_stats.clone(),
+        auto_unwind: result.auto_unwind,
+    };
+    fs::create_dir_all(dir)?;
+    fs::write(path, serde_json::to_string(&entry)?)?;
+    Ok(())
+}
+
+impl KaniSession {
+    /// Whether results can be cached at all: coverage and `--trace=always` need the full
+    /// output of CBMC, and the old output format doesn't produce results.
+    fn can_cache_results(&self) -> bool {
+        !self.args.coverage
+            && !matches!(self.traced_checks(), TracedChecks::All)
+            && self.args.output_format != OutputFormat::Old
+    }
+
+    /// The key of the cached result of `harness` in `binary`: the binary itself, the CBMC flags,
+    /// and the options that affect how the CBMC output is interpreted.
+    pub fn result_cache_key(
+        &self,
+        binary: &Path,
+        harness: &HarnessMetadata,
+        unwind: Option<u32>,
+    ) -> Result<Option<String>> {
+        if !self.can_cache_results() {
+            return Ok(None);
+        }
+        let mut inputs = self.cbmc_flags(binary, harness, unwind, self.traced_checks())?;
+        inputs.push(format!("should_panic={}", harness.attributes.should_panic).into());
+        if self.args.auto_unwind {
+            inputs.push(format!("auto_unwind_max={}", self.args.auto_unwind_cap()).into());
+        }
+        Ok(Some(cache_key(binary, &inputs)?))
+    }
+
+    /// Reuse the cached result of `harness`, unless `--force-build` asks to verify it again.
+    pub fn cached_result(
+        &self,
+        dir: &Path,
+        harness: &HarnessMetadata,
+        key: &str,
+    ) -> Option<VerificationResult> {
+        if self.args.force_build {
+            return None;
+        }
+        load_result(dir, harness, key)
+    }
+
+    /// Update the cached result of `harness`. Failing to do so only costs a cache miss later,
+    /// so it is a warning.
+    pub fn cache_result(
+        &self,
+        dir: &Path,
+        harness: &HarnessMetadata,
+        key: &str,
+        result: &VerificationResult,
+    ) {
+        if let Err(err) = store_result(dir, harness, key, result) {
+            crate::util::warning(&format!(
+                "Failed to cache the result of harness `{}`: {err:#}",
+                harness.pretty_name
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::tests::{mock_harness, mock_property, mock_result};
+
+    fn result(status: CheckStatus) -> VerificationResult {
+        let mut property = mock_property("assertion", 1, status);
+        property.reach = Some(CheckStatus::Success);
+        let status = if status == CheckStatus::Failure {
+            VerificationStatus::Failure
+        } else {
+            VerificationStatus::Success
+        };
+        mock_result(status, Ok(vec![property]))
+    }
+
+    #[test]
+    fn check_cache_key_covers_content_and_flags() {
+        let dir = tempfile::tempdir().unwrap();
+        let goto = dir.path().join("harness.out");
+        let flags: Vec<OsString> = vec!["--unwind".into(), "2".into()];
+        fs::write(&goto, b"goto binary").unwrap();
+        let key = cache_key(&goto, &flags).unwrap();
+        assert_eq!(key.len(), 32);
+        assert_eq!(cache_key(&goto, &flags).unwrap(), key);
+
+        let other_flags: Vec<OsString> = vec!["--unwind".into(), "3".into()];
+        assert_ne!(cache_key(&goto, &other_flags).unwrap(), key);
+
+        // Only the bytes matter: a change that keeps the size is detected, and rewriting the
+        // same content is not.
+        fs::write(&goto, b"goto binarz").unwrap();
+        assert_ne!(cache_key(&goto, &flags).unwrap(), key);
+        fs::write(&goto, b"goto binary").unwrap();
+        assert_eq!(cache_key(&goto, &flags).unwrap(), key);
+    }
+
+    #[test]
+    fn check_cache_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let harness = mock_harness("check_cached");
+        store_result(dir.path(), &harness, "key", &result(CheckStatus::Success)).unwrap();
+
+        let cached = load_result(dir.path(), &harness, "key").unwrap();
+        assert!(cached.cached);
+        assert_eq!(cached.status, VerificationStatus::Success);
+        let properties = cached.results.unwrap();
+        assert_eq!(properties.len(), 1);
+        assert_eq!(properties[0].property_name(), "foo.assertion.1");
+        assert_eq!(properties[0].source_location.line.as_deref(), Some("42"));
+        assert_eq!(properties[0].reach, Some(CheckStatus::Success));
+
+        assert!(load_result(dir.path(), &harness, "other key").is_none());
+    }
+
+    #[test]
+    fn check_failures_are_not_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let harness = mock_harness("check_cached");
+        store_result(dir.path(), &harness, "key", &result(CheckStatus::Success)).unwrap();
+        store_result(dir.path(), &harness, "key", &result(CheckStatus::Failure)).unwrap();
+        assert!(!entry_path(dir.path(), &harness).exists());
+        assert!(load_result(dir.path(), &harness, "key").is_none());
+    }
+}
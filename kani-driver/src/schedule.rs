@@ -0,0 +1,223 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! This module implements the order in which harnesses are verified with `--jobs`.
+//!
+//! A parallel run lasts at least as long as its slowest harness, so starting that harness last
+//! leaves the other threads idle at the end of the run. Harnesses are thus started by decreasing
+//! expected duration, which is their duration in the most recent run of the history where they
+//! were verified. Harnesses without a recorded duration, e.g. new ones, are started afterwards in
+//! declaration order. The order only affects when each result is available, never the results.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use kani_metadata::HarnessMetadata;
+
+use crate::call_cbmc::VerificationStatus;
+use crate::harness_runner::HarnessResult;
+use crate::history::{RunRecord, history_file, read_records};
+use crate::project::Project;
+use crate::session::KaniSession;
+
+/// The expected duration of each harness, by name, according to the history `records`.
+pub fn expected_durations(records: &[RunRecord]) -> HashMap<String, Duration> {
+    records
+        .iter()
+        .flat_map(|record| &record.harnesses)
+        .filter_map(|harness| {
+            Some((harness.name.clone(), Duration::from_millis(harness.duration_ms?)))
+        })
+        .collect()
+}
+
+/// Order `harnesses` by decreasing `expected` duration, keeping the declaration order for the
+/// harnesses with the same or no expected duration. Each harness is paired with its index in
+/// `harnesses`.
+pub fn schedule<'a>(
+    harnesses: &[&'a HarnessMetadata],
+    expected: &HashMap<String, Duration>,
+) -> Vec<(usize, &'a HarnessMetadata)> {
+    let mut scheduled: Vec<_> = harnesses.iter().copied().enumerate().collect();
+    scheduled
+        .sort_by_key(|(_, harness)| std::cmp::Reverse(expected.get(&harness.pretty_name).copied()));
+    scheduled
+}
+
+/// How well a parallel run used its threads, as the fraction of the thread time that was spent
+/// verifying harnesses.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParallelEfficiency {
+    pub achieved: f64,
+    /// The best efficiency any order could achieve, since no run is shorter than its longest
+    /// harness.
+    pub best: f64,
+}
+
+/// The efficiency of a run of `threads` threads that took `elapsed` to verify harnesses that took
+/// `durations`. Returns `None` if nothing was verified.
+pub fn parallel_efficiency(
+    durations: &[Duration],
+    elapsed: Duration,
+    threads: usize,
+) -> Option<ParallelEfficiency> {
+    let total: f64 = durations.iter().map(Duration::as_secs_f64).sum();
+    let longest = durations.iter().max()?.as_secs_f64();
+    let threads = threads.max(1) as f64;
+    if total == 0.0 || elapsed.is_zero() {
+        return None;
+    }
+    Some(ParallelEfficiency {
+        achieved: (total / (elapsed.as_secs_f64() * threads)).min(1.0),
+        best: total / total.max(longest * threads),
+    })
+}
+
+impl KaniSession {
+    /// The order in which `harnesses` are verified. Only parallel runs of cargo projects are
+    /// reordered, since they are the only ones with both a history and idle threads.
+    pub fn harness_schedule<'a>(
+        &self,
+        project: &Project,
+        harnesses: &[&'a HarnessMetadata],
+    ) -> Vec<(usize, &'a HarnessMetadata)> {
+        let records = history_file(&self.args, project)
+            .filter(|_| self.args.jobs().will_multithread())
+            .and_then(|path| read_records(&path).ok())
+            .unwrap_or_default();
+        schedule(harnesses, &expected_durations(&records))
+    }
+
+    /// Print the parallel efficiency of a run of `threads` threads that took `elapsed` to produce
+    /// `results`.
+    pub fn print_parallel_efficiency(
+        &self,
+        results: &[HarnessResult<'_>],
+        elapsed: Duration,
+        threads: usize,
+    ) {
+        if self.args.common_args.quiet || threads <= 1 {
+            return;
+        }
+        let durations: Vec<_> = results
+            .iter()
+            .filter(|r| {
+                !matches!(
+                    r.result.status,
+                    VerificationStatus::Skipped | VerificationStatus::Excluded
+                )
+            })
+            .map(|r| r.result.runtime)
+            .collect();
+        if let Some(efficiency) = parallel_efficiency(&durations, elapsed, threads) {
+            println!(
+                "Parallel efficiency: {:.0}% of {threads} threads (best possible: {:.0}%)",
+                efficiency.achieved * 100.0,
+                efficiency.best * 100.0,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call_cbmc::VerificationResult;
+    use crate::frontend::tests::mock_harness;
+    use crate::history::HarnessRecord;
+
+    fn record(durations: &[(&str, Option<u64>)]) -> RunRecord {
+        RunRecord {
+            timestamp: "2024-05-01T10:00:00Z".to_string(),
+            duration_ms: 0,
+            flags: vec![],
+            successes: durations.len(),
+            failures: 0,
+            harnesses: durations
+                .iter()
+                .map(|(name, duration_ms)| HarnessRecord {
+                    name: name.to_string(),
+                    status: "SUCCESS".to_string(),
+                    duration_ms: *duration_ms,
+                })
+                .collect(),
+            export: None,
+        }
+    }
+
+    #[test]
+    fn check_slowest_harnesses_first() {
+        let harnesses =
+            [mock_harness("a"), mock_harness("b"), mock_harness("c"), mock_harness("d")];
+        let harnesses: Vec<_> = harnesses.iter().collect();
+        // The most recent duration of `b` wins, and `c` was skipped in the last run.
+        let records = [
+            record(&[("b", Some(10)), ("c", Some(200))]),
+            record(&[("b", Some(500)), ("c", None), ("d", Some(100))]),
+        ];
+        let order: Vec<_> = schedule(&harnesses, &expected_durations(&records))
+            .into_iter()
+            .map(|(idx, harness)| (idx, harness.pretty_name.as_str()))
+            .collect();
+        assert_eq!(order, [(1, "b"), (2, "c"), (3, "d"), (0, "a")]);
+    }
+
+    #[test]
+    fn check_schedule_is_a_permutation() {
+        let harnesses = [mock_harness("a"), mock_harness("b"), mock_harness("c")];
+        let harnesses: Vec<_> = harnesses.iter().collect();
+        let declared = schedule(&harnesses, &HashMap::new());
+        assert_eq!(declared.iter().map(|(idx, _)| *idx).collect::<Vec<_>>(), [0, 1, 2]);
+
+        let records = [record(&[("a", Some(1)), ("b", Some(3)), ("c", Some(2))])];
+        let mut scheduled = schedule(&harnesses, &expected_durations(&records));
+        assert_eq!(scheduled.iter().map(|(idx, _)| *idx).collect::<Vec<_>>(), [1, 2, 0]);
+        // Every harness is verified exactly once, so the results are the same in either order.
+        scheduled.sort_by_key(|(idx, _)| *idx);
+        assert_eq!(scheduled, declared);
+    }
+
+    #[test]
+    fn check_results_do_not_depend_on_the_order() {
+        let harnesses =
+            [mock_harness("c"), mock_harness("a"), mock_harness("d"), mock_harness("b")];
+        let harnesses: Vec<_> = harnesses.iter().collect();
+        let records = [record(&[("a", Some(5)), ("b", Some(40)), ("d", Some(20))])];
+        // Verify the harnesses in the given order, and report the results like the runner does.
+        let verify = |order: Vec<(usize, &HarnessMetadata)>| {
+            let mut results: Vec<_> = order
+                .into_iter()
+                .map(|(_, harness)| {
+                    let result = if harness.pretty_name == "d" {
+                        VerificationResult::driver_panic(Duration::ZERO)
+                    } else {
+                        VerificationResult::mock_success()
+                    };
+                    HarnessResult { harness, result }
+                })
+                .collect();
+            results
+                .sort_by(|r1, r2| crate::metadata::cmp_harnesses_by_name(r1.harness, r2.harness));
+            results
+                .iter()
+                .map(|r| (r.harness.pretty_name.clone(), r.result.status))
+                .collect::<Vec<_>>()
+        };
+        let declared = schedule(&harnesses, &HashMap::new());
+        let scheduled = schedule(&harnesses, &expected_durations(&records));
+        assert_ne!(declared, scheduled);
+        assert_eq!(verify(declared), verify(scheduled));
+    }
+
+    #[test]
+    fn check_parallel_efficiency() {
+        let secs = Duration::from_secs;
+        // 4 threads verifying 8s of harnesses in 4s.
+        let efficiency = parallel_efficiency(&[secs(2), secs(2), secs(4)], secs(4), 4).unwrap();
+        assert_eq!(efficiency, ParallelEfficiency { achieved: 0.5, best: 0.5 });
+        // The same harnesses on 2 threads could end after the longest one.
+        let efficiency = parallel_efficiency(&[secs(2), secs(2), secs(4)], secs(6), 2).unwrap();
+        assert_eq!(efficiency.best, 1.0);
+        assert!((efficiency.achieved - 8.0 / 12.0).abs() < 1e-9);
+        assert_eq!(parallel_efficiency(&[], secs(1), 2), None);
+    }
+}
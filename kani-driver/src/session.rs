@@ -1,21 +1,27 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use crate::args::OutputFormat;
+use crate::args::QuickSettings;
 use crate::args::Timeout;
 use crate::args::VerificationArgs;
 use crate::args::common::Verbosity;
+use crate::autoharness::skip_cache::SkipCache;
+use crate::error::KaniDriverError;
+use crate::event_log;
+use crate::metadata::HarnessPattern;
 use crate::util::render_command;
-use anyhow::{Context, Result, bail};
+use anyhow::{Context, Result, anyhow};
 use std::io::IsTerminal;
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::process::{Child, Command, Stdio};
+use std::process::{Child, Command, ExitStatus, Stdio};
 use std::sync::Mutex;
 use std::time::Instant;
 use strum_macros::Display;
 use tokio::process::Command as TokioCommand;
 use tracing::level_filters::LevelFilter;
-use tracing_subscriber::{EnvFilter, Registry, layer::SubscriberExt};
+use tracing_subscriber::{EnvFilter, Layer, Registry, layer::SubscriberExt};
 
 pub const BUG_REPORT_URL: &str =
     "https://github.com/model-checking/kani/issues/new?labels=bug&template=bug_report.md";
@@ -30,6 +36,10 @@
 const FLAMEGRAPH_DIR: &str = "flamegraphs";
 const FLAMEGRAPH_SAMPLING_RATE: &str = "8000"; // in Hz
 
+// Defaults applied by `--quick` unless the user sets the option explicitly.
+pub const QUICK_TIMEOUT: &str = "60s";
+pub const QUICK_UNWIND_DEFAULT: u32 = 2;
+
 /// Contains information about the execution environment and arguments that affect operations
 pub struct KaniSession {
     /// The common command-line arguments
@@ -39,6 +49,15 @@ pub struct KaniSession {
     /// Invariant: this field is_some() iff the autoharness subcommand is enabled.
     pub autoharness_compiler_flags: Option<Vec<String>>,
 
+    /// The compiled `--harness-pattern` and `--harness-regex` selectors, in this order.
+    pub harness_patterns: Vec<HarnessPattern>,
+    /// The compiled `--exclude-harness` patterns.
+    pub exclusion_patterns: Vec<HarnessPattern>,
+
+    /// The skip cache of the autoharness subcommand.
+    /// `None` unless the autoharness subcommand verifies a cargo project.
+    pub autoharness_skip_cache: Option<SkipCache>,
+
     /// The location we found the 'kani_rustc' command
     pub kani_compiler: PathBuf,
     /// The location we found 'kani_lib.c'
@@ -63,17 +82,48 @@ pub enum InstallType {
 
 impl KaniSession {
     pub fn new(args: VerificationArgs) -> Result<Self> {
-        init_logger(&args);
+        init_logger(&args)?;
         let install = InstallType::new()?;
+        let invalid_pattern =
+            |err: regex::Error| KaniDriverError::InvalidArguments(err.to_string());
+        let harness_patterns = args.harness_selection_patterns().map_err(invalid_pattern)?;
+        let exclusion_patterns = args.harness_exclusion_patterns().map_err(invalid_pattern)?;
 
-        Ok(KaniSession {
+        let mut session = KaniSession {
             args,
+            harness_patterns,
+            exclusion_patterns,
             autoharness_compiler_flags: None,
+            autoharness_skip_cache: None,
             kani_compiler: install.kani_compiler()?,
             kani_lib_c: install.kani_lib_c()?,
             temporaries: Mutex::new(vec![]),
             runtime: tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap(),
-        })
+        };
+        if session.args.quick {
+            session.add_quick_defaults()?;
+        }
+        if session.args.output_format == OutputFormat::Json {
+            // The results are printed as JSON events, and everything else goes to stderr.
+            session.args.common_args.quiet = true;
+            crate::util::reserve_stdout_for_json();
+        }
+        Ok(session)
+    }
+
+    /// Apply the `--quick` bundle: a low unwind bound, a harness timeout and `--fail-fast`, with
+    /// unwinding, overflow and memory safety checks turned off, as adjusted by `--quick-setting`.
+    /// Each setting only applies where the user didn't set the option explicitly.
+    fn add_quick_defaults(&mut self) -> Result<()> {
+        let settings = QuickSettings::with_overrides(&self.args.quick_setting)
+            .map_err(|err| anyhow!("Invalid argument to `--quick-setting`: {err}"))?;
+        self.args.harness_timeout.get_or_insert(settings.harness_timeout);
+        self.args.default_unwind.get_or_insert(settings.default_unwind);
+        self.args.fail_fast |= settings.fail_fast;
+        self.args.checks.no_unwinding_checks |= !settings.unwinding_checks;
+        self.args.checks.no_overflow_checks |= !settings.overflow_checks;
+        self.args.checks.no_memory_safety_checks |= !settings.memory_safety_checks;
+        Ok(())
     }
 
     /// Record a temporary file so we can cleanup after ourselves at the end.
@@ -183,13 +233,14 @@ pub fn run_terminal(verbosity: &impl Verbosity, mut cmd: Command) -> Result<()>
     let result = with_timer(
         verbosity,
         || {
-            cmd.status()
-                .context(format!("Failed to invoke {}", cmd.get_program().to_string_lossy()))
+            event_log::status(&mut cmd).map_err(|source| {
+                KaniDriverError::tool_not_started(&cmd.get_program().to_string_lossy(), source)
+            })
         },
         &program,
     )?;
     if !result.success() {
-        bail!("{} exited with status {}", cmd.get_program().to_string_lossy(), result);
+        return Err(command_failed(&cmd, result));
     }
     Ok(())
 }
@@ -211,17 +262,25 @@ async fn run_terminal_timeout(
     let result = with_timer(
         verbosity,
         || async {
-            if let Some(timeout) = timeout {
-                let mut child = cmd.spawn().unwrap();
+            let start = Instant::now();
+            let mut child = match cmd.spawn() {
+                Ok(child) => child,
+                Err(err) => return Ok(Err(err)),
+            };
+            let pid = child.id();
+            event_log::process_spawned(cmd.as_std(), pid);
+            let res = if let Some(timeout) = timeout {
                 let res = tokio::time::timeout(timeout.into(), child.wait()).await;
                 if res.is_err() {
-                    // Kill the process
-                    child.kill().await.unwrap();
+                    kill_process_tree(&mut child).await.unwrap();
                 }
                 res
             } else {
-                Ok(cmd.status().await)
-            }
+                Ok(child.wait().await)
+            };
+            let status = res.as_ref().ok().and_then(|status| status.as_ref().ok()).copied();
+            event_log::process_exited(&program, pid, status, start.elapsed());
+            res
         },
         &program,
     )
@@ -230,21 +289,59 @@ async fn run_terminal_timeout(
     if result.is_err() {
         return Ok(true);
     }
-    let result = result.unwrap().context(format!("Failed to invoke {program}"))?;
+    let result =
+        result.unwrap().map_err(|source| KaniDriverError::tool_not_started(&program, source))?;
     if !result.success() {
-        bail!("{} exited with status {}", cmd.as_std().get_program().to_string_lossy(), result);
+        return Err(command_failed(cmd.as_std(), result));
     }
     Ok(false)
 }
 
+/// Kill `child` and all of its descendants, e.g. the solvers started by CBMC, which would
+/// otherwise keep running after CBMC is killed.
+/// Descendants are found with `pgrep`; if it is not available, only `child` is killed.
+pub async fn kill_process_tree(child: &mut tokio::process::Child) -> std::io::Result<()> {
+    if let Some(pid) = child.id() {
+        // Collect the descendants before killing anything, since they are reparented once their
+        // parent exits.
+        let mut descendants = vec![];
+        let mut parents = vec![pid];
+        while let Some(parent) = parents.pop() {
+            let children = child_pids(parent);
+            parents.extend(&children);
+            descendants.extend(children);
+        }
+        if !descendants.is_empty() {
+            // Best effort: some of the descendants may have exited already.
+            let _ = Command::new("kill")
+                .arg("-KILL")
+                .args(descendants.iter().map(u32::to_string))
+                .stderr(Stdio::null())
+                .status();
+        }
+    }
+    child.kill().await
+}
+
+/// The direct children of the process `pid`.
+fn child_pids(pid: u32) -> Vec<u32> {
+    let Ok(output) = Command::new("pgrep").args(["-P", &pid.to_string()]).output() else {
+        return vec![];
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.trim().parse().ok())
+        .collect()
+}
+
 /// Run a job, but only output (unless --quiet) if it fails, and fail if there's a problem.
 pub fn run_suppress(verbosity: &impl Verbosity, mut cmd: Command) -> Result<()> {
     if verbosity.is_set() {
         return run_terminal(verbosity, cmd);
     }
-    let result = cmd
-        .output()
-        .context(format!("Failed to invoke {}", cmd.get_program().to_string_lossy()))?;
+    let result = event_log::output(&mut cmd).map_err(|source| {
+        KaniDriverError::tool_not_started(&cmd.get_program().to_string_lossy(), source)
+    })?;
     if !result.status.success() {
         // Don't suppress the output. There doesn't seem to be a way to easily get Command
         // to give one output stream of both out/err with interleaving correct, it seems
@@ -253,11 +350,21 @@ pub fn run_suppress(verbosity: &impl Verbosity, mut cmd: Command) -> Result<()>
         let mut handle = stdout.lock();
         handle.write_all(&result.stdout)?;
         handle.write_all(&result.stderr)?;
-        bail!("{} exited with status {}", cmd.get_program().to_string_lossy(), result.status);
+        return Err(command_failed(&cmd, result.status));
     }
     Ok(())
 }
 
+/// The error for `cmd` exiting with `status`.
+fn command_failed(cmd: &Command, status: ExitStatus) -> anyhow::Error {
+    KaniDriverError::CommandFailed {
+        program: cmd.get_program().to_string_lossy().into_owned(),
+        status,
+        command: render_command(cmd).to_string_lossy().into_owned(),
+    }
+    .into()
+}
+
 /// Run a job and pipe its output to this process.
 /// Returns an error if the process could not be spawned.
 ///
@@ -268,10 +375,10 @@ pub fn run_piped(verbosity: &impl Verbosity, mut cmd: Command) -> Result<Child>
         println!("[Kani] Running: `{}`", render_command(&cmd).to_string_lossy());
     }
     // Run the process as a child process
-    let process = cmd
-        .stdout(Stdio::piped())
-        .spawn()
-        .context(format!("Failed to invoke {}", cmd.get_program().to_string_lossy()))?;
+    let process = cmd.stdout(Stdio::piped()).spawn().map_err(|source| {
+        KaniDriverError::tool_not_started(&cmd.get_program().to_string_lossy(), source)
+    })?;
+    event_log::process_spawned(&cmd, Some(process.id()));
 
     Ok(process)
 }
@@ -344,10 +451,11 @@ pub fn new() -> Result<Self> {
 
             Ok(InstallType::Release(path))
         } else {
-            bail!(
+            Err(KaniDriverError::Setup(format!(
                 "Unable to determine installation location. {} doesn't look typical",
                 path.display()
-            )
+            ))
+            .into())
         }
     }
 
@@ -384,16 +492,18 @@ fn expect_path(path: PathBuf) -> Result<PathBuf> {
     if path.exists() {
         Ok(path)
     } else {
-        bail!(
+        Err(KaniDriverError::Setup(format!(
             "Unable to find {}. Looked for {}",
             path.file_name().unwrap().to_string_lossy(),
             path.display()
-        );
+        ))
+        .into())
     }
 }
 
-/// Initialize the logger using the KANI_LOG environment variable and `--debug` argument.
-fn init_logger(args: &VerificationArgs) {
+/// Initialize the logger using the KANI_LOG environment variable and `--debug` argument, and the
+/// event log of `--event-log`.
+fn init_logger(args: &VerificationArgs) -> Result<()> {
     let filter = EnvFilter::from_env(LOG_ENV_VAR);
     let filter = if args.common_args.debug {
         filter.add_directive(LevelFilter::DEBUG.into())
@@ -403,14 +513,17 @@ fn init_logger(args: &VerificationArgs) {
 
     // Use a hierarchical view for now.
     let use_colors = std::io::stdout().is_terminal();
-    let subscriber = Registry::default().with(filter);
-    let subscriber = subscriber.with(
+    // The filter only applies to this layer, since the event log has a filter of its own.
+    let subscriber = Registry::default().with(
         tracing_subscriber::fmt::layer()
             .with_writer(std::io::stderr)
             .with_ansi(use_colors)
-            .with_target(true),
+            .with_target(true)
+            .with_filter(filter),
     );
-    tracing::subscriber::set_global_default(subscriber).unwrap();
+    let event_log = args.event_log.as_deref().map(event_log::layer).transpose()?;
+    tracing::subscriber::set_global_default(subscriber.with(event_log)).unwrap();
+    Ok(())
 }
 
 pub fn setup_cargo_command() -> Result<Command> {
@@ -0,0 +1,423 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! This module implements `--smoke-test`, a fast pre-check that executes every harness with
+//! random concrete values for `kani::any()` before verifying it.
+//!
+//! The harnesses are compiled against the concrete playback library into a test binary with one
+//! test per harness, which calls `kani::smoke_test_run`. A failing sample is a real failure of the
+//! harness, but samples that pass prove nothing, so a smoke test never makes a harness pass.
+
+use std::collections::BTreeMap;
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use kani_metadata::{HarnessKind, HarnessMetadata};
+use serde::{Deserialize, Serialize};
+use tempfile::NamedTempFile;
+use tokio::process::Command as TokioCommand;
+
+use crate::call_single_file::{LibConfig, base_rustc_flags};
+use crate::project::Project;
+use crate::session::{KaniSession, lib_playback_folder};
+use crate::util::args::{CommandWrapper, PassTo, RustcArg};
+
+/// Name of the test binary that contains the smoke tests.
+const SMOKE_TEST_CRATE: &str = "kani_smoke_test";
+/// Prefix of the lines printed by `kani::smoke_test_run`.
+const OUTPUT_PREFIX: &str = "kani_smoke_test";
+/// How long the samples of one harness may run, unless `--harness-timeout` is given.
+const SMOKE_TEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The outcome of the smoke test of a harness.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SmokeTestStatus {
+    /// No sample failed. This is NOT a proof that the harness cannot fail.
+    NoFailure,
+    /// A sample failed, see [SmokeTest::failure].
+    Failure,
+    /// The harness cannot be executed concretely, see [SmokeTest::reason].
+    Skipped,
+    /// The smoke test could not be built or run, see [SmokeTest::reason].
+    Error,
+}
+
+/// The smoke test of a harness.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SmokeTest {
+    pub status: SmokeTestStatus,
+    /// Seed of the random values of the first sample. Every sample increments it by one.
+    pub seed: u64,
+    /// Number of samples requested with `--smoke-test`.
+    pub samples: u32,
+    /// Number of samples that ran, including the rejected ones and the failing one.
+    pub executed: u32,
+    /// Number of samples discarded because they violated a `kani::assume`.
+    pub rejected: u32,
+    pub failure: Option<SmokeTestFailure>,
+    /// Why the harness was skipped or its smoke test could not run.
+    pub reason: Option<String>,
+    /// Whether the harness was not verified because its smoke test failed.
+    pub verification_skipped: bool,
+}
+
+/// The first sample of a smoke test that panicked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SmokeTestFailure {
+    /// Index of the sample, starting at 0.
+    pub sample: u32,
+    pub message: String,
+    /// Where the harness panicked, as `file:line:column`.
+    pub location: Option<String>,
+    /// The bytes returned by each `kani::any()` call, in the format of concrete playback tests.
+    pub inputs: Vec<Vec<u8>>,
+}
+
+impl SmokeTest {
+    fn new(status: SmokeTestStatus, harness: &HarnessMetadata, samples: u32) -> SmokeTest {
+        SmokeTest {
+            status,
+            seed: seed(harness),
+            samples,
+            executed: 0,
+            rejected: 0,
+            failure: None,
+            reason: None,
+            verification_skipped: false,
+        }
+    }
+
+    fn with_reason(
+        status: SmokeTestStatus,
+        harness: &HarnessMetadata,
+        samples: u32,
+        reason: String,
+    ) -> SmokeTest {
+        SmokeTest { reason: Some(reason), ..SmokeTest::new(status, harness, samples) }
+    }
+
+    /// Describe the smoke test in the output of a harness.
+    pub fn render(&self) -> String {
+        match (&self.status, &self.failure) {
+            (SmokeTestStatus::Failure, Some(failure)) => {
+                let location = failure.location.as_deref().unwrap_or("an unknown location");
+                let inputs: Vec<_> =
+                    failure.inputs.iter().map(|input| format!("    {input:?}\n")).collect();
+                format!(
+                    "\nSMOKE TEST FAILURE (found by a concrete execution, seed {})\n\
+                    Sample {} of {} panicked at {location}: {}\n\
+                    Concrete values returned by `kani::any()`:\n{}",
+                    self.seed,
+                    failure.sample + 1,
+                    self.samples,
+                    failure.message,
+                    inputs.concat()
+                )
+            }
+            (SmokeTestStatus::NoFailure, _) => format!(
+                "[Kani] info: Smoke test: no failure in {} random samples ({} rejected by \
+                assumptions). This is not a proof.\n",
+                self.executed, self.rejected
+            ),
+            _ => format!(
+                "[Kani] info: Smoke test did not run: {}.\n",
+                self.reason.as_deref().unwrap_or("unknown reason")
+            ),
+        }
+    }
+}
+
+impl KaniSession {
+    /// Implement `--smoke-test` for `harnesses`, returning the smoke test of each harness by its
+    /// pretty name. Returns no smoke tests if the option is not set.
+    pub fn smoke_test(
+        &self,
+        project: &Project,
+        harnesses: &[&HarnessMetadata],
+    ) -> Result<BTreeMap<String, SmokeTest>> {
+        let Some(samples) = self.args.smoke_test else { return Ok(BTreeMap::new()) };
+        let Some(input) = &project.input else {
+            let reason = "smoke tests are only supported for standalone `kani`".to_string();
+            return Ok(harnesses
+                .iter()
+                .map(|h| {
+                    let test = SmokeTest::with_reason(
+                        SmokeTestStatus::Skipped,
+                        h,
+                        samples,
+                        reason.clone(),
+                    );
+                    (h.pretty_name.clone(), test)
+                })
+                .collect());
+        };
+        let input = input.canonicalize()?;
+
+        let mut smoke_tests = BTreeMap::new();
+        let mut runnable = vec![];
+        for harness in harnesses {
+            if let Some(reason) = unsupported_reason(harness, &input) {
+                let test =
+                    SmokeTest::with_reason(SmokeTestStatus::Skipped, harness, samples, reason);
+                smoke_tests.insert(harness.pretty_name.clone(), test);
+            } else {
+                runnable.push(*harness);
+            }
+        }
+        if runnable.is_empty() {
+            return Ok(smoke_tests);
+        }
+
+        if !self.args.common_args.quiet {
+            println!("Running smoke tests with {samples} random samples per harness...");
+        }
+        let outdir = tempfile::tempdir()?;
+        let exe = outdir.path().join(SMOKE_TEST_CRATE);
+        if let Err(err) = self.build_smoke_tests(&input, &runnable, samples, &exe) {
+            for harness in runnable {
+                let reason = format!("failed to build the smoke tests: {err:#}");
+                let test = SmokeTest::with_reason(SmokeTestStatus::Error, harness, samples, reason);
+                smoke_tests.insert(harness.pretty_name.clone(), test);
+            }
+            return Ok(smoke_tests);
+        }
+        for harness in runnable {
+            let test = self.run_smoke_test(&exe, harness, samples);
+            smoke_tests.insert(harness.pretty_name.clone(), test);
+        }
+        Ok(smoke_tests)
+    }
+
+    /// Compile a copy of `input` with a smoke test after each harness into the test binary `exe`.
+    /// The copy is placed next to `input`, so the paths of its modules resolve the same way.
+    fn build_smoke_tests(
+        &self,
+        input: &Path,
+        harnesses: &[&HarnessMetadata],
+        samples: u32,
+        exe: &Path,
+    ) -> Result<()> {
+        let source = fs::read_to_string(input)
+            .with_context(|| format!("Failed to read `{}`", input.display()))?;
+        let tests: Vec<_> = harnesses
+            .iter()
+            .map(|harness| (harness.original_end_line, smoke_test_code(harness, samples)))
+            .collect();
+        let mut file = NamedTempFile::with_prefix_in(SMOKE_TEST_CRATE, input.parent().unwrap())?;
+        file.write_all(insert_after_lines(&source, tests).as_bytes())?;
+        file.flush()?;
+
+        let mut rustc_args = base_rustc_flags(LibConfig::new(lib_playback_folder()?));
+        rustc_args.push("--test".into());
+        rustc_args.push(RustcArg::from(file.path()));
+        rustc_args.push(format!("--crate-name={SMOKE_TEST_CRATE}").into());
+        rustc_args.push("-o".into());
+        rustc_args.push(RustcArg::from(exe));
+
+        let mut cmd = std::process::Command::new(&self.kani_compiler);
+        cmd.pass_rustc_args(&rustc_args, PassTo::OnlyLocalCrate);
+        self.run_suppress(cmd)
+    }
+
+    /// Run the smoke test of `harness` in the test binary `exe`.
+    fn run_smoke_test(&self, exe: &Path, harness: &HarnessMetadata, samples: u32) -> SmokeTest {
+        let timeout = self.args.harness_timeout.map_or(SMOKE_TEST_TIMEOUT, Into::into);
+        let test_path = smoke_test_path(harness);
+        let mut cmd = TokioCommand::new(exe);
+        cmd.args([test_path.as_str(), "--exact", "--nocapture", "--test-threads=1"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        let output =
+            self.runtime.block_on(async { tokio::time::timeout(timeout, cmd.output()).await });
+        let error =
+            |reason| SmokeTest::with_reason(SmokeTestStatus::Error, harness, samples, reason);
+        let output = match output {
+            Err(_) => return error(format!("timed out after {}s", timeout.as_secs())),
+            Ok(Err(err)) => return error(format!("failed to run the smoke test: {err}")),
+            Ok(Ok(output)) => output,
+        };
+        let Some((executed, rejected, failure)) =
+            parse_smoke_test_output(&String::from_utf8_lossy(&output.stdout))
+        else {
+            return error(format!("the smoke test exited with {}", output.status));
+        };
+        let status = if failure.is_some() {
+            SmokeTestStatus::Failure
+        } else if output.status.success() {
+            SmokeTestStatus::NoFailure
+        } else {
+            return error(format!("the smoke test exited with {}", output.status));
+        };
+        SmokeTest { executed, rejected, failure, ..SmokeTest::new(status, harness, samples) }
+    }
+}
+
+/// Why `harness` cannot be smoke tested, or `None` if it can.
+fn unsupported_reason(harness: &HarnessMetadata, input: &Path) -> Option<String> {
+    if harness.attributes.should_panic {
+        Some("`should_panic` harnesses are expected to panic".to_string())
+    } else if matches!(harness.attributes.kind, HarnessKind::ProofForContract { .. }) {
+        Some("contracts are not checked by concrete executions".to_string())
+    } else if !harness.attributes.stubs.is_empty() {
+        Some("stubs are not applied to concrete executions".to_string())
+    } else if Path::new(&harness.original_file).canonicalize().ok().as_deref() != Some(input) {
+        Some("the harness is not defined in the input file".to_string())
+    } else {
+        None
+    }
+}
+
+/// The seed of the first sample of `harness`, derived from its name so that runs are reproducible.
+fn seed(harness: &HarnessMetadata) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    harness.pretty_name.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn smoke_test_name(harness: &HarnessMetadata) -> String {
+    format!("{SMOKE_TEST_CRATE}_{}", harness.mangled_name)
+}
+
+/// The path of the smoke test of `harness` in the test binary. The test is generated right after
+/// the harness, so it is in the same module.
+fn smoke_test_path(harness: &HarnessMetadata) -> String {
+    match harness.pretty_name.rsplit_once("::") {
+        Some((module, _)) => format!("{module}::{}", smoke_test_name(harness)),
+        None => smoke_test_name(harness),
+    }
+}
+
+fn smoke_test_code(harness: &HarnessMetadata, samples: u32) -> Vec<String> {
+    vec![
+        "#[test]".to_string(),
+        format!("fn {}() {{", smoke_test_name(harness)),
+        format!(
+            "    kani::smoke_test_run({samples}, {}, {});",
+            seed(harness),
+            harness.get_harness_name_unqualified()
+        ),
+        "}".to_string(),
+    ]
+}
+
+/// Insert each block of `lines` after the given (1-based) line of `source`.
+fn insert_after_lines(source: &str, mut lines: Vec<(usize, Vec<String>)>) -> String {
+    lines.sort_by_key(|(line, _)| *line);
+    let mut lines = lines.into_iter().peekable();
+    let mut result = String::with_capacity(source.len());
+    for (idx, source_line) in source.lines().enumerate() {
+        result.push_str(source_line);
+        result.push('\n');
+        while let Some((_, block)) = lines.next_if(|(line, _)| *line == idx + 1) {
+            for line in block {
+                result.push_str(&line);
+                result.push('\n');
+            }
+        }
+    }
+    result
+}
+
+/// Parse the lines printed by `kani::smoke_test_run`: the number of executed and rejected
+/// samples, and the failing sample if any. Returns `None` if the smoke test did not report.
+fn parse_smoke_test_output(stdout: &str) -> Option<(u32, u32, Option<SmokeTestFailure>)> {
+    let field = |name: &str| {
+        let prefix = format!("{OUTPUT_PREFIX}_{name}: ");
+        stdout.lines().find_map(|line| line.strip_prefix(&prefix))
+    };
+    let (executed, rejected) = field("samples")?.split_once(' ')?;
+    let (executed, rejected) = (executed.parse().ok()?, rejected.parse().ok()?);
+    let failure = field("failure_sample").and_then(|sample| {
+        let message = field("failure_message").unwrap_or_default();
+        Some(SmokeTestFailure {
+            sample: sample.parse().ok()?,
+            // The message is printed with `{:?}`, which is valid JSON unless it has `\u{..}`
+            // escapes.
+            message: serde_json::from_str(message).unwrap_or_else(|_| message.to_string()),
+            location: field("failure_location").filter(|loc| !loc.is_empty()).map(String::from),
+            inputs: serde_json::from_str(field("failure_inputs")?).ok()?,
+        })
+    });
+    Some((executed, rejected, failure))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_insert_after_lines() {
+        let source = "fn a() {}\nfn b() {}\nfn c() {}\n";
+        let blocks = vec![
+            (3, vec!["// after c".to_string()]),
+            (1, vec!["// after a".to_string(), "// still after a".to_string()]),
+        ];
+        assert_eq!(
+            insert_after_lines(source, blocks),
+            "fn a() {}\n// after a\n// still after a\nfn b() {}\nfn c() {}\n// after c\n"
+        );
+    }
+
+    #[test]
+    fn check_parse_smoke_test_output() {
+        let passed = "running 1 test\nkani_smoke_test_samples: 100 7\ntest ... ok\n";
+        assert_eq!(parse_smoke_test_output(passed), Some((100, 7, None)));
+
+        let failed = "running 1 test\n\
+            kani_smoke_test_samples: 4 1\n\
+            kani_smoke_test_failure_sample: 3\n\
+            kani_smoke_test_failure_location: src/main.rs:10:5\n\
+            kani_smoke_test_failure_inputs: [[1, 0, 0, 0], [255]]\n\
+            kani_smoke_test_failure_message: \"assertion failed: x < \\\"10\\\"\"\n\
+            test proofs::kani_smoke_test_check ... FAILED\n";
+        let failure = SmokeTestFailure {
+            sample: 3,
+            message: "assertion failed: x < \"10\"".to_string(),
+            location: Some("src/main.rs:10:5".to_string()),
+            inputs: vec![vec![1, 0, 0, 0], vec![255]],
+        };
+        assert_eq!(parse_smoke_test_output(failed), Some((4, 1, Some(failure))));
+
+        // The test binary crashed before reporting anything.
+        assert_eq!(parse_smoke_test_output("running 1 test\n"), None);
+    }
+
+    #[test]
+    fn check_smoke_test_failure_skips_verification() {
+        use crate::args::OutputFormat;
+        use crate::call_cbmc::{VerificationResult, VerificationStatus};
+
+        let failure = SmokeTestFailure {
+            sample: 0,
+            message: "attempt to add with overflow".to_string(),
+            location: Some("main.rs:4:5".to_string()),
+            inputs: vec![vec![255]],
+        };
+        let smoke_test = SmokeTest {
+            status: SmokeTestStatus::Failure,
+            seed: 1,
+            samples: 100,
+            executed: 1,
+            rejected: 0,
+            failure: Some(failure),
+            reason: None,
+            verification_skipped: false,
+        };
+        let result = VerificationResult::smoke_test_failure(smoke_test, Duration::ZERO);
+        assert_eq!(result.status, VerificationStatus::Failure);
+        assert!(result.smoke_test.as_ref().unwrap().verification_skipped);
+
+        let output = result.render(&OutputFormat::Regular, false);
+        assert!(output.contains("SMOKE TEST FAILURE"));
+        assert!(output.contains("Sample 1 of 100 panicked at main.rs:4:5"));
+        assert!(output.contains("[255]"));
+        assert!(output.contains("Symbolic verification was skipped"));
+    }
+}
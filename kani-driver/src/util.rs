@@ -13,6 +13,10 @@
 use std::ffi::OsString;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether stdout only carries the events of `--output-format json`.
+static STDOUT_IS_JSON: AtomicBool = AtomicBool::new(false);
 
 /// Replace an extension with another one, in a new PathBuf. (See tests for examples)
 pub fn alter_extension(path: &Path, ext: &str) -> PathBuf {
@@ -26,16 +30,15 @@ pub fn crate_name(path: &Path) -> String {
     stem.replace(['-', '.'], "_")
 }
 
-/// Given a path of some sort (usually from argv0), this attempts to extract the basename / stem
-/// of the executable. e.g. "/path/foo -> foo" "./foo.exe -> foo" "foo -> foo"
+/// Given a path of some sort (usually from argv0), this attempts to extract the basename of the
+/// executable without its `.exe` suffix. Other extensions are kept, since they are usually part
+/// of a version. e.g. "/path/foo -> foo" "./foo.exe -> foo" "foo-0.55 -> foo-0.55"
 pub fn executable_basename(argv0: &Option<&OsString>) -> Option<OsString> {
-    if let Some(path) = argv0 {
-        let basename = Path::new(&path).file_stem();
-        if let Some(stem) = basename {
-            return Some(stem.to_os_string());
-        }
+    let basename = Path::new(argv0.as_ref()?).file_name()?;
+    match basename.to_str().and_then(|name| name.strip_suffix(".exe")) {
+        Some(stem) => Some(stem.into()),
+        None => Some(basename.to_os_string()),
     }
-    None
 }
 
 /// Render a Command as a string, to log it (e.g. in dry runs)
@@ -69,23 +72,39 @@ pub fn render_command(cmd: &Command) -> OsString {
 
 /// Print a warning message. This will add a "warning:" tag before the message and style accordingly.
 pub fn warning(msg: &str) {
+    crate::event_log::warning(msg);
     let warning = console::style("warning:").bold().yellow();
     let msg_fmt = console::style(msg).bold();
-    println!("{warning} {msg_fmt}")
+    print_diagnostic(format!("{warning} {msg_fmt}"))
 }
 
 /// Print an error message. This will add an "error:" tag before the message and style accordingly.
 pub fn error(msg: &str) {
     let error = console::style("error:").bold().red();
     let msg_fmt = console::style(msg).bold();
-    println!("{error} {msg_fmt}")
+    print_diagnostic(format!("{error} {msg_fmt}"))
 }
 
 /// Print an info message. This will print the stage in bold green and the rest in regular style.
 pub fn info_operation(op: &str, msg: &str) {
     let op_fmt = console::style(op).bold().green();
     let msg_fmt = console::style(msg);
-    println!("{op_fmt} {msg_fmt}")
+    print_diagnostic(format!("{op_fmt} {msg_fmt}"))
+}
+
+/// Reserve stdout for the events of `--output-format json`. Diagnostics are printed to stderr
+/// from now on.
+pub fn reserve_stdout_for_json() {
+    STDOUT_IS_JSON.store(true, Ordering::Relaxed);
+}
+
+/// Whether stdout is reserved for the events of `--output-format json`.
+pub fn stdout_is_json() -> bool {
+    STDOUT_IS_JSON.load(Ordering::Relaxed)
+}
+
+fn print_diagnostic(msg: String) {
+    if stdout_is_json() { eprintln!("{msg}") } else { println!("{msg}") }
 }
 
 /// # Kani Argument Types
@@ -266,6 +285,11 @@ fn check_exe_basename() {
         assert_eq!(executable_basename(&Some(&OsString::from("./foo.exe"))), Some("foo".into()));
         assert_eq!(executable_basename(&Some(&OsString::from("foo.exe"))), Some("foo".into()));
         assert_eq!(executable_basename(&Some(&OsString::from("foo"))), Some("foo".into()));
+        assert_eq!(
+            executable_basename(&Some(&OsString::from("/bin/foo-0.55.exe"))),
+            Some("foo-0.55".into())
+        );
+        assert_eq!(executable_basename(&None), None);
     }
 
     #[test]
@@ -50,6 +50,21 @@ pub struct AutoHarnessMetadata {
     pub chosen: BTreeSet<String>,
     /// Map function names to the reason why we did not generate an automatic harness for that function.
     pub skipped: BTreeMap<String, AutoHarnessSkipReason>,
+    /// The arguments of the functions whose argument types were checked, i.e., the chosen
+    /// functions and the ones skipped because of a missing `kani::Arbitrary` implementation.
+    #[serde(default)]
+    pub arguments: BTreeMap<String, Vec<AutoHarnessArgument>>,
+}
+
+/// An argument of a function considered for an automatic harness.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AutoHarnessArgument {
+    /// The name of the argument, or `_` if it has none.
+    pub name: String,
+    /// The type of the argument.
+    pub ty: String,
+    /// Whether the type implements or can derive `kani::Arbitrary`.
+    pub arbitrary: bool,
 }
 
 /// Reasons that Kani does not generate an automatic harness for a function.
@@ -80,6 +95,25 @@ pub struct ContractedFunction {
     pub file: String,
     /// The pretty names of the proof harnesses (`#[kani::proof_for_contract]`) for this function
     pub harnesses: Vec<String>,
+    /// The clauses of the function's contract, in source order.
+    #[serde(default)]
+    pub clauses: Vec<ContractClause>,
+}
+
+/// A single `requires`, `ensures` or `modifies` clause of a function contract.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, PartialOrd, Ord)]
+pub struct ContractClause {
+    /// The kind of clause, i.e. `requires`, `ensures` or `modifies`.
+    pub kind: String,
+    /// The source text of the clause, as written by the user.
+    pub text: String,
+}
+
+impl ContractClause {
+    /// The clause text collapsed to a single line, with consecutive whitespace squeezed.
+    pub fn normalized_text(&self) -> String {
+        self.text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -4,12 +4,76 @@
 //! Helper code for concrete playback.
 
 use std::cell::RefCell;
+use std::panic::AssertUnwindSafe;
+
+/// Message of the panic raised by `kani::assume` during concrete playback.
+const ASSUME_FAILURE: &str = "`kani::assume` should always hold";
+
+/// Prefix of the lines that report the outcome of a smoke test to the Kani driver.
+const SMOKE_TEST_PREFIX: &str = "kani_smoke_test";
 
 thread_local! {
     /// thread_local! gives us a separate CONCRETE_VALS instance for each thread.
     /// This allows us to run concrete playback unit tests in parallel.
     /// RefCell is necessary for mut statics.
     static CONCRETE_VALS: RefCell<Vec<Vec<u8>>> = RefCell::new(Vec::new());
+
+    /// While a smoke test sample runs, `kani::any()` returns random bytes from here instead of
+    /// popping them from CONCRETE_VALS.
+    static SMOKE_TEST_SAMPLE: RefCell<Option<SmokeTestSample>> = const { RefCell::new(None) };
+
+    /// Location of the last panic in this thread, recorded by the smoke test panic hook.
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// The random values generated for one execution of a harness in a smoke test.
+struct SmokeTestSample {
+    /// State of the SplitMix64 generator.
+    state: u64,
+    /// The bytes returned so far, in the order of the `kani::any()` calls.
+    inputs: Vec<Vec<u8>>,
+}
+
+impl SmokeTestSample {
+    fn new(seed: u64) -> Self {
+        SmokeTestSample { state: seed, inputs: vec![] }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Generate `size` bytes. Uniformly random values rarely hit edge cases or satisfy the
+    /// assumptions made by `bool` and enum values, so small values and all ones are favored.
+    /// Zero-sized values are not recorded, so the inputs are the values of a concrete playback test.
+    fn next_bytes(&mut self, size: usize) -> Vec<u8> {
+        if size == 0 {
+            return vec![];
+        }
+        let mut bytes: Vec<u8> = (0..size).map(|_| self.next_u64() as u8).collect();
+        match self.next_u64() % 4 {
+            0 => {
+                let small = (self.next_u64() % 4) as u8;
+                bytes.fill(0);
+                let lowest = if cfg!(target_endian = "little") {
+                    bytes.first_mut()
+                } else {
+                    bytes.last_mut()
+                };
+                if let Some(lowest) = lowest {
+                    *lowest = small;
+                }
+            }
+            1 => bytes.fill(u8::MAX),
+            _ => {}
+        }
+        self.inputs.push(bytes.clone());
+        bytes
+    }
 }
 
 /// This function sets concrete values and plays back the user's proof harness.
@@ -40,6 +104,55 @@ pub fn concrete_playback_run<F: Fn()>(mut local_concrete_vals: Vec<Vec<u8>>, pro
     });
 }
 
+/// Run the user's proof harness `samples` times with random values for `kani::any()`, starting
+/// from `seed`, and report the outcome to the Kani driver on stdout.
+///
+/// Samples that violate a `kani::assume` are rejected. The first sample that panics for any other
+/// reason stops the smoke test, and its values are reported before the panic is propagated.
+/// Passing all samples does not prove anything about the harness.
+pub fn smoke_test_run<F: Fn()>(samples: u32, seed: u64, proof_harness: F) {
+    let default_hook = std::panic::take_hook();
+    // Rejected samples are expected, so record the location of panics instead of printing them.
+    std::panic::set_hook(Box::new(|info| {
+        let location = info.location().map(|location| location.to_string());
+        PANIC_LOCATION.with(|panic_location| *panic_location.borrow_mut() = location);
+    }));
+    let mut rejected = 0;
+    let mut failure = None;
+    for sample in 0..samples {
+        let sample_seed = seed.wrapping_add(sample as u64);
+        SMOKE_TEST_SAMPLE
+            .with(|state| *state.borrow_mut() = Some(SmokeTestSample::new(sample_seed)));
+        let outcome = std::panic::catch_unwind(AssertUnwindSafe(&proof_harness));
+        let inputs = SMOKE_TEST_SAMPLE.with(|state| state.borrow_mut().take()).unwrap().inputs;
+        if let Err(payload) = outcome {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            if message == ASSUME_FAILURE {
+                rejected += 1;
+            } else {
+                failure = Some((sample, message, inputs, payload));
+                break;
+            }
+        }
+    }
+    std::panic::set_hook(default_hook);
+
+    let executed = failure.as_ref().map_or(samples, |(sample, ..)| sample + 1);
+    println!("{SMOKE_TEST_PREFIX}_samples: {executed} {rejected}");
+    if let Some((sample, message, inputs, payload)) = failure {
+        let location = PANIC_LOCATION.with(|location| location.borrow_mut().take());
+        println!("{SMOKE_TEST_PREFIX}_failure_sample: {sample}");
+        println!("{SMOKE_TEST_PREFIX}_failure_location: {}", location.unwrap_or_default());
+        println!("{SMOKE_TEST_PREFIX}_failure_inputs: {inputs:?}");
+        println!("{SMOKE_TEST_PREFIX}_failure_message: {message:?}");
+        std::panic::resume_unwind(payload);
+    }
+}
+
 /// Iterate over `any_raw_internal` since CBMC produces assignment per element.
 pub(crate) unsafe fn any_raw_array<T: Copy, const N: usize>() -> [T; N] {
     unsafe { [(); N].map(|_| crate::any_raw_internal::<T>()) }
@@ -55,6 +168,11 @@ pub fn concrete_playback_run<F: Fn()>(mut local_concrete_vals: Vec<Vec<u8>>, pro
 pub(crate) unsafe fn any_raw_internal<T: Copy>() -> T {
     let sz = size_of::<T>();
     let mut next_concrete_val: Vec<u8> = Vec::new();
+    let smoke_test_val = SMOKE_TEST_SAMPLE
+        .with(|state| state.borrow_mut().as_mut().map(|sample| sample.next_bytes(sz)));
+    if let Some(random_val) = smoke_test_val {
+        return unsafe { std::ptr::read_unaligned(random_val.as_ptr() as *const T) };
+    }
     CONCRETE_VALS.with(|glob_concrete_vals| {
         let mut_ref_glob_concrete_vals = &mut *glob_concrete_vals.borrow_mut();
         next_concrete_val = if sz > 0 {
@@ -39,7 +39,7 @@
 mod models;
 
 #[cfg(feature = "concrete_playback")]
-pub use concrete_playback::concrete_playback_run;
+pub use concrete_playback::{concrete_playback_run, smoke_test_run};
 pub use invariant::Invariant;
 
 #[cfg(not(feature = "concrete_playback"))]
@@ -48,6 +48,12 @@ pub fn concrete_playback_run<F: Fn()>(_: Vec<Vec<u8>>, _: F) {
     unreachable!("Concrete playback does not work during verification")
 }
 
+#[cfg(not(feature = "concrete_playback"))]
+/// NOP `smoke_test_run` for type checking during verification mode.
+pub fn smoke_test_run<F: Fn()>(_: u32, _: u64, _: F) {
+    unreachable!("Smoke tests do not work during verification")
+}
+
 pub use futures::{RoundRobin, block_on, block_on_with_spawn, spawn, yield_now};
 
 // Kani proc macros must be in a separate crate
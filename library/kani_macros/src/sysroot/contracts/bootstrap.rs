@@ -6,7 +6,7 @@
 
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
-use syn::{Expr, ItemFn, Stmt};
+use syn::{Attribute, Expr, ItemFn, Stmt, parse_quote};
 
 use super::{ContractConditionsHandler, INTERNAL_RESULT_IDENT, helpers::*};
 
@@ -39,8 +39,10 @@ pub fn handle_untouched(&mut self) {
         // sees those attributes and can use them to determine
         // `function_state`.
         let ItemFn { attrs, vis, sig, block } = &self.annotated_fn;
+        let clause_attr = self.clause_attribute();
         self.output.extend(quote!(
             #(#attrs)*
+            #clause_attr
             #[kanitool::recursion_check = #recursion_name]
             #[kanitool::checked_with = #check_name]
             #[kanitool::replaced_with = #replace_name]
@@ -106,6 +108,9 @@ const fn kani_contract_mode() -> kani::internal::Mode {
     /// to the attribute being handled.
     pub fn handle_expanded(&mut self) {
         let mut annotated_fn = self.annotated_fn.clone();
+        // Appending keeps the clause attributes in the order the clauses were written,
+        // since contract attributes are expanded outside in.
+        annotated_fn.attrs.push(self.clause_attribute());
         let ItemFn { block, .. } = &mut annotated_fn;
         let recursion_closure = expect_closure_in_match(&mut block.stmts, "recursion_check");
         self.expand_recursion(recursion_closure);
@@ -122,6 +127,14 @@ pub fn handle_expanded(&mut self) {
         self.output.extend(quote!(#annotated_fn));
     }
 
+    /// Generate the `kanitool::contract_clause` attribute that records the kind and source text
+    /// of this clause, so it can be displayed by `kani list` and in failure messages.
+    fn clause_attribute(&self) -> Attribute {
+        let kind = self.condition_type.kind().to_string();
+        let attr_copy = &self.attr_copy;
+        parse_quote!(#[kanitool::contract_clause = concat!(#kind, ": ", stringify!(#attr_copy))])
+    }
+
     /// Generate the tokens for the recursion closure.
     fn new_recursion_closure(
         &self,
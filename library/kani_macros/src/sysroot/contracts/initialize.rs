@@ -108,4 +108,13 @@ fn new_modifies(attr: TokenStream, output: &mut TokenStream2) -> Self {
 
         ContractConditionsData::Modifies { attr }
     }
+
+    /// The kind of attribute this data was parsed from.
+    pub fn kind(&self) -> ContractConditionsType {
+        match self {
+            ContractConditionsData::Requires { .. } => ContractConditionsType::Requires,
+            ContractConditionsData::Ensures { .. } => ContractConditionsType::Ensures,
+            ContractConditionsData::Modifies { .. } => ContractConditionsType::Modifies,
+        }
+    }
 }
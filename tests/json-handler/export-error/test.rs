@@ -0,0 +1,8 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#[kani::proof]
+fn check_compilation_error() {
+    let x: u8 = "not a number";
+    assert!(x == 0);
+}
@@ -0,0 +1,49 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Test that errors that stop Kani before verification are still exported, with a category and
+# a distinct exit code
+
+set -u
+
+OUTPUT_FILE="error.json"
+
+# Check that running Kani with the arguments after the first two exits with the code `$1` and
+# exports an error of category `$2`.
+check_error() {
+    local expected_code=$1
+    local expected_category=$2
+    shift 2
+    rm -f "$OUTPUT_FILE"
+    kani "$@" --export-json "$OUTPUT_FILE" > /dev/null 2>&1
+    local code=$?
+    if [ "$code" -ne "$expected_code" ]; then
+        echo "ERROR: Expected \`kani $*\` to exit with $expected_code, got $code"
+        exit 1
+    fi
+    python3 - "$OUTPUT_FILE" "$expected_category" << 'EOF_PY'
+import json
+import sys
+
+path, expected = sys.argv[1], sys.argv[2]
+with open(path, 'r') as f:
+    data = json.load(f)
+
+error = data.get('error')
+if error is None or error['category'] != expected:
+    print(f"ERROR: Expected an error of category `{expected}`, got {error}")
+    sys.exit(1)
+if not error['messages'] or 'run_error' not in data:
+    print(f"ERROR: Incomplete error export: {data}")
+    sys.exit(1)
+print(f"Exported `{expected}` error")
+EOF_PY
+    [ $? -eq 0 ] || exit 1
+}
+
+check_error 4 codegen_failed test.rs
+check_error 2 no_harnesses_matched valid.rs --harness check_missing
+check_error 2 no_harnesses_matched valid.rs --harness check_missing --dry-run
+
+rm -f "$OUTPUT_FILE"
@@ -0,0 +1,10 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Test how `--export-json` interacts with `--only-codegen` and the subcommands
+
+#[kani::proof]
+fn check_export() {
+    let x: u8 = kani::any();
+    assert!(x as u16 <= 255);
+}
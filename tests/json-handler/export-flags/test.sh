@@ -0,0 +1,54 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Test that `--only-codegen` still exports the project, and that subcommands that don't write an
+# export reject `--export-json`
+
+set -eu
+
+OUTPUT_FILE="only_codegen.json"
+
+kani test.rs --only-codegen --export-json "$OUTPUT_FILE"
+
+if [ ! -f "$OUTPUT_FILE" ]; then
+    echo "ERROR: JSON file $OUTPUT_FILE was not created"
+    exit 1
+fi
+
+python3 << 'EOF_PY'
+import json
+import sys
+
+with open('only_codegen.json', 'r') as f:
+    data = json.load(f)
+
+for key in ['project', 'session']:
+    if key not in data:
+        print(f"ERROR: Expected `{key}` in the export of --only-codegen")
+        sys.exit(1)
+
+harnesses = data.get('verification_results') or []
+if harnesses:
+    print(f"ERROR: Expected no verification results, got {harnesses}")
+    sys.exit(1)
+
+print("The export of --only-codegen has the project and no results")
+EOF_PY
+
+rm -f "$OUTPUT_FILE"
+
+# `kani` doesn't take verification flags before a subcommand.
+for subcommand in "list test.rs" "playback test.rs"; do
+    if kani --export-json "$OUTPUT_FILE" $subcommand > /dev/null 2>&1; then
+        echo "ERROR: Expected \`--export-json $subcommand\` to be rejected"
+        exit 1
+    fi
+done
+
+if [ -f "$OUTPUT_FILE" ]; then
+    echo "ERROR: JSON file $OUTPUT_FILE was created by a rejected command"
+    exit 1
+fi
+
+echo "Subcommands without an export reject --export-json"
@@ -0,0 +1,22 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Test the SARIF log of failed checks written by `--sarif`
+
+fn divide(x: u32, y: u32) -> u32 {
+    x / y
+}
+
+#[kani::proof]
+fn check_divide() {
+    let x: u32 = kani::any();
+    let y: u32 = kani::any();
+    let result = divide(x, y);
+    assert!(result <= x);
+}
+
+#[kani::proof]
+fn check_passes() {
+    let x: u8 = kani::any();
+    assert!(x as u16 <= 255);
+}
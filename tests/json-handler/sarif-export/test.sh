@@ -0,0 +1,61 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Test the SARIF log written by `--sarif` and validate it against the SARIF 2.1.0 schema
+
+set -eu
+
+OUTPUT_FILE="failed_checks.sarif"
+
+# Run Kani with a SARIF log (expect failure, so don't use -e)
+set +e
+kani test.rs -Z unstable-options --sarif "$OUTPUT_FILE"
+EXIT_CODE=$?
+set -e
+
+if [ $EXIT_CODE -eq 0 ]; then
+    echo "ERROR: Expected Kani to fail but it succeeded"
+    exit 1
+fi
+
+if [ ! -f "$OUTPUT_FILE" ]; then
+    echo "ERROR: SARIF file $OUTPUT_FILE was not created"
+    exit 1
+fi
+
+python3 << 'EOF_PY'
+import json
+import sys
+import urllib.request
+
+import jsonschema
+
+with open('failed_checks.sarif', 'r') as f:
+    log = json.load(f)
+
+# The log refers to the schema it conforms to.
+with urllib.request.urlopen(log['$schema']) as response:
+    schema = json.load(response)
+jsonschema.validate(log, schema)
+print("SARIF log matches the SARIF 2.1.0 schema")
+
+run = log['runs'][0]
+rules = [rule['id'] for rule in run['tool']['driver']['rules']]
+if 'division_by_zero' not in rules:
+    print(f"ERROR: Expected a `division_by_zero` rule, got {rules}")
+    sys.exit(1)
+
+for result in run['results']:
+    if result['properties']['harness'] != 'check_divide':
+        print(f"ERROR: Unexpected result {result}")
+        sys.exit(1)
+    location = result['locations'][0]['physicalLocation']
+    if not location['artifactLocation']['uri'].endswith('test.rs'):
+        print(f"ERROR: Unexpected location {location}")
+        sys.exit(1)
+
+print(f"Found {len(run['results'])} failed checks")
+EOF_PY
+
+rm -f "$OUTPUT_FILE"
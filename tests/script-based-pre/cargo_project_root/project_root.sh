@@ -0,0 +1,36 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Checks that `cargo kani` behaves the same when invoked from the workspace root, from a member
+# crate and from a nested directory of a member: the configuration of the workspace root is used,
+# and relative output paths are resolved against the workspace root.
+
+set -eu
+
+ROOT=$(cd workspace && pwd)
+
+function check_from {
+    local dir=$1
+    echo "Running from ${dir}"
+    rm -rf "${ROOT}/out" "${ROOT}/target"
+    (cd "${ROOT}/${dir}" && cargo kani --target-dir target --export-json out/run.json --verbose \
+        > "${ROOT}/kani.log" 2>&1) || true
+
+    grep "Project root:" "${ROOT}/kani.log" | sed "s|${ROOT}|<root>|"
+    grep "Checking harness" "${ROOT}/kani.log"
+    grep "successfully verified harnesses" "${ROOT}/kani.log"
+    if [ -f "${ROOT}/out/run.json" ]; then
+        echo "Exported run at the workspace root"
+    fi
+    if [ -d "${ROOT}/target/kani" ]; then
+        echo "Artifacts in the workspace target directory"
+    fi
+}
+
+check_from .
+check_from member
+check_from member/src
+
+# Try to leave a clean workspace at the end
+rm -rf "${ROOT}/out" "${ROOT}/target" "${ROOT}/kani.log" "${ROOT}/Cargo.lock"
@@ -0,0 +1,18 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+pub fn add(a: u8, b: u8) -> u8 {
+    a.wrapping_add(b)
+}
+
+#[kani::proof]
+fn check_add() {
+    let a: u8 = kani::any();
+    assert_eq!(add(a, 0), a);
+}
+
+/// Only verified if the workspace configuration was not found.
+#[kani::proof]
+fn check_other() {
+    assert!(add(1, 1) == 2);
+}
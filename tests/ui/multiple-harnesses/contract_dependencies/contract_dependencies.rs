@@ -0,0 +1,37 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts -Z unstable-options --harness main --with-contract-dependencies
+//! Ensure that `--with-contract-dependencies` also verifies the harnesses of the contracts
+//! assumed by the selected harness, including the ones assumed by those harnesses.
+
+#[kani::ensures(|result : &u32| *result == 1)]
+fn one() -> u32 {
+    1
+}
+
+#[kani::proof_for_contract(one)]
+fn check_one() {
+    let _ = one();
+}
+
+#[kani::ensures(|result : &u32| *result == 2)]
+fn two() -> u32 {
+    one() + one()
+}
+
+#[kani::proof_for_contract(two)]
+#[kani::stub_verified(one)]
+fn check_two() {
+    let _ = two();
+}
+
+#[kani::proof]
+fn unrelated() {
+    assert!(false, "Harnesses that are not needed are not verified");
+}
+
+#[kani::proof]
+#[kani::stub_verified(two)]
+fn main() {
+    assert_eq!(two(), 2);
+}
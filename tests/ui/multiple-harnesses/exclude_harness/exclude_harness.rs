@@ -0,0 +1,23 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --exclude-harness slow::*
+//! Ensure that the harnesses matching `--exclude-harness` are not verified, and that they are
+//! reported as excluded.
+
+#[kani::proof]
+fn check_fast() {
+    let x: u8 = kani::any();
+    assert!(x as u16 <= 255);
+}
+
+mod slow {
+    #[kani::proof]
+    fn check_slow_one() {
+        assert!(false, "Excluded harnesses are not verified");
+    }
+
+    #[kani::proof]
+    fn check_slow_two() {
+        assert!(false, "Excluded harnesses are not verified");
+    }
+}
@@ -1,7 +1,8 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 // kani-flags: --fail-fast
-//! Ensure that the verification process stops as soon as one of the harnesses fails.
+//! Ensure that the verification process stops as soon as one of the harnesses fails, and that the
+//! remaining harnesses are reported as skipped.
 
 mod tests {
     #[kani::proof]
@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 // kani-flags: --fail-fast --jobs 4 --output-format=terse
 //! Ensure that the verification process stops as soon as one of the harnesses fails.
-//! This test runs on 4 parallel threads. Stops verification as soon as a harness on any of the threads fails.
+//! This test runs on 4 parallel threads. Harnesses that are already running when a harness fails
+//! are allowed to finish, and the harnesses that have not started yet are skipped.
 
 mod tests {
     #[kani::proof]
@@ -5,7 +5,7 @@
 //! Checks that we print a line which points the user to the path where coverage
 //! results have been saved. The line should look like:
 //! ```
-//! [info] Coverage results saved to /path/to/outdir/kanicov_YYYY-MM-DD_hh-mm
+//! [info] Coverage results saved to /path/to/outdir/kanicov_YYYY-MM-DD_hh-mm-ss
 //! ```
 
 fn _other_function() {